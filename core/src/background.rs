@@ -1,14 +1,17 @@
+//! Fill the bounds of an element with a color, gradient or image.
 use crate::gradient::{self, Gradient};
-use crate::Color;
+use crate::image::{self, FilterMethod};
+use crate::{Color, Padding, Point, Rectangle, Size};
 
 /// The background of some element.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Background {
     /// A solid color.
     Color(Color),
     /// Linearly interpolate between several colors.
     Gradient(Gradient),
-    // TODO: Add image variant
+    /// An image, clipped to the border radius of the element.
+    Image(Image),
 }
 
 impl Background {
@@ -20,6 +23,7 @@ impl Background {
             Self::Gradient(gradient) => {
                 Self::Gradient(gradient.scale_alpha(factor))
             }
+            Self::Image(image) => Self::Image(image.scale_alpha(factor)),
         }
     }
 }
@@ -41,3 +45,367 @@ impl From<gradient::Linear> for Background {
         Background::Gradient(Gradient::Linear(gradient))
     }
 }
+
+impl From<gradient::Radial> for Background {
+    fn from(gradient: gradient::Radial) -> Self {
+        Background::Gradient(Gradient::Radial(gradient))
+    }
+}
+
+impl From<gradient::Conic> for Background {
+    fn from(gradient: gradient::Conic) -> Self {
+        Background::Gradient(Gradient::Conic(gradient))
+    }
+}
+
+impl From<Image> for Background {
+    fn from(image: Image) -> Self {
+        Background::Image(image)
+    }
+}
+
+impl From<image::Handle> for Background {
+    fn from(handle: image::Handle) -> Self {
+        Background::Image(Image::new(handle))
+    }
+}
+
+/// An image [`Background`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    /// The handle of the image.
+    pub handle: image::Handle,
+
+    /// How the image fills the bounds of the element.
+    pub fit: Fit,
+
+    /// The filter method of the image.
+    pub filter_method: FilterMethod,
+
+    /// The opacity of the image.
+    ///
+    /// 0 means transparent. 1 means opaque.
+    pub opacity: f32,
+}
+
+impl Image {
+    /// Creates a new [`Image`] background with the given handle.
+    ///
+    /// By default, the image will [`Fit::Cover`] the bounds of the element.
+    pub fn new(handle: impl Into<image::Handle>) -> Self {
+        Self {
+            handle: handle.into(),
+            fit: Fit::default(),
+            filter_method: FilterMethod::default(),
+            opacity: 1.0,
+        }
+    }
+
+    /// Sets the [`Fit`] of the [`Image`].
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Sets the filter method of the [`Image`].
+    pub fn filter_method(mut self, filter_method: FilterMethod) -> Self {
+        self.filter_method = filter_method;
+        self
+    }
+
+    /// Sets the opacity of the [`Image`].
+    pub fn opacity(mut self, opacity: impl Into<f32>) -> Self {
+        self.opacity = opacity.into();
+        self
+    }
+
+    /// Scales the opacity of the [`Image`] by the given factor.
+    pub fn scale_alpha(mut self, factor: f32) -> Self {
+        self.opacity *= factor;
+        self
+    }
+}
+
+/// The strategy used to fill the bounds of an element with an [`Image`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Fit {
+    /// Scale the image to cover the whole bounds, preserving its aspect
+    /// ratio and cropping whatever overflows.
+    #[default]
+    Cover,
+    /// Scale the image to fit inside the bounds, preserving its aspect
+    /// ratio and centering it.
+    Contain,
+    /// Repeat the image at its original size, starting at the top-left
+    /// corner of the bounds.
+    Tile,
+    /// Split the image into nine slices using the given insets, in image
+    /// pixels.
+    ///
+    /// The corners keep their original size, the edges stretch along one
+    /// axis, and the center stretches to fill the rest of the bounds.
+    NineSlice(Padding),
+}
+
+impl Fit {
+    /// Computes the bounds of the drawn image, relative to the bounds of the
+    /// element, for the given image and element sizes.
+    ///
+    /// Only [`Fit::Cover`] and [`Fit::Contain`] scale the whole image; any
+    /// other [`Fit`] returns the original size of the image.
+    pub fn image_bounds(
+        self,
+        image_size: Size,
+        bounds_size: Size,
+    ) -> Rectangle {
+        let scale = match self {
+            Fit::Cover => (bounds_size.width / image_size.width)
+                .max(bounds_size.height / image_size.height),
+            Fit::Contain => (bounds_size.width / image_size.width)
+                .min(bounds_size.height / image_size.height),
+            Fit::Tile | Fit::NineSlice(_) => 1.0,
+        };
+
+        let size = image_size * scale;
+
+        let position = match self {
+            Fit::Cover | Fit::Contain => Point::new(
+                (bounds_size.width - size.width) / 2.0,
+                (bounds_size.height - size.height) / 2.0,
+            ),
+            Fit::Tile | Fit::NineSlice(_) => Point::ORIGIN,
+        };
+
+        Rectangle::new(position, size)
+    }
+
+    /// Computes the slices of the image drawn by this [`Fit`], as pairs of
+    /// source rectangles in image pixels and target rectangles in the
+    /// coordinates of the given element bounds.
+    ///
+    /// [`Fit::NineSlice`] produces up to nine slices, shrinking its corners
+    /// when the bounds are too small to fit them. Any other [`Fit`] produces
+    /// a single slice with the whole image; [`Fit::Tile`] leaves the tiling
+    /// of that slice to the renderer.
+    pub fn slices(
+        self,
+        image_size: Size,
+        bounds: Rectangle,
+    ) -> Vec<(Rectangle, Rectangle)> {
+        let Fit::NineSlice(insets) = self else {
+            let target = self.image_bounds(image_size, bounds.size());
+
+            return vec![(
+                Rectangle::with_size(image_size),
+                Rectangle {
+                    x: bounds.x + target.x,
+                    y: bounds.y + target.y,
+                    ..target
+                },
+            )];
+        };
+
+        let scale_x = (bounds.width / (insets.left + insets.right)).min(1.0);
+        let scale_y = (bounds.height / (insets.top + insets.bottom)).min(1.0);
+
+        let source_x = [
+            0.0,
+            insets.left,
+            image_size.width - insets.right,
+            image_size.width,
+        ];
+        let source_y = [
+            0.0,
+            insets.top,
+            image_size.height - insets.bottom,
+            image_size.height,
+        ];
+
+        let target_x = [
+            bounds.x,
+            bounds.x + insets.left * scale_x,
+            bounds.x + bounds.width - insets.right * scale_x,
+            bounds.x + bounds.width,
+        ];
+        let target_y = [
+            bounds.y,
+            bounds.y + insets.top * scale_y,
+            bounds.y + bounds.height - insets.bottom * scale_y,
+            bounds.y + bounds.height,
+        ];
+
+        let mut slices = Vec::with_capacity(9);
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let source = Rectangle {
+                    x: source_x[column],
+                    y: source_y[row],
+                    width: source_x[column + 1] - source_x[column],
+                    height: source_y[row + 1] - source_y[row],
+                };
+
+                let target = Rectangle {
+                    x: target_x[column],
+                    y: target_y[row],
+                    width: target_x[column + 1] - target_x[column],
+                    height: target_y[row + 1] - target_y[row],
+                };
+
+                if source.width > 0.0
+                    && source.height > 0.0
+                    && target.width > 0.0
+                    && target.height > 0.0
+                {
+                    slices.push((source, target));
+                }
+            }
+        }
+
+        slices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IMAGE: Size = Size::new(40.0, 20.0);
+
+    fn rectangle(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn cover_fills_and_centers() {
+        assert_eq!(
+            Fit::Cover.image_bounds(IMAGE, Size::new(100.0, 100.0)),
+            rectangle(-50.0, 0.0, 200.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn contain_fits_and_centers() {
+        assert_eq!(
+            Fit::Contain.image_bounds(IMAGE, Size::new(100.0, 100.0)),
+            rectangle(0.0, 25.0, 100.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn tile_and_nine_slice_keep_image_size() {
+        let bounds = Size::new(100.0, 100.0);
+
+        assert_eq!(
+            Fit::Tile.image_bounds(IMAGE, bounds),
+            rectangle(0.0, 0.0, 40.0, 20.0)
+        );
+        assert_eq!(
+            Fit::NineSlice(Padding::new(5.0)).image_bounds(IMAGE, bounds),
+            rectangle(0.0, 0.0, 40.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn slices_offset_single_slice_by_bounds() {
+        let bounds = rectangle(10.0, 20.0, 100.0, 100.0);
+
+        assert_eq!(
+            Fit::Contain.slices(IMAGE, bounds),
+            vec![(
+                rectangle(0.0, 0.0, 40.0, 20.0),
+                rectangle(10.0, 45.0, 100.0, 50.0)
+            )]
+        );
+    }
+
+    #[test]
+    fn nine_slice_keeps_corners_and_stretches_the_rest() {
+        let fit = Fit::NineSlice(Padding::new(5.0));
+        let slices = fit.slices(IMAGE, rectangle(10.0, 10.0, 100.0, 50.0));
+
+        assert_eq!(slices.len(), 9);
+
+        // Top-left corner
+        assert_eq!(
+            slices[0],
+            (
+                rectangle(0.0, 0.0, 5.0, 5.0),
+                rectangle(10.0, 10.0, 5.0, 5.0)
+            )
+        );
+
+        // Top edge
+        assert_eq!(
+            slices[1],
+            (
+                rectangle(5.0, 0.0, 30.0, 5.0),
+                rectangle(15.0, 10.0, 90.0, 5.0)
+            )
+        );
+
+        // Center
+        assert_eq!(
+            slices[4],
+            (
+                rectangle(5.0, 5.0, 30.0, 10.0),
+                rectangle(15.0, 15.0, 90.0, 40.0)
+            )
+        );
+
+        // Bottom-right corner
+        assert_eq!(
+            slices[8],
+            (
+                rectangle(35.0, 15.0, 5.0, 5.0),
+                rectangle(105.0, 55.0, 5.0, 5.0)
+            )
+        );
+    }
+
+    #[test]
+    fn nine_slice_shrinks_corners_in_small_bounds() {
+        let fit = Fit::NineSlice(Padding::new(5.0));
+        let slices = fit.slices(IMAGE, rectangle(0.0, 0.0, 5.0, 20.0));
+
+        // The center column collapses and is skipped
+        assert_eq!(slices.len(), 6);
+
+        assert_eq!(
+            slices[0],
+            (rectangle(0.0, 0.0, 5.0, 5.0), rectangle(0.0, 0.0, 2.5, 5.0))
+        );
+        assert_eq!(
+            slices[1],
+            (
+                rectangle(35.0, 0.0, 5.0, 5.0),
+                rectangle(2.5, 0.0, 2.5, 5.0)
+            )
+        );
+    }
+
+    #[test]
+    fn nine_slice_skips_empty_insets() {
+        let fit = Fit::NineSlice(Padding::ZERO.top(5.0));
+        let slices = fit.slices(IMAGE, rectangle(0.0, 0.0, 100.0, 50.0));
+
+        assert_eq!(
+            slices,
+            vec![
+                (
+                    rectangle(0.0, 0.0, 40.0, 5.0),
+                    rectangle(0.0, 0.0, 100.0, 5.0)
+                ),
+                (
+                    rectangle(0.0, 5.0, 40.0, 15.0),
+                    rectangle(0.0, 5.0, 100.0, 45.0)
+                ),
+            ]
+        );
+    }
+}
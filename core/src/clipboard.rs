@@ -1,4 +1,5 @@
 //! Access the clipboard.
+use bytes::Bytes;
 
 /// A buffer for short-term storage and transfer within and between
 /// applications.
@@ -8,6 +9,43 @@ pub trait Clipboard {
 
     /// Writes the given text contents to the [`Clipboard`].
     fn write(&mut self, kind: Kind, contents: String);
+
+    /// Returns the formats the current content of the [`Clipboard`] is
+    /// available in.
+    ///
+    /// Formats are MIME types in most platforms.
+    fn formats(&self, _kind: Kind) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Reads the current content of the [`Clipboard`] in the given
+    /// [`Format`].
+    ///
+    /// By default, only [`Format::Text`] is supported.
+    fn read_content(&self, kind: Kind, format: &Format) -> Option<Content> {
+        match format {
+            Format::Text => self.read(kind).map(Content::Text),
+            _ => None,
+        }
+    }
+
+    /// Writes the given contents to the [`Clipboard`], each one in its own
+    /// format.
+    ///
+    /// Readers can then choose the format they prefer; for instance, some
+    /// [`Content::Html`] may be paired with a [`Content::Text`] fallback.
+    ///
+    /// By default, only the first [`Content::Text`] is written.
+    fn write_content(&mut self, kind: Kind, contents: Vec<Content>) {
+        let text = contents.into_iter().find_map(|content| match content {
+            Content::Text(text) => Some(text),
+            _ => None,
+        });
+
+        if let Some(text) = text {
+            self.write(kind, text);
+        }
+    }
 }
 
 /// The kind of [`Clipboard`].
@@ -21,6 +59,91 @@ pub enum Kind {
     Primary,
 }
 
+/// A format of some [`Content`] of a [`Clipboard`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Plain text.
+    Text,
+
+    /// HTML markup.
+    Html,
+
+    /// An image.
+    Image,
+
+    /// Some arbitrary data with the given MIME type.
+    Mime(String),
+}
+
+/// Some typed content of a [`Clipboard`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    /// Some plain text.
+    Text(String),
+
+    /// Some HTML markup.
+    Html(String),
+
+    /// An image.
+    Image {
+        /// The width of the image, in pixels.
+        width: u32,
+        /// The height of the image, in pixels.
+        height: u32,
+        /// The RGBA pixels of the image.
+        pixels: Bytes,
+    },
+
+    /// Some arbitrary data.
+    Mime {
+        /// The MIME type of the data.
+        format: String,
+        /// The raw bytes of the data.
+        bytes: Bytes,
+    },
+}
+
+impl Content {
+    /// Creates some [`Content`] containing the given text.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// Creates some [`Content`] containing the given HTML markup.
+    pub fn html(html: impl Into<String>) -> Self {
+        Self::Html(html.into())
+    }
+
+    /// Creates some [`Content`] containing an image with the given
+    /// dimensions and RGBA pixels.
+    pub fn image(width: u32, height: u32, pixels: impl Into<Bytes>) -> Self {
+        Self::Image {
+            width,
+            height,
+            pixels: pixels.into(),
+        }
+    }
+
+    /// Creates some [`Content`] containing the given bytes with the given
+    /// MIME type.
+    pub fn mime(format: impl Into<String>, bytes: impl Into<Bytes>) -> Self {
+        Self::Mime {
+            format: format.into(),
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Returns the [`Format`] of the [`Content`].
+    pub fn format(&self) -> Format {
+        match self {
+            Self::Text(_) => Format::Text,
+            Self::Html(_) => Format::Html,
+            Self::Image { .. } => Format::Image,
+            Self::Mime { format, .. } => Format::Mime(format.clone()),
+        }
+    }
+}
+
 /// A null implementation of the [`Clipboard`] trait.
 #[derive(Debug, Clone, Copy)]
 pub struct Null;
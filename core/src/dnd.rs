@@ -0,0 +1,340 @@
+//! Drag and drop data between applications and windows.
+use bytes::Bytes;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Some data that can be dragged out of an application.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Data {
+    /// A list of files.
+    Files(Vec<PathBuf>),
+
+    /// Some plain text.
+    Text(String),
+
+    /// An image.
+    Image {
+        /// The width of the image, in pixels.
+        width: u32,
+        /// The height of the image, in pixels.
+        height: u32,
+        /// The RGBA pixels of the image.
+        pixels: Bytes,
+    },
+}
+
+impl Data {
+    /// Creates some [`Data`] containing the given files.
+    pub fn files(paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self::Files(paths.into_iter().map(Into::into).collect())
+    }
+
+    /// Creates some [`Data`] containing the given text.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// Creates some [`Data`] containing an image with the given dimensions
+    /// and RGBA pixels.
+    pub fn image(width: u32, height: u32, pixels: impl Into<Bytes>) -> Self {
+        Self::Image {
+            width,
+            height,
+            pixels: pixels.into(),
+        }
+    }
+}
+
+/// The outcome of dragging some [`Data`] out of an application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The [`Data`] was dropped and accepted by its target.
+    Accepted,
+
+    /// The drag was cancelled or the [`Data`] was rejected by its target.
+    Cancelled,
+
+    /// Dragging [`Data`] out of an application is not supported by the
+    /// platform.
+    ///
+    /// Only X11, Wayland, Windows and macOS support outgoing drags for now.
+    Unsupported,
+}
+
+/// The formats of some data being dragged over a window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Offer {
+    formats: Arc<[String]>,
+}
+
+impl Offer {
+    /// The format of a list of URIs.
+    pub const URI_LIST: &'static str = "text/uri-list";
+
+    /// The formats of plain text, by order of preference.
+    pub const TEXT: &'static [&'static str] = &[
+        "text/plain;charset=utf-8",
+        "UTF8_STRING",
+        "text/plain",
+        "STRING",
+        "TEXT",
+    ];
+
+    /// Creates a new [`Offer`] with the given formats.
+    pub fn new(formats: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            formats: formats.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the formats of the [`Offer`].
+    ///
+    /// Formats are MIME types on most platforms.
+    pub fn formats(&self) -> &[String] {
+        &self.formats
+    }
+
+    /// Returns whether the [`Offer`] contains the given format.
+    pub fn contains(&self, format: &str) -> bool {
+        self.formats.iter().any(|offered| offered == format)
+    }
+
+    /// Returns the format of the [`Offer`] that can be received as a
+    /// [`Payload::Uris`], if any.
+    pub fn uris(&self) -> Option<&str> {
+        self.contains(Self::URI_LIST).then_some(Self::URI_LIST)
+    }
+
+    /// Returns the preferred format of the [`Offer`] that can be received as
+    /// [`Payload::Text`], if any.
+    pub fn text(&self) -> Option<&str> {
+        Self::TEXT
+            .iter()
+            .copied()
+            .find(|format| self.contains(format))
+    }
+
+    /// Returns the format of the [`Offer`] that is received by default when
+    /// no widget responds to the drag.
+    ///
+    /// URIs are preferred over text, and text is preferred over any other
+    /// format.
+    pub fn preferred(&self) -> Option<&str> {
+        self.uris()
+            .or_else(|| self.text())
+            .or_else(|| self.formats.first().map(String::as_str))
+    }
+}
+
+/// Some data dropped into a window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Payload {
+    /// Some plain text.
+    Text(String),
+
+    /// A list of URIs, like files or links.
+    Uris(Vec<String>),
+
+    /// Some data in any other format.
+    Mime {
+        /// The format of the data.
+        format: String,
+        /// The raw contents of the data.
+        bytes: Bytes,
+    },
+}
+
+impl Payload {
+    /// Creates the [`Payload`] of some data received in the given format.
+    ///
+    /// Formats of the [`Offer::URI_LIST`] and [`Offer::TEXT`] kinds are
+    /// decoded into [`Payload::Uris`] and [`Payload::Text`], respectively.
+    pub fn new(format: impl Into<String>, bytes: impl Into<Bytes>) -> Self {
+        let format = format.into();
+        let bytes = bytes.into();
+
+        if format == Offer::URI_LIST {
+            let uris = String::from_utf8_lossy(&bytes)
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_owned)
+                .collect();
+
+            Self::Uris(uris)
+        } else if format == "STRING" {
+            Self::Text(bytes.iter().copied().map(char::from).collect())
+        } else if Offer::TEXT.contains(&format.as_str()) {
+            Self::Text(String::from_utf8_lossy(&bytes).into_owned())
+        } else {
+            Self::Mime { format, bytes }
+        }
+    }
+
+    /// Returns the local file paths of the [`Payload`], if it is a list of
+    /// URIs.
+    ///
+    /// URIs that do not point to a local file are skipped.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let Self::Uris(uris) = self else {
+            return Vec::new();
+        };
+
+        uris.iter().filter_map(|uri| path(uri)).collect()
+    }
+}
+
+/// The response of a widget to some data being dragged over it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    /// The data will be accepted in the given format if dropped.
+    Accept(String),
+
+    /// The data will be rejected if dropped.
+    Reject,
+}
+
+/// Decodes the local file path of a `file://` URI.
+fn path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+
+    // Skip the host of the URI, if any
+    let path = &path[path.find('/')?..];
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut input = path.bytes();
+
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let high = char::from(input.next()?).to_digit(16)?;
+            let low = char::from(input.next()?).to_digit(16)?;
+
+            bytes.push(u8::try_from(high * 16 + low).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        Some(PathBuf::from(std::ffi::OsString::from_vec(bytes)))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let path = String::from_utf8(bytes).ok()?;
+
+        // Windows paths look like `/C:/Users`
+        Some(PathBuf::from(
+            path.strip_prefix('/')
+                .filter(|path| path.get(1..2) == Some(":"))
+                .unwrap_or(path.as_str()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_decodes_local_file_uris() {
+        assert_eq!(
+            path("file:///home/user/notes.txt"),
+            Some(PathBuf::from("/home/user/notes.txt"))
+        );
+        assert_eq!(
+            path("file:///home/user/My%20Notes%2B1.txt"),
+            Some(PathBuf::from("/home/user/My Notes+1.txt"))
+        );
+    }
+
+    #[test]
+    fn path_skips_host_of_uri() {
+        assert_eq!(
+            path("file://localhost/tmp/a"),
+            Some(PathBuf::from("/tmp/a"))
+        );
+    }
+
+    #[test]
+    fn path_rejects_invalid_uris() {
+        assert_eq!(path("https://iced.rs/index.html"), None);
+        assert_eq!(path("file://localhost"), None);
+        assert_eq!(path("file:///tmp/%2"), None);
+        assert_eq!(path("file:///tmp/%zz"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_keeps_non_utf8_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = path("file:///tmp/%FF").expect("Decode path");
+
+        assert_eq!(path.as_os_str().as_bytes(), b"/tmp/\xFF");
+    }
+
+    #[test]
+    fn payload_parses_uri_lists() {
+        let payload = Payload::new(
+            Offer::URI_LIST,
+            &b"# comment\r\nfile:///tmp/a%20b\r\n\r\nhttps://iced.rs\r\n"[..],
+        );
+
+        assert_eq!(
+            payload,
+            Payload::Uris(vec![
+                String::from("file:///tmp/a%20b"),
+                String::from("https://iced.rs"),
+            ])
+        );
+        assert_eq!(payload.paths(), [PathBuf::from("/tmp/a b")]);
+    }
+
+    #[test]
+    fn payload_decodes_text_formats() {
+        assert_eq!(
+            Payload::new("text/plain;charset=utf-8", "héllo".as_bytes()),
+            Payload::Text(String::from("héllo"))
+        );
+
+        // `STRING` is Latin-1
+        assert_eq!(
+            Payload::new("STRING", &b"h\xE9llo"[..]),
+            Payload::Text(String::from("héllo"))
+        );
+    }
+
+    #[test]
+    fn payload_keeps_other_formats_raw() {
+        let payload = Payload::new("image/png", &b"\x89PNG"[..]);
+
+        assert_eq!(
+            payload,
+            Payload::Mime {
+                format: String::from("image/png"),
+                bytes: Bytes::from_static(b"\x89PNG"),
+            }
+        );
+        assert!(payload.paths().is_empty());
+    }
+
+    #[test]
+    fn offer_prefers_uris_then_text() {
+        let offer = Offer::new(["image/png", "TEXT", "UTF8_STRING"]);
+
+        assert_eq!(offer.uris(), None);
+        assert_eq!(offer.text(), Some("UTF8_STRING"));
+        assert_eq!(offer.preferred(), Some("UTF8_STRING"));
+
+        let offer = Offer::new(["text/plain", Offer::URI_LIST]);
+
+        assert_eq!(offer.preferred(), Some(Offer::URI_LIST));
+        assert_eq!(Offer::new(["image/png"]).preferred(), Some("image/png"));
+        assert_eq!(Offer::new(Vec::<String>::new()).preferred(), None);
+    }
+}
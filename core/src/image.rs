@@ -1,11 +1,13 @@
 //! Load and draw raster graphics.
 pub use bytes::Bytes;
 
-use crate::{Radians, Rectangle, Size};
+use crate::time::Duration;
+use crate::{Color, Radians, Rectangle, Size};
 
 use rustc_hash::FxHasher;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// A raster image that can be drawn.
 #[derive(Debug, Clone, PartialEq)]
@@ -29,17 +31,42 @@ pub struct Image<H = Handle> {
     /// This can avoid graphical glitches, specially when using
     /// [`FilterMethod::Nearest`].
     pub snap: bool,
+
+    /// The region of the image to draw, in physical pixels of the image.
+    ///
+    /// If `None`, the whole image is drawn.
+    pub crop: Option<Rectangle<u32>>,
+
+    /// The color that multiplies the pixels of the image.
+    ///
+    /// [`Color::WHITE`] leaves the image untouched.
+    pub tint: Color,
+
+    /// The color [`Adjustments`] of the image.
+    pub adjustments: Adjustments,
 }
 
 impl Image<Handle> {
     /// Creates a new [`Image`] with the given handle.
     pub fn new(handle: impl Into<Handle>) -> Self {
+        Self::from_handle(handle.into())
+    }
+}
+
+impl<H> Image<H> {
+    /// Creates a new [`Image`] with the given handle of any type.
+    ///
+    /// This is useful for renderers with their own kind of image handle.
+    pub fn from_handle(handle: H) -> Self {
         Self {
-            handle: handle.into(),
+            handle,
             filter_method: FilterMethod::default(),
             rotation: Radians(0.0),
             opacity: 1.0,
             snap: false,
+            crop: None,
+            tint: Color::WHITE,
+            adjustments: Adjustments::NONE,
         }
     }
 
@@ -66,6 +93,175 @@ impl Image<Handle> {
         self.snap = snap;
         self
     }
+
+    /// Crops the [`Image`] to the given region, in physical pixels of the
+    /// image.
+    ///
+    /// This is useful to draw a single sprite of a sprite sheet.
+    pub fn crop(mut self, region: Rectangle<u32>) -> Self {
+        self.crop = Some(region);
+        self
+    }
+
+    /// Sets the tint of the [`Image`], which multiplies its pixels.
+    pub fn tint(mut self, tint: impl Into<Color>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+
+    /// Sets the color [`Adjustments`] of the [`Image`].
+    pub fn adjustments(mut self, adjustments: Adjustments) -> Self {
+        self.adjustments = adjustments;
+        self
+    }
+
+    /// Sets the brightness of the [`Image`].
+    ///
+    /// `0.0` makes it black and `1.0` leaves it untouched.
+    pub fn brightness(mut self, brightness: f32) -> Self {
+        self.adjustments.brightness = brightness;
+        self
+    }
+
+    /// Sets the contrast of the [`Image`].
+    ///
+    /// `0.0` makes it completely gray and `1.0` leaves it untouched.
+    pub fn contrast(mut self, contrast: f32) -> Self {
+        self.adjustments.contrast = contrast;
+        self
+    }
+
+    /// Sets the saturation of the [`Image`].
+    ///
+    /// `0.0` makes it grayscale and `1.0` leaves it untouched.
+    pub fn saturation(mut self, saturation: f32) -> Self {
+        self.adjustments.saturation = saturation;
+        self
+    }
+
+    /// Turns the [`Image`] grayscale.
+    pub fn grayscale(self) -> Self {
+        self.saturation(0.0)
+    }
+
+    /// Returns the region of the [`Image`] that must be drawn, given the
+    /// size of its underlying image.
+    ///
+    /// Returns `None` if the region is empty.
+    pub fn region(&self, size: Size<u32>) -> Option<Rectangle<u32>> {
+        region(self.crop, size)
+    }
+}
+
+/// Returns the region of an image of the given size that must be drawn,
+/// given an optional crop.
+///
+/// Returns `None` if the region is empty.
+pub fn region(
+    crop: Option<Rectangle<u32>>,
+    size: Size<u32>,
+) -> Option<Rectangle<u32>> {
+    let x = crop.map_or(0, |crop| crop.x.min(size.width));
+    let y = crop.map_or(0, |crop| crop.y.min(size.height));
+
+    let width = crop.map_or(size.width, |crop| crop.width);
+    let height = crop.map_or(size.height, |crop| crop.height);
+
+    let width = width.min(size.width - x);
+    let height = height.min(size.height - y);
+
+    (width > 0 && height > 0).then_some(Rectangle {
+        x,
+        y,
+        width,
+        height,
+    })
+}
+
+/// The color adjustments of an [`Image`].
+///
+/// They behave like the `brightness`, `contrast` and `saturate` filters of
+/// CSS, and are applied in that order to the non-premultiplied sRGB
+/// channels of the image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Adjustments {
+    /// The brightness factor. `1.0` leaves the image untouched.
+    pub brightness: f32,
+
+    /// The contrast factor. `1.0` leaves the image untouched.
+    pub contrast: f32,
+
+    /// The saturation factor. `1.0` leaves the image untouched.
+    pub saturation: f32,
+}
+
+impl Adjustments {
+    /// The [`Adjustments`] that leave an image untouched.
+    pub const NONE: Self = Self {
+        brightness: 1.0,
+        contrast: 1.0,
+        saturation: 1.0,
+    };
+
+    /// Returns true if the [`Adjustments`] leave an image untouched.
+    pub fn is_none(self) -> bool {
+        self == Self::NONE
+    }
+
+    /// Returns the color matrix of the [`Adjustments`].
+    ///
+    /// Each row produces a channel by multiplying the red, green and blue
+    /// channels by its first three entries and adding the last one.
+    pub fn matrix(self) -> [[f32; 4]; 3] {
+        const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+        let Self {
+            brightness,
+            contrast,
+            saturation,
+        } = self;
+
+        // Every row of the saturation matrix adds up to 1, so it
+        // leaves the gray offset of the contrast untouched
+        let scale = brightness * contrast;
+        let offset = 0.5 * (1.0 - contrast);
+
+        let row = |channel: usize| {
+            let mut row = [0.0, 0.0, 0.0, offset];
+
+            for (i, luma) in LUMA.into_iter().enumerate() {
+                let identity = if i == channel { 1.0 } else { 0.0 };
+
+                row[i] =
+                    scale * ((1.0 - saturation) * luma + saturation * identity);
+            }
+
+            row
+        };
+
+        [row(0), row(1), row(2)]
+    }
+
+    /// Applies the [`Adjustments`] and the given tint to a non-premultiplied
+    /// sRGB color.
+    pub fn apply(self, color: Color, tint: Color) -> Color {
+        let [r, g, b] = self.matrix().map(|[r, g, b, offset]| {
+            (r * color.r + g * color.g + b * color.b + offset).clamp(0.0, 1.0)
+        });
+
+        Color {
+            r: r * tint.r,
+            g: g * tint.g,
+            b: b * tint.b,
+            a: color.a * tint.a,
+        }
+    }
+}
+
+impl Default for Adjustments {
+    fn default() -> Self {
+        Self::NONE
+    }
 }
 
 impl From<&Handle> for Image {
@@ -186,6 +382,104 @@ impl std::fmt::Debug for Handle {
     }
 }
 
+/// A sequence of decoded image frames, played one after another.
+///
+/// Every frame is an independent [`Handle`]; so it can be uploaded and cached
+/// by a renderer like any other image.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    id: Id,
+    frames: Arc<[Frame]>,
+}
+
+impl Animation {
+    /// Creates a new [`Animation`] with the given frames.
+    ///
+    /// # Panics
+    /// Panics if `frames` is empty.
+    pub fn new(frames: impl IntoIterator<Item = Frame>) -> Self {
+        let frames: Arc<[Frame]> = frames.into_iter().collect();
+
+        assert!(!frames.is_empty(), "An animation needs at least one frame");
+
+        Self {
+            id: Id::unique(),
+            frames,
+        }
+    }
+
+    /// Returns the unique identifier of the [`Animation`].
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Returns the frames of the [`Animation`].
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Returns the total duration of a single loop of the [`Animation`].
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.delay).sum()
+    }
+}
+
+impl From<Handle> for Animation {
+    fn from(handle: Handle) -> Self {
+        Self::new([Frame {
+            handle,
+            delay: Duration::ZERO,
+        }])
+    }
+}
+
+impl PartialEq for Animation {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+/// A single frame of an [`Animation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// The image of the frame.
+    pub handle: Handle,
+
+    /// The time the frame stays on screen before the next one.
+    pub delay: Duration,
+}
+
+/// The statistics of the image caches of a renderer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The amount of times a cached image was reused to draw it.
+    ///
+    /// Measuring an image does not count as a hit.
+    pub hits: u64,
+
+    /// The amount of times an image had to be loaded or rasterized.
+    pub misses: u64,
+
+    /// The memory used by the cached images, in bytes.
+    pub memory: usize,
+
+    /// The amount of layers of the texture atlas storing the images, if any.
+    pub atlas_layers: usize,
+}
+
+impl std::ops::Add for Stats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+            memory: self.memory + other.memory,
+            atlas_layers: self.atlas_layers + other.atlas_layers,
+        }
+    }
+}
+
 /// The unique identifier of some [`Handle`] data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Id(_Id);
@@ -241,4 +535,133 @@ pub trait Renderer: crate::Renderer {
 
     /// Draws an [`Image`] inside the provided `bounds`.
     fn draw_image(&mut self, image: Image<Self::Handle>, bounds: Rectangle);
+
+    /// Returns true if the image of the given [`Handle`] is still being
+    /// loaded; and, therefore, it cannot be drawn yet.
+    ///
+    /// By default, images are never loading.
+    ///
+    /// [`Handle`]: Self::Handle
+    fn is_image_loading(&self, _handle: &Self::Handle) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_close(a: Color, b: Color) -> bool {
+        [a.r - b.r, a.g - b.g, a.b - b.b, a.a - b.a]
+            .into_iter()
+            .all(|difference| difference.abs() < 1e-5)
+    }
+
+    #[test]
+    fn region_defaults_to_whole_image() {
+        assert_eq!(
+            region(None, Size::new(4, 3)),
+            Some(Rectangle {
+                x: 0,
+                y: 0,
+                width: 4,
+                height: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn region_starting_past_the_edge_is_empty() {
+        let crop = Rectangle {
+            x: 5,
+            y: 0,
+            width: 2,
+            height: 2,
+        };
+
+        assert_eq!(region(Some(crop), Size::new(4, 3)), None);
+        assert_eq!(
+            region(Some(Rectangle { x: 0, y: 3, ..crop }), Size::new(4, 3)),
+            None
+        );
+    }
+
+    #[test]
+    fn region_clamps_overflowing_crops() {
+        let crop = Rectangle {
+            x: 2,
+            y: 1,
+            width: 10,
+            height: 10,
+        };
+
+        assert_eq!(
+            region(Some(crop), Size::new(4, 3)),
+            Some(Rectangle {
+                x: 2,
+                y: 1,
+                width: 2,
+                height: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn no_adjustments_are_identity() {
+        assert_eq!(
+            Adjustments::NONE.matrix(),
+            [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+            ]
+        );
+
+        let color = Color::from_rgba(0.2, 0.4, 0.6, 0.8);
+
+        assert_eq!(Adjustments::NONE.apply(color, Color::WHITE), color);
+    }
+
+    #[test]
+    fn zero_saturation_uses_luma_weights() {
+        let grayscale = Adjustments {
+            saturation: 0.0,
+            ..Adjustments::NONE
+        };
+
+        for row in grayscale.matrix() {
+            assert_eq!(row, [0.2126, 0.7152, 0.0722, 0.0]);
+        }
+
+        let gray =
+            grayscale.apply(Color::from_rgb(1.0, 0.0, 0.0), Color::WHITE);
+
+        assert!(is_close(gray, Color::from_rgb(0.2126, 0.2126, 0.2126)));
+    }
+
+    #[test]
+    fn contrast_scales_around_half() {
+        let contrast = Adjustments {
+            contrast: 2.0,
+            ..Adjustments::NONE
+        };
+
+        let apply = |value| {
+            contrast.apply(Color::from_rgb(value, value, value), Color::WHITE)
+        };
+
+        assert!(is_close(apply(0.5), Color::from_rgb(0.5, 0.5, 0.5)));
+        assert!(is_close(apply(0.6), Color::from_rgb(0.7, 0.7, 0.7)));
+        assert!(is_close(apply(0.1), Color::BLACK));
+    }
+
+    #[test]
+    fn tint_multiplies_adjusted_color() {
+        let color = Adjustments::NONE.apply(
+            Color::from_rgba(1.0, 0.5, 1.0, 1.0),
+            Color::from_rgba(0.5, 1.0, 0.0, 0.5),
+        );
+
+        assert_eq!(color, Color::from_rgba(0.5, 0.5, 0.0, 0.5));
+    }
 }
@@ -0,0 +1,141 @@
+//! Listen to input method events and request input method support.
+use crate::Rectangle;
+
+use std::ops::Range;
+
+/// An input method event.
+///
+/// Input methods are used to compose text that cannot be typed directly
+/// with a keyboard, like Chinese, Japanese, or Korean characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// The input method was opened.
+    Opened,
+
+    /// The composing text (or "preedit") changed.
+    ///
+    /// The optional range is the byte range of the composing text that
+    /// should be highlighted as its cursor. An empty string means the
+    /// preedit was cleared.
+    Preedit(String, Option<Range<usize>>),
+
+    /// The composed text should be inserted at the cursor.
+    Commit(String),
+
+    /// The input method was closed.
+    Closed,
+}
+
+/// The composing text of an input method that has not been committed yet.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Preedit {
+    /// The contents of the [`Preedit`].
+    pub content: String,
+    /// The byte range of the content highlighted by the input method, if any.
+    pub selection: Option<Range<usize>>,
+}
+
+impl Preedit {
+    /// Creates a new [`Preedit`] from the data of an [`Event::Preedit`],
+    /// returning `None` if the content is empty.
+    pub fn new(
+        content: String,
+        selection: Option<Range<usize>>,
+    ) -> Option<Self> {
+        if content.is_empty() {
+            None
+        } else {
+            Some(Self { content, selection })
+        }
+    }
+}
+
+/// The input method requested by a widget.
+///
+/// Widgets accepting text input should request an enabled [`InputMethod`]
+/// every time they are redrawn while focused, so the shell can place the
+/// candidate window of the input method next to the caret.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum InputMethod {
+    /// No input method is allowed.
+    #[default]
+    Disabled,
+    /// Input methods are allowed.
+    Enabled {
+        /// The area of the caret, in logical coordinates.
+        ///
+        /// The candidate window of the input method will be placed close
+        /// to it, without covering it.
+        cursor: Rectangle,
+    },
+}
+
+impl InputMethod {
+    /// Returns true if the [`InputMethod`] is enabled.
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled { .. })
+    }
+
+    /// Merges two [`InputMethod`] requests into one.
+    ///
+    /// An enabled request takes precedence over a disabled one; and, if
+    /// both are enabled, the latter request wins.
+    pub fn merge(&mut self, other: &Self) {
+        if other.is_enabled() {
+            *self = *other;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Point, Size};
+
+    fn enabled(x: f32) -> InputMethod {
+        InputMethod::Enabled {
+            cursor: Rectangle::new(Point::new(x, 0.0), Size::new(1.0, 10.0)),
+        }
+    }
+
+    #[test]
+    fn merge_prefers_enabled_requests() {
+        let mut input_method = InputMethod::Disabled;
+        input_method.merge(&enabled(1.0));
+
+        assert_eq!(input_method, enabled(1.0));
+
+        input_method.merge(&InputMethod::Disabled);
+
+        assert_eq!(input_method, enabled(1.0));
+    }
+
+    #[test]
+    fn merge_prefers_latter_enabled_request() {
+        let mut input_method = enabled(1.0);
+        input_method.merge(&enabled(2.0));
+
+        assert_eq!(input_method, enabled(2.0));
+    }
+
+    #[test]
+    fn merge_keeps_disabled_requests_disabled() {
+        let mut input_method = InputMethod::Disabled;
+        input_method.merge(&InputMethod::Disabled);
+
+        assert!(!input_method.is_enabled());
+    }
+
+    #[test]
+    fn empty_preedits_are_cleared() {
+        assert_eq!(Preedit::new(String::new(), Some(0..0)), None);
+        assert_eq!(
+            Preedit::new("ka".to_owned(), None),
+            Some(Preedit {
+                content: "ka".to_owned(),
+                selection: None,
+            })
+        );
+    }
+}
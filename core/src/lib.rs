@@ -10,12 +10,15 @@
     html_logo_url = "https://raw.githubusercontent.com/iced-rs/iced/9ab6923e943f784985e9ef9ca28b10278297225d/docs/logo.svg"
 )]
 pub mod alignment;
+pub mod background;
 pub mod border;
 pub mod clipboard;
+pub mod dnd;
 pub mod event;
 pub mod font;
 pub mod gradient;
 pub mod image;
+pub mod input_method;
 pub mod keyboard;
 pub mod layout;
 pub mod mouse;
@@ -27,11 +30,11 @@ pub mod text;
 pub mod theme;
 pub mod time;
 pub mod touch;
+pub mod tray;
 pub mod widget;
 pub mod window;
 
 mod angle;
-mod background;
 mod color;
 mod content_fit;
 mod element;
@@ -58,6 +61,7 @@ pub use event::Event;
 pub use font::Font;
 pub use gradient::Gradient;
 pub use image::Image;
+pub use input_method::InputMethod;
 pub use layout::Layout;
 pub use length::Length;
 pub use overlay::Overlay;
@@ -3,7 +3,8 @@
 mod null;
 
 use crate::{
-    Background, Border, Color, Rectangle, Shadow, Size, Transformation, Vector,
+    Background, Border, Color, Pixels, Rectangle, Shadow, Size, Transformation,
+    Vector,
 };
 
 /// A component that can be used by widgets to draw themselves on a screen.
@@ -25,6 +26,37 @@ pub trait Renderer {
         self.end_layer();
     }
 
+    /// Starts recording a new layer with the given [`Effect`].
+    ///
+    /// The [`Effect`] applies to the new layer and every layer started
+    /// inside of it, as a single group. Renderers that do not support
+    /// effects will start a plain layer instead.
+    fn start_effect(&mut self, bounds: Rectangle, effect: Effect) {
+        let _ = effect;
+
+        self.start_layer(bounds);
+    }
+
+    /// Ends recording a new layer started with an [`Effect`].
+    fn end_effect(&mut self) {
+        self.end_layer();
+    }
+
+    /// Draws the primitives recorded in the given closure in a new layer
+    /// with the given [`Effect`].
+    ///
+    /// The layer will clip its contents to the provided `bounds`.
+    fn with_effect(
+        &mut self,
+        bounds: Rectangle,
+        effect: Effect,
+        f: impl FnOnce(&mut Self),
+    ) {
+        self.start_effect(bounds, effect);
+        f(self);
+        self.end_effect();
+    }
+
     /// Starts recording with a new [`Transformation`].
     fn start_transformation(&mut self, transformation: Transformation);
 
@@ -86,6 +118,68 @@ impl Default for Quad {
     }
 }
 
+/// A visual effect applied to a whole layer of a [`Renderer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Effect {
+    /// The opacity of the layer.
+    ///
+    /// Unlike the opacity of each primitive, it is applied once to the
+    /// contents of the layer after they have been composited together.
+    pub opacity: f32,
+
+    /// The blur radius of the contents of the layer.
+    pub blur: f32,
+
+    /// The blur radius of whatever lies behind the layer.
+    ///
+    /// This can be used to produce frosted glass surfaces.
+    pub backdrop_blur: f32,
+}
+
+impl Effect {
+    /// An [`Effect`] that leaves the layer untouched.
+    pub const NONE: Self = Self {
+        opacity: 1.0,
+        blur: 0.0,
+        backdrop_blur: 0.0,
+    };
+
+    /// Sets the opacity of the [`Effect`].
+    pub fn opacity(self, opacity: impl Into<f32>) -> Self {
+        Self {
+            opacity: opacity.into(),
+            ..self
+        }
+    }
+
+    /// Sets the blur radius of the contents of the [`Effect`].
+    pub fn blur(self, blur: impl Into<Pixels>) -> Self {
+        Self {
+            blur: blur.into().0,
+            ..self
+        }
+    }
+
+    /// Sets the blur radius of the backdrop of the [`Effect`].
+    pub fn backdrop_blur(self, backdrop_blur: impl Into<Pixels>) -> Self {
+        Self {
+            backdrop_blur: backdrop_blur.into().0,
+            ..self
+        }
+    }
+
+    /// Returns true if the [`Effect`] leaves the layer untouched.
+    pub fn is_none(&self) -> bool {
+        self.opacity >= 1.0 && self.blur <= 0.0 && self.backdrop_blur <= 0.0
+    }
+}
+
+impl Default for Effect {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
 /// The styling attributes of a [`Renderer`].
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Style {
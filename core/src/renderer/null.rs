@@ -114,6 +114,26 @@ impl text::Paragraph for () {
     fn span_bounds(&self, _index: usize) -> Vec<Rectangle> {
         vec![]
     }
+
+    fn hit_position(&self, _point: Point) -> Option<text::paragraph::Position> {
+        None
+    }
+
+    fn range_bounds(
+        &self,
+        _start: text::paragraph::Position,
+        _end: text::paragraph::Position,
+    ) -> Vec<Rectangle> {
+        vec![]
+    }
+
+    fn line(&self, _index: usize) -> Option<&str> {
+        None
+    }
+
+    fn line_count(&self) -> usize {
+        0
+    }
 }
 
 impl text::Editor for () {
@@ -1,6 +1,8 @@
+use crate::dnd;
 use crate::event;
 use crate::time::Instant;
 use crate::window;
+use crate::InputMethod;
 
 /// A connection to the state of a shell.
 ///
@@ -13,6 +15,8 @@ pub struct Shell<'a, Message> {
     messages: &'a mut Vec<Message>,
     event_status: event::Status,
     redraw_request: Option<window::RedrawRequest>,
+    input_method: InputMethod,
+    drop_response: Option<dnd::Response>,
     is_layout_invalid: bool,
     are_widgets_invalid: bool,
 }
@@ -24,6 +28,8 @@ impl<'a, Message> Shell<'a, Message> {
             messages,
             event_status: event::Status::Ignored,
             redraw_request: None,
+            input_method: InputMethod::Disabled,
+            drop_response: None,
             is_layout_invalid: false,
             are_widgets_invalid: false,
         }
@@ -80,6 +86,44 @@ impl<'a, Message> Shell<'a, Message> {
         self.redraw_request
     }
 
+    /// Requests the given [`InputMethod`] to be used by the shell.
+    ///
+    /// An enabled [`InputMethod`] takes precedence over a disabled one.
+    pub fn request_input_method(&mut self, input_method: &InputMethod) {
+        self.input_method.merge(input_method);
+    }
+
+    /// Returns the [`InputMethod`] requested by the widgets, if any.
+    pub fn input_method(&self) -> &InputMethod {
+        &self.input_method
+    }
+
+    /// Accepts the data being dragged over the window in the given format.
+    ///
+    /// Widgets should only respond to a [`DataHovered`] event when it is
+    /// over them.
+    ///
+    /// [`DataHovered`]: window::Event::DataHovered
+    pub fn accept_drop(&mut self, format: impl Into<String>) {
+        self.drop_response = Some(dnd::Response::Accept(format.into()));
+    }
+
+    /// Rejects the data being dragged over the window.
+    ///
+    /// Widgets should only respond to a [`DataHovered`] event when it is
+    /// over them.
+    ///
+    /// [`DataHovered`]: window::Event::DataHovered
+    pub fn reject_drop(&mut self) {
+        self.drop_response = Some(dnd::Response::Reject);
+    }
+
+    /// Returns the [`dnd::Response`] of the widgets to the data being
+    /// dragged over the window, if any.
+    pub fn drop_response(&self) -> Option<&dnd::Response> {
+        self.drop_response.as_ref()
+    }
+
     /// Returns whether the current layout is invalid or not.
     pub fn is_layout_invalid(&self) -> bool {
         self.is_layout_invalid
@@ -130,6 +174,12 @@ impl<'a, Message> Shell<'a, Message> {
             );
         }
 
+        self.input_method.merge(&other.input_method);
+
+        if other.drop_response.is_some() {
+            self.drop_response = other.drop_response;
+        }
+
         self.is_layout_invalid =
             self.is_layout_invalid || other.is_layout_invalid;
 
@@ -3,6 +3,7 @@ use crate::{Color, Radians, Rectangle, Size};
 
 use rustc_hash::FxHasher;
 use std::borrow::Cow;
+use std::fmt::Write as _;
 use std::hash::{Hash, Hasher as _};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -22,6 +23,14 @@ pub struct Svg<H = Handle> {
     /// (e.g. with a theme).
     pub color: Option<Color>,
 
+    /// The [`Color`] overrides of the elements of the [`Svg`] with a
+    /// certain class.
+    ///
+    /// Each pair maps a class name to the `fill` and `currentColor` of the
+    /// elements with that class—which makes it easy to theme multi-colored
+    /// icons (e.g. duotone icon sets).
+    pub classes: Vec<(Cow<'static, str>, Color)>,
+
     /// The rotation to be applied to the image; on its center.
     pub rotation: Radians,
 
@@ -37,6 +46,7 @@ impl Svg<Handle> {
         Self {
             handle: handle.into(),
             color: None,
+            classes: Vec::new(),
             rotation: Radians(0.0),
             opacity: 1.0,
         }
@@ -48,6 +58,17 @@ impl Svg<Handle> {
         self
     }
 
+    /// Overrides the [`Color`] of the elements of the [`Svg`] with the
+    /// given class.
+    pub fn class_color(
+        mut self,
+        class: impl Into<Cow<'static, str>>,
+        color: impl Into<Color>,
+    ) -> Self {
+        self.classes.push((class.into(), color.into()));
+        self
+    }
+
     /// Sets the rotation of the [`Svg`].
     pub fn rotation(mut self, rotation: impl Into<Radians>) -> Self {
         self.rotation = rotation.into();
@@ -72,6 +93,7 @@ impl From<&Handle> for Svg {
 pub struct Handle {
     id: u64,
     data: Arc<Data>,
+    stylesheet: Option<Arc<str>>,
 }
 
 impl Handle {
@@ -97,6 +119,26 @@ impl Handle {
         Handle {
             id: hasher.finish(),
             data: Arc::new(data),
+            stylesheet: None,
+        }
+    }
+
+    /// Injects the given CSS stylesheet into the SVG of the [`Handle`].
+    ///
+    /// The rules of the stylesheet take precedence over the presentation
+    /// attributes of the SVG; which can be used to restyle whole icon sets
+    /// at once.
+    pub fn with_stylesheet(self, stylesheet: impl Into<Arc<str>>) -> Handle {
+        let stylesheet = stylesheet.into();
+
+        let mut hasher = FxHasher::default();
+        self.data.hash(&mut hasher);
+        stylesheet.hash(&mut hasher);
+
+        Handle {
+            id: hasher.finish(),
+            data: self.data,
+            stylesheet: Some(stylesheet),
         }
     }
 
@@ -109,6 +151,11 @@ impl Handle {
     pub fn data(&self) -> &Data {
         &self.data
     }
+
+    /// Returns the CSS stylesheet injected into the SVG, if any.
+    pub fn stylesheet(&self) -> Option<&str> {
+        self.stylesheet.as_deref()
+    }
 }
 
 impl<T> From<T> for Handle
@@ -147,6 +194,73 @@ impl std::fmt::Debug for Data {
     }
 }
 
+/// Returns the CSS stylesheet of an SVG with the given [`Handle`] and class
+/// [`Color`] overrides, if any.
+///
+/// Class names are escaped, so they can only ever select a class.
+pub fn stylesheet(
+    handle: &Handle,
+    classes: &[(Cow<'static, str>, Color)],
+) -> Option<String> {
+    let mut stylesheet = handle.stylesheet().unwrap_or_default().to_owned();
+
+    for (class, color) in classes {
+        let [r, g, b, a] = color.into_rgba8();
+        let color = format!("rgba({r}, {g}, {b}, {})", f32::from(a) / 255.0);
+
+        let _ = write!(
+            stylesheet,
+            ".{} {{ fill: {color}; color: {color}; }}",
+            identifier(class)
+        );
+    }
+
+    (!stylesheet.is_empty()).then_some(stylesheet)
+}
+
+/// Injects the given CSS stylesheet at the end of an SVG document.
+///
+/// Returns `None` if the document is not closed by an `svg` element.
+pub fn inject_stylesheet(svg: &str, stylesheet: &str) -> Option<String> {
+    let end = svg.rfind("</svg>")?;
+
+    // The stylesheet must not close its own character data section
+    let stylesheet = stylesheet.replace("]]>", "]]]]><![CDATA[>");
+
+    Some(format!(
+        "{}<style><![CDATA[{stylesheet}]]></style>{}",
+        &svg[..end],
+        &svg[end..]
+    ))
+}
+
+/// Escapes a class name, so it can be used as a CSS identifier.
+fn identifier(class: &str) -> String {
+    let mut identifier = String::with_capacity(class.len());
+
+    for (i, c) in class.chars().enumerate() {
+        // Identifiers cannot start with a digit, even after a hyphen
+        let is_start = i == 0 || i == 1 && class.starts_with('-');
+
+        match c {
+            '\0' => identifier.push('\u{FFFD}'),
+            '-' if class.len() == 1 => identifier.push_str("\\-"),
+            'a'..='z' | 'A'..='Z' | '_' | '-' => identifier.push(c),
+            '0'..='9' if !is_start => identifier.push(c),
+            _ if !c.is_ascii() => identifier.push(c),
+            _ if c.is_ascii_control() || c.is_ascii_digit() => {
+                let _ = write!(identifier, "\\{:x} ", u32::from(c));
+            }
+            _ => {
+                identifier.push('\\');
+                identifier.push(c);
+            }
+        }
+    }
+
+    identifier
+}
+
 /// A [`Renderer`] that can render vector graphics.
 ///
 /// [renderer]: crate::renderer
@@ -157,3 +271,59 @@ pub trait Renderer: crate::Renderer {
     /// Draws an SVG with the given [`Handle`], an optional [`Color`] filter, and inside the provided `bounds`.
     fn draw_svg(&mut self, svg: Svg, bounds: Rectangle);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifier_keeps_valid_class_names() {
+        assert_eq!(identifier("primary"), "primary");
+        assert_eq!(identifier("fa-secondary_2"), "fa-secondary_2");
+        assert_eq!(identifier("--ícono"), "--ícono");
+    }
+
+    #[test]
+    fn identifier_escapes_special_characters() {
+        assert_eq!(identifier("a{b}"), "a\\{b\\}");
+        assert_eq!(identifier("a]]>b"), "a\\]\\]\\>b");
+        assert_eq!(identifier("a b\nc"), "a\\ b\\a c");
+        assert_eq!(identifier("-"), "\\-");
+    }
+
+    #[test]
+    fn identifier_escapes_leading_digits() {
+        assert_eq!(identifier("1st"), "\\31 st");
+        assert_eq!(identifier("-2"), "-\\32 ");
+    }
+
+    #[test]
+    fn stylesheet_appends_class_rules() {
+        let handle = Handle::from_memory(&b"<svg/>"[..])
+            .with_stylesheet(".a { stroke: red; }");
+
+        assert_eq!(
+            stylesheet(&handle, &[(Cow::Borrowed("b}"), Color::WHITE)]),
+            Some(String::from(
+                ".a { stroke: red; }.b\\} { fill: rgba(255, 255, 255, 1); \
+                 color: rgba(255, 255, 255, 1); }"
+            ))
+        );
+    }
+
+    #[test]
+    fn stylesheet_is_none_without_rules() {
+        let handle = Handle::from_memory(&b"<svg/>"[..]);
+
+        assert_eq!(stylesheet(&handle, &[]), None);
+    }
+
+    #[test]
+    fn inject_stylesheet_keeps_character_data_closed() {
+        assert_eq!(
+            inject_stylesheet("<svg></svg>", "a]]>b").as_deref(),
+            Some("<svg><style><![CDATA[a]]]]><![CDATA[>b]]></style></svg>")
+        );
+        assert_eq!(inject_stylesheet("<svg/>", "a"), None);
+    }
+}
@@ -271,7 +271,7 @@ pub struct Span<'a, Link = (), Font = crate::Font> {
 }
 
 /// A text highlight.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Highlight {
     /// The [`Background`] of the highlight.
     pub background: Background,
@@ -49,6 +49,20 @@ pub trait Paragraph: Sized + Default {
     /// Returns the distance to the given grapheme index in the [`Paragraph`].
     fn grapheme_position(&self, line: usize, index: usize) -> Option<Point>;
 
+    /// Tests whether the provided point is within the boundaries of the
+    /// [`Paragraph`], returning the [`Position`] of the nearest cursor.
+    fn hit_position(&self, point: Point) -> Option<Position>;
+
+    /// Returns the bounds of every visual line covered by the text between
+    /// the given [`Position`]s.
+    fn range_bounds(&self, start: Position, end: Position) -> Vec<Rectangle>;
+
+    /// Returns the text of the given line in the [`Paragraph`], if it exists.
+    fn line(&self, index: usize) -> Option<&str>;
+
+    /// Returns the amount of lines in the [`Paragraph`].
+    fn line_count(&self) -> usize;
+
     /// Returns the minimum width that can fit the contents of the [`Paragraph`].
     fn min_width(&self) -> f32 {
         self.min_bounds().width
@@ -60,6 +74,17 @@ pub trait Paragraph: Sized + Default {
     }
 }
 
+/// A position in a [`Paragraph`].
+///
+/// The `index` is a byte offset into the given `line`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Position {
+    /// The line of the [`Position`].
+    pub line: usize,
+    /// The byte offset of the [`Position`] in its line.
+    pub index: usize,
+}
+
 /// A [`Paragraph`] of plain text.
 #[derive(Debug, Clone, Default)]
 pub struct Plain<P: Paragraph> {
@@ -129,6 +154,11 @@ impl<P: Paragraph> Plain<P> {
         self.raw.min_width()
     }
 
+    /// Returns the contents of the [`Paragraph`].
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
     /// Returns the cached [`Paragraph`].
     pub fn raw(&self) -> &P {
         &self.raw
@@ -0,0 +1,491 @@
+//! Show an icon with a menu in the system tray.
+use crate::window;
+
+use bytes::Bytes;
+use std::fmt;
+
+/// An icon in the system tray with an optional [`Menu`].
+///
+/// A [`Tray`] is described declaratively, just like the widgets of a
+/// window: it is rebuilt after every update and the runtime takes care
+/// of synchronizing it with the platform.
+pub struct Tray<Message> {
+    /// The [`Icon`] of the [`Tray`].
+    pub icon: Icon,
+
+    /// The title of the [`Tray`], used by some platforms to identify it.
+    pub title: Option<String>,
+
+    /// The tooltip shown when hovering the [`Tray`].
+    pub tooltip: Option<String>,
+
+    /// The [`Menu`] of the [`Tray`].
+    pub menu: Menu<Message>,
+
+    on_click: Option<Box<dyn Fn() -> Message + Send>>,
+    on_middle_click: Option<Box<dyn Fn() -> Message + Send>>,
+}
+
+impl<Message> Tray<Message> {
+    /// Creates a new [`Tray`] with the given [`Icon`].
+    pub fn new(icon: impl Into<Icon>) -> Self {
+        Self {
+            icon: icon.into(),
+            title: None,
+            tooltip: None,
+            menu: Menu::new(),
+            on_click: None,
+            on_middle_click: None,
+        }
+    }
+
+    /// Sets the title of the [`Tray`].
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the tooltip of the [`Tray`].
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Sets the [`Menu`] of the [`Tray`].
+    pub fn menu(mut self, menu: Menu<Message>) -> Self {
+        self.menu = menu;
+        self
+    }
+
+    /// Sets the message that will be produced when the [`Tray`] is
+    /// clicked.
+    ///
+    /// Some platforms open the [`Menu`] on click instead.
+    pub fn on_click(self, message: Message) -> Self
+    where
+        Message: Clone + Send + 'static,
+    {
+        self.on_click_with(move || message.clone())
+    }
+
+    /// Sets the message that will be produced when the [`Tray`] is
+    /// clicked.
+    ///
+    /// This is analogous to [`Tray::on_click`], but using a closure to
+    /// produce the message.
+    pub fn on_click_with(
+        mut self,
+        on_click: impl Fn() -> Message + Send + 'static,
+    ) -> Self {
+        self.on_click = Some(Box::new(on_click));
+        self
+    }
+
+    /// Sets the message that will be produced when the [`Tray`] is
+    /// clicked with the middle button.
+    pub fn on_middle_click(mut self, message: Message) -> Self
+    where
+        Message: Clone + Send + 'static,
+    {
+        self.on_middle_click = Some(Box::new(move || message.clone()));
+        self
+    }
+
+    /// Returns true if the [`Tray`] produces a message when clicked.
+    pub fn is_clickable(&self) -> bool {
+        self.on_click.is_some()
+    }
+
+    /// Produces the message of a click in the [`Tray`], if any.
+    pub fn click(&self) -> Option<Message> {
+        self.on_click.as_ref().map(|on_click| on_click())
+    }
+
+    /// Produces the message of a middle click in the [`Tray`], if any.
+    pub fn middle_click(&self) -> Option<Message> {
+        self.on_middle_click
+            .as_ref()
+            .map(|on_middle_click| on_middle_click())
+    }
+}
+
+impl<Message> fmt::Debug for Tray<Message> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tray")
+            .field("icon", &self.icon)
+            .field("title", &self.title)
+            .field("tooltip", &self.tooltip)
+            .field("menu", &self.menu)
+            .field("on_click", &self.on_click.is_some())
+            .field("on_middle_click", &self.on_middle_click.is_some())
+            .finish()
+    }
+}
+
+/// The icon of a [`Tray`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Icon {
+    /// An icon of the current icon theme with the given name.
+    ///
+    /// Only supported on Linux.
+    Named(String),
+
+    /// An image.
+    Rgba {
+        /// The width of the image, in pixels.
+        width: u32,
+        /// The height of the image, in pixels.
+        height: u32,
+        /// The RGBA pixels of the image.
+        pixels: Bytes,
+    },
+}
+
+impl Icon {
+    /// Creates an [`Icon`] of the current icon theme with the given name.
+    pub fn named(name: impl Into<String>) -> Self {
+        Self::Named(name.into())
+    }
+
+    /// Creates an [`Icon`] with the given dimensions and RGBA pixels.
+    pub fn rgba(width: u32, height: u32, pixels: impl Into<Bytes>) -> Self {
+        Self::Rgba {
+            width,
+            height,
+            pixels: pixels.into(),
+        }
+    }
+}
+
+impl From<window::Icon> for Icon {
+    fn from(icon: window::Icon) -> Self {
+        let (pixels, size) = icon.into_raw();
+
+        Self::rgba(size.width, size.height, pixels)
+    }
+}
+
+/// A menu of a [`Tray`].
+pub struct Menu<Message> {
+    /// The items of the [`Menu`].
+    pub items: Vec<Item<Message>>,
+}
+
+impl<Message> Menu<Message> {
+    /// Creates an empty [`Menu`].
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Creates a [`Menu`] with the given items.
+    pub fn with_items(items: impl IntoIterator<Item = Item<Message>>) -> Self {
+        Self {
+            items: items.into_iter().collect(),
+        }
+    }
+
+    /// Adds an [`Item`] to the [`Menu`].
+    pub fn push(mut self, item: impl Into<Item<Message>>) -> Self {
+        self.items.push(item.into());
+        self
+    }
+
+    /// Returns true if the [`Menu`] has no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<Message> Default for Menu<Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Message> fmt::Debug for Menu<Message> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.items).finish()
+    }
+}
+
+/// An item of a [`Menu`].
+pub enum Item<Message> {
+    /// A button that can be clicked.
+    Button {
+        /// The label of the button.
+        label: String,
+        /// The function producing the message of a click, if enabled.
+        on_click: Option<Box<dyn Fn() -> Message + Send>>,
+    },
+
+    /// A checkbox that can be toggled.
+    Checkbox {
+        /// The label of the checkbox.
+        label: String,
+        /// Whether the checkbox is checked.
+        is_checked: bool,
+        /// The function producing the message of a toggle, if enabled.
+        on_toggle: Option<Box<dyn Fn(bool) -> Message + Send>>,
+    },
+
+    /// A line separating groups of items.
+    Separator,
+
+    /// A nested [`Menu`].
+    Submenu {
+        /// The label of the submenu.
+        label: String,
+        /// The [`Menu`] shown when the submenu is hovered.
+        menu: Menu<Message>,
+    },
+}
+
+impl<Message> Item<Message> {
+    /// Creates a disabled button [`Item`] with the given label.
+    ///
+    /// Use [`Item::on_click`] to enable it.
+    pub fn button(label: impl Into<String>) -> Self {
+        Self::Button {
+            label: label.into(),
+            on_click: None,
+        }
+    }
+
+    /// Creates a disabled checkbox [`Item`] with the given label and
+    /// state.
+    ///
+    /// Use [`Item::on_toggle`] to enable it.
+    pub fn checkbox(label: impl Into<String>, is_checked: bool) -> Self {
+        Self::Checkbox {
+            label: label.into(),
+            is_checked,
+            on_toggle: None,
+        }
+    }
+
+    /// Creates a separator [`Item`].
+    pub fn separator() -> Self {
+        Self::Separator
+    }
+
+    /// Creates a submenu [`Item`] with the given label and [`Menu`].
+    pub fn submenu(label: impl Into<String>, menu: Menu<Message>) -> Self {
+        Self::Submenu {
+            label: label.into(),
+            menu,
+        }
+    }
+
+    /// Sets the message that will be produced when the button [`Item`]
+    /// is clicked.
+    ///
+    /// It has no effect on other kinds of items.
+    pub fn on_click(self, message: Message) -> Self
+    where
+        Message: Clone + Send + 'static,
+    {
+        match self {
+            Self::Button { label, .. } => Self::Button {
+                label,
+                on_click: Some(Box::new(move || message.clone())),
+            },
+            item => item,
+        }
+    }
+
+    /// Sets the function that will be called when the checkbox [`Item`]
+    /// is toggled.
+    ///
+    /// It receives the new state of the checkbox and must produce a
+    /// message. It has no effect on other kinds of items.
+    pub fn on_toggle(
+        self,
+        on_toggle: impl Fn(bool) -> Message + Send + 'static,
+    ) -> Self {
+        match self {
+            Self::Checkbox {
+                label, is_checked, ..
+            } => Self::Checkbox {
+                label,
+                is_checked,
+                on_toggle: Some(Box::new(on_toggle)),
+            },
+            item => item,
+        }
+    }
+
+    /// Returns the label of the [`Item`], if any.
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            Self::Button { label, .. }
+            | Self::Checkbox { label, .. }
+            | Self::Submenu { label, .. } => Some(label),
+            Self::Separator => None,
+        }
+    }
+
+    /// Returns true if the [`Item`] can be interacted with.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Self::Button { on_click, .. } => on_click.is_some(),
+            Self::Checkbox { on_toggle, .. } => on_toggle.is_some(),
+            Self::Separator => false,
+            Self::Submenu { .. } => true,
+        }
+    }
+
+    /// Produces the message of activating the [`Item`], if any.
+    ///
+    /// Checkboxes produce the message of toggling them.
+    pub fn activate(&self) -> Option<Message> {
+        match self {
+            Self::Button { on_click, .. } => {
+                on_click.as_ref().map(|on_click| on_click())
+            }
+            Self::Checkbox {
+                is_checked,
+                on_toggle,
+                ..
+            } => on_toggle.as_ref().map(|on_toggle| on_toggle(!is_checked)),
+            Self::Separator | Self::Submenu { .. } => None,
+        }
+    }
+}
+
+impl<Message> fmt::Debug for Item<Message> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Button { label, on_click } => f
+                .debug_struct("Button")
+                .field("label", label)
+                .field("on_click", &on_click.is_some())
+                .finish(),
+            Self::Checkbox {
+                label,
+                is_checked,
+                on_toggle,
+            } => f
+                .debug_struct("Checkbox")
+                .field("label", label)
+                .field("is_checked", is_checked)
+                .field("on_toggle", &on_toggle.is_some())
+                .finish(),
+            Self::Separator => f.write_str("Separator"),
+            Self::Submenu { label, menu } => f
+                .debug_struct("Submenu")
+                .field("label", label)
+                .field("menu", menu)
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tray_is_clickable_only_with_on_click() {
+        let tray = Tray::<i32>::new(Icon::named("app"));
+
+        assert!(!tray.is_clickable());
+        assert_eq!(tray.click(), None);
+        assert_eq!(tray.middle_click(), None);
+
+        let tray = tray.on_click(1).on_middle_click(2);
+
+        assert!(tray.is_clickable());
+        assert_eq!(tray.click(), Some(1));
+        assert_eq!(tray.middle_click(), Some(2));
+    }
+
+    #[test]
+    fn tray_builders_set_fields() {
+        let tray = Tray::<()>::new(Icon::named("app"))
+            .title("Title")
+            .tooltip("Tooltip")
+            .menu(Menu::new().push(Item::separator()));
+
+        assert_eq!(tray.icon, Icon::Named("app".to_owned()));
+        assert_eq!(tray.title.as_deref(), Some("Title"));
+        assert_eq!(tray.tooltip.as_deref(), Some("Tooltip"));
+        assert_eq!(tray.menu.items.len(), 1);
+    }
+
+    #[test]
+    fn icon_from_window_icon_keeps_pixels() {
+        let pixels = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let icon = window::icon::from_rgba(pixels.clone(), 2, 1)
+            .expect("Create window icon");
+
+        assert_eq!(Icon::from(icon), Icon::rgba(2, 1, pixels));
+    }
+
+    #[test]
+    fn menu_is_empty() {
+        assert!(Menu::<()>::new().is_empty());
+        assert!(Menu::<()>::default().is_empty());
+        assert!(!Menu::<()>::with_items([Item::separator()]).is_empty());
+    }
+
+    #[test]
+    fn buttons_are_enabled_by_on_click() {
+        let button = Item::button("Open");
+
+        assert_eq!(button.label(), Some("Open"));
+        assert!(!button.is_enabled());
+        assert_eq!(button.activate(), None);
+
+        let button = button.on_click(1);
+
+        assert!(button.is_enabled());
+        assert_eq!(button.activate(), Some(1));
+    }
+
+    #[test]
+    fn checkboxes_toggle_their_state() {
+        let checkbox = Item::checkbox("Sync", true);
+
+        assert!(!checkbox.is_enabled());
+        assert_eq!(checkbox.activate(), None);
+
+        let checkbox = checkbox.on_toggle(|is_checked| is_checked);
+
+        assert!(checkbox.is_enabled());
+        assert_eq!(checkbox.activate(), Some(false));
+
+        let checkbox =
+            Item::checkbox("Sync", false).on_toggle(|is_checked| is_checked);
+
+        assert_eq!(checkbox.activate(), Some(true));
+    }
+
+    #[test]
+    fn handlers_only_apply_to_their_kind() {
+        let separator = Item::separator().on_click(1).on_toggle(|_| 2);
+
+        assert!(matches!(separator, Item::Separator));
+        assert_eq!(separator.label(), None);
+        assert!(!separator.is_enabled());
+
+        let button = Item::button("Open").on_toggle(|_| 2);
+
+        assert!(!button.is_enabled());
+
+        let checkbox = Item::checkbox("Sync", false).on_click(1);
+
+        assert!(!checkbox.is_enabled());
+    }
+
+    #[test]
+    fn submenus_are_enabled_but_not_activated() {
+        let submenu = Item::submenu(
+            "More",
+            Menu::new().push(Item::button("A").on_click(1)),
+        );
+
+        assert_eq!(submenu.label(), Some("More"));
+        assert!(submenu.is_enabled());
+        assert_eq!(submenu.activate(), None);
+    }
+}
@@ -21,17 +21,22 @@
 //! }
 //! ```
 use crate::alignment;
+use crate::clipboard::{self, Clipboard};
+use crate::keyboard;
 use crate::layout;
 use crate::mouse;
+use crate::mouse::click;
 use crate::renderer;
 use crate::text;
-use crate::text::paragraph::{self, Paragraph};
+use crate::text::paragraph::{self, Paragraph, Position};
 use crate::widget::tree::{self, Tree};
 use crate::{
-    Color, Element, Layout, Length, Pixels, Point, Rectangle, Size, Theme,
-    Widget,
+    Color, Element, Event, Layout, Length, Pixels, Point, Rectangle, Shell,
+    Size, Theme, Vector, Widget,
 };
 
+use unicode_segmentation::UnicodeSegmentation;
+
 pub use text::{LineHeight, Shaping, Wrapping};
 
 /// A bunch of text.
@@ -72,6 +77,7 @@ where
     font: Option<Renderer::Font>,
     shaping: Shaping,
     wrapping: Wrapping,
+    selectable: bool,
     class: Theme::Class<'a>,
 }
 
@@ -93,6 +99,7 @@ where
             vertical_alignment: alignment::Vertical::Top,
             shaping: Shaping::default(),
             wrapping: Wrapping::default(),
+            selectable: false,
             class: Theme::default(),
         }
     }
@@ -165,6 +172,14 @@ where
         self
     }
 
+    /// Sets whether the [`Text`] can be selected and copied by the user.
+    ///
+    /// By default, it cannot.
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
     /// Sets the style of the [`Text`].
     #[must_use]
     pub fn style(mut self, style: impl Fn(&Theme) -> Style + 'a) -> Self
@@ -190,7 +205,10 @@ where
     {
         let color = color.map(Into::into);
 
-        self.style(move |_theme| Style { color })
+        self.style(move |_theme| Style {
+            color,
+            ..Style::default()
+        })
     }
 
     /// Sets the style class of the [`Text`].
@@ -204,7 +222,7 @@ where
 
 /// The internal state of a [`Text`] widget.
 #[derive(Debug, Default)]
-pub struct State<P: Paragraph>(pub paragraph::Plain<P>);
+pub struct State<P: Paragraph>(pub paragraph::Plain<P>, Selection);
 
 impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
     for Text<'_, Theme, Renderer>
@@ -217,9 +235,7 @@ where
     }
 
     fn state(&self) -> tree::State {
-        tree::State::new(State::<Renderer::Paragraph>(
-            paragraph::Plain::default(),
-        ))
+        tree::State::new(State::<Renderer::Paragraph>::default())
     }
 
     fn size(&self) -> Size<Length> {
@@ -265,8 +281,62 @@ where
         let state = tree.state.downcast_ref::<State<Renderer::Paragraph>>();
         let style = theme.style(&self.class);
 
+        if self.selectable {
+            state.1.draw(
+                renderer,
+                layout.bounds(),
+                state.0.raw(),
+                style.selection.unwrap_or_else(|| {
+                    style.color.unwrap_or(defaults.text_color).scale_alpha(0.3)
+                }),
+            );
+        }
+
         draw(renderer, defaults, layout, state.0.raw(), style, viewport);
     }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        if !self.selectable {
+            return;
+        }
+
+        let State(paragraph, selection) =
+            tree.state.downcast_mut::<State<Renderer::Paragraph>>();
+
+        selection.update(
+            &event,
+            layout.bounds(),
+            cursor,
+            paragraph.raw(),
+            clipboard,
+            shell,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if self.selectable && cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Text
+        } else {
+            mouse::Interaction::None
+        }
+    }
 }
 
 /// Produces the [`layout::Node`] of a [`Text`] widget.
@@ -294,7 +364,11 @@ where
         let size = size.unwrap_or_else(|| renderer.default_size());
         let font = font.unwrap_or_else(|| renderer.default_font());
 
-        let State(ref mut paragraph) = state;
+        let State(paragraph, selection) = state;
+
+        if paragraph.content() != content {
+            *selection = Selection::default();
+        }
 
         paragraph.update(text::Text {
             content,
@@ -312,6 +386,283 @@ where
     })
 }
 
+/// The text selection of a selectable [`Text`].
+///
+/// It can be reused by other widgets displaying a [`Paragraph`] to support
+/// selecting and copying its contents.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Selection {
+    anchor: Position,
+    focus: Position,
+    drag: Option<Drag>,
+    last_click: Option<mouse::Click>,
+    modifiers: keyboard::Modifiers,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    kind: click::Kind,
+    origin: (Position, Position),
+}
+
+impl Selection {
+    /// Returns true if the [`Selection`] contains no text.
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.focus
+    }
+
+    /// Returns the start and end [`Position`] of the [`Selection`], in order.
+    pub fn range(&self) -> (Position, Position) {
+        (self.anchor.min(self.focus), self.anchor.max(self.focus))
+    }
+
+    /// Clears the [`Selection`].
+    pub fn clear(&mut self) {
+        self.anchor = self.focus;
+        self.drag = None;
+    }
+
+    /// Returns the selected text of the given [`Paragraph`].
+    ///
+    /// Lines are joined with a line break.
+    pub fn text(&self, paragraph: &impl Paragraph) -> String {
+        let (start, end) = self.range();
+
+        (start.line..=end.line)
+            .filter_map(|line| {
+                let text = paragraph.line(line)?;
+
+                let from = if line == start.line { start.index } else { 0 };
+                let to = if line == end.line {
+                    end.index
+                } else {
+                    text.len()
+                };
+
+                text.get(from..to.min(text.len()))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Processes an [`Event`] related to the [`Selection`] of a [`Paragraph`]
+    /// displayed inside the given bounds.
+    pub fn update<Message>(
+        &mut self,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        paragraph: &impl Paragraph,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let Some(cursor_position) = cursor.position_over(bounds) else {
+                    if !self.is_empty() {
+                        self.clear();
+                        shell.request_redraw();
+                    }
+
+                    return;
+                };
+
+                let Some(position) = paragraph
+                    .hit_position(cursor_position - offset(bounds, paragraph))
+                else {
+                    return;
+                };
+
+                let click = mouse::Click::new(
+                    cursor_position,
+                    mouse::Button::Left,
+                    self.last_click,
+                );
+
+                let origin = match click.kind() {
+                    click::Kind::Single => {
+                        if self.modifiers.shift() {
+                            self.focus = position;
+                        } else {
+                            self.anchor = position;
+                            self.focus = position;
+                        }
+
+                        (position, position)
+                    }
+                    click::Kind::Double => word_at(paragraph, position),
+                    click::Kind::Triple => line_at(paragraph, position),
+                };
+
+                if !matches!(click.kind(), click::Kind::Single) {
+                    self.anchor = origin.0;
+                    self.focus = origin.1;
+                }
+
+                self.drag = Some(Drag {
+                    kind: click.kind(),
+                    origin,
+                });
+                self.last_click = Some(click);
+
+                shell.capture_event();
+                shell.request_redraw();
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let Some(drag) = self.drag else {
+                    return;
+                };
+
+                let Some(cursor_position) = cursor.position() else {
+                    return;
+                };
+
+                let Some(position) = paragraph
+                    .hit_position(cursor_position - offset(bounds, paragraph))
+                else {
+                    return;
+                };
+
+                let (start, end) = match drag.kind {
+                    click::Kind::Single => (self.anchor, position),
+                    click::Kind::Double => word_at(paragraph, position),
+                    click::Kind::Triple => line_at(paragraph, position),
+                };
+
+                if matches!(drag.kind, click::Kind::Single) {
+                    self.focus = end;
+                } else if start < drag.origin.0 {
+                    self.anchor = drag.origin.1;
+                    self.focus = start;
+                } else {
+                    self.anchor = drag.origin.0;
+                    self.focus = end.max(drag.origin.1);
+                }
+
+                shell.request_redraw();
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                self.drag = None;
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => {
+                if let keyboard::Key::Character("c") = key.as_ref() {
+                    if self.modifiers.command() && !self.is_empty() {
+                        clipboard.write(
+                            clipboard::Kind::Standard,
+                            self.text(paragraph),
+                        );
+
+                        shell.capture_event();
+                    }
+                }
+            }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                self.modifiers = *modifiers;
+            }
+            _ => {}
+        }
+    }
+
+    /// Draws the highlight of the [`Selection`] of a [`Paragraph`] displayed
+    /// inside the given bounds.
+    pub fn draw<Renderer>(
+        &self,
+        renderer: &mut Renderer,
+        bounds: Rectangle,
+        paragraph: &Renderer::Paragraph,
+        color: Color,
+    ) where
+        Renderer: text::Renderer,
+    {
+        if self.is_empty() {
+            return;
+        }
+
+        let (start, end) = self.range();
+        let translation = offset(bounds, paragraph);
+
+        for region in paragraph.range_bounds(start, end) {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: region + translation,
+                    ..renderer::Quad::default()
+                },
+                color,
+            );
+        }
+    }
+}
+
+fn offset(bounds: Rectangle, paragraph: &impl Paragraph) -> Vector {
+    let min_bounds = paragraph.min_bounds();
+
+    let x = match paragraph.horizontal_alignment() {
+        alignment::Horizontal::Left => bounds.x,
+        alignment::Horizontal::Center => {
+            bounds.center_x() - min_bounds.width / 2.0
+        }
+        alignment::Horizontal::Right => {
+            bounds.x + bounds.width - min_bounds.width
+        }
+    };
+
+    let y = match paragraph.vertical_alignment() {
+        alignment::Vertical::Top => bounds.y,
+        alignment::Vertical::Center => {
+            bounds.center_y() - min_bounds.height / 2.0
+        }
+        alignment::Vertical::Bottom => {
+            bounds.y + bounds.height - min_bounds.height
+        }
+    };
+
+    Vector::new(x, y)
+}
+
+fn word_at(
+    paragraph: &impl Paragraph,
+    position: Position,
+) -> (Position, Position) {
+    let Some(line) = paragraph.line(position.line) else {
+        return (position, position);
+    };
+
+    let (start, word) = line
+        .split_word_bound_indices()
+        .find(|(start, word)| position.index < start + word.len())
+        .or_else(|| line.split_word_bound_indices().next_back())
+        .unwrap_or((0, ""));
+
+    (
+        Position {
+            line: position.line,
+            index: start,
+        },
+        Position {
+            line: position.line,
+            index: start + word.len(),
+        },
+    )
+}
+
+fn line_at(
+    paragraph: &impl Paragraph,
+    position: Position,
+) -> (Position, Position) {
+    let length = paragraph.line(position.line).map(str::len).unwrap_or(0);
+
+    (
+        Position {
+            line: position.line,
+            index: 0,
+        },
+        Position {
+            line: position.line,
+            index: length,
+        },
+    )
+}
+
 /// Draws text using the same logic as the [`Text`] widget.
 ///
 /// Specifically:
@@ -395,6 +746,11 @@ pub struct Style {
     ///
     /// The default, `None`, means using the inherited color.
     pub color: Option<Color>,
+    /// The [`Color`] of the selection highlight of selectable text.
+    ///
+    /// The default, `None`, means using a translucent version of the
+    /// text color.
+    pub selection: Option<Color>,
 }
 
 /// The theme catalog of a [`Text`].
@@ -428,13 +784,14 @@ impl Catalog for Theme {
 
 /// The default text styling; color is inherited.
 pub fn default(_theme: &Theme) -> Style {
-    Style { color: None }
+    Style::default()
 }
 
 /// Text with the default base color.
 pub fn base(theme: &Theme) -> Style {
     Style {
         color: Some(theme.palette().text),
+        ..Style::default()
     }
 }
 
@@ -442,6 +799,7 @@ pub fn base(theme: &Theme) -> Style {
 pub fn primary(theme: &Theme) -> Style {
     Style {
         color: Some(theme.palette().primary),
+        ..Style::default()
     }
 }
 
@@ -449,6 +807,7 @@ pub fn primary(theme: &Theme) -> Style {
 pub fn secondary(theme: &Theme) -> Style {
     Style {
         color: Some(theme.extended_palette().secondary.strong.color),
+        ..Style::default()
     }
 }
 
@@ -456,6 +815,7 @@ pub fn secondary(theme: &Theme) -> Style {
 pub fn success(theme: &Theme) -> Style {
     Style {
         color: Some(theme.palette().success),
+        ..Style::default()
     }
 }
 
@@ -463,5 +823,173 @@ pub fn success(theme: &Theme) -> Style {
 pub fn danger(theme: &Theme) -> Style {
     Style {
         color: Some(theme.palette().danger),
+        ..Style::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A [`Paragraph`] that only knows about its lines.
+    #[derive(Default)]
+    struct Lines(Vec<&'static str>);
+
+    impl Paragraph for Lines {
+        type Font = crate::Font;
+
+        fn with_text(_text: text::Text<&str>) -> Self {
+            Self::default()
+        }
+
+        fn with_spans<Link>(
+            _text: text::Text<&[text::Span<'_, Link, Self::Font>], Self::Font>,
+        ) -> Self {
+            Self::default()
+        }
+
+        fn resize(&mut self, _new_bounds: Size) {}
+
+        fn compare(&self, _text: text::Text<()>) -> text::Difference {
+            text::Difference::None
+        }
+
+        fn horizontal_alignment(&self) -> alignment::Horizontal {
+            alignment::Horizontal::Left
+        }
+
+        fn vertical_alignment(&self) -> alignment::Vertical {
+            alignment::Vertical::Top
+        }
+
+        fn min_bounds(&self) -> Size {
+            Size::ZERO
+        }
+
+        fn hit_test(&self, _point: Point) -> Option<text::Hit> {
+            None
+        }
+
+        fn hit_span(&self, _point: Point) -> Option<usize> {
+            None
+        }
+
+        fn span_bounds(&self, _index: usize) -> Vec<Rectangle> {
+            Vec::new()
+        }
+
+        fn grapheme_position(
+            &self,
+            _line: usize,
+            _index: usize,
+        ) -> Option<Point> {
+            None
+        }
+
+        fn hit_position(&self, _point: Point) -> Option<Position> {
+            None
+        }
+
+        fn range_bounds(
+            &self,
+            _start: Position,
+            _end: Position,
+        ) -> Vec<Rectangle> {
+            Vec::new()
+        }
+
+        fn line(&self, index: usize) -> Option<&str> {
+            self.0.get(index).copied()
+        }
+
+        fn line_count(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    fn position(line: usize, index: usize) -> Position {
+        Position { line, index }
+    }
+
+    fn selection(anchor: Position, focus: Position) -> Selection {
+        Selection {
+            anchor,
+            focus,
+            ..Selection::default()
+        }
+    }
+
+    #[test]
+    fn range_is_ordered() {
+        let forward = selection(position(0, 2), position(1, 3));
+        let backward = selection(position(1, 3), position(0, 2));
+
+        assert_eq!(forward.range(), (position(0, 2), position(1, 3)));
+        assert_eq!(backward.range(), forward.range());
+        assert!(!backward.is_empty());
+    }
+
+    #[test]
+    fn clear_collapses_into_focus() {
+        let mut selection = selection(position(0, 2), position(0, 5));
+        selection.clear();
+
+        assert!(selection.is_empty());
+        assert_eq!(selection.range(), (position(0, 5), position(0, 5)));
+    }
+
+    #[test]
+    fn text_of_single_line() {
+        let paragraph = Lines(vec!["Hello, world!"]);
+
+        let selection = selection(position(0, 12), position(0, 7));
+
+        assert_eq!(selection.text(&paragraph), "world");
+    }
+
+    #[test]
+    fn text_spans_lines() {
+        let paragraph = Lines(vec!["first line", "second", "third line"]);
+
+        let selection = selection(position(0, 6), position(2, 5));
+
+        assert_eq!(selection.text(&paragraph), "line\nsecond\nthird");
+    }
+
+    #[test]
+    fn text_clamps_out_of_bounds() {
+        let paragraph = Lines(vec!["short"]);
+
+        let selection = selection(position(0, 2), position(3, 10));
+
+        assert_eq!(selection.text(&paragraph), "ort");
+    }
+
+    #[test]
+    fn word_at_finds_word_bounds() {
+        let paragraph = Lines(vec!["select some words"]);
+
+        assert_eq!(
+            word_at(&paragraph, position(0, 9)),
+            (position(0, 7), position(0, 11))
+        );
+        assert_eq!(
+            word_at(&paragraph, position(0, 17)),
+            (position(0, 12), position(0, 17))
+        );
+    }
+
+    #[test]
+    fn line_at_covers_whole_line() {
+        let paragraph = Lines(vec!["one", "two words"]);
+
+        assert_eq!(
+            line_at(&paragraph, position(1, 4)),
+            (position(1, 0), position(1, 9))
+        );
+        assert_eq!(
+            line_at(&paragraph, position(5, 0)),
+            (position(5, 0), position(5, 0))
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crate::dnd;
 use crate::time::Instant;
 use crate::{Point, Size};
 
@@ -46,29 +47,69 @@ pub enum Event {
     ///
     /// When the user hovers multiple files at once, this event will be emitted
     /// for each file separately.
-    ///
-    /// ## Platform-specific
-    ///
-    /// - **Wayland:** Not implemented.
     FileHovered(PathBuf),
 
     /// A file has been dropped into the window.
     ///
     /// When the user drops multiple files at once, this event will be emitted
     /// for each file separately.
-    ///
-    /// ## Platform-specific
-    ///
-    /// - **Wayland:** Not implemented.
     FileDropped(PathBuf),
 
     /// A file was hovered, but has exited the window.
     ///
     /// There will be a single `FilesHoveredLeft` event triggered even if
     /// multiple files were hovered.
+    FilesHoveredLeft,
+
+    /// Some data is being dragged over the window.
+    ///
+    /// This event is emitted when the data enters the window and every time
+    /// it moves afterwards. Widgets may respond to it with
+    /// [`Shell::accept_drop`] or [`Shell::reject_drop`]. Otherwise, the
+    /// [preferred] format of the [`Offer`] is accepted.
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland:** Not implemented.
-    FilesHoveredLeft,
+    /// - **Windows / macOS / Web:** Not implemented. Use [`FileHovered`]
+    ///   instead.
+    ///
+    /// [`Shell::accept_drop`]: crate::Shell::accept_drop
+    /// [`Shell::reject_drop`]: crate::Shell::reject_drop
+    /// [preferred]: dnd::Offer::preferred
+    /// [`Offer`]: dnd::Offer
+    /// [`FileHovered`]: Self::FileHovered
+    DataHovered {
+        /// The position of the cursor, in logical coordinates relative to
+        /// the window.
+        position: Point,
+        /// The formats of the data being dragged.
+        offer: dnd::Offer,
+    },
+
+    /// Some data has been dropped into the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Web:** Not implemented. Use [`FileDropped`]
+    ///   instead.
+    ///
+    /// [`FileDropped`]: Self::FileDropped
+    DataDropped {
+        /// The position of the cursor, in logical coordinates relative to
+        /// the window.
+        position: Point,
+        /// The dropped data, in the accepted format.
+        payload: dnd::Payload,
+    },
+
+    /// Some data was hovered, but has exited the window or was rejected
+    /// when dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Web:** Not implemented. Use
+    ///   [`FilesHoveredLeft`] instead.
+    ///
+    /// [`FilesHoveredLeft`]: Self::FilesHoveredLeft
+    DataHoveredLeft,
 }
@@ -17,16 +17,22 @@ struct Example {
 
 #[derive(Debug, Clone, Copy)]
 enum Message {
-    AddCurve(bezier::Curve),
+    Bezier(bezier::Message),
     Clear,
 }
 
 impl Example {
     fn update(&mut self, message: Message) {
         match message {
-            Message::AddCurve(curve) => {
+            Message::Bezier(bezier::Message::AddCurve(curve)) => {
                 self.curves.push(curve);
-                self.bezier.request_redraw();
+                self.bezier.request_redraw(&self.curves);
+            }
+            Message::Bezier(bezier::Message::MoveHandle(handle, position)) => {
+                if let Some(curve) = self.curves.get_mut(handle.curve) {
+                    *curve.point_mut(handle.point) = position;
+                    self.bezier.request_redraw(&self.curves);
+                }
             }
             Message::Clear => {
                 self.bezier = bezier::State::default();
@@ -37,7 +43,7 @@ impl Example {
 
     fn view(&self) -> Element<Message> {
         container(hover(
-            self.bezier.view(&self.curves).map(Message::AddCurve),
+            self.bezier.view(&self.curves).map(Message::Bezier),
             if self.curves.is_empty() {
                 container(horizontal_space())
             } else {
@@ -58,17 +64,24 @@ impl Example {
 mod bezier {
     use iced::mouse;
     use iced::widget::canvas::{
-        self, Canvas, Event, Frame, Geometry, Path, Stroke,
+        self, scene, Canvas, Event, Frame, Geometry, Path, Scene, Stroke,
     };
     use iced::{Element, Fill, Point, Rectangle, Renderer, Theme};
 
+    #[derive(Debug, Clone, Copy)]
+    pub enum Message {
+        AddCurve(Curve),
+        MoveHandle(Handle, Point),
+    }
+
     #[derive(Default)]
     pub struct State {
         cache: canvas::Cache,
+        scene: Scene<Shape>,
     }
 
     impl State {
-        pub fn view<'a>(&'a self, curves: &'a [Curve]) -> Element<'a, Curve> {
+        pub fn view<'a>(&'a self, curves: &'a [Curve]) -> Element<'a, Message> {
             Canvas::new(Bezier {
                 state: self,
                 curves,
@@ -78,7 +91,33 @@ mod bezier {
             .into()
         }
 
-        pub fn request_redraw(&mut self) {
+        pub fn request_redraw(&mut self, curves: &[Curve]) {
+            self.scene.clear();
+
+            for (index, curve) in curves.iter().enumerate() {
+                self.scene.push(
+                    scene::Shape::new(Shape::Curve(index), curve.path())
+                        .stroke(Stroke::default().with_width(8.0)),
+                );
+
+                for point in [Kind::From, Kind::To, Kind::Control] {
+                    let handle = Handle {
+                        curve: index,
+                        point,
+                    };
+
+                    self.scene.push(
+                        scene::Shape::new(
+                            Shape::Handle(handle),
+                            Path::circle(curve.point(point), 5.0),
+                        )
+                        .fill(iced::Color::WHITE)
+                        .z_index(1)
+                        .interaction(mouse::Interaction::Grab),
+                    );
+                }
+            }
+
             self.cache.clear();
         }
     }
@@ -88,8 +127,14 @@ mod bezier {
         curves: &'a [Curve],
     }
 
-    impl canvas::Program<Curve> for Bezier<'_> {
-        type State = Option<Pending>;
+    #[derive(Default)]
+    struct Interaction {
+        scene: scene::State<Shape>,
+        pending: Option<Pending>,
+    }
+
+    impl canvas::Program<Message> for Bezier<'_> {
+        type State = Interaction;
 
         fn update(
             &self,
@@ -97,23 +142,63 @@ mod bezier {
             event: Event,
             bounds: Rectangle,
             cursor: mouse::Cursor,
-        ) -> Option<canvas::Action<Curve>> {
-            let cursor_position = cursor.position_in(bounds)?;
+        ) -> Option<canvas::Action<Message>> {
+            let mut action = None;
+
+            for event in self.state.scene.update(
+                &mut state.scene,
+                &event,
+                bounds,
+                cursor,
+            ) {
+                match event {
+                    scene::Event::Entered(_) | scene::Event::Left(_) => {
+                        action =
+                            action.or(Some(canvas::Action::request_redraw()));
+                    }
+                    scene::Event::Pressed {
+                        id: Shape::Handle(_),
+                        ..
+                    } => {
+                        action = Some(canvas::Action::capture());
+                    }
+                    scene::Event::Dragged {
+                        id: Shape::Handle(handle),
+                        position,
+                    } => {
+                        action = Some(
+                            canvas::Action::publish(Message::MoveHandle(
+                                handle, position,
+                            ))
+                            .and_capture(),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+
+            let Some(cursor_position) = cursor.position_in(bounds) else {
+                return action;
+            };
+
+            if let Some(Shape::Handle(_)) = state.scene.pressed() {
+                return action;
+            }
 
             match event {
                 Event::Mouse(mouse::Event::ButtonPressed(
                     mouse::Button::Left,
                 )) => Some(
-                    match *state {
+                    match state.pending {
                         None => {
-                            *state = Some(Pending::One {
+                            state.pending = Some(Pending::One {
                                 from: cursor_position,
                             });
 
                             canvas::Action::request_redraw()
                         }
                         Some(Pending::One { from }) => {
-                            *state = Some(Pending::Two {
+                            state.pending = Some(Pending::Two {
                                 from,
                                 to: cursor_position,
                             });
@@ -121,23 +206,23 @@ mod bezier {
                             canvas::Action::request_redraw()
                         }
                         Some(Pending::Two { from, to }) => {
-                            *state = None;
+                            state.pending = None;
 
-                            canvas::Action::publish(Curve {
+                            canvas::Action::publish(Message::AddCurve(Curve {
                                 from,
                                 to,
                                 control: cursor_position,
-                            })
+                            }))
                         }
                     }
                     .and_capture(),
                 ),
                 Event::Mouse(mouse::Event::CursorMoved { .. })
-                    if state.is_some() =>
+                    if state.pending.is_some() =>
                 {
                     Some(canvas::Action::request_redraw())
                 }
-                _ => None,
+                _ => action,
             }
         }
 
@@ -161,20 +246,53 @@ mod bezier {
                     );
                 });
 
-            if let Some(pending) = state {
-                vec![content, pending.draw(renderer, theme, bounds, cursor)]
-            } else {
-                vec![content]
+            let mut overlay = Frame::new(renderer, bounds.size());
+
+            let highlighted = state
+                .scene
+                .pressed()
+                .or(state.scene.hovered())
+                .map(|shape| match shape {
+                    Shape::Curve(index) => *index,
+                    Shape::Handle(handle) => handle.curve,
+                });
+
+            if let Some(curve) =
+                highlighted.and_then(|index| self.curves.get(index))
+            {
+                overlay.stroke(
+                    &curve.path(),
+                    Stroke::default()
+                        .with_width(2.0)
+                        .with_color(theme.palette().primary),
+                );
+
+                for point in [Kind::From, Kind::To, Kind::Control] {
+                    overlay.fill(
+                        &Path::circle(curve.point(point), 5.0),
+                        theme.palette().primary,
+                    );
+                }
+            }
+
+            if let Some(pending) = state.pending {
+                pending.draw(&mut overlay, theme, bounds, cursor);
             }
+
+            vec![content, overlay.into_geometry()]
         }
 
         fn mouse_interaction(
             &self,
-            _state: &Self::State,
+            state: &Self::State,
             bounds: Rectangle,
             cursor: mouse::Cursor,
         ) -> mouse::Interaction {
-            if cursor.is_over(bounds) {
+            if state.scene.pressed().is_some() {
+                mouse::Interaction::Grabbing
+            } else if state.scene.hovered().is_some() {
+                self.state.scene.mouse_interaction(&state.scene)
+            } else if cursor.is_over(bounds) {
                 mouse::Interaction::Crosshair
             } else {
                 mouse::Interaction::default()
@@ -182,6 +300,25 @@ mod bezier {
         }
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Shape {
+        Curve(usize),
+        Handle(Handle),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Handle {
+        pub curve: usize,
+        pub point: Kind,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Kind {
+        From,
+        To,
+        Control,
+    }
+
     #[derive(Debug, Clone, Copy)]
     pub struct Curve {
         from: Point,
@@ -190,6 +327,29 @@ mod bezier {
     }
 
     impl Curve {
+        fn point(&self, kind: Kind) -> Point {
+            match kind {
+                Kind::From => self.from,
+                Kind::To => self.to,
+                Kind::Control => self.control,
+            }
+        }
+
+        pub fn point_mut(&mut self, kind: Kind) -> &mut Point {
+            match kind {
+                Kind::From => &mut self.from,
+                Kind::To => &mut self.to,
+                Kind::Control => &mut self.control,
+            }
+        }
+
+        fn path(&self) -> Path {
+            Path::new(|p| {
+                p.move_to(self.from);
+                p.quadratic_curve_to(self.control, self.to);
+            })
+        }
+
         fn draw_all(curves: &[Curve], frame: &mut Frame, theme: &Theme) {
             let curves = Path::new(|p| {
                 for curve in curves {
@@ -216,13 +376,11 @@ mod bezier {
     impl Pending {
         fn draw(
             &self,
-            renderer: &Renderer,
+            frame: &mut Frame,
             theme: &Theme,
             bounds: Rectangle,
             cursor: mouse::Cursor,
-        ) -> Geometry {
-            let mut frame = Frame::new(renderer, bounds.size());
-
+        ) {
             if let Some(cursor_position) = cursor.position_in(bounds) {
                 match *self {
                     Pending::One { from } => {
@@ -241,12 +399,10 @@ mod bezier {
                             control: cursor_position,
                         };
 
-                        Curve::draw_all(&[curve], &mut frame, theme);
+                        Curve::draw_all(&[curve], frame, theme);
                     }
                 };
             }
-
-            frame.into_geometry()
         }
     }
 }
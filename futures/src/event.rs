@@ -1,8 +1,11 @@
 //! Listen to runtime events.
+use crate::core::clipboard;
 use crate::core::event::{self, Event};
 use crate::core::window;
 use crate::subscription::{self, Subscription};
-use crate::MaybeSend;
+use crate::{BoxStream, MaybeSend};
+
+use std::hash::Hash;
 
 /// Returns a [`Subscription`] to all the ignored runtime events.
 ///
@@ -37,6 +40,7 @@ where
             event: Event::Window(window::Event::RedrawRequested(_)),
             ..
         }
+        | subscription::Event::ClipboardChanged(_)
         | subscription::Event::PlatformSpecific(_) => None,
         subscription::Event::Interaction {
             window,
@@ -66,7 +70,8 @@ where
             event,
             status,
         } => f(event, status, window),
-        subscription::Event::PlatformSpecific(_) => None,
+        subscription::Event::ClipboardChanged(_)
+        | subscription::Event::PlatformSpecific(_) => None,
     })
 }
 
@@ -89,3 +94,112 @@ pub fn listen_url() -> Subscription<String> {
         _ => None,
     })
 }
+
+/// Creates a [`Subscription`] that notifies of the changes of the contents
+/// of the clipboards of the system, producing the [`clipboard::Kind`] that
+/// changed.
+pub fn listen_clipboard() -> Subscription<clipboard::Kind> {
+    struct ListenClipboard;
+
+    impl subscription::Recipe for ListenClipboard {
+        type Output = clipboard::Kind;
+
+        fn hash(&self, state: &mut subscription::Hasher) {
+            std::any::TypeId::of::<Self>().hash(state);
+        }
+
+        fn stream(
+            self: Box<Self>,
+            input: subscription::EventStream,
+        ) -> BoxStream<Self::Output> {
+            use futures::future;
+            use futures::stream::StreamExt;
+
+            crate::boxed_stream(input.filter_map(|event| {
+                future::ready(match event {
+                    subscription::Event::ClipboardChanged(kind) => Some(kind),
+                    _ => None,
+                })
+            }))
+        }
+
+        fn is_clipboard_listener(&self) -> bool {
+            true
+        }
+    }
+
+    subscription::from_recipe(ListenClipboard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mouse;
+    use crate::core::time::Instant;
+
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+
+    fn run<T: 'static>(subscription: Subscription<T>) -> Vec<T> {
+        let window = window::Id::unique();
+
+        let interaction = |event, status| subscription::Event::Interaction {
+            window,
+            event,
+            status,
+        };
+
+        let events = vec![
+            interaction(
+                Event::Mouse(mouse::Event::CursorEntered),
+                event::Status::Ignored,
+            ),
+            subscription::Event::ClipboardChanged(clipboard::Kind::Standard),
+            interaction(
+                Event::Mouse(mouse::Event::CursorLeft),
+                event::Status::Captured,
+            ),
+            interaction(
+                Event::Window(window::Event::RedrawRequested(Instant::now())),
+                event::Status::Ignored,
+            ),
+            subscription::Event::ClipboardChanged(clipboard::Kind::Primary),
+        ];
+
+        let mut recipes = subscription::into_recipes(subscription);
+        assert_eq!(recipes.len(), 1);
+
+        let input = crate::boxed_stream(stream::iter(events));
+
+        block_on(recipes.remove(0).stream(input).collect())
+    }
+
+    #[test]
+    fn listen_ignores_clipboard_changes() {
+        assert_eq!(run(listen()), [Event::Mouse(mouse::Event::CursorEntered)]);
+    }
+
+    #[test]
+    fn listen_with_ignores_clipboard_changes() {
+        fn status(
+            _event: Event,
+            status: event::Status,
+            _window: window::Id,
+        ) -> Option<event::Status> {
+            Some(status)
+        }
+
+        assert_eq!(
+            run(listen_with(status)),
+            [event::Status::Ignored, event::Status::Captured]
+        );
+    }
+
+    #[test]
+    fn listen_clipboard_only_produces_clipboard_changes() {
+        assert_eq!(
+            run(listen_clipboard()),
+            [clipboard::Kind::Standard, clipboard::Kind::Primary]
+        );
+    }
+}
@@ -129,4 +129,14 @@ where
     pub fn broadcast(&mut self, event: subscription::Event) {
         self.subscriptions.broadcast(event);
     }
+
+    /// Returns true if any subscription alive in the [`Runtime`] listens to
+    /// the changes of the clipboards.
+    ///
+    /// See [`Tracker::is_listening_clipboard`] to learn more.
+    ///
+    /// [`Tracker::is_listening_clipboard`]: subscription::Tracker::is_listening_clipboard
+    pub fn is_listening_clipboard(&self) -> bool {
+        self.subscriptions.is_listening_clipboard()
+    }
 }
@@ -3,6 +3,7 @@ mod tracker;
 
 pub use tracker::Tracker;
 
+use crate::core::clipboard;
 use crate::core::event;
 use crate::core::window;
 use crate::futures::Stream;
@@ -27,6 +28,9 @@ pub enum Event {
         status: event::Status,
     },
 
+    /// The contents of a clipboard changed.
+    ClipboardChanged(clipboard::Kind),
+
     /// A platform specific event.
     PlatformSpecific(PlatformSpecific),
 }
@@ -338,6 +342,14 @@ pub trait Recipe {
     /// Executes the [`Recipe`] and produces the stream of events of its
     /// [`Subscription`].
     fn stream(self: Box<Self>, input: EventStream) -> BoxStream<Self::Output>;
+
+    /// Returns true if the [`Recipe`] listens to [`Event::ClipboardChanged`].
+    ///
+    /// Runtimes that need to poll the clipboards for changes only do so
+    /// while one of their running recipes listens to them.
+    fn is_clipboard_listener(&self) -> bool {
+        false
+    }
 }
 
 struct Map<A, B, F>
@@ -377,6 +389,10 @@ where
 
         Box::pin(self.recipe.stream(input).map(mapper))
     }
+
+    fn is_clipboard_listener(&self) -> bool {
+        self.recipe.is_clipboard_listener()
+    }
 }
 
 struct With<A, B> {
@@ -414,6 +430,10 @@ where
                 .map(move |element| (value.clone(), element)),
         )
     }
+
+    fn is_clipboard_listener(&self) -> bool {
+        self.recipe.is_clipboard_listener()
+    }
 }
 
 pub(crate) fn filter_map<I, F, T>(id: I, f: F) -> Subscription<T>
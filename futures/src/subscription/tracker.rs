@@ -23,6 +23,7 @@ pub struct Tracker {
 pub struct Execution {
     _cancel: futures::channel::oneshot::Sender<()>,
     listener: Option<futures::channel::mpsc::Sender<Event>>,
+    is_clipboard_listener: bool,
 }
 
 impl Tracker {
@@ -85,6 +86,7 @@ impl Tracker {
             }
 
             let (cancel, mut canceled) = futures::channel::oneshot::channel();
+            let is_clipboard_listener = recipe.is_clipboard_listener();
 
             // TODO: Use bus if/when it supports async
             let (event_sender, event_receiver) =
@@ -117,6 +119,7 @@ impl Tracker {
                     } else {
                         Some(event_sender)
                     },
+                    is_clipboard_listener,
                 },
             );
 
@@ -128,6 +131,16 @@ impl Tracker {
         futures
     }
 
+    /// Returns true if any subscription currently alive listens to
+    /// [`Event::ClipboardChanged`].
+    ///
+    /// See [`Recipe::is_clipboard_listener`].
+    pub fn is_listening_clipboard(&self) -> bool {
+        self.subscriptions
+            .values()
+            .any(|execution| execution.is_clipboard_listener)
+    }
+
     /// Broadcasts an event to the subscriptions currently alive.
     ///
     /// A subscription's [`Recipe::stream`] always receives a stream of events
@@ -151,3 +164,46 @@ impl Tracker {
             });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event;
+    use crate::subscription::{self, Subscription};
+
+    fn track(tracker: &mut Tracker, subscription: Subscription<()>) {
+        let (sender, _receiver) = mpsc::channel(1);
+
+        let _ = tracker.update(
+            subscription::into_recipes(subscription).into_iter(),
+            sender,
+        );
+    }
+
+    #[test]
+    fn clipboard_listeners_are_tracked_while_alive() {
+        let mut tracker = Tracker::new();
+        let other = Tracker::new();
+
+        assert!(!tracker.is_listening_clipboard());
+
+        track(&mut tracker, event::listen().map(|_| ()));
+        assert!(!tracker.is_listening_clipboard());
+
+        track(
+            &mut tracker,
+            Subscription::batch([
+                event::listen().map(|_| ()),
+                event::listen_clipboard().map(|_| ()),
+            ]),
+        );
+        assert!(tracker.is_listening_clipboard());
+        assert!(!other.is_listening_clipboard());
+
+        track(&mut tracker, event::listen_clipboard().with(1).map(|_| ()));
+        assert!(tracker.is_listening_clipboard());
+
+        track(&mut tracker, Subscription::none());
+        assert!(!tracker.is_listening_clipboard());
+    }
+}
@@ -39,6 +39,14 @@ pub trait Compositor: Sized {
     /// Creates a [`Self::Renderer`] for the [`Compositor`].
     fn create_renderer(&self) -> Self::Renderer;
 
+    /// Creates a [`Self::Renderer`] to render single frames offscreen.
+    ///
+    /// Since there is no later frame to draw them in, its images are
+    /// decoded synchronously instead of in the background.
+    fn create_offscreen_renderer(&self) -> Self::Renderer {
+        self.create_renderer()
+    }
+
     /// Crates a new [`Surface`] for the given window.
     ///
     /// [`Surface`]: Self::Surface
@@ -62,6 +70,18 @@ pub trait Compositor: Sized {
     /// Returns [`Information`] used by this [`Compositor`].
     fn fetch_information(&self) -> Information;
 
+    /// Returns the [`Stats`] of the image caches of the given
+    /// [`Renderer`].
+    ///
+    /// [`Stats`]: crate::image::Stats
+    /// [`Renderer`]: Self::Renderer
+    fn image_cache_stats(
+        &self,
+        _renderer: &Self::Renderer,
+    ) -> crate::image::Stats {
+        crate::image::Stats::default()
+    }
+
     /// Loads a font from its bytes.
     fn load_font(&mut self, font: Cow<'static, [u8]>) {
         crate::text::font_system()
@@ -1,4 +1,5 @@
 //! Build and draw geometry.
+pub mod export;
 pub mod fill;
 pub mod frame;
 pub mod path;
@@ -0,0 +1,961 @@
+//! Record geometry and export it to vector formats, like SVG and PDF.
+//!
+//! The [`Renderer`] of this module records the geometry produced by a
+//! [`Frame`] instead of drawing it. Any drawing logic that is generic over a
+//! [`geometry::Renderer`] can therefore be reused to produce a [`Drawing`].
+//!
+//! [`Frame`]: crate::geometry::Frame
+//! [`geometry::Renderer`]: crate::geometry::Renderer
+mod pdf;
+mod svg;
+
+use crate::cache::{self, Cached};
+use crate::core::{
+    self, Background, Color, Point, Radians, Rectangle, Size, Svg,
+    Transformation, Vector,
+};
+use crate::geometry::fill::{self, Fill};
+use crate::geometry::stroke::{LineCap, LineJoin, Stroke};
+use crate::geometry::{self, Path, Style, Text};
+use crate::gradient::{self, Gradient};
+
+use lyon_path::math;
+use std::sync::Arc;
+
+/// A renderer that records geometry, so it can be exported to vector formats.
+#[derive(Debug, Clone, Default)]
+pub struct Renderer {
+    layers: Vec<Geometry>,
+}
+
+impl Renderer {
+    /// Creates a new [`Renderer`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turns the geometry drawn with the [`Renderer`] into a [`Drawing`] of
+    /// the given [`Size`].
+    pub fn into_drawing(self, size: Size) -> Drawing {
+        Drawing::new(size, self.layers)
+    }
+}
+
+impl core::Renderer for Renderer {
+    fn start_layer(&mut self, _bounds: Rectangle) {}
+
+    fn end_layer(&mut self) {}
+
+    fn start_transformation(&mut self, _transformation: Transformation) {}
+
+    fn end_transformation(&mut self) {}
+
+    fn fill_quad(
+        &mut self,
+        _quad: core::renderer::Quad,
+        _background: impl Into<Background>,
+    ) {
+    }
+
+    fn clear(&mut self) {
+        self.layers.clear();
+    }
+}
+
+impl geometry::Renderer for Renderer {
+    type Geometry = Geometry;
+    type Frame = Frame;
+
+    fn new_frame(&self, size: Size) -> Self::Frame {
+        Frame::new(size)
+    }
+
+    fn draw_geometry(&mut self, geometry: Self::Geometry) {
+        self.layers.push(geometry);
+    }
+}
+
+/// A set of recorded geometry.
+#[derive(Debug, Clone)]
+pub struct Geometry {
+    commands: Arc<Vec<Command>>,
+}
+
+impl Cached for Geometry {
+    type Cache = Self;
+
+    fn load(cache: &Self::Cache) -> Self {
+        cache.clone()
+    }
+
+    fn cache(self, _group: cache::Group, _previous: Option<Self>) -> Self {
+        self
+    }
+}
+
+/// A drawing operation recorded by a [`Frame`].
+///
+/// Paths and gradients are already transformed, since strokes are not
+/// affected by the transform of a [`Frame`].
+#[derive(Debug, Clone)]
+enum Command {
+    Fill {
+        path: Path,
+        style: Style,
+        rule: fill::Rule,
+    },
+    Stroke {
+        path: Path,
+        style: Style,
+        width: f32,
+        line_cap: LineCap,
+        line_join: LineJoin,
+        miter_limit: f32,
+    },
+    Text {
+        text: Text,
+        transform: math::Transform,
+    },
+    Image {
+        bounds: Rectangle,
+        image: core::Image,
+        transform: math::Transform,
+    },
+    Svg {
+        bounds: Rectangle,
+        svg: Svg,
+        transform: math::Transform,
+    },
+    Clip {
+        bounds: Rectangle,
+        commands: Vec<Command>,
+    },
+}
+
+/// A frame that records geometry.
+#[derive(Debug)]
+pub struct Frame {
+    size: Size,
+    clip_bounds: Option<Rectangle>,
+    commands: Vec<Command>,
+    transform: math::Transform,
+    stack: Vec<math::Transform>,
+}
+
+impl Frame {
+    /// Creates a new [`Frame`] with the given [`Size`].
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            clip_bounds: None,
+            commands: Vec::new(),
+            transform: math::Transform::identity(),
+            stack: Vec::new(),
+        }
+    }
+
+    fn transform_path(&self, path: &Path) -> Path {
+        if self.transform == math::Transform::identity() {
+            path.clone()
+        } else {
+            path.transform(&self.transform)
+        }
+    }
+
+    fn transform_style(&self, style: Style) -> Style {
+        let Style::Gradient(mut gradient) = style else {
+            return style;
+        };
+
+        let transform_point = |point: Point| {
+            let point = self
+                .transform
+                .transform_point(math::Point::new(point.x, point.y));
+
+            Point::new(point.x, point.y)
+        };
+
+        match &mut gradient {
+            Gradient::Linear(linear) => {
+                linear.start = transform_point(linear.start);
+                linear.end = transform_point(linear.end);
+            }
+            Gradient::Radial(radial) => {
+                let center = transform_point(radial.center);
+                let edge = transform_point(
+                    radial.center + Vector::new(radial.radius, 0.0),
+                );
+
+                radial.center = center;
+                radial.radius = center.distance(edge);
+            }
+            Gradient::Conic(conic) => {
+                conic.center = transform_point(conic.center);
+                conic.angle.0 += self.transform.m12.atan2(self.transform.m11);
+            }
+        }
+
+        Style::Gradient(gradient)
+    }
+}
+
+impl geometry::frame::Backend for Frame {
+    type Geometry = Geometry;
+
+    fn width(&self) -> f32 {
+        self.size.width
+    }
+
+    fn height(&self) -> f32 {
+        self.size.height
+    }
+
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn center(&self) -> Point {
+        Point::new(self.size.width / 2.0, self.size.height / 2.0)
+    }
+
+    fn push_transform(&mut self) {
+        self.stack.push(self.transform);
+    }
+
+    fn pop_transform(&mut self) {
+        self.transform = self.stack.pop().expect("Pop transform");
+    }
+
+    fn translate(&mut self, translation: Vector) {
+        self.transform = self
+            .transform
+            .pre_translate(math::Vector::new(translation.x, translation.y));
+    }
+
+    fn rotate(&mut self, angle: impl Into<Radians>) {
+        self.transform = self
+            .transform
+            .pre_rotate(math::Angle::radians(angle.into().0));
+    }
+
+    fn scale(&mut self, scale: impl Into<f32>) {
+        let scale = scale.into();
+
+        self.scale_nonuniform(Vector::new(scale, scale));
+    }
+
+    fn scale_nonuniform(&mut self, scale: impl Into<Vector>) {
+        let scale = scale.into();
+
+        self.transform = self.transform.pre_scale(scale.x, scale.y);
+    }
+
+    fn draft(&mut self, clip_bounds: Rectangle) -> Self {
+        Self {
+            size: clip_bounds.size(),
+            clip_bounds: Some(clip_bounds),
+            commands: Vec::new(),
+            transform: math::Transform::translation(
+                clip_bounds.x,
+                clip_bounds.y,
+            ),
+            stack: Vec::new(),
+        }
+    }
+
+    fn paste(&mut self, frame: Self) {
+        match frame.clip_bounds {
+            Some(bounds) => self.commands.push(Command::Clip {
+                bounds,
+                commands: frame.commands,
+            }),
+            None => self.commands.extend(frame.commands),
+        }
+    }
+
+    fn stroke<'a>(&mut self, path: &Path, stroke: impl Into<Stroke<'a>>) {
+        let stroke = stroke.into();
+
+        self.commands.push(Command::Stroke {
+            path: self.transform_path(&stroke.apply(path)),
+            style: self.transform_style(stroke.style),
+            width: stroke.width,
+            line_cap: stroke.line_cap,
+            line_join: stroke.line_join,
+            miter_limit: stroke.miter_limit.max(2.0),
+        });
+    }
+
+    fn stroke_rectangle<'a>(
+        &mut self,
+        top_left: Point,
+        size: Size,
+        stroke: impl Into<Stroke<'a>>,
+    ) {
+        self.stroke(&Path::rectangle(top_left, size), stroke);
+    }
+
+    fn fill(&mut self, path: &Path, fill: impl Into<Fill>) {
+        let fill = fill.into();
+
+        self.commands.push(Command::Fill {
+            path: self.transform_path(path),
+            style: self.transform_style(fill.style),
+            rule: fill.rule,
+        });
+    }
+
+    fn fill_text(&mut self, text: impl Into<Text>) {
+        self.commands.push(Command::Text {
+            text: text.into(),
+            transform: self.transform,
+        });
+    }
+
+    fn fill_rectangle(
+        &mut self,
+        top_left: Point,
+        size: Size,
+        fill: impl Into<Fill>,
+    ) {
+        self.fill(&Path::rectangle(top_left, size), fill);
+    }
+
+    fn draw_image(&mut self, bounds: Rectangle, image: impl Into<core::Image>) {
+        self.commands.push(Command::Image {
+            bounds,
+            image: image.into(),
+            transform: self.transform,
+        });
+    }
+
+    fn draw_svg(&mut self, bounds: Rectangle, svg: impl Into<Svg>) {
+        self.commands.push(Command::Svg {
+            bounds,
+            svg: svg.into(),
+            transform: self.transform,
+        });
+    }
+
+    fn into_geometry(self) -> Geometry {
+        Geometry {
+            commands: Arc::new(self.commands),
+        }
+    }
+}
+
+/// Some recorded geometry with a [`Size`], ready to be exported.
+#[derive(Debug, Clone)]
+pub struct Drawing {
+    size: Size,
+    layers: Vec<Geometry>,
+}
+
+impl Drawing {
+    /// Creates a new [`Drawing`] of the given [`Size`] with the given
+    /// layers of [`Geometry`].
+    pub fn new(size: Size, layers: impl IntoIterator<Item = Geometry>) -> Self {
+        Self {
+            size,
+            layers: layers.into_iter().collect(),
+        }
+    }
+
+    /// Returns the [`Size`] of the [`Drawing`].
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Serializes the [`Drawing`] into an SVG document.
+    ///
+    /// Text is kept as text, so it can be selected and searched. Conic
+    /// gradients are approximated with a set of solid wedges. Images are
+    /// embedded as data URIs, so the document is self-contained; cropped,
+    /// tinted or adjusted images are embedded as processed PNGs, which
+    /// requires the `image` feature for encoded images.
+    pub fn to_svg(&self) -> String {
+        svg::write(self.size, self.commands())
+    }
+
+    /// Serializes the [`Drawing`] into a single-page PDF document.
+    ///
+    /// Text is converted to outlines and [`Svg`] images are not supported.
+    /// Raster images stored as encoded bytes or files are only supported if
+    /// the `image` feature is enabled. The alpha channel of gradients is
+    /// ignored.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        pdf::write(self.size, self.commands())
+    }
+
+    fn commands(&self) -> impl Iterator<Item = &Command> {
+        self.layers.iter().flat_map(|layer| layer.commands.iter())
+    }
+}
+
+/// Returns the sorted color stops of a [`Gradient`], covering the whole
+/// `0.0..=1.0` range.
+fn stops(gradient: &Gradient) -> Vec<(f32, Color)> {
+    let stops = match gradient {
+        Gradient::Linear(linear) => &linear.stops,
+        Gradient::Radial(radial) => &radial.stops,
+        Gradient::Conic(conic) => &conic.stops,
+    };
+
+    let mut stops: Vec<_> = stops
+        .iter()
+        .flatten()
+        .map(|stop| (stop.offset, stop.color))
+        .collect();
+
+    stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    match (stops.first().copied(), stops.last().copied()) {
+        (Some(first), Some(last)) => {
+            if first.0 > 0.0 {
+                stops.insert(0, (0.0, first.1));
+            }
+
+            if last.0 < 1.0 {
+                stops.push((1.0, last.1));
+            }
+        }
+        _ => {
+            stops = vec![(0.0, Color::TRANSPARENT), (1.0, Color::TRANSPARENT)];
+        }
+    }
+
+    stops
+}
+
+/// Returns the [`Color`] of the given sorted stops at the given offset.
+fn color_at(stops: &[(f32, Color)], offset: f32) -> Color {
+    let next = stops
+        .iter()
+        .position(|(stop, _)| *stop >= offset)
+        .unwrap_or(stops.len() - 1);
+
+    if next == 0 {
+        return stops[0].1;
+    }
+
+    let (start, from) = stops[next - 1];
+    let (end, to) = stops[next];
+
+    let t = if end > start {
+        ((offset - start) / (end - start)).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+
+    Color {
+        r: from.r + (to.r - from.r) * t,
+        g: from.g + (to.g - from.g) * t,
+        b: from.b + (to.b - from.b) * t,
+        a: from.a + (to.a - from.a) * t,
+    }
+}
+
+/// The amount of wedges used to approximate a [`gradient::Conic`].
+const WEDGES: usize = 90;
+
+/// Returns the solid wedges approximating a [`gradient::Conic`] around the
+/// given [`Path`], as triangles with their colors.
+fn wedges(conic: &gradient::Conic, path: &Path) -> Vec<([Point; 3], Color)> {
+    let stops = stops(&Gradient::Conic(*conic));
+
+    let radius = path
+        .raw()
+        .iter()
+        .flat_map(|event| [event.from(), event.to()])
+        .map(|point| conic.center.distance(Point::new(point.x, point.y)))
+        .fold(0.0, f32::max)
+        * 1.5
+        + 1.0;
+
+    let point = |i: usize| {
+        let angle =
+            conic.angle.0 + std::f32::consts::TAU * i as f32 / WEDGES as f32;
+
+        conic.center + Vector::new(angle.cos(), angle.sin()) * radius
+    };
+
+    (0..WEDGES)
+        .map(|i| {
+            let offset = (i as f32 + 0.5) / WEDGES as f32;
+
+            (
+                [conic.center, point(i), point(i + 1)],
+                color_at(&stops, offset),
+            )
+        })
+        .collect()
+}
+
+/// Formats a number compactly, with at most 3 decimals.
+fn number(value: f32) -> String {
+    let value = if value.is_finite() { value } else { 0.0 };
+    let formatted = format!("{value:.3}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    if formatted == "-0" {
+        String::from("0")
+    } else {
+        formatted.to_owned()
+    }
+}
+
+/// Returns true if the pixels of a [`core::Image`] must be processed before
+/// exporting it, because it is cropped, tinted or adjusted.
+fn is_processed(image: &core::Image) -> bool {
+    image.crop.is_some()
+        || image.tint != Color::WHITE
+        || !image.adjustments.is_none()
+}
+
+/// Returns the width, height and non-premultiplied RGBA pixels of a
+/// [`core::Image`] to export, with its crop, tint and adjustments applied.
+fn pixels(image: &core::Image) -> Option<(u32, u32, Vec<u8>)> {
+    let (width, height, pixels) = match &image.handle {
+        core::image::Handle::Rgba {
+            width,
+            height,
+            pixels,
+            ..
+        } => (*width, *height, pixels.clone()),
+        #[cfg(feature = "image")]
+        handle => {
+            let image = crate::image::load(handle)
+                .map_err(|error| {
+                    log::warn!("Failed to load image to export: {error}");
+                })
+                .ok()?;
+
+            (image.width(), image.height(), image.into_raw())
+        }
+        #[cfg(not(feature = "image"))]
+        _ => {
+            log::warn!("Exporting encoded images requires the `image` feature");
+
+            return None;
+        }
+    };
+
+    let region = image.region(Size::new(width, height))?;
+    let stride = width as usize * 4;
+    let row = region.width as usize * 4;
+
+    let mut cropped = Vec::with_capacity(row * region.height as usize);
+
+    for y in region.y..region.y + region.height {
+        let start = y as usize * stride + region.x as usize * 4;
+
+        cropped.extend_from_slice(pixels.get(start..start + row)?);
+    }
+
+    if image.tint != Color::WHITE || !image.adjustments.is_none() {
+        for pixel in cropped.chunks_exact_mut(4) {
+            let color = Color::from_rgba8(
+                pixel[0],
+                pixel[1],
+                pixel[2],
+                f32::from(pixel[3]) / 255.0,
+            );
+
+            pixel.copy_from_slice(
+                &image.adjustments.apply(color, image.tint).into_rgba8(),
+            );
+        }
+    }
+
+    Some((region.width, region.height, cropped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::geometry::Renderer as _;
+
+    fn drawing(draw: impl FnOnce(&mut geometry::Frame<Renderer>)) -> Drawing {
+        let size = Size::new(100.0, 50.0);
+
+        let mut renderer = Renderer::new();
+        let mut frame = geometry::Frame::new(&renderer, size);
+
+        draw(&mut frame);
+
+        renderer.draw_geometry(frame.into_geometry());
+        renderer.into_drawing(size)
+    }
+
+    fn shapes(frame: &mut geometry::Frame<Renderer>) {
+        frame.fill_rectangle(
+            Point::new(10.0, 10.0),
+            Size::new(20.0, 10.0),
+            Color::from_rgb8(255, 0, 0),
+        );
+
+        frame.stroke(
+            &Path::line(Point::ORIGIN, Point::new(100.0, 50.0)),
+            Stroke::default().with_width(2.0).with_color(Color {
+                a: 0.5,
+                ..Color::BLACK
+            }),
+        );
+    }
+
+    fn pdf_objects(pdf: &[u8]) -> Vec<String> {
+        // Binary bytes are masked, so offsets are kept
+        let pdf: String = pdf
+            .iter()
+            .map(|byte| if byte.is_ascii() { *byte as char } else { '?' })
+            .collect();
+
+        let xref: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .expect("Parse startxref");
+
+        assert!(pdf[xref..].starts_with("xref\n"));
+
+        pdf[xref..]
+            .lines()
+            .skip(3)
+            .take_while(|line| !line.starts_with("trailer"))
+            .enumerate()
+            .map(|(i, entry)| {
+                let offset: usize =
+                    entry[..10].parse().expect("Parse object offset");
+                let object = &pdf[offset..];
+                let header = format!("{} 0 obj\n", i + 1);
+
+                assert!(object.starts_with(&header), "object {}", i + 1);
+
+                object[header.len()..object.find("\nendobj").unwrap()]
+                    .to_owned()
+            })
+            .collect()
+    }
+
+    fn content(pdf: &[u8]) -> String {
+        let objects = pdf_objects(pdf);
+
+        let page = &objects[2];
+        let contents: usize = page
+            .split("/Contents ")
+            .nth(1)
+            .and_then(|rest| rest.split(' ').next())
+            .and_then(|object| object.parse().ok())
+            .expect("Find page contents");
+
+        let stream = &objects[contents - 1];
+        let start = stream.find("stream\n").unwrap() + "stream\n".len();
+        let end = stream.rfind("\nendstream").unwrap();
+
+        stream[start..end].to_owned()
+    }
+
+    #[cfg(feature = "image")]
+    fn embedded_png(svg: &str) -> (png::OutputInfo, Vec<u8>) {
+        use base64::Engine;
+
+        let data = svg
+            .split("href=\"data:image/png;base64,")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .expect("Find image data");
+
+        let png = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .expect("Decode base64");
+
+        let mut reader = png::Decoder::new(png.as_slice())
+            .read_info()
+            .expect("Read PNG");
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).expect("Decode PNG");
+
+        (info, decoded)
+    }
+
+    #[test]
+    fn to_svg_writes_shapes() {
+        assert_eq!(
+            drawing(shapes).to_svg(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" \
+             height=\"50\" viewBox=\"0 0 100 50\">\n\
+             <path d=\"M10 10L30 10L30 20L10 20Z\" fill-rule=\"nonzero\" \
+             fill=\"#ff0000\"/>\n\
+             <path d=\"M0 0L100 50\" fill=\"none\" stroke-width=\"2\" \
+             stroke-linecap=\"butt\" stroke-linejoin=\"miter\" \
+             stroke-miterlimit=\"4\" stroke=\"#000000\" \
+             stroke-opacity=\"0.5\"/>\n\
+             </svg>\n"
+        );
+    }
+
+    #[test]
+    fn to_svg_applies_transforms_and_clips() {
+        let svg = drawing(|frame| {
+            frame.translate(Vector::new(5.0, 5.0));
+            frame.scale(2.0);
+            frame.fill_rectangle(
+                Point::ORIGIN,
+                Size::new(1.0, 1.0),
+                Color::WHITE,
+            );
+
+            frame.with_clip(
+                Rectangle::new(Point::new(50.0, 0.0), Size::new(10.0, 10.0)),
+                |frame| {
+                    frame.fill_rectangle(
+                        Point::ORIGIN,
+                        Size::new(20.0, 20.0),
+                        Color::BLACK,
+                    );
+                },
+            );
+        })
+        .to_svg();
+
+        assert!(svg.contains("<path d=\"M5 5L7 5L7 7L5 7Z\""));
+        assert!(svg.contains(
+            "<clipPath id=\"clip-1\"><rect x=\"50\" y=\"0\" width=\"10\" \
+             height=\"10\"/></clipPath>\n<g clip-path=\"url(#clip-1)\">"
+        ));
+        assert!(svg.contains("<path d=\"M50 0L70 0L70 20L50 20Z\""));
+    }
+
+    #[test]
+    fn to_svg_escapes_text() {
+        let svg = drawing(|frame| {
+            frame.fill_text(Text {
+                content: String::from("<a & \"b\">"),
+                ..Text::default()
+            });
+        })
+        .to_svg();
+
+        assert!(svg.contains(">&lt;a &amp; &quot;b&quot;&gt;</tspan>"));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn to_svg_embeds_rgba_images_as_png() {
+        let pixels = vec![255, 0, 0, 255, 0, 0, 255, 128];
+
+        let svg = drawing(|frame| {
+            frame.draw_image(
+                Rectangle::new(Point::ORIGIN, Size::new(2.0, 1.0)),
+                &core::image::Handle::from_rgba(2, 1, pixels.clone()),
+            );
+        })
+        .to_svg();
+
+        let (info, decoded) = embedded_png(&svg);
+
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(decoded, pixels);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn to_svg_crops_and_tints_images() {
+        let svg = drawing(|frame| {
+            frame.draw_image(
+                Rectangle::new(Point::ORIGIN, Size::new(1.0, 1.0)),
+                core::Image::new(core::image::Handle::from_rgba(
+                    2,
+                    1,
+                    vec![255, 0, 0, 255, 0, 0, 255, 128],
+                ))
+                .crop(Rectangle {
+                    x: 1,
+                    y: 0,
+                    width: 1,
+                    height: 1,
+                })
+                .tint(Color::from_rgba(1.0, 1.0, 0.5, 0.5)),
+            );
+        })
+        .to_svg();
+
+        let (info, decoded) = embedded_png(&svg);
+
+        assert_eq!((info.width, info.height), (1, 1));
+        assert_eq!(decoded, [0, 0, 128, 64]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn to_svg_embeds_image_files() {
+        let path = std::env::temp_dir()
+            .join(format!("iced-export-{}.png", std::process::id()));
+
+        std::fs::write(&path, b"\x89PNG").expect("Write image");
+
+        let svg = drawing(|frame| {
+            frame.draw_image(
+                Rectangle::new(Point::ORIGIN, Size::new(1.0, 1.0)),
+                &core::image::Handle::from_path(&path),
+            );
+        })
+        .to_svg();
+
+        let _ = std::fs::remove_file(&path);
+
+        assert!(svg.contains("href=\"data:image/png;base64,iVBORw==\""));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn to_svg_links_missing_image_files() {
+        let svg = drawing(|frame| {
+            frame.draw_image(
+                Rectangle::new(Point::ORIGIN, Size::new(1.0, 1.0)),
+                &core::image::Handle::from_path("/missing/image.png"),
+            );
+        })
+        .to_svg();
+
+        assert!(svg.contains("href=\"file:///missing/image.png\""));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn to_svg_styles_svg_classes() {
+        use base64::Engine;
+
+        let svg = drawing(|frame| {
+            frame.draw_svg(
+                Rectangle::new(Point::ORIGIN, Size::new(10.0, 10.0)),
+                Svg::new(core::svg::Handle::from_memory(
+                    &b"<svg><rect class=\"a\"/></svg>"[..],
+                ))
+                .class_color("a", Color::BLACK),
+            );
+        })
+        .to_svg();
+
+        let data = svg
+            .split("href=\"data:image/svg+xml;base64,")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .expect("Find SVG data");
+
+        let document = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .expect("Decode base64");
+
+        assert_eq!(
+            String::from_utf8(document).expect("Read SVG"),
+            "<svg><rect class=\"a\"/><style><![CDATA[.a { fill: \
+             rgba(0, 0, 0, 1); color: rgba(0, 0, 0, 1); }]]></style></svg>"
+        );
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn to_svg_round_trips() {
+        let svg = drawing(shapes).to_svg();
+
+        let tree =
+            resvg::usvg::Tree::from_str(&svg, &resvg::usvg::Options::default())
+                .expect("Parse exported SVG");
+
+        assert_eq!(tree.size().width(), 100.0);
+        assert_eq!(tree.size().height(), 50.0);
+        assert_eq!(tree.root().children().len(), 2);
+    }
+
+    #[test]
+    fn to_pdf_writes_valid_structure() {
+        let pdf = drawing(shapes).to_pdf();
+
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let objects = pdf_objects(&pdf);
+
+        assert_eq!(objects[0], "<< /Type /Catalog /Pages 2 0 R >>");
+        assert_eq!(objects[1], "<< /Type /Pages /Kids [3 0 R] /Count 1 >>");
+        assert!(objects[2].contains("/MediaBox [0 0 100 50]"));
+        assert!(objects[2].contains("/ExtGState << /G4 4 0 R >>"));
+        assert_eq!(objects[3], "<< /Type /ExtGState /ca 1 /CA 0.5 >>");
+    }
+
+    #[test]
+    fn to_pdf_writes_shapes() {
+        assert_eq!(
+            content(&drawing(shapes).to_pdf()),
+            "1 0 0 -1 0 50 cm\n\
+             q\n1 0 0 rg\n10 10 m\n30 10 l\n30 20 l\n10 20 l\nh\nf\nQ\n\
+             q\n2 w 0 J 0 j 4 M\n/G4 gs\n0 0 0 RG\n0 0 m\n100 50 l\nS\nQ\n"
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn to_pdf_embeds_rgba_images() {
+        let pdf = drawing(|frame| {
+            frame.draw_image(
+                Rectangle::new(Point::ORIGIN, Size::new(2.0, 1.0)),
+                &core::image::Handle::from_rgba(
+                    2,
+                    1,
+                    vec![255, 0, 0, 255, 0, 0, 255, 128],
+                ),
+            );
+        })
+        .to_pdf();
+
+        let objects = pdf_objects(&pdf);
+
+        assert!(objects[2].contains("/XObject << /I5 5 0 R >>"));
+        assert!(objects[3].contains("/ColorSpace /DeviceGray /Length 2"));
+        assert!(objects[3].contains("stream\n??\nendstream"));
+        assert!(objects[4].contains("/Width 2 /Height 1"));
+        assert!(objects[4].contains("/SMask 4 0 R"));
+        assert!(content(&pdf).contains("/I5 Do"));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn to_pdf_crops_and_tints_images() {
+        let handle = core::image::Handle::from_rgba(
+            2,
+            1,
+            vec![255, 0, 0, 255, 0, 0, 255, 128],
+        );
+
+        let pdf = drawing(|frame| {
+            let bounds = Rectangle::new(Point::ORIGIN, Size::new(1.0, 1.0));
+
+            frame.draw_image(
+                bounds,
+                core::Image::new(handle.clone())
+                    .crop(Rectangle {
+                        x: 1,
+                        y: 0,
+                        width: 1,
+                        height: 1,
+                    })
+                    .tint(Color::from_rgba(1.0, 1.0, 0.5, 0.5)),
+            );
+            frame.draw_image(bounds, &handle);
+        })
+        .to_pdf();
+
+        let objects = pdf_objects(&pdf);
+
+        assert!(objects[3].contains("/ColorSpace /DeviceGray /Length 1"));
+        assert!(objects[3].contains("stream\n@\nendstream"));
+        assert!(objects[4].contains("/Width 1 /Height 1"));
+        assert!(objects[6].contains("/Width 2 /Height 1"));
+        assert!(content(&pdf).contains("/I5 Do"));
+        assert!(content(&pdf).contains("/I7 Do"));
+    }
+}
@@ -0,0 +1,548 @@
+use crate::core::image;
+use crate::core::{Color, Rectangle, Size};
+use crate::geometry::export::{number, pixels, stops, wedges, Command};
+use crate::geometry::fill;
+use crate::geometry::stroke::{LineCap, LineJoin};
+use crate::geometry::{Path, Style};
+use crate::gradient::Gradient;
+
+use lyon_path::math;
+use lyon_path::PathEvent;
+use std::fmt::Write;
+
+pub fn write<'a>(
+    size: Size,
+    commands: impl Iterator<Item = &'a Command>,
+) -> Vec<u8> {
+    let mut writer = Writer {
+        content: String::new(),
+        objects: vec![Vec::new(); 3],
+        height: size.height,
+        states: Vec::new(),
+        patterns: Vec::new(),
+        images: Vec::new(),
+    };
+
+    // PDF coordinates grow upwards
+    let _ = writeln!(writer.content, "1 0 0 -1 0 {} cm", number(size.height));
+
+    for command in commands {
+        writer.command(command);
+    }
+
+    writer.finish(size)
+}
+
+struct Writer {
+    content: String,
+    objects: Vec<Vec<u8>>,
+    height: f32,
+    states: Vec<(String, usize)>,
+    patterns: Vec<usize>,
+    images: Vec<(Key, usize)>,
+}
+
+/// The pixels embedded for an image, shared by images that only differ in
+/// their placement.
+#[derive(PartialEq)]
+struct Key {
+    id: image::Id,
+    crop: Option<Rectangle<u32>>,
+    tint: [u8; 4],
+    adjustments: image::Adjustments,
+}
+
+impl Writer {
+    /// Adds an object to the document, returning its number.
+    fn object(&mut self, object: impl Into<Vec<u8>>) -> usize {
+        self.objects.push(object.into());
+        self.objects.len()
+    }
+
+    /// Adds a stream object to the document, returning its number.
+    fn stream(&mut self, dictionary: &str, data: &[u8]) -> usize {
+        let mut object = format!(
+            "<< {dictionary}{}/Length {} >>\nstream\n",
+            if dictionary.is_empty() { "" } else { " " },
+            data.len()
+        )
+        .into_bytes();
+
+        object.extend(data);
+        object.extend(b"\nendstream");
+
+        self.object(object)
+    }
+
+    fn command(&mut self, command: &Command) {
+        match command {
+            Command::Fill { path, style, rule } => {
+                self.content.push_str("q\n");
+                self.paint(*style, path, false);
+                self.path(path);
+                self.content.push_str(match rule {
+                    fill::Rule::NonZero => "f\n",
+                    fill::Rule::EvenOdd => "f*\n",
+                });
+                self.content.push_str("Q\n");
+            }
+            Command::Stroke {
+                path,
+                style,
+                width,
+                line_cap,
+                line_join,
+                miter_limit,
+            } => {
+                let _ = writeln!(
+                    self.content,
+                    "q\n{} w {} J {} j {} M",
+                    number(*width),
+                    match line_cap {
+                        LineCap::Butt => 0,
+                        LineCap::Round => 1,
+                        LineCap::Square => 2,
+                    },
+                    match line_join {
+                        LineJoin::Miter => 0,
+                        LineJoin::Round => 1,
+                        LineJoin::Bevel => 2,
+                    },
+                    number(*miter_limit),
+                );
+
+                self.paint(*style, path, true);
+                self.path(path);
+                self.content.push_str("S\nQ\n");
+            }
+            Command::Text { text, transform } => {
+                text.draw_with(|path, color| {
+                    self.command(&Command::Fill {
+                        path: path.transform(transform),
+                        style: Style::Solid(color),
+                        rule: fill::Rule::NonZero,
+                    });
+                });
+            }
+            Command::Image {
+                bounds,
+                image,
+                transform,
+            } => {
+                let Some(name) = self.image(image) else {
+                    return;
+                };
+
+                self.content.push_str("q\n");
+                self.alpha(image.opacity, 1.0);
+                self.place(transform, *bounds, image.rotation.0);
+
+                let _ = writeln!(self.content, "/{name} Do\nQ");
+            }
+            Command::Svg { .. } => {
+                log::warn!("Exporting SVG images to PDF is not supported");
+            }
+            Command::Clip { bounds, commands } => {
+                let _ = writeln!(
+                    self.content,
+                    "q\n{} {} {} {} re W n",
+                    number(bounds.x),
+                    number(bounds.y),
+                    number(bounds.width),
+                    number(bounds.height),
+                );
+
+                for command in commands {
+                    self.command(command);
+                }
+
+                self.content.push_str("Q\n");
+            }
+        }
+    }
+
+    fn path(&mut self, path: &Path) {
+        for event in path.raw().iter() {
+            let _ = match event {
+                PathEvent::Begin { at } => {
+                    writeln!(
+                        self.content,
+                        "{} {} m",
+                        number(at.x),
+                        number(at.y)
+                    )
+                }
+                PathEvent::Line { to, .. } => {
+                    writeln!(
+                        self.content,
+                        "{} {} l",
+                        number(to.x),
+                        number(to.y)
+                    )
+                }
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    let ctrl1 = from + (ctrl - from) * (2.0 / 3.0);
+                    let ctrl2 = to + (ctrl - to) * (2.0 / 3.0);
+
+                    self.curve(ctrl1, ctrl2, to)
+                }
+                PathEvent::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => self.curve(ctrl1, ctrl2, to),
+                PathEvent::End { close: true, .. } => {
+                    writeln!(self.content, "h")
+                }
+                PathEvent::End { close: false, .. } => Ok(()),
+            };
+        }
+    }
+
+    fn curve(
+        &mut self,
+        ctrl1: math::Point,
+        ctrl2: math::Point,
+        to: math::Point,
+    ) -> std::fmt::Result {
+        writeln!(
+            self.content,
+            "{} {} {} {} {} {} c",
+            number(ctrl1.x),
+            number(ctrl1.y),
+            number(ctrl2.x),
+            number(ctrl2.y),
+            number(to.x),
+            number(to.y)
+        )
+    }
+
+    /// Sets the fill or stroke paint of the content stream.
+    fn paint(&mut self, style: Style, path: &Path, is_stroke: bool) {
+        match style {
+            Style::Solid(color) => {
+                if is_stroke {
+                    self.alpha(1.0, color.a);
+                } else {
+                    self.alpha(color.a, 1.0);
+                }
+
+                let _ = writeln!(
+                    self.content,
+                    "{} {} {} {}",
+                    number(color.r),
+                    number(color.g),
+                    number(color.b),
+                    if is_stroke { "RG" } else { "rg" }
+                );
+            }
+            Style::Gradient(gradient) => {
+                let shading = self.shading(&gradient, path);
+
+                let pattern = self.object(format!(
+                    "<< /Type /Pattern /PatternType 2 /Shading {shading} 0 R \
+                     /Matrix [1 0 0 -1 0 {}] >>",
+                    number(self.height)
+                ));
+
+                self.patterns.push(pattern);
+
+                let _ = writeln!(
+                    self.content,
+                    "{} /P{pattern} {}",
+                    if is_stroke {
+                        "/Pattern CS"
+                    } else {
+                        "/Pattern cs"
+                    },
+                    if is_stroke { "SCN" } else { "scn" }
+                );
+            }
+        }
+    }
+
+    /// Adds the shading of a [`Gradient`], returning its object number.
+    fn shading(&mut self, gradient: &Gradient, path: &Path) -> usize {
+        let (kind, coords) = match gradient {
+            Gradient::Linear(linear) => (
+                2,
+                [linear.start.x, linear.start.y, linear.end.x, linear.end.y]
+                    .map(number)
+                    .join(" "),
+            ),
+            Gradient::Radial(radial) => (
+                3,
+                [
+                    radial.center.x,
+                    radial.center.y,
+                    0.0,
+                    radial.center.x,
+                    radial.center.y,
+                    radial.radius,
+                ]
+                .map(number)
+                .join(" "),
+            ),
+            Gradient::Conic(conic) => {
+                // PDF has no conic shadings; we use a mesh of solid wedges
+                let wedges = wedges(conic, path);
+
+                let (min, max) =
+                    wedges.iter().flat_map(|(points, _)| points).fold(
+                        (
+                            math::Point::new(f32::MAX, f32::MAX),
+                            math::Point::new(f32::MIN, f32::MIN),
+                        ),
+                        |(min, max), point| {
+                            (
+                                min.min(math::Point::new(point.x, point.y)),
+                                max.max(math::Point::new(point.x, point.y)),
+                            )
+                        },
+                    );
+
+                let encode = |value: f32, min: f32, max: f32| {
+                    (((value - min) / (max - min).max(f32::EPSILON))
+                        .clamp(0.0, 1.0) as f64
+                        * u32::MAX as f64) as u32
+                };
+
+                let mut data = Vec::with_capacity(wedges.len() * 3 * 12);
+
+                for (points, color) in &wedges {
+                    let [r, g, b, _] = color.into_rgba8();
+
+                    for point in points {
+                        data.push(0);
+                        data.extend(
+                            encode(point.x, min.x, max.x).to_be_bytes(),
+                        );
+                        data.extend(
+                            encode(point.y, min.y, max.y).to_be_bytes(),
+                        );
+                        data.extend([r, g, b]);
+                    }
+                }
+
+                return self.stream(
+                    &format!(
+                        "/ShadingType 4 /ColorSpace /DeviceRGB \
+                         /BitsPerCoordinate 32 /BitsPerComponent 8 \
+                         /BitsPerFlag 8 /Decode [{} {} {} {} 0 1 0 1 0 1]",
+                        number(min.x),
+                        number(max.x),
+                        number(min.y),
+                        number(max.y)
+                    ),
+                    &data,
+                );
+            }
+        };
+
+        let stops = stops(gradient);
+        let rgb =
+            |color: Color| [color.r, color.g, color.b].map(number).join(" ");
+
+        let functions: Vec<_> = stops
+            .windows(2)
+            .map(|stops| {
+                format!(
+                    "<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>",
+                    rgb(stops[0].1),
+                    rgb(stops[1].1)
+                )
+            })
+            .collect();
+
+        let bounds: Vec<_> = stops[1..stops.len() - 1]
+            .iter()
+            .map(|(offset, _)| number(*offset))
+            .collect();
+
+        self.object(format!(
+            "<< /ShadingType {kind} /ColorSpace /DeviceRGB \
+             /Coords [{coords}] /Extend [true true] \
+             /Function << /FunctionType 3 /Domain [0 1] /Functions [{}] \
+             /Bounds [{}] /Encode [{}] >> >>",
+            functions.join(" "),
+            bounds.join(" "),
+            vec!["0 1"; functions.len()].join(" "),
+        ))
+    }
+
+    /// Sets the fill and stroke opacities of the content stream.
+    fn alpha(&mut self, fill: f32, stroke: f32) {
+        if fill >= 1.0 && stroke >= 1.0 {
+            return;
+        }
+
+        let key = format!("/ca {} /CA {}", number(fill), number(stroke));
+
+        let state = if let Some((_, state)) =
+            self.states.iter().find(|(state, _)| *state == key)
+        {
+            *state
+        } else {
+            let state = self.object(format!("<< /Type /ExtGState {key} >>"));
+            self.states.push((key, state));
+
+            state
+        };
+
+        let _ = writeln!(self.content, "/G{state} gs");
+    }
+
+    /// Maps the unit square of an image to the given bounds, rotated on its
+    /// center.
+    fn place(
+        &mut self,
+        transform: &math::Transform,
+        bounds: Rectangle,
+        rotation: f32,
+    ) {
+        let center = bounds.center();
+        let (sin, cos) = rotation.sin_cos();
+
+        let _ = writeln!(
+            self.content,
+            "{} cm\n{} cm\n{} cm",
+            [
+                transform.m11,
+                transform.m12,
+                transform.m21,
+                transform.m22,
+                transform.m31,
+                transform.m32
+            ]
+            .map(number)
+            .join(" "),
+            [
+                cos,
+                sin,
+                -sin,
+                cos,
+                center.x - center.x * cos + center.y * sin,
+                center.y - center.x * sin - center.y * cos,
+            ]
+            .map(number)
+            .join(" "),
+            [
+                bounds.width,
+                0.0,
+                0.0,
+                -bounds.height,
+                bounds.x,
+                bounds.y + bounds.height
+            ]
+            .map(number)
+            .join(" "),
+        );
+    }
+
+    /// Adds the XObject of an image, returning its name.
+    fn image(&mut self, image: &image::Image) -> Option<String> {
+        let key = Key {
+            id: image.handle.id(),
+            crop: image.crop,
+            tint: image.tint.into_rgba8(),
+            adjustments: image.adjustments,
+        };
+
+        if let Some((_, object)) =
+            self.images.iter().find(|(image, _)| *image == key)
+        {
+            return Some(format!("I{object}"));
+        }
+
+        let (width, height, pixels) = pixels(image)?;
+
+        let (colors, alpha): (Vec<_>, Vec<_>) = pixels
+            .chunks_exact(4)
+            .map(|pixel| ([pixel[0], pixel[1], pixel[2]], pixel[3]))
+            .unzip();
+
+        let dictionary = format!(
+            "/Type /XObject /Subtype /Image /Width {width} /Height {height} \
+             /BitsPerComponent 8"
+        );
+
+        let mask = self
+            .stream(&format!("{dictionary} /ColorSpace /DeviceGray"), &alpha);
+
+        let object = self.stream(
+            &format!(
+                "{dictionary} /ColorSpace /DeviceRGB /SMask {mask} 0 R \
+                 /Interpolate true"
+            ),
+            &colors.concat(),
+        );
+
+        self.images.push((key, object));
+
+        Some(format!("I{object}"))
+    }
+
+    fn finish(mut self, size: Size) -> Vec<u8> {
+        let resources =
+            |prefix: &str, objects: &mut dyn Iterator<Item = usize>| {
+                objects
+                    .map(|object| format!("/{prefix}{object} {object} 0 R"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+
+        let states =
+            resources("G", &mut self.states.iter().map(|(_, state)| *state));
+        let patterns = resources("P", &mut self.patterns.iter().copied());
+        let images =
+            resources("I", &mut self.images.iter().map(|(_, image)| *image));
+
+        let content = std::mem::take(&mut self.content);
+        let content = self.stream("", content.as_bytes());
+
+        self.objects[0] = b"<< /Type /Catalog /Pages 2 0 R >>".to_vec();
+        self.objects[1] = b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec();
+        self.objects[2] = format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /ExtGState << {states} >> \
+             /Pattern << {patterns} >> /XObject << {images} >> >> \
+             /Contents {content} 0 R >>",
+            number(size.width),
+            number(size.height),
+        )
+        .into_bytes();
+
+        let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(self.objects.len());
+
+        for (i, object) in self.objects.iter().enumerate() {
+            offsets.push(pdf.len());
+
+            pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+            pdf.extend(object);
+            pdf.extend(b"\nendobj\n");
+        }
+
+        let xref = pdf.len();
+
+        pdf.extend(
+            format!(
+                "xref\n0 {}\n0000000000 65535 f \n",
+                self.objects.len() + 1
+            )
+            .into_bytes(),
+        );
+
+        for offset in offsets {
+            pdf.extend(format!("{offset:010} 00000 n \n").into_bytes());
+        }
+
+        pdf.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+                self.objects.len() + 1
+            )
+            .into_bytes(),
+        );
+
+        pdf
+    }
+}
@@ -0,0 +1,608 @@
+use crate::core::font::{self, Family};
+use crate::core::image;
+use crate::core::svg;
+use crate::core::{alignment, Color, Point, Rectangle, Size};
+use crate::geometry::export::{
+    is_processed, number, pixels, stops, wedges, Command,
+};
+use crate::geometry::fill;
+use crate::geometry::stroke::{LineCap, LineJoin};
+use crate::geometry::{Path, Style, Text};
+use crate::gradient::Gradient;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use lyon_path::math;
+use lyon_path::PathEvent;
+use std::borrow::Cow;
+use std::fmt::Write;
+
+pub fn write<'a>(
+    size: Size,
+    commands: impl Iterator<Item = &'a Command>,
+) -> String {
+    let mut writer = Writer {
+        output: String::new(),
+        ids: 0,
+    };
+
+    let _ = writeln!(
+        writer.output,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" \
+         height=\"{height}\" viewBox=\"0 0 {width} {height}\">",
+        width = number(size.width),
+        height = number(size.height),
+    );
+
+    for command in commands {
+        writer.command(command);
+    }
+
+    writer.output.push_str("</svg>\n");
+    writer.output
+}
+
+struct Writer {
+    output: String,
+    ids: usize,
+}
+
+impl Writer {
+    fn id(&mut self, prefix: &str) -> String {
+        self.ids += 1;
+
+        format!("{prefix}-{}", self.ids)
+    }
+
+    fn command(&mut self, command: &Command) {
+        match command {
+            Command::Fill { path, style, rule } => {
+                let attributes = format!(
+                    "fill-rule=\"{}\"",
+                    match rule {
+                        fill::Rule::NonZero => "nonzero",
+                        fill::Rule::EvenOdd => "evenodd",
+                    }
+                );
+
+                self.shape(path, *style, "fill", &attributes);
+            }
+            Command::Stroke {
+                path,
+                style,
+                width,
+                line_cap,
+                line_join,
+                miter_limit,
+            } => {
+                let attributes = format!(
+                    "fill=\"none\" stroke-width=\"{}\" \
+                     stroke-linecap=\"{}\" stroke-linejoin=\"{}\" \
+                     stroke-miterlimit=\"{}\"",
+                    number(*width),
+                    match line_cap {
+                        LineCap::Butt => "butt",
+                        LineCap::Square => "square",
+                        LineCap::Round => "round",
+                    },
+                    match line_join {
+                        LineJoin::Miter => "miter",
+                        LineJoin::Round => "round",
+                        LineJoin::Bevel => "bevel",
+                    },
+                    number(*miter_limit),
+                );
+
+                self.shape(path, *style, "stroke", &attributes);
+            }
+            Command::Text { text, transform } => {
+                self.text(text, transform);
+            }
+            Command::Image {
+                bounds,
+                image,
+                transform,
+            } => {
+                let href = match &image.handle {
+                    _ if is_processed(image) => {
+                        let Some((width, height, pixels)) = pixels(image)
+                        else {
+                            return;
+                        };
+
+                        match png(width, height, &pixels) {
+                            Ok(png) => data_uri("image/png", &png),
+                            Err(error) => {
+                                log::warn!(
+                                    "Failed to encode image to export: {error}"
+                                );
+                                return;
+                            }
+                        }
+                    }
+                    image::Handle::Path(_, path) => file_uri(path, None),
+                    image::Handle::Bytes(_, bytes) => {
+                        data_uri(mime_type(bytes), bytes)
+                    }
+                    image::Handle::Rgba {
+                        width,
+                        height,
+                        pixels,
+                        ..
+                    } => match png(*width, *height, pixels) {
+                        Ok(png) => data_uri("image/png", &png),
+                        Err(error) => {
+                            log::warn!(
+                                "Failed to encode image to export: {error}"
+                            );
+                            return;
+                        }
+                    },
+                };
+
+                let rendering = match image.filter_method {
+                    image::FilterMethod::Linear => "",
+                    image::FilterMethod::Nearest => {
+                        " style=\"image-rendering:pixelated\""
+                    }
+                };
+
+                let _ = writeln!(
+                    self.output,
+                    "<image {} preserveAspectRatio=\"none\" \
+                     opacity=\"{}\" transform=\"{}\"{rendering} \
+                     href=\"{href}\"/>",
+                    rectangle(*bounds),
+                    number(image.opacity),
+                    placement(transform, *bounds, image.rotation.0),
+                );
+            }
+            Command::Svg {
+                bounds,
+                svg,
+                transform,
+            } => {
+                let styled = svg::stylesheet(&svg.handle, &svg.classes)
+                    .and_then(|stylesheet| styled(&svg.handle, &stylesheet));
+
+                let href = match (styled, svg.handle.data()) {
+                    (Some(styled), _) => {
+                        data_uri("image/svg+xml", styled.as_bytes())
+                    }
+                    (None, svg::Data::Path(path)) => {
+                        file_uri(path, Some("image/svg+xml"))
+                    }
+                    (None, svg::Data::Bytes(bytes)) => {
+                        data_uri("image/svg+xml", bytes)
+                    }
+                };
+
+                let filter = if let Some(color) = svg.color {
+                    let id = self.id("color");
+
+                    let _ = writeln!(
+                        self.output,
+                        "<filter id=\"{id}\"><feFlood {}/>\
+                         <feComposite operator=\"in\" in2=\"SourceGraphic\"/>\
+                         </filter>",
+                        paint("flood", color),
+                    );
+
+                    format!(" filter=\"url(#{id})\"")
+                } else {
+                    String::new()
+                };
+
+                let _ = writeln!(
+                    self.output,
+                    "<image {} opacity=\"{}\" transform=\"{}\"{filter} \
+                     href=\"{href}\"/>",
+                    rectangle(*bounds),
+                    number(svg.opacity),
+                    placement(transform, *bounds, svg.rotation.0),
+                );
+            }
+            Command::Clip { bounds, commands } => {
+                let id = self.id("clip");
+
+                let _ = writeln!(
+                    self.output,
+                    "<clipPath id=\"{id}\"><rect {}/></clipPath>\n\
+                     <g clip-path=\"url(#{id})\">",
+                    rectangle(*bounds),
+                );
+
+                for command in commands {
+                    self.command(command);
+                }
+
+                self.output.push_str("</g>\n");
+            }
+        }
+    }
+
+    /// Writes a filled or stroked [`Path`] painted with the given [`Style`].
+    fn shape(
+        &mut self,
+        path: &Path,
+        style: Style,
+        kind: &str,
+        attributes: &str,
+    ) {
+        let data = path_data(path);
+
+        let paint = match style {
+            Style::Solid(color) => paint(kind, color),
+            Style::Gradient(Gradient::Linear(linear)) => {
+                let id = self.id("gradient");
+
+                let _ = writeln!(
+                    self.output,
+                    "<linearGradient id=\"{id}\" \
+                     gradientUnits=\"userSpaceOnUse\" x1=\"{}\" y1=\"{}\" \
+                     x2=\"{}\" y2=\"{}\">{}</linearGradient>",
+                    number(linear.start.x),
+                    number(linear.start.y),
+                    number(linear.end.x),
+                    number(linear.end.y),
+                    gradient_stops(&Gradient::Linear(linear)),
+                );
+
+                format!("{kind}=\"url(#{id})\"")
+            }
+            Style::Gradient(Gradient::Radial(radial)) => {
+                let id = self.id("gradient");
+
+                let _ = writeln!(
+                    self.output,
+                    "<radialGradient id=\"{id}\" \
+                     gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" \
+                     r=\"{}\">{}</radialGradient>",
+                    number(radial.center.x),
+                    number(radial.center.y),
+                    number(radial.radius),
+                    gradient_stops(&Gradient::Radial(radial)),
+                );
+
+                format!("{kind}=\"url(#{id})\"")
+            }
+            Style::Gradient(Gradient::Conic(conic)) => {
+                // SVG has no conic gradients; we mask a bunch of wedges with
+                // the shape instead
+                let id = self.id("mask");
+
+                let _ = writeln!(
+                    self.output,
+                    "<mask id=\"{id}\"><path d=\"{data}\" {attributes} \
+                     {}/></mask>\n<g mask=\"url(#{id})\">",
+                    paint(kind, Color::WHITE),
+                );
+
+                for ([a, b, c], color) in wedges(&conic, path) {
+                    let _ = writeln!(
+                        self.output,
+                        "<path d=\"M{} {}L{} {}L{} {}Z\" {} {} \
+                         stroke-width=\"0.5\"/>",
+                        number(a.x),
+                        number(a.y),
+                        number(b.x),
+                        number(b.y),
+                        number(c.x),
+                        number(c.y),
+                        paint("fill", color),
+                        paint("stroke", color),
+                    );
+                }
+
+                self.output.push_str("</g>\n");
+
+                return;
+            }
+        };
+
+        let _ =
+            writeln!(self.output, "<path d=\"{data}\" {attributes} {paint}/>");
+    }
+
+    fn text(&mut self, text: &Text, transform: &math::Transform) {
+        if !text.is_plain() {
+            text.draw_with(|path, color| {
+                self.command(&Command::Fill {
+                    path: path.transform(transform),
+                    style: Style::Solid(color),
+                    rule: fill::Rule::NonZero,
+                });
+            });
+
+            return;
+        }
+
+        let line_height = text.line_height.to_absolute(text.size).0;
+        let lines: Vec<_> = text.content.lines().collect();
+        let height = line_height * lines.len() as f32;
+
+        let top = match text.vertical_alignment {
+            alignment::Vertical::Top => text.position.y,
+            alignment::Vertical::Center => text.position.y - height / 2.0,
+            alignment::Vertical::Bottom => text.position.y - height,
+        };
+
+        let anchor = match text.horizontal_alignment {
+            alignment::Horizontal::Left => "start",
+            alignment::Horizontal::Center => "middle",
+            alignment::Horizontal::Right => "end",
+        };
+
+        let family = match text.font.family {
+            Family::Name(name) => format!("'{}', sans-serif", escape(name)),
+            Family::Serif => String::from("serif"),
+            Family::SansSerif => String::from("sans-serif"),
+            Family::Cursive => String::from("cursive"),
+            Family::Fantasy => String::from("fantasy"),
+            Family::Monospace => String::from("monospace"),
+        };
+
+        let weight = match text.font.weight {
+            font::Weight::Thin => 100,
+            font::Weight::ExtraLight => 200,
+            font::Weight::Light => 300,
+            font::Weight::Normal => 400,
+            font::Weight::Medium => 500,
+            font::Weight::Semibold => 600,
+            font::Weight::Bold => 700,
+            font::Weight::ExtraBold => 800,
+            font::Weight::Black => 900,
+        };
+
+        let style = match text.font.style {
+            font::Style::Normal => "normal",
+            font::Style::Italic => "italic",
+            font::Style::Oblique => "oblique",
+        };
+
+        let _ = write!(
+            self.output,
+            "<text font-family=\"{family}\" font-size=\"{}\" \
+             font-weight=\"{weight}\" font-style=\"{style}\" \
+             text-anchor=\"{anchor}\" dominant-baseline=\"central\" \
+             xml:space=\"preserve\" {} transform=\"{}\">",
+            number(text.size.0),
+            paint("fill", text.color),
+            matrix(transform),
+        );
+
+        for (i, line) in lines.iter().enumerate() {
+            let _ = write!(
+                self.output,
+                "<tspan x=\"{}\" y=\"{}\">{}</tspan>",
+                number(text.position.x),
+                number(top + line_height * (i as f32 + 0.5)),
+                escape(line),
+            );
+        }
+
+        self.output.push_str("</text>\n");
+    }
+}
+
+fn path_data(path: &Path) -> String {
+    let mut data = String::new();
+
+    for event in path.raw().iter() {
+        let _ = match event {
+            PathEvent::Begin { at } => {
+                write!(data, "M{} {}", number(at.x), number(at.y))
+            }
+            PathEvent::Line { to, .. } => {
+                write!(data, "L{} {}", number(to.x), number(to.y))
+            }
+            PathEvent::Quadratic { ctrl, to, .. } => write!(
+                data,
+                "Q{} {} {} {}",
+                number(ctrl.x),
+                number(ctrl.y),
+                number(to.x),
+                number(to.y)
+            ),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => write!(
+                data,
+                "C{} {} {} {} {} {}",
+                number(ctrl1.x),
+                number(ctrl1.y),
+                number(ctrl2.x),
+                number(ctrl2.y),
+                number(to.x),
+                number(to.y)
+            ),
+            PathEvent::End { close: true, .. } => write!(data, "Z"),
+            PathEvent::End { close: false, .. } => Ok(()),
+        };
+    }
+
+    data
+}
+
+fn paint(kind: &str, color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+
+    if color.a >= 1.0 {
+        format!("{kind}=\"#{r:02x}{g:02x}{b:02x}\"")
+    } else {
+        format!(
+            "{kind}=\"#{r:02x}{g:02x}{b:02x}\" {kind}-opacity=\"{}\"",
+            number(color.a)
+        )
+    }
+}
+
+fn gradient_stops(gradient: &Gradient) -> String {
+    stops(gradient)
+        .into_iter()
+        .map(|(offset, color)| {
+            let [r, g, b, _] = color.into_rgba8();
+
+            format!(
+                "<stop offset=\"{}\" stop-color=\"#{r:02x}{g:02x}{b:02x}\" \
+                 stop-opacity=\"{}\"/>",
+                number(offset),
+                number(color.a),
+            )
+        })
+        .collect()
+}
+
+fn rectangle(bounds: Rectangle) -> String {
+    format!(
+        "x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+        number(bounds.x),
+        number(bounds.y),
+        number(bounds.width),
+        number(bounds.height)
+    )
+}
+
+fn matrix(transform: &math::Transform) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        number(transform.m11),
+        number(transform.m12),
+        number(transform.m21),
+        number(transform.m22),
+        number(transform.m31),
+        number(transform.m32),
+    )
+}
+
+/// Returns the transform of an image with the given bounds and rotation on
+/// its center.
+fn placement(
+    transform: &math::Transform,
+    bounds: Rectangle,
+    rotation: f32,
+) -> String {
+    let Point { x, y } = bounds.center();
+
+    format!(
+        "{} rotate({} {} {})",
+        matrix(transform),
+        number(rotation.to_degrees()),
+        number(x),
+        number(y)
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn mime_type(bytes: &[u8]) -> &'static str {
+    match bytes {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => {
+            "image/webp"
+        }
+        [b'B', b'M', ..] => "image/bmp",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Embeds the file at the given path as a data URI, so the exported
+/// document is self-contained; falling back to a `file://` URI if the file
+/// cannot be read.
+fn file_uri(path: &std::path::Path, mime: Option<&str>) -> String {
+    match std::fs::read(path) {
+        Ok(bytes) => {
+            data_uri(mime.unwrap_or_else(|| mime_type(&bytes)), &bytes)
+        }
+        Err(error) => {
+            log::warn!(
+                "Failed to embed {} in exported SVG: {error}",
+                path.display()
+            );
+
+            let path = std::path::absolute(path)
+                .unwrap_or_else(|_| path.to_path_buf());
+
+            escape(&format!("file://{}", path.display()))
+        }
+    }
+}
+
+/// Returns the document of the SVG with the given [`svg::Handle`], with the
+/// given stylesheet injected.
+fn styled(handle: &svg::Handle, stylesheet: &str) -> Option<String> {
+    let data = match handle.data() {
+        svg::Data::Path(path) => match std::fs::read(path) {
+            Ok(bytes) => Cow::Owned(bytes),
+            Err(error) => {
+                log::warn!(
+                    "Failed to style {} in exported SVG: {error}",
+                    path.display()
+                );
+
+                return None;
+            }
+        },
+        svg::Data::Bytes(bytes) => Cow::Borrowed(bytes.as_ref()),
+    };
+
+    if data.starts_with(&[0x1f, 0x8b]) {
+        #[cfg(feature = "svg")]
+        {
+            let data = crate::svg::usvg::decompress_svgz(&data).ok()?;
+            let document = String::from_utf8(data).ok()?;
+
+            return svg::inject_stylesheet(&document, stylesheet);
+        }
+
+        #[cfg(not(feature = "svg"))]
+        {
+            log::warn!(
+                "Styling compressed SVGs to export requires the `svg` feature"
+            );
+
+            return None;
+        }
+    }
+
+    svg::inject_stylesheet(std::str::from_utf8(&data).ok()?, stylesheet)
+}
+
+fn data_uri(mime_type: &str, bytes: &[u8]) -> String {
+    format!("data:{mime_type};base64,{}", STANDARD.encode(bytes))
+}
+
+fn png(
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> Result<Vec<u8>, png::EncodingError> {
+    let mut png = Vec::new();
+
+    {
+        let mut encoder = png::Encoder::new(&mut png, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder.write_header()?.write_image_data(pixels)?;
+    }
+
+    Ok(png)
+}
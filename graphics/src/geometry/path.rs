@@ -1,6 +1,7 @@
 //! Build different kinds of 2D shapes.
 pub mod arc;
 
+mod boolean;
 mod builder;
 
 #[doc(no_inline)]
@@ -10,7 +11,16 @@ pub use builder::Builder;
 pub use lyon_path;
 
 use crate::core::border;
-use crate::core::{Point, Size};
+use crate::core::{Point, Rectangle, Size, Vector};
+use crate::geometry::{fill, Stroke};
+
+use lyon_path::iterator::PathIterator;
+use lyon_path::math;
+use lyon_path::PathEvent;
+
+/// The maximum distance between a curve and the line segments used to
+/// approximate it when inspecting a [`Path`].
+pub(crate) const TOLERANCE: f32 = 0.1;
 
 /// An immutable set of points that may or may not be connected.
 ///
@@ -70,6 +80,245 @@ impl Path {
         &self.raw
     }
 
+    /// Returns the length of the [`Path`], including the segments closing
+    /// its closed subpaths.
+    pub fn length(&self) -> f32 {
+        self.segments(false)
+            .map(|(from, to)| (to - from).length())
+            .sum()
+    }
+
+    /// Returns the [`Point`] of the [`Path`] at the given distance from its
+    /// start, clamped to its length.
+    ///
+    /// Returns `None` if the [`Path`] is empty.
+    pub fn point_at(&self, distance: f32) -> Option<Point> {
+        let (from, to, t) = self.segment_at(distance)?;
+        let point = from.lerp(to, t);
+
+        Some(Point::new(point.x, point.y))
+    }
+
+    /// Returns the unit tangent of the [`Path`] at the given distance from
+    /// its start, clamped to its length.
+    ///
+    /// Returns `None` if the [`Path`] is empty or has no length.
+    pub fn tangent_at(&self, distance: f32) -> Option<Vector> {
+        let (from, to, _) = self.segment_at(distance)?;
+        let tangent = (to - from).try_normalize()?;
+
+        Some(Vector::new(tangent.x, tangent.y))
+    }
+
+    /// Returns the segment of the [`Path`] at the given distance from its
+    /// start, together with the relative position of the distance in it.
+    fn segment_at(
+        &self,
+        distance: f32,
+    ) -> Option<(math::Point, math::Point, f32)> {
+        let mut remaining = distance.max(0.0);
+        let mut last = None;
+
+        for (from, to) in self.segments(false) {
+            let length = (to - from).length();
+
+            if length > 0.0 && remaining <= length {
+                return Some((from, to, remaining / length));
+            }
+
+            remaining -= length;
+
+            if length > 0.0 || last.is_none() {
+                last = Some((from, to, 1.0));
+            }
+        }
+
+        last
+    }
+
+    /// Returns the smallest [`Rectangle`] containing the whole [`Path`].
+    ///
+    /// Curves are approximated, so the bounds may be off by a fraction of a
+    /// pixel.
+    pub fn bounds(&self) -> Rectangle {
+        let mut points = self.segments(false).flat_map(|(from, to)| [from, to]);
+
+        let Some(first) = points.next() else {
+            return Rectangle::default();
+        };
+
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (min.min(point), max.max(point))
+        });
+
+        Rectangle::new(
+            Point::new(min.x, min.y),
+            Size::new(max.x - min.x, max.y - min.y),
+        )
+    }
+
+    /// Returns a [`Path`] approximating the current one only with line
+    /// segments, which are never farther than `tolerance` from the curves
+    /// they replace.
+    pub fn flatten(&self, tolerance: f32) -> Path {
+        let mut builder = Builder::new();
+
+        for event in self.raw.iter().flattened(tolerance) {
+            match event {
+                PathEvent::Begin { at } => {
+                    builder.move_to(Point::new(at.x, at.y));
+                }
+                PathEvent::Line { to, .. } => {
+                    builder.line_to(Point::new(to.x, to.y));
+                }
+                PathEvent::End { close: true, .. } => builder.close(),
+                _ => {}
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Returns the part of the [`Path`] between the given distances from its
+    /// start.
+    ///
+    /// This is useful to animate the drawing of a [`Path`]. Curves are
+    /// flattened in the resulting [`Path`], which is empty if `start` is not
+    /// before `end`.
+    pub fn trim(&self, start: f32, end: f32) -> Path {
+        let mut builder = Builder::new();
+
+        if start >= end {
+            return builder.build();
+        }
+        let mut distance = 0.0;
+        let mut last = None;
+
+        for (from, to) in self.segments(false) {
+            let length = (to - from).length();
+            let (a, b) = (start - distance, end - distance);
+
+            distance += length;
+
+            if length == 0.0 || b < 0.0 || a > length {
+                continue;
+            }
+
+            let (from, to) = (
+                from.lerp(to, (a / length).max(0.0)),
+                from.lerp(to, (b / length).min(1.0)),
+            );
+
+            if last != Some(from) {
+                builder.move_to(Point::new(from.x, from.y));
+            }
+
+            builder.line_to(Point::new(to.x, to.y));
+            last = Some(to);
+        }
+
+        builder.build()
+    }
+
+    /// Returns the union of the [`Path`] and another one.
+    ///
+    /// Both paths are interpreted with the [`fill::Rule::NonZero`] rule and
+    /// their curves are flattened in the resulting [`Path`].
+    pub fn union(&self, other: &Path) -> Path {
+        boolean::apply(self, other, |a, b| a || b)
+    }
+
+    /// Returns the intersection of the [`Path`] and another one.
+    ///
+    /// Both paths are interpreted with the [`fill::Rule::NonZero`] rule and
+    /// their curves are flattened in the resulting [`Path`].
+    pub fn intersection(&self, other: &Path) -> Path {
+        boolean::apply(self, other, |a, b| a && b)
+    }
+
+    /// Returns the [`Path`] without the area covered by another one.
+    ///
+    /// Both paths are interpreted with the [`fill::Rule::NonZero`] rule and
+    /// their curves are flattened in the resulting [`Path`].
+    pub fn difference(&self, other: &Path) -> Path {
+        boolean::apply(self, other, |a, b| a && !b)
+    }
+
+    /// Returns the area covered by either the [`Path`] or another one, but
+    /// not both.
+    ///
+    /// Both paths are interpreted with the [`fill::Rule::NonZero`] rule and
+    /// their curves are flattened in the resulting [`Path`].
+    pub fn xor(&self, other: &Path) -> Path {
+        boolean::apply(self, other, |a, b| a != b)
+    }
+
+    /// Returns true if the given [`Point`] is inside of the [`Path`] when
+    /// filled with the given [`fill::Rule`].
+    ///
+    /// Open subpaths are considered implicitly closed, like when filling.
+    pub fn contains(&self, point: Point, rule: fill::Rule) -> bool {
+        let winding =
+            winding(self.segments(true), math::Point::new(point.x, point.y));
+
+        match rule {
+            fill::Rule::NonZero => winding != 0,
+            fill::Rule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Returns true if the given [`Point`] is covered by the [`Path`] when
+    /// stroked with the given [`Stroke`].
+    ///
+    /// Caps and joins are approximated as round.
+    pub fn stroke_contains(&self, point: Point, stroke: &Stroke<'_>) -> bool {
+        let point = math::Point::new(point.x, point.y);
+        let radius = stroke.width / 2.0;
+
+        stroke.apply(self).segments(false).any(|(from, to)| {
+            let segment = to - from;
+            let length = segment.square_length();
+
+            let t = if length > 0.0 {
+                ((point - from).dot(segment) / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            (from + segment * t - point).length() <= radius
+        })
+    }
+
+    /// Returns the line segments that approximate the [`Path`], including
+    /// the ones closing every subpath if `close` is true.
+    ///
+    /// Subpaths without any segments yield a single, degenerate one.
+    fn segments(
+        &self,
+        close: bool,
+    ) -> impl Iterator<Item = (math::Point, math::Point)> + '_ {
+        let mut is_empty = true;
+
+        self.raw.iter().flattened(TOLERANCE).filter_map(
+            move |event| match event {
+                PathEvent::Begin { .. } => {
+                    is_empty = true;
+                    None
+                }
+                PathEvent::Line { from, to } => {
+                    is_empty = false;
+                    Some((from, to))
+                }
+                PathEvent::End {
+                    last,
+                    first,
+                    close: is_closed,
+                } => (is_empty || close || is_closed).then_some((last, first)),
+                _ => None,
+            },
+        )
+    }
+
     /// Returns the current [`Path`] with the given transform applied to it.
     #[inline]
     pub fn transform(&self, transform: &lyon_path::math::Transform) -> Path {
@@ -78,3 +327,184 @@ impl Path {
         }
     }
 }
+
+/// Returns the winding number of the given closed segments around a point.
+fn winding(
+    segments: impl IntoIterator<Item = (math::Point, math::Point)>,
+    point: math::Point,
+) -> i32 {
+    let mut winding = 0;
+
+    for (from, to) in segments {
+        let side = (to - from).cross(point - from);
+
+        if from.y <= point.y {
+            if to.y > point.y && side > 0.0 {
+                winding += 1;
+            }
+        } else if to.y <= point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+
+    winding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polyline(points: &[(f32, f32)]) -> Path {
+        Path::new(|builder| {
+            for (i, (x, y)) in points.iter().enumerate() {
+                if i == 0 {
+                    builder.move_to(Point::new(*x, *y));
+                } else {
+                    builder.line_to(Point::new(*x, *y));
+                }
+            }
+        })
+    }
+
+    fn lines(path: &Path) -> Vec<(Point, Point)> {
+        path.raw()
+            .iter()
+            .filter_map(|event| match event {
+                PathEvent::Line { from, to } => {
+                    Some((Point::new(from.x, from.y), Point::new(to.x, to.y)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounds_include_every_point() {
+        let line = Path::line(Point::new(10.0, 20.0), Point::new(30.0, 5.0));
+
+        assert_eq!(
+            line.bounds(),
+            Rectangle::new(Point::new(10.0, 5.0), Size::new(20.0, 15.0))
+        );
+    }
+
+    #[test]
+    fn bounds_approximate_curves() {
+        let bounds = Path::circle(Point::new(5.0, 5.0), 5.0).bounds();
+
+        assert!(bounds.x.abs() <= TOLERANCE);
+        assert!(bounds.y.abs() <= TOLERANCE);
+        assert!((bounds.width - 10.0).abs() <= TOLERANCE * 2.0);
+        assert!((bounds.height - 10.0).abs() <= TOLERANCE * 2.0);
+    }
+
+    #[test]
+    fn bounds_of_empty_path() {
+        assert_eq!(Path::new(|_| {}).bounds(), Rectangle::default());
+    }
+
+    #[test]
+    fn length_of_open_and_closed_paths() {
+        assert_eq!(polyline(&[(0.0, 0.0), (3.0, 4.0)]).length(), 5.0);
+        assert_eq!(
+            polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 20.0)]).length(),
+            30.0
+        );
+        assert_eq!(
+            Path::rectangle(Point::ORIGIN, Size::new(10.0, 20.0)).length(),
+            60.0
+        );
+    }
+
+    #[test]
+    fn point_at_walks_segments() {
+        let path = polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        assert_eq!(path.point_at(5.0), Some(Point::new(5.0, 0.0)));
+        assert_eq!(path.point_at(15.0), Some(Point::new(10.0, 5.0)));
+    }
+
+    #[test]
+    fn point_at_clamps_distance() {
+        let path = polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        assert_eq!(path.point_at(-1.0), Some(Point::ORIGIN));
+        assert_eq!(path.point_at(100.0), Some(Point::new(10.0, 10.0)));
+        assert_eq!(Path::new(|_| {}).point_at(0.0), None);
+    }
+
+    #[test]
+    fn tangent_at_follows_segments() {
+        let path = polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        assert_eq!(path.tangent_at(5.0), Some(Vector::new(1.0, 0.0)));
+        assert_eq!(path.tangent_at(15.0), Some(Vector::new(0.0, 1.0)));
+        assert_eq!(path.tangent_at(100.0), Some(Vector::new(0.0, 1.0)));
+    }
+
+    #[test]
+    fn tangent_at_needs_length() {
+        let point = Path::new(|builder| builder.move_to(Point::ORIGIN));
+
+        assert_eq!(point.tangent_at(0.0), None);
+    }
+
+    #[test]
+    fn trim_keeps_range() {
+        let path = polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+
+        assert_eq!(
+            lines(&path.trim(5.0, 15.0)),
+            vec![
+                (Point::new(5.0, 0.0), Point::new(10.0, 0.0)),
+                (Point::new(10.0, 0.0), Point::new(10.0, 5.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn trim_outside_is_empty() {
+        let path = Path::line(Point::ORIGIN, Point::new(10.0, 0.0));
+
+        assert!(lines(&path.trim(20.0, 30.0)).is_empty());
+        assert!(lines(&path.trim(5.0, 2.0)).is_empty());
+    }
+
+    #[test]
+    fn flatten_replaces_curves_with_lines() {
+        let arch = Path::new(|builder| {
+            builder.move_to(Point::ORIGIN);
+            builder.quadratic_curve_to(
+                Point::new(5.0, 10.0),
+                Point::new(10.0, 0.0),
+            );
+            builder.close();
+        });
+
+        let flattened = arch.flatten(0.01);
+
+        assert!(flattened.raw().iter().all(|event| matches!(
+            event,
+            PathEvent::Begin { .. }
+                | PathEvent::Line { .. }
+                | PathEvent::End { close: true, .. }
+        )));
+
+        let lines = lines(&flattened);
+
+        assert_eq!(lines.first().map(|line| line.0), Some(Point::ORIGIN));
+        assert_eq!(
+            lines.last().map(|line| line.1),
+            Some(Point::new(10.0, 0.0))
+        );
+
+        // Every vertex lies on the curve: (10t, 20t(1 - t))
+        for (from, _) in &lines {
+            let t = from.x / 10.0;
+
+            assert!((from.y - 20.0 * t * (1.0 - t)).abs() < 1e-3);
+        }
+
+        assert!(lines.len() > arch.flatten(1.0).raw().iter().count());
+    }
+}
@@ -0,0 +1,340 @@
+use crate::core::Point;
+use crate::geometry::path::{winding, Builder, Path};
+
+use lyon_path::math;
+use std::collections::HashMap;
+
+/// The size of the grid that all the points are snapped to.
+const GRID: f32 = 1.0 / 4096.0;
+
+/// The distance from the middle of an edge at which its sides are sampled.
+const SIDE: f32 = 1e-2;
+
+/// The distance under which an intersection is snapped to an endpoint.
+const SNAP: f32 = 1e-3;
+
+type Edge = (math::Point, math::Point);
+
+/// Combines the areas of two paths, keeping the points for which `operation`
+/// returns true given their presence in each path.
+///
+/// Both paths are flattened, snapped to a grid and split at their intersections. Then, only
+/// the edges separating the resulting area from the rest are kept and
+/// chained into closed subpaths, consistently oriented.
+pub fn apply(
+    a: &Path,
+    b: &Path,
+    operation: impl Fn(bool, bool) -> bool,
+) -> Path {
+    let a = edges(a);
+    let b = edges(b);
+
+    let is_inside = |point: math::Point| {
+        operation(
+            winding(a.iter().copied(), point) != 0,
+            winding(b.iter().copied(), point) != 0,
+        )
+    };
+
+    let edges = [a.as_slice(), b.as_slice()].concat();
+
+    let mut boundary: Vec<Edge> = split(&edges)
+        .into_iter()
+        .filter_map(|(from, to)| {
+            let middle = from.lerp(to, 0.5);
+            let direction = (to - from).normalize();
+            let normal = math::vector(-direction.y, direction.x) * SIDE;
+
+            match (is_inside(middle + normal), is_inside(middle - normal)) {
+                (true, false) => Some((from, to)),
+                (false, true) => Some((to, from)),
+                _ => None,
+            }
+        })
+        .collect();
+
+    // Edges shared by both paths are kept twice
+    boundary.sort_by(|a, b| {
+        [a.0.x, a.0.y, a.1.x, a.1.y]
+            .iter()
+            .zip([b.0.x, b.0.y, b.1.x, b.1.y].iter())
+            .map(|(a, b)| a.total_cmp(b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    boundary.dedup();
+
+    chain(&boundary)
+}
+
+/// Returns the closed, non-degenerate edges of a [`Path`] snapped to the
+/// grid.
+fn edges(path: &Path) -> Vec<Edge> {
+    path.segments(true)
+        .map(|(from, to)| (snap(from), snap(to)))
+        .filter(|(from, to)| from != to)
+        .collect()
+}
+
+/// Snaps a point to the grid.
+fn snap(point: math::Point) -> math::Point {
+    math::point(
+        (point.x / GRID).round() * GRID + 0.0,
+        (point.y / GRID).round() * GRID + 0.0,
+    )
+}
+
+/// Splits the given edges at all of their intersections.
+fn split(edges: &[Edge]) -> Vec<Edge> {
+    let mut splits: Vec<Vec<(f32, math::Point)>> =
+        vec![Vec::new(); edges.len()];
+
+    for (i, &(p1, p2)) in edges.iter().enumerate() {
+        for (j, &(q1, q2)) in edges.iter().enumerate().skip(i + 1) {
+            for (t, u, point) in intersections((p1, p2), (q1, q2)) {
+                if t > 0.0 && t < 1.0 {
+                    splits[i].push((t, point));
+                }
+
+                if u > 0.0 && u < 1.0 {
+                    splits[j].push((u, point));
+                }
+            }
+        }
+    }
+
+    edges
+        .iter()
+        .zip(splits)
+        .flat_map(|(&(from, to), mut splits)| {
+            splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let points: Vec<_> = std::iter::once(from)
+                .chain(splits.into_iter().map(|(_, point)| point))
+                .chain(std::iter::once(to))
+                .collect();
+
+            points
+                .windows(2)
+                .map(|points| (points[0], points[1]))
+                .filter(|(from, to)| from != to)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Returns the intersections between two edges, as the relative positions
+/// in each edge and the intersection point.
+///
+/// Collinear edges intersect at the endpoints of each that lie in the other.
+fn intersections(
+    (p1, p2): Edge,
+    (q1, q2): Edge,
+) -> Vec<(f32, f32, math::Point)> {
+    let r = p2 - p1;
+    let s = q2 - q1;
+    let denominator = r.cross(s);
+
+    let (r_length, s_length) = (r.length(), s.length());
+
+    if denominator.abs() > f32::EPSILON * r_length * s_length {
+        let t = (q1 - p1).cross(s) / denominator;
+        let u = (q1 - p1).cross(r) / denominator;
+
+        let (t_margin, u_margin) = (SNAP / r_length, SNAP / s_length);
+
+        if t < -t_margin
+            || t > 1.0 + t_margin
+            || u < -u_margin
+            || u > 1.0 + u_margin
+        {
+            return Vec::new();
+        }
+
+        // Snap to endpoints, so split edges stay connected
+        let (t, point) = if t <= t_margin {
+            (0.0, p1)
+        } else if t >= 1.0 - t_margin {
+            (1.0, p2)
+        } else if u <= u_margin {
+            (t, q1)
+        } else if u >= 1.0 - u_margin {
+            (t, q2)
+        } else {
+            (t, snap(p1 + r * t))
+        };
+
+        let u = if u <= u_margin {
+            0.0
+        } else if u >= 1.0 - u_margin {
+            1.0
+        } else {
+            u
+        };
+
+        return vec![(t, u, point)];
+    }
+
+    if (q1 - p1).cross(r).abs() > SNAP * r_length {
+        return Vec::new();
+    }
+
+    let project =
+        |point: math::Point, from: math::Point, edge: math::Vector| {
+            (point - from).dot(edge) / edge.square_length()
+        };
+
+    [q1, q2]
+        .into_iter()
+        .map(|point| (project(point, p1, r), 2.0, point))
+        .chain(
+            [p1, p2]
+                .into_iter()
+                .map(|point| (2.0, project(point, q1, s), point)),
+        )
+        .collect()
+}
+
+/// Chains the given edges into subpaths.
+fn chain(edges: &[Edge]) -> Path {
+    let key = |point: math::Point| (point.x.to_bits(), point.y.to_bits());
+
+    let mut outgoing: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+
+    for (i, (from, _)) in edges.iter().enumerate() {
+        outgoing.entry(key(*from)).or_default().push(i);
+    }
+
+    let mut is_used = vec![false; edges.len()];
+    let mut builder = Builder::new();
+
+    for first in 0..edges.len() {
+        if is_used[first] {
+            continue;
+        }
+
+        let start = edges[first].0;
+        let mut current = first;
+
+        builder.move_to(Point::new(start.x, start.y));
+
+        loop {
+            is_used[current] = true;
+
+            let end = edges[current].1;
+
+            if end == start {
+                builder.close();
+                break;
+            }
+
+            builder.line_to(Point::new(end.x, end.y));
+
+            let next = outgoing.get(&key(end)).and_then(|candidates| {
+                candidates.iter().copied().find(|i| !is_used[*i])
+            });
+
+            match next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Point, Size};
+    use crate::geometry::fill;
+    use crate::geometry::Path;
+
+    fn square(x: f32, y: f32) -> Path {
+        Path::rectangle(Point::new(x, y), Size::new(10.0, 10.0))
+    }
+
+    fn area(path: &Path) -> f32 {
+        path.segments(true)
+            .map(|(from, to)| from.x * to.y - to.x * from.y)
+            .sum::<f32>()
+            .abs()
+            / 2.0
+    }
+
+    fn covers(path: &Path, x: f32, y: f32) -> bool {
+        path.contains(Point::new(x, y), fill::Rule::NonZero)
+    }
+
+    #[test]
+    fn union_covers_both() {
+        let union = square(0.0, 0.0).union(&square(5.0, 0.0));
+
+        assert!((area(&union) - 150.0).abs() < 1e-2);
+        assert!(covers(&union, 2.0, 5.0));
+        assert!(covers(&union, 7.0, 5.0));
+        assert!(covers(&union, 12.0, 5.0));
+        assert!(!covers(&union, 17.0, 5.0));
+    }
+
+    #[test]
+    fn intersection_covers_overlap() {
+        let intersection = square(0.0, 0.0).intersection(&square(5.0, 0.0));
+
+        assert!((area(&intersection) - 50.0).abs() < 1e-2);
+        assert!(!covers(&intersection, 2.0, 5.0));
+        assert!(covers(&intersection, 7.0, 5.0));
+        assert!(!covers(&intersection, 12.0, 5.0));
+    }
+
+    #[test]
+    fn intersection_of_disjoint_paths_is_empty() {
+        let intersection = square(0.0, 0.0).intersection(&square(20.0, 0.0));
+
+        assert_eq!(area(&intersection), 0.0);
+    }
+
+    #[test]
+    fn difference_removes_other() {
+        let difference = square(0.0, 0.0).difference(&square(5.0, 0.0));
+
+        assert!((area(&difference) - 50.0).abs() < 1e-2);
+        assert!(covers(&difference, 2.0, 5.0));
+        assert!(!covers(&difference, 7.0, 5.0));
+        assert!(!covers(&difference, 12.0, 5.0));
+    }
+
+    #[test]
+    fn difference_can_make_holes() {
+        let ring = Path::rectangle(Point::ORIGIN, Size::new(30.0, 30.0))
+            .difference(&square(10.0, 10.0));
+
+        assert!(covers(&ring, 5.0, 5.0));
+        assert!(!covers(&ring, 15.0, 15.0));
+    }
+
+    #[test]
+    fn xor_removes_overlap() {
+        let xor = square(0.0, 0.0).xor(&square(5.0, 0.0));
+
+        assert!((area(&xor) - 100.0).abs() < 1e-2);
+        assert!(covers(&xor, 2.0, 5.0));
+        assert!(!covers(&xor, 7.0, 5.0));
+        assert!(covers(&xor, 12.0, 5.0));
+    }
+
+    #[test]
+    fn operations_ignore_winding_direction() {
+        let reversed = Path::new(|builder| {
+            builder.move_to(Point::new(5.0, 0.0));
+            builder.line_to(Point::new(5.0, 10.0));
+            builder.line_to(Point::new(15.0, 10.0));
+            builder.line_to(Point::new(15.0, 0.0));
+            builder.close();
+        });
+
+        let union = square(0.0, 0.0).union(&reversed);
+
+        assert!((area(&union) - 150.0).abs() < 1e-2);
+    }
+}
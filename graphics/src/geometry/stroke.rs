@@ -3,7 +3,14 @@
 //! [`Path`]: super::Path
 pub use crate::geometry::Style;
 
+use crate::core::{Point, Vector};
+use crate::geometry::path::{self, Path};
+
 use iced_core::Color;
+use lyon_path::iterator::PathIterator;
+use lyon_path::PathEvent;
+
+use std::borrow::Cow;
 
 /// The style of a stroke.
 #[derive(Debug, Clone, Copy)]
@@ -21,6 +28,21 @@ pub struct Stroke<'a> {
     pub line_join: LineJoin,
     /// The dash pattern used when stroking the line.
     pub line_dash: LineDash<'a>,
+    /// The distance to advance the [`LineDash`] by before starting it.
+    ///
+    /// Increasing it over time makes the dashes march along the path; which
+    /// is handy to highlight selections.
+    pub dash_phase: f32,
+    /// The limit on the ratio between the length of a miter join and the
+    /// [`width`] of the stroke; beyond it, the join is bevelled.
+    ///
+    /// Only used with [`LineJoin::Miter`]. Values below `2.0` are treated as
+    /// `2.0` by every backend.
+    ///
+    /// [`width`]: Self::width
+    pub miter_limit: f32,
+    /// The position of the stroke relative to the [`Path`].
+    pub alignment: Alignment,
 }
 
 impl Stroke<'_> {
@@ -46,6 +68,59 @@ impl Stroke<'_> {
     pub fn with_line_join(self, line_join: LineJoin) -> Self {
         Stroke { line_join, ..self }
     }
+
+    /// Sets the miter limit of the [`Stroke`].
+    pub fn with_miter_limit(self, miter_limit: f32) -> Self {
+        Stroke {
+            miter_limit,
+            ..self
+        }
+    }
+
+    /// Sets the dash phase of the [`Stroke`].
+    pub fn with_dash_phase(self, dash_phase: f32) -> Self {
+        Stroke { dash_phase, ..self }
+    }
+
+    /// Sets the [`Alignment`] of the [`Stroke`].
+    pub fn with_alignment(self, alignment: Alignment) -> Self {
+        Stroke { alignment, ..self }
+    }
+}
+
+impl<'a> Stroke<'a> {
+    /// Sets the [`LineDash`] of the [`Stroke`].
+    pub fn with_line_dash(self, line_dash: LineDash<'a>) -> Self {
+        Stroke { line_dash, ..self }
+    }
+
+    /// Returns the [`Path`] that a backend needs to stroke along its center
+    /// in order to draw the given [`Path`] with this [`Stroke`].
+    ///
+    /// The [`Alignment`] and the [`LineDash`] of the [`Stroke`] are applied
+    /// to the returned [`Path`], which is borrowed when neither is set.
+    pub fn apply<'b>(&self, path: &'b Path) -> Cow<'b, Path> {
+        let offset = match self.alignment {
+            Alignment::Center => 0.0,
+            Alignment::Inside => self.width / 2.0,
+            Alignment::Outside => -self.width / 2.0,
+        };
+
+        let path = if offset == 0.0 {
+            Cow::Borrowed(path)
+        } else {
+            Cow::Owned(aligned(path, offset, self.miter_limit.max(2.0)))
+        };
+
+        match self.line_dash.intervals() {
+            Some(intervals) => Cow::Owned(dashed(
+                &path,
+                &intervals,
+                self.line_dash.start(&intervals, self.dash_phase),
+            )),
+            None => path,
+        }
+    }
 }
 
 impl Default for Stroke<'_> {
@@ -56,6 +131,9 @@ impl Default for Stroke<'_> {
             line_cap: LineCap::default(),
             line_join: LineJoin::default(),
             line_dash: LineDash::default(),
+            dash_phase: 0.0,
+            miter_limit: 4.0,
+            alignment: Alignment::default(),
         }
     }
 }
@@ -87,12 +165,489 @@ pub enum LineJoin {
     Bevel,
 }
 
+/// The position of a stroke relative to the [`Path`] it outlines.
+///
+/// The [`Alignment`] only affects closed subpaths; open ones are always
+/// stroked along their center.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// The stroke is centered on the path.
+    #[default]
+    Center,
+    /// The stroke lies entirely inside the path.
+    Inside,
+    /// The stroke lies entirely outside the path.
+    Outside,
+}
+
 /// The dash pattern used when stroking the line.
+///
+/// The pattern restarts at the beginning of every subpath.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct LineDash<'a> {
     /// The alternating lengths of lines and gaps which describe the pattern.
+    ///
+    /// If the amount of lengths is odd, they are repeated to yield an even
+    /// amount.
     pub segments: &'a [f32],
 
-    /// The offset of [`LineDash::segments`] to start the pattern.
+    /// The index of the segment in [`LineDash::segments`] which starts the
+    /// pattern.
     pub offset: usize,
 }
+
+impl<'a> LineDash<'a> {
+    /// Creates a new [`LineDash`] with the given segments.
+    pub fn new(segments: &'a [f32]) -> Self {
+        Self {
+            segments,
+            offset: 0,
+        }
+    }
+
+    /// Sets the offset of the [`LineDash`].
+    pub fn offset(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Returns the even amount of intervals of the pattern, or `None` if the
+    /// [`LineDash`] does not describe a valid pattern.
+    fn intervals(&self) -> Option<Cow<'a, [f32]>> {
+        let is_valid = self
+            .segments
+            .iter()
+            .all(|segment| segment.is_finite() && *segment >= 0.0)
+            && self.segments.iter().sum::<f32>() > 0.0;
+
+        if !is_valid {
+            return None;
+        }
+
+        Some(if self.segments.len() % 2 == 1 {
+            Cow::Owned([self.segments, self.segments].concat())
+        } else {
+            Cow::Borrowed(self.segments)
+        })
+    }
+
+    /// Returns the distance into the given intervals where the pattern
+    /// starts, once advanced by the given phase.
+    fn start(&self, intervals: &[f32], phase: f32) -> f32 {
+        let length: f32 = intervals.iter().sum();
+        let offset: f32 =
+            intervals.iter().take(self.offset % intervals.len()).sum();
+
+        let start = (offset + phase).rem_euclid(length);
+
+        if start.is_finite() {
+            start
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Dashes the given [`Path`] by walking the intervals of a pattern along its
+/// subpaths, starting at the given distance into the pattern.
+fn dashed(path: &Path, intervals: &[f32], start: f32) -> Path {
+    let (index, remaining) = {
+        let mut index = 0;
+        let mut remaining = start;
+
+        while remaining >= intervals[index] {
+            remaining -= intervals[index];
+            index = (index + 1) % intervals.len();
+        }
+
+        (index, intervals[index] - remaining)
+    };
+
+    Path::new(|builder| {
+        let mut dash = Dash {
+            intervals,
+            index,
+            remaining,
+        };
+
+        for event in path.raw().iter().flattened(path::TOLERANCE) {
+            match event {
+                PathEvent::Begin { at } => {
+                    dash = Dash {
+                        intervals,
+                        index,
+                        remaining,
+                    };
+
+                    if dash.is_on() {
+                        builder.move_to(Point::new(at.x, at.y));
+                    }
+                }
+                PathEvent::Line { from, to } => {
+                    dash.walk(builder, from, to);
+                }
+                PathEvent::End {
+                    last,
+                    first,
+                    close: true,
+                } => {
+                    dash.walk(builder, last, first);
+                }
+                _ => {}
+            }
+        }
+    })
+}
+
+/// The state of a pattern being walked along a subpath.
+struct Dash<'a> {
+    intervals: &'a [f32],
+    index: usize,
+    remaining: f32,
+}
+
+impl Dash<'_> {
+    fn is_on(&self) -> bool {
+        self.index % 2 == 0
+    }
+
+    fn walk(
+        &mut self,
+        builder: &mut path::Builder,
+        from: lyon_path::math::Point,
+        to: lyon_path::math::Point,
+    ) {
+        let length = (to - from).length();
+        let mut distance = 0.0;
+
+        while length - distance > self.remaining {
+            distance += self.remaining;
+
+            let point = from.lerp(to, distance / length);
+            let point = Point::new(point.x, point.y);
+
+            if self.is_on() {
+                builder.line_to(point);
+            } else {
+                builder.move_to(point);
+            }
+
+            self.index = (self.index + 1) % self.intervals.len();
+            self.remaining = self.intervals[self.index];
+        }
+
+        self.remaining -= length - distance;
+
+        if self.is_on() {
+            builder.line_to(Point::new(to.x, to.y));
+        }
+    }
+}
+
+/// Moves every closed subpath of the given [`Path`] towards its interior
+/// by the given distance; or towards its exterior if negative.
+///
+/// Corners are moved along their bisector, up to the given miter limit.
+fn aligned(path: &Path, offset: f32, miter_limit: f32) -> Path {
+    Path::new(|builder| {
+        let mut points: Vec<Point> = Vec::new();
+
+        for event in path.raw().iter().flattened(path::TOLERANCE) {
+            match event {
+                PathEvent::Begin { at } => {
+                    points.clear();
+                    points.push(Point::new(at.x, at.y));
+                }
+                PathEvent::Line { to, .. } => {
+                    let to = Point::new(to.x, to.y);
+
+                    if points.last() != Some(&to) {
+                        points.push(to);
+                    }
+                }
+                PathEvent::End { close, .. } => {
+                    let is_closed = close
+                        || (points.len() > 2
+                            && points.first() == points.last());
+
+                    if is_closed && points.len() > 1 {
+                        if points.first() == points.last() {
+                            let _ = points.pop();
+                        }
+
+                        if points.len() > 2 {
+                            offset_polygon(
+                                builder,
+                                &points,
+                                offset,
+                                miter_limit,
+                            );
+
+                            continue;
+                        }
+                    }
+
+                    if let Some((first, rest)) = points.split_first() {
+                        builder.move_to(*first);
+
+                        for point in rest {
+                            builder.line_to(*point);
+                        }
+
+                        if is_closed {
+                            builder.close();
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    })
+}
+
+/// Draws the given closed polygon moved towards its interior by the given
+/// distance.
+fn offset_polygon(
+    builder: &mut path::Builder,
+    points: &[Point],
+    offset: f32,
+    miter_limit: f32,
+) {
+    let area: f32 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum();
+
+    if area == 0.0 {
+        return;
+    }
+
+    // The interior of the polygon lies on the left of its edges if its
+    // signed area is positive; and on the right otherwise.
+    let side = area.signum();
+
+    let normal = |a: Point, b: Point| {
+        let edge = b - a;
+        let length = edge.x.hypot(edge.y);
+
+        Vector::new(-edge.y * side / length, edge.x * side / length)
+    };
+
+    for (i, point) in points.iter().enumerate() {
+        let previous = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+
+        let before = normal(previous, *point);
+        let after = normal(*point, next);
+
+        let bisector = before + after;
+        let length = bisector.x.hypot(bisector.y);
+
+        let direction = if length > f32::EPSILON {
+            let bisector = bisector * (1.0 / length);
+            let cos = bisector.x * before.x + bisector.y * before.y;
+
+            bisector * (1.0 / cos.max(1.0 / miter_limit))
+        } else {
+            before
+        };
+
+        let point = *point + direction * offset;
+
+        if i == 0 {
+            builder.move_to(point);
+        } else {
+            builder.line_to(point);
+        }
+    }
+
+    builder.close();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::Size;
+
+    fn lines(path: &Path) -> Vec<(Point, Point)> {
+        path.raw()
+            .iter()
+            .filter_map(|event| match event {
+                PathEvent::Line { from, to } => {
+                    Some((Point::new(from.x, from.y), Point::new(to.x, to.y)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn vertices(path: &Path) -> Vec<Point> {
+        path.raw()
+            .iter()
+            .filter_map(|event| match event {
+                PathEvent::Begin { at } => Some(Point::new(at.x, at.y)),
+                PathEvent::Line { to, .. } => Some(Point::new(to.x, to.y)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn square(is_clockwise: bool) -> Path {
+        Path::new(|builder| {
+            builder.move_to(Point::ORIGIN);
+
+            if is_clockwise {
+                builder.line_to(Point::new(10.0, 0.0));
+                builder.line_to(Point::new(10.0, 10.0));
+                builder.line_to(Point::new(0.0, 10.0));
+            } else {
+                builder.line_to(Point::new(0.0, 10.0));
+                builder.line_to(Point::new(10.0, 10.0));
+                builder.line_to(Point::new(10.0, 0.0));
+            }
+
+            builder.close();
+        })
+    }
+
+    fn is_close(a: Point, b: Point) -> bool {
+        a.distance(b) < 1e-4
+    }
+
+    #[test]
+    fn dashed_walks_intervals() {
+        let line = Path::line(Point::ORIGIN, Point::new(10.0, 0.0));
+
+        assert_eq!(
+            lines(&dashed(&line, &[2.0, 3.0], 0.0)),
+            vec![
+                (Point::ORIGIN, Point::new(2.0, 0.0)),
+                (Point::new(5.0, 0.0), Point::new(7.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dashed_starts_into_pattern() {
+        let line = Path::line(Point::ORIGIN, Point::new(10.0, 0.0));
+
+        assert_eq!(
+            lines(&dashed(&line, &[2.0, 3.0], 1.0)),
+            vec![
+                (Point::ORIGIN, Point::new(1.0, 0.0)),
+                (Point::new(4.0, 0.0), Point::new(6.0, 0.0)),
+                (Point::new(9.0, 0.0), Point::new(10.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dashed_walks_closing_edge() {
+        let dashes = lines(&dashed(&square(true), &[5.0, 5.0], 0.0));
+
+        assert_eq!(dashes.len(), 4);
+        assert!(is_close(dashes[3].0, Point::new(0.0, 10.0)));
+        assert!(is_close(dashes[3].1, Point::new(0.0, 5.0)));
+    }
+
+    #[test]
+    fn line_dash_repeats_odd_segments() {
+        let line_dash = LineDash::new(&[1.0, 2.0, 3.0]);
+
+        assert_eq!(
+            line_dash.intervals().as_deref(),
+            Some(&[1.0, 2.0, 3.0, 1.0, 2.0, 3.0][..])
+        );
+    }
+
+    #[test]
+    fn line_dash_rejects_invalid_segments() {
+        assert_eq!(LineDash::new(&[]).intervals(), None);
+        assert_eq!(LineDash::new(&[0.0, 0.0]).intervals(), None);
+        assert_eq!(LineDash::new(&[1.0, -1.0]).intervals(), None);
+        assert_eq!(LineDash::new(&[1.0, f32::NAN]).intervals(), None);
+    }
+
+    #[test]
+    fn line_dash_start_adds_offset_and_phase() {
+        let intervals = [2.0, 3.0];
+
+        assert_eq!(LineDash::new(&intervals).start(&intervals, 0.0), 0.0);
+        assert_eq!(
+            LineDash::new(&intervals).offset(1).start(&intervals, 4.0),
+            1.0
+        );
+        assert_eq!(LineDash::new(&intervals).start(&intervals, -1.0), 4.0);
+    }
+
+    #[test]
+    fn aligned_moves_inside_regardless_of_winding() {
+        for is_clockwise in [true, false] {
+            let inner = aligned(&square(is_clockwise), 1.0, 4.0);
+            let bounds = inner.bounds();
+
+            assert!(is_close(bounds.position(), Point::new(1.0, 1.0)));
+            assert!((bounds.width - 8.0).abs() < 1e-4);
+            assert!((bounds.height - 8.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn aligned_moves_outside_with_negative_offset() {
+        let outer = aligned(&square(true), -1.0, 4.0);
+        let bounds = outer.bounds();
+
+        assert!(is_close(bounds.position(), Point::new(-1.0, -1.0)));
+        assert!((bounds.size().width - 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn aligned_keeps_open_subpaths() {
+        let line = Path::line(Point::ORIGIN, Point::new(10.0, 0.0));
+
+        assert_eq!(
+            lines(&aligned(&line, 1.0, 4.0)),
+            vec![(Point::ORIGIN, Point::new(10.0, 0.0))]
+        );
+    }
+
+    #[test]
+    fn offset_polygon_limits_miters() {
+        let spike =
+            [Point::ORIGIN, Point::new(100.0, 1.0), Point::new(0.0, 2.0)];
+
+        let path = Path::new(|builder| {
+            offset_polygon(builder, &spike, 1.0, 2.0);
+        });
+
+        for (original, moved) in spike.iter().zip(vertices(&path)) {
+            assert!(original.distance(moved) <= 2.0 + 1e-4);
+        }
+    }
+
+    #[test]
+    fn offset_polygon_ignores_degenerate_polygons() {
+        let collinear =
+            [Point::ORIGIN, Point::new(1.0, 0.0), Point::new(2.0, 0.0)];
+
+        let path = Path::new(|builder| {
+            offset_polygon(builder, &collinear, 1.0, 2.0);
+        });
+
+        assert!(vertices(&path).is_empty());
+    }
+
+    #[test]
+    fn apply_borrows_plain_strokes() {
+        let path = Path::rectangle(Point::ORIGIN, Size::new(10.0, 10.0));
+
+        assert!(matches!(Stroke::default().apply(&path), Cow::Borrowed(_)));
+        assert!(matches!(
+            Stroke::default()
+                .with_alignment(Alignment::Inside)
+                .apply(&path),
+            Cow::Owned(_)
+        ));
+    }
+}
@@ -1,9 +1,11 @@
 use crate::core::alignment;
-use crate::core::text::{LineHeight, Shaping};
-use crate::core::{Color, Font, Pixels, Point, Size, Vector};
+use crate::core::text::{LineHeight, Shaping, Span, Wrapping};
+use crate::core::{Background, Color, Font, Pixels, Point, Size};
 use crate::geometry::Path;
 use crate::text;
 
+use lyon_path::math;
+
 /// A bunch of text that can be drawn to a canvas
 #[derive(Debug, Clone)]
 pub struct Text {
@@ -32,69 +34,199 @@ pub struct Text {
     pub vertical_alignment: alignment::Vertical,
     /// The shaping strategy of the text.
     pub shaping: Shaping,
+    /// The rich spans of the text.
+    ///
+    /// When not empty, they are drawn instead of the
+    /// [`content`](Self::content).
+    pub spans: Vec<Span<'static>>,
+    /// The maximum width of the text before it wraps.
+    pub max_width: f32,
+    /// The wrapping strategy of the text.
+    pub wrapping: Wrapping,
+    /// The [`Path`] the text is laid along, if any.
+    ///
+    /// When set, the x coordinate of the [`position`](Self::position) is the
+    /// distance along the [`Path`] where the text is placed, and the y
+    /// coordinate is its offset perpendicular to the [`Path`].
+    pub path: Option<Path>,
 }
 
 impl Text {
+    /// Returns true if the [`Text`] is a single run of plain text; that is,
+    /// without spans, wrapping nor a [`Path`].
+    ///
+    /// Renderers can draw plain text natively, while the rest of text needs
+    /// to be drawn with [`draw_with`](Self::draw_with).
+    pub fn is_plain(&self) -> bool {
+        self.spans.is_empty()
+            && self.max_width.is_infinite()
+            && self.path.is_none()
+    }
+
     /// Computes the [`Path`]s of the [`Text`] and draws them using
     /// the given closure.
     pub fn draw_with(&self, mut f: impl FnMut(Path, Color)) {
         let mut font_system =
             text::font_system().write().expect("Write font system");
 
-        let mut buffer = cosmic_text::BufferLine::new(
-            &self.content,
-            cosmic_text::LineEnding::default(),
-            cosmic_text::AttrsList::new(text::to_attributes(self.font)),
-            text::to_shaping(self.shaping),
+        let mut buffer = cosmic_text::Buffer::new(
+            font_system.raw(),
+            cosmic_text::Metrics::new(
+                self.size.0,
+                self.line_height.to_absolute(self.size).0,
+            ),
         );
 
-        let layout = buffer.layout(
+        buffer.set_size(
             font_system.raw(),
-            self.size.0,
-            None,
-            cosmic_text::Wrap::None,
+            self.max_width.is_finite().then_some(self.max_width),
             None,
-            4,
         );
 
-        let translation_x = match self.horizontal_alignment {
-            alignment::Horizontal::Left => self.position.x,
-            alignment::Horizontal::Center | alignment::Horizontal::Right => {
-                let mut line_width = 0.0f32;
+        buffer.set_wrap(font_system.raw(), text::to_wrap(self.wrapping));
+
+        if self.spans.is_empty() {
+            buffer.set_text(
+                font_system.raw(),
+                &self.content,
+                text::to_attributes(self.font),
+                text::to_shaping(self.shaping),
+            );
+        } else {
+            buffer.set_rich_text(
+                font_system.raw(),
+                self.spans.iter().enumerate().map(|(i, span)| {
+                    let attrs =
+                        text::to_attributes(span.font.unwrap_or(self.font));
+
+                    let attrs = match (span.size, span.line_height) {
+                        (None, None) => attrs,
+                        _ => {
+                            let size = span.size.unwrap_or(self.size);
+
+                            attrs.metrics(cosmic_text::Metrics::new(
+                                size.into(),
+                                span.line_height
+                                    .unwrap_or(self.line_height)
+                                    .to_absolute(size)
+                                    .into(),
+                            ))
+                        }
+                    };
+
+                    let attrs = if let Some(color) = span.color {
+                        attrs.color(text::to_color(color))
+                    } else {
+                        attrs
+                    };
+
+                    (span.text.as_ref(), attrs.metadata(i))
+                }),
+                text::to_attributes(self.font),
+                text::to_shaping(self.shaping),
+            );
+        }
+
+        let bounds = text::measure(&buffer);
+
+        let alignment = match self.horizontal_alignment {
+            alignment::Horizontal::Left => 0.0,
+            alignment::Horizontal::Center => 0.5,
+            alignment::Horizontal::Right => 1.0,
+        };
+
+        let top = match self.vertical_alignment {
+            alignment::Vertical::Top => 0.0,
+            alignment::Vertical::Center => -bounds.height / 2.0,
+            alignment::Vertical::Bottom => -bounds.height,
+        };
+
+        let layout = Layout {
+            origin: Point::new(-bounds.width * alignment, top)
+                + (self.position - Point::ORIGIN),
+            path: self.path.as_ref().map(|path| (path, path.length())),
+        };
+
+        let runs: Vec<_> = buffer.layout_runs().collect();
 
-                for line in layout.iter() {
-                    line_width = line_width.max(line.w);
+        // Highlights and decorations are drawn for every stretch of glyphs
+        // of the same span
+        let mut stretches = Vec::new();
+
+        for run in &runs {
+            let x = (bounds.width - run.line_w) * alignment;
+
+            for glyph in run.glyphs {
+                if glyph.metadata >= self.spans.len() {
+                    continue;
                 }
 
-                if self.horizontal_alignment == alignment::Horizontal::Center {
-                    self.position.x - line_width / 2.0
-                } else {
-                    self.position.x - line_width
+                match stretches.last_mut() {
+                    Some(Stretch {
+                        span: last,
+                        line_top: top,
+                        end,
+                        ..
+                    }) if *last == glyph.metadata && *top == run.line_top => {
+                        *end = x + glyph.x + glyph.w;
+                    }
+                    _ => {
+                        stretches.push(Stretch {
+                            span: glyph.metadata,
+                            start: x + glyph.x,
+                            end: x + glyph.x + glyph.w,
+                            line_top: run.line_top,
+                            line_height: run.line_height,
+                            baseline: run.line_y,
+                        });
+                    }
                 }
             }
-        };
+        }
 
-        let translation_y = {
-            let line_height = self.line_height.to_absolute(self.size);
+        for stretch in &stretches {
+            let span = &self.spans[stretch.span];
 
-            match self.vertical_alignment {
-                alignment::Vertical::Top => self.position.y,
-                alignment::Vertical::Center => {
-                    self.position.y - line_height.0 / 2.0
-                }
-                alignment::Vertical::Bottom => self.position.y - line_height.0,
+            let Some(highlight) = &span.highlight else {
+                continue;
+            };
+
+            // Only solid highlights are supported
+            let Background::Color(color) = highlight.background else {
+                continue;
+            };
+
+            if let Some(path) = layout.band(
+                stretch.start - span.padding.left,
+                stretch.end + span.padding.right,
+                stretch.line_top - span.padding.top,
+                stretch.line_top + stretch.line_height + span.padding.bottom,
+            ) {
+                f(path, color);
             }
-        };
+        }
 
         let mut swash_cache = cosmic_text::SwashCache::new();
 
-        for run in layout.iter() {
-            for glyph in run.glyphs.iter() {
+        for run in &runs {
+            let x = (bounds.width - run.line_w) * alignment;
+
+            for glyph in run.glyphs {
                 let physical_glyph = glyph.physical((0.0, 0.0), 1.0);
 
-                let start_x = translation_x + glyph.x + glyph.x_offset;
-                let start_y = translation_y + glyph.y_offset + self.size.0;
-                let offset = Vector::new(start_x, start_y);
+                let Some(transform) = layout.place(
+                    x + glyph.x + glyph.font_size * glyph.x_offset,
+                    run.line_y + glyph.y - glyph.font_size * glyph.y_offset,
+                    glyph.w,
+                ) else {
+                    continue;
+                };
+
+                let color = glyph.color_opt.map_or(self.color, |color| {
+                    let [r, g, b, a] = color.as_rgba();
+
+                    Color::from_rgba8(r, g, b, a as f32 / 255.0)
+                });
 
                 if let Some(commands) = swash_cache.get_outline_commands(
                     font_system.raw(),
@@ -106,29 +238,22 @@ impl Text {
                         for command in commands {
                             match command {
                                 Command::MoveTo(p) => {
-                                    path.move_to(
-                                        Point::new(p.x, -p.y) + offset,
-                                    );
+                                    path.move_to(Point::new(p.x, -p.y));
                                 }
                                 Command::LineTo(p) => {
-                                    path.line_to(
-                                        Point::new(p.x, -p.y) + offset,
-                                    );
+                                    path.line_to(Point::new(p.x, -p.y));
                                 }
                                 Command::CurveTo(control_a, control_b, to) => {
                                     path.bezier_curve_to(
-                                        Point::new(control_a.x, -control_a.y)
-                                            + offset,
-                                        Point::new(control_b.x, -control_b.y)
-                                            + offset,
-                                        Point::new(to.x, -to.y) + offset,
+                                        Point::new(control_a.x, -control_a.y),
+                                        Point::new(control_b.x, -control_b.y),
+                                        Point::new(to.x, -to.y),
                                     );
                                 }
                                 Command::QuadTo(control, to) => {
                                     path.quadratic_curve_to(
-                                        Point::new(control.x, -control.y)
-                                            + offset,
-                                        Point::new(to.x, -to.y) + offset,
+                                        Point::new(control.x, -control.y),
+                                        Point::new(to.x, -to.y),
                                     );
                                 }
                                 Command::Close => {
@@ -138,21 +263,20 @@ impl Text {
                         }
                     });
 
-                    f(glyph, self.color);
+                    f(glyph.transform(&transform), color);
                 } else {
                     // TODO: Raster image support for `Canvas`
-                    let [r, g, b, a] = self.color.into_rgba8();
-
                     swash_cache.with_pixels(
                         font_system.raw(),
                         physical_glyph.cache_key,
-                        cosmic_text::Color::rgba(r, g, b, a),
+                        text::to_color(color),
                         |x, y, color| {
                             f(
                                 Path::rectangle(
-                                    Point::new(x as f32, y as f32) + offset,
+                                    Point::new(x as f32, y as f32),
                                     Size::new(1.0, 1.0),
-                                ),
+                                )
+                                .transform(&transform),
                                 Color::from_rgba8(
                                     color.r(),
                                     color.g(),
@@ -165,6 +289,149 @@ impl Text {
                 }
             }
         }
+
+        for stretch in &stretches {
+            let span = &self.spans[stretch.span];
+
+            if !span.underline && !span.strikethrough {
+                continue;
+            }
+
+            let size = span.size.unwrap_or(self.size).0;
+
+            let color = span.color.unwrap_or(self.color);
+
+            for (is_enabled, y) in [
+                (span.underline, stretch.baseline + size * 0.1),
+                (span.strikethrough, stretch.baseline - size * 0.3),
+            ] {
+                if let Some(path) = is_enabled
+                    .then(|| {
+                        layout.band(stretch.start, stretch.end, y, y + 1.0)
+                    })
+                    .flatten()
+                {
+                    f(path, color);
+                }
+            }
+        }
+    }
+}
+
+/// A stretch of glyphs of the same span in a line of a [`Text`].
+struct Stretch {
+    span: usize,
+    start: f32,
+    end: f32,
+    line_top: f32,
+    line_height: f32,
+    baseline: f32,
+}
+
+/// The placement of the glyphs of a [`Text`], either in a plane or along a
+/// [`Path`].
+struct Layout<'a> {
+    origin: Point,
+    path: Option<(&'a Path, f32)>,
+}
+
+impl Layout<'_> {
+    /// Returns the transform of a glyph at the given position of the layout
+    /// with the given width, if visible.
+    fn place(&self, x: f32, y: f32, width: f32) -> Option<math::Transform> {
+        let Some((path, length)) = self.path else {
+            return Some(math::Transform::translation(
+                self.origin.x + x,
+                self.origin.y + y,
+            ));
+        };
+
+        // Glyphs are placed on the path by their center
+        let distance = self.origin.x + x + width / 2.0;
+
+        if distance < 0.0 || distance > length {
+            return None;
+        }
+
+        let point = path.point_at(distance)?;
+        let tangent = path.tangent_at(distance)?;
+
+        Some(
+            math::Transform::translation(-width / 2.0, self.origin.y + y)
+                .then_rotate(math::Angle::radians(tangent.y.atan2(tangent.x)))
+                .then_translate(math::vector(point.x, point.y)),
+        )
+    }
+
+    /// Returns the area of the layout between the given horizontal and
+    /// vertical positions, following the [`Path`] if any.
+    fn band(
+        &self,
+        left: f32,
+        right: f32,
+        top: f32,
+        bottom: f32,
+    ) -> Option<Path> {
+        let Some((path, _)) = self.path else {
+            return Some(Path::rectangle(
+                Point::new(self.origin.x + left, self.origin.y + top),
+                Size::new(right - left, bottom - top),
+            ));
+        };
+
+        let trimmed = path.trim(self.origin.x + left, self.origin.x + right);
+
+        let points: Vec<_> = trimmed
+            .raw()
+            .iter()
+            .filter_map(|event| match event {
+                lyon_path::PathEvent::Begin { at } => Some(at),
+                lyon_path::PathEvent::Line { to, .. } => Some(to),
+                _ => None,
+            })
+            .collect();
+
+        if points.len() < 2 {
+            return None;
+        }
+
+        let normals: Vec<_> = (0..points.len())
+            .map(|i| {
+                let (from, to) = if i + 1 < points.len() {
+                    (points[i], points[i + 1])
+                } else {
+                    (points[i - 1], points[i])
+                };
+
+                let direction = (to - from).normalize();
+
+                math::vector(-direction.y, direction.x)
+            })
+            .collect();
+
+        let (top, bottom) = (self.origin.y + top, self.origin.y + bottom);
+
+        Some(Path::new(|builder| {
+            for (i, (point, normal)) in points.iter().zip(&normals).enumerate()
+            {
+                let point = *point + *normal * top;
+                let point = Point::new(point.x, point.y);
+
+                if i == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+
+            for (point, normal) in points.iter().zip(&normals).rev() {
+                let point = *point + *normal * bottom;
+
+                builder.line_to(Point::new(point.x, point.y));
+            }
+
+            builder.close();
+        }))
     }
 }
 
@@ -180,6 +447,10 @@ impl Default for Text {
             horizontal_alignment: alignment::Horizontal::Left,
             vertical_alignment: alignment::Vertical::Top,
             shaping: Shaping::Basic,
+            spans: Vec::new(),
+            max_width: f32::INFINITY,
+            wrapping: Wrapping::default(),
+            path: None,
         }
     }
 }
@@ -198,3 +469,234 @@ impl From<&str> for Text {
         String::from(content).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::Rectangle;
+
+    const ICON: &str = "\u{e800}";
+
+    fn icons(content: &str) -> Text {
+        Text {
+            content: content.to_owned(),
+            font: Font::with_name("Iced-Icons"),
+            ..Text::default()
+        }
+    }
+
+    fn draw(text: &Text) -> Vec<(Rectangle, Color)> {
+        let mut paths = Vec::new();
+
+        text.draw_with(|path, color| paths.push((path.bounds(), color)));
+
+        paths
+    }
+
+    fn bounds(paths: &[(Rectangle, Color)]) -> Rectangle {
+        paths
+            .iter()
+            .map(|(bounds, _)| *bounds)
+            .reduce(|a, b| a.union(&b))
+            .expect("Draw some paths")
+    }
+
+    fn is_close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    fn transform_point(transform: math::Transform, x: f32, y: f32) -> Point {
+        let point = transform.transform_point(math::point(x, y));
+
+        Point::new(point.x, point.y)
+    }
+
+    #[test]
+    fn place_translates_in_plane() {
+        let layout = Layout {
+            origin: Point::new(10.0, 20.0),
+            path: None,
+        };
+
+        let transform = layout.place(5.0, 6.0, 4.0).expect("Place glyph");
+
+        assert_eq!(
+            transform_point(transform, 0.0, 0.0),
+            Point::new(15.0, 26.0)
+        );
+    }
+
+    #[test]
+    fn place_follows_path() {
+        let path = Path::line(Point::ORIGIN, Point::new(0.0, 100.0));
+
+        let layout = Layout {
+            origin: Point::ORIGIN,
+            path: Some((&path, path.length())),
+        };
+
+        let transform = layout.place(10.0, 3.0, 4.0).expect("Place glyph");
+        let point = transform_point(transform, 0.0, 0.0);
+
+        // The glyph is rotated along the path, centered at 12
+        assert!(is_close(point.x, -3.0));
+        assert!(is_close(point.y, 10.0));
+    }
+
+    #[test]
+    fn place_hides_glyphs_off_path() {
+        let path = Path::line(Point::ORIGIN, Point::new(100.0, 0.0));
+
+        let layout = Layout {
+            origin: Point::new(-50.0, 0.0),
+            path: Some((&path, path.length())),
+        };
+
+        assert!(layout.place(0.0, 0.0, 10.0).is_none());
+        assert!(layout.place(60.0, 0.0, 10.0).is_some());
+        assert!(layout.place(150.0, 0.0, 10.0).is_none());
+    }
+
+    #[test]
+    fn band_covers_area_in_plane() {
+        let layout = Layout {
+            origin: Point::new(10.0, 20.0),
+            path: None,
+        };
+
+        let band = layout.band(1.0, 5.0, 2.0, 8.0).expect("Draw band");
+
+        assert_eq!(
+            band.bounds(),
+            Rectangle::new(Point::new(11.0, 22.0), Size::new(4.0, 6.0))
+        );
+    }
+
+    #[test]
+    fn band_follows_path() {
+        let path = Path::line(Point::ORIGIN, Point::new(100.0, 0.0));
+
+        let layout = Layout {
+            origin: Point::new(10.0, 0.0),
+            path: Some((&path, path.length())),
+        };
+
+        let band = layout.band(0.0, 20.0, -5.0, 5.0).expect("Draw band");
+
+        let bounds = band.bounds();
+
+        assert!(is_close(bounds.x, 10.0));
+        assert!(is_close(bounds.y, -5.0));
+        assert!(is_close(bounds.width, 20.0));
+        assert!(is_close(bounds.height, 10.0));
+    }
+
+    #[test]
+    fn band_needs_path_length() {
+        let path = Path::line(Point::ORIGIN, Point::new(10.0, 0.0));
+
+        let layout = Layout {
+            origin: Point::ORIGIN,
+            path: Some((&path, path.length())),
+        };
+
+        assert!(layout.band(20.0, 30.0, 0.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn horizontal_alignment_shifts_by_width() {
+        let align = |alignment| {
+            bounds(&draw(&Text {
+                horizontal_alignment: alignment,
+                ..icons(ICON)
+            }))
+            .x
+        };
+
+        let left = align(alignment::Horizontal::Left);
+        let center = align(alignment::Horizontal::Center);
+        let right = align(alignment::Horizontal::Right);
+
+        assert!(right < center && center < left);
+        assert!(is_close(left - center, (left - right) / 2.0));
+    }
+
+    #[test]
+    fn vertical_alignment_shifts_by_height() {
+        let align = |alignment| {
+            bounds(&draw(&Text {
+                vertical_alignment: alignment,
+                ..icons(ICON)
+            }))
+            .y
+        };
+
+        let top = align(alignment::Vertical::Top);
+        let center = align(alignment::Vertical::Center);
+        let bottom = align(alignment::Vertical::Bottom);
+
+        // A single line of 16 pixels with a line height of 1.2
+        assert!(is_close(top - center, 9.6));
+        assert!(is_close(top - bottom, 19.2));
+    }
+
+    #[test]
+    fn wrapping_breaks_lines() {
+        let line = bounds(&draw(&icons(&[ICON; 4].join(" "))));
+
+        let wrapped = bounds(&draw(&Text {
+            max_width: line.width / 2.0,
+            ..icons(&[ICON; 4].join(" "))
+        }));
+
+        assert!(wrapped.width < line.width);
+        assert!(wrapped.height > line.height);
+    }
+
+    #[test]
+    fn spans_draw_highlights_then_glyphs_then_decorations() {
+        let highlight = Color::from_rgb(1.0, 0.0, 0.0);
+        let glyph = Color::from_rgb(0.0, 1.0, 0.0);
+
+        let paths = draw(&Text {
+            spans: vec![Span::new(ICON)
+                .font(Font::with_name("Iced-Icons"))
+                .color(glyph)
+                .background(highlight)
+                .underline(true)],
+            ..icons("")
+        });
+
+        let colors: Vec<_> = paths.iter().map(|(_, color)| *color).collect();
+
+        assert_eq!(colors.first(), Some(&highlight));
+        assert!(colors[1..].iter().all(|color| *color == glyph));
+
+        // The highlight covers the whole line
+        let (band, _) = paths[0];
+        assert!(is_close(band.y, 0.0));
+        assert!(is_close(band.height, 19.2));
+
+        // The underline is the last path, right below the baseline
+        let (underline, _) = paths[paths.len() - 1];
+        assert!(is_close(underline.height, 1.0));
+        assert!(underline.y > band.y && underline.y < band.y + band.height);
+    }
+
+    #[test]
+    fn spans_are_padded() {
+        let paths = draw(&Text {
+            spans: vec![Span::new(ICON)
+                .font(Font::with_name("Iced-Icons"))
+                .background(Color::BLACK)
+                .padding(2)],
+            ..icons("")
+        });
+
+        let (band, _) = paths[0];
+
+        assert!(is_close(band.y, -2.0));
+        assert!(is_close(band.height, 23.2));
+    }
+}
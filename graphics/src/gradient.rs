@@ -3,20 +3,26 @@
 //! For a gradient that you can use as a background variant for a widget, see [`Gradient`].
 use crate::color;
 use crate::core::gradient::ColorStop;
-use crate::core::{self, Color, Point, Rectangle};
+use crate::core::{self, Color, Point, Radians, Rectangle};
 
 use bytemuck::{Pod, Zeroable};
 use half::f16;
 use std::cmp::Ordering;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-/// A fill which linearly interpolates colors along a direction.
+/// A fill which interpolates colors along a direction, outwards from a center, or
+/// around a center.
 ///
 /// For a gradient which can be used as a fill for a background of a widget, see [`crate::core::Gradient`].
 pub enum Gradient {
     /// A linear gradient interpolates colors along a direction from its `start` to its `end`
     /// point.
     Linear(Linear),
+    /// A radial gradient interpolates colors from its `center` outwards, up to its `radius`.
+    Radial(Radial),
+    /// A conic gradient interpolates colors clockwise around its `center`, starting at its
+    /// `angle`.
+    Conic(Conic),
 }
 
 impl From<Linear> for Gradient {
@@ -25,11 +31,25 @@ impl From<Linear> for Gradient {
     }
 }
 
+impl From<Radial> for Gradient {
+    fn from(gradient: Radial) -> Self {
+        Self::Radial(gradient)
+    }
+}
+
+impl From<Conic> for Gradient {
+    fn from(gradient: Conic) -> Self {
+        Self::Conic(gradient)
+    }
+}
+
 impl Gradient {
     /// Packs the [`Gradient`] for use in shader code.
     pub fn pack(&self) -> Packed {
         match self {
             Gradient::Linear(linear) => linear.pack(),
+            Gradient::Radial(radial) => radial.pack(),
+            Gradient::Conic(conic) => conic.pack(),
         }
     }
 }
@@ -63,20 +83,121 @@ impl Linear {
     ///
     /// Any stop added after the 8th will be silently ignored.
     pub fn add_stop(mut self, offset: f32, color: Color) -> Self {
-        if offset.is_finite() && (0.0..=1.0).contains(&offset) {
-            let (Ok(index) | Err(index)) =
-                self.stops.binary_search_by(|stop| match stop {
-                    None => Ordering::Greater,
-                    Some(stop) => stop.offset.partial_cmp(&offset).unwrap(),
-                });
-
-            if index < 8 {
-                self.stops[index] = Some(ColorStop { offset, color });
-            }
-        } else {
-            log::warn!("Gradient: ColorStop must be within 0.0..=1.0 range.");
-        };
+        add_stop(&mut self.stops, offset, color);
+        self
+    }
+
+    /// Adds multiple [`ColorStop`]s to the gradient.
+    ///
+    /// Any stop added after the 8th will be silently ignored.
+    pub fn add_stops(
+        mut self,
+        stops: impl IntoIterator<Item = ColorStop>,
+    ) -> Self {
+        for stop in stops {
+            self = self.add_stop(stop.offset, stop.color);
+        }
+
+        self
+    }
+
+    /// Packs the [`Gradient`] for use in shader code.
+    pub fn pack(&self) -> Packed {
+        Packed::new(
+            &self.stops,
+            [self.start.x, self.start.y, self.end.x, self.end.y],
+            Packed::LINEAR,
+        )
+    }
+}
+
+/// A radial gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Radial {
+    /// The absolute center of the gradient.
+    pub center: Point,
+
+    /// The radius of the gradient.
+    pub radius: f32,
+
+    /// [`ColorStop`]s along the radius of the gradient.
+    pub stops: [Option<ColorStop>; 8],
+}
+
+impl Radial {
+    /// Creates a new [`Radial`] builder.
+    pub fn new(center: Point, radius: f32) -> Self {
+        Self {
+            center,
+            radius,
+            stops: [None; 8],
+        }
+    }
+
+    /// Adds a new [`ColorStop`], defined by an offset and a color, to the gradient.
+    ///
+    /// Any `offset` that is not within `0.0..=1.0` will be silently ignored.
+    ///
+    /// Any stop added after the 8th will be silently ignored.
+    pub fn add_stop(mut self, offset: f32, color: Color) -> Self {
+        add_stop(&mut self.stops, offset, color);
+        self
+    }
+
+    /// Adds multiple [`ColorStop`]s to the gradient.
+    ///
+    /// Any stop added after the 8th will be silently ignored.
+    pub fn add_stops(
+        mut self,
+        stops: impl IntoIterator<Item = ColorStop>,
+    ) -> Self {
+        for stop in stops {
+            self = self.add_stop(stop.offset, stop.color);
+        }
+
+        self
+    }
+
+    /// Packs the [`Gradient`] for use in shader code.
+    pub fn pack(&self) -> Packed {
+        Packed::new(
+            &self.stops,
+            [self.center.x, self.center.y, self.radius, 0.0],
+            Packed::RADIAL,
+        )
+    }
+}
+
+/// A conic gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conic {
+    /// The absolute center of the gradient.
+    pub center: Point,
+
+    /// The angle where the gradient starts, clockwise from the positive x-axis.
+    pub angle: Radians,
+
+    /// [`ColorStop`]s around the center of the gradient.
+    pub stops: [Option<ColorStop>; 8],
+}
+
+impl Conic {
+    /// Creates a new [`Conic`] builder.
+    pub fn new(center: Point, angle: impl Into<Radians>) -> Self {
+        Self {
+            center,
+            angle: angle.into(),
+            stops: [None; 8],
+        }
+    }
 
+    /// Adds a new [`ColorStop`], defined by an offset and a color, to the gradient.
+    ///
+    /// Any `offset` that is not within `0.0..=1.0` will be silently ignored.
+    ///
+    /// Any stop added after the 8th will be silently ignored.
+    pub fn add_stop(mut self, offset: f32, color: Color) -> Self {
+        add_stop(&mut self.stops, offset, color);
         self
     }
 
@@ -94,12 +215,53 @@ impl Linear {
         self
     }
 
+    /// Returns the offset of the [`Conic`] gradient at the given point,
+    /// within `0.0..1.0`.
+    pub fn offset(&self, point: Point) -> f32 {
+        let angle = (point.y - self.center.y).atan2(point.x - self.center.x);
+
+        ((angle - self.angle.0) / (2.0 * std::f32::consts::PI)).rem_euclid(1.0)
+    }
+
     /// Packs the [`Gradient`] for use in shader code.
     pub fn pack(&self) -> Packed {
+        Packed::new(
+            &self.stops,
+            [self.center.x, self.center.y, self.angle.0, 0.0],
+            Packed::CONIC,
+        )
+    }
+}
+
+/// Packed [`Gradient`] data for use in shader code.
+#[derive(Debug, Copy, Clone, PartialEq, Zeroable, Pod)]
+#[repr(C)]
+pub struct Packed {
+    // 8 colors, each channel = 16 bit float, 2 colors packed into 1 u32
+    colors: [[u32; 2]; 8],
+    // 8 offsets, 8x 16 bit floats packed into 4 u32s
+    offsets: [u32; 4],
+    // Linear: start & end points
+    // Radial: center, radius
+    // Conic: center, angle
+    direction: [f32; 4],
+    kind: u32,
+}
+
+impl Packed {
+    const LINEAR: u32 = 0;
+    const RADIAL: u32 = 1;
+    const CONIC: u32 = 2;
+
+    fn new(
+        stops: &[Option<ColorStop>; 8],
+        direction: [f32; 4],
+        kind: u32,
+    ) -> Self {
         let mut colors = [[0u32; 2]; 8];
         let mut offsets = [f16::from(0u8); 8];
 
-        for (index, stop) in self.stops.iter().enumerate() {
+        for (index, stop) in stops.iter().enumerate() {
             let [r, g, b, a] =
                 color::pack(stop.map_or(Color::default(), |s| s.color))
                     .components();
@@ -120,68 +282,69 @@ impl Linear {
             pack_f16s([offsets[6], offsets[7]]),
         ];
 
-        let direction = [self.start.x, self.start.y, self.end.x, self.end.y];
-
-        Packed {
+        Self {
             colors,
             offsets,
             direction,
+            kind,
         }
     }
 }
 
-/// Packed [`Gradient`] data for use in shader code.
-#[derive(Debug, Copy, Clone, PartialEq, Zeroable, Pod)]
-#[repr(C)]
-pub struct Packed {
-    // 8 colors, each channel = 16 bit float, 2 colors packed into 1 u32
-    colors: [[u32; 2]; 8],
-    // 8 offsets, 8x 16 bit floats packed into 4 u32s
-    offsets: [u32; 4],
-    direction: [f32; 4],
-}
-
 /// Creates a new [`Packed`] gradient for use in shader code.
 pub fn pack(gradient: &core::Gradient, bounds: Rectangle) -> Packed {
+    into_absolute(gradient, bounds).pack()
+}
+
+/// Converts a [`core::Gradient`], relative to the given bounds, into an
+/// absolute [`Gradient`].
+pub fn into_absolute(gradient: &core::Gradient, bounds: Rectangle) -> Gradient {
+    let center = |center: Point| {
+        Point::new(
+            bounds.x + center.x * bounds.width,
+            bounds.y + center.y * bounds.height,
+        )
+    };
+
     match gradient {
         core::Gradient::Linear(linear) => {
-            let mut colors = [[0u32; 2]; 8];
-            let mut offsets = [f16::from(0u8); 8];
-
-            for (index, stop) in linear.stops.iter().enumerate() {
-                let [r, g, b, a] =
-                    color::pack(stop.map_or(Color::default(), |s| s.color))
-                        .components();
-
-                colors[index] = [
-                    pack_f16s([f16::from_f32(r), f16::from_f32(g)]),
-                    pack_f16s([f16::from_f32(b), f16::from_f32(a)]),
-                ];
-
-                offsets[index] = stop
-                    .map_or(f16::from_f32(2.0), |s| f16::from_f32(s.offset));
-            }
-
-            let offsets = [
-                pack_f16s([offsets[0], offsets[1]]),
-                pack_f16s([offsets[2], offsets[3]]),
-                pack_f16s([offsets[4], offsets[5]]),
-                pack_f16s([offsets[6], offsets[7]]),
-            ];
-
             let (start, end) = linear.angle.to_distance(&bounds);
 
-            let direction = [start.x, start.y, end.x, end.y];
-
-            Packed {
-                colors,
-                offsets,
-                direction,
-            }
+            Gradient::Linear(Linear {
+                start,
+                end,
+                stops: linear.stops,
+            })
         }
+        core::Gradient::Radial(radial) => Gradient::Radial(Radial {
+            center: center(radial.center),
+            radius: radial.radius * bounds.width.min(bounds.height) / 2.0,
+            stops: radial.stops,
+        }),
+        core::Gradient::Conic(conic) => Gradient::Conic(Conic {
+            center: center(conic.center),
+            angle: conic.angle,
+            stops: conic.stops,
+        }),
     }
 }
 
+fn add_stop(stops: &mut [Option<ColorStop>; 8], offset: f32, color: Color) {
+    if offset.is_finite() && (0.0..=1.0).contains(&offset) {
+        let (Ok(index) | Err(index)) =
+            stops.binary_search_by(|stop| match stop {
+                None => Ordering::Greater,
+                Some(stop) => stop.offset.partial_cmp(&offset).unwrap(),
+            });
+
+        if index < 8 {
+            stops[index] = Some(ColorStop { offset, color });
+        }
+    } else {
+        log::warn!("Gradient: ColorStop must be within 0.0..=1.0 range.");
+    };
+}
+
 /// Packs two f16s into one u32.
 fn pack_f16s(f: [f16; 2]) -> u32 {
     let one = (f[0].to_bits() as u32) << 16;
@@ -189,3 +352,96 @@ fn pack_f16s(f: [f16; 2]) -> u32 {
 
     one | two
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f32::consts::PI;
+
+    fn offsets(packed: &Packed) -> [f32; 8] {
+        let mut offsets = [0.0; 8];
+
+        for (i, pair) in packed.offsets.iter().enumerate() {
+            offsets[i * 2] = f16::from_bits((pair >> 16) as u16).to_f32();
+            offsets[i * 2 + 1] = f16::from_bits(*pair as u16).to_f32();
+        }
+
+        offsets
+    }
+
+    #[test]
+    fn pack_encodes_kind_and_direction() {
+        let linear =
+            Linear::new(Point::new(1.0, 2.0), Point::new(3.0, 4.0)).pack();
+        let radial = Radial::new(Point::new(1.0, 2.0), 5.0).pack();
+        let conic = Conic::new(Point::new(1.0, 2.0), Radians(0.5)).pack();
+
+        assert_eq!(linear.kind, Packed::LINEAR);
+        assert_eq!(linear.direction, [1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(radial.kind, Packed::RADIAL);
+        assert_eq!(radial.direction, [1.0, 2.0, 5.0, 0.0]);
+
+        assert_eq!(conic.kind, Packed::CONIC);
+        assert_eq!(conic.direction, [1.0, 2.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn gradient_pack_matches_variant_pack() {
+        let conic = Conic::new(Point::ORIGIN, Radians(1.0))
+            .add_stop(0.0, Color::WHITE)
+            .add_stop(1.0, Color::BLACK);
+
+        assert_eq!(Gradient::Conic(conic).pack(), conic.pack());
+    }
+
+    #[test]
+    fn pack_marks_missing_stops() {
+        let packed = Conic::new(Point::ORIGIN, Radians(0.0))
+            .add_stop(0.25, Color::WHITE)
+            .add_stop(0.75, Color::BLACK)
+            .pack();
+
+        assert_eq!(
+            offsets(&packed),
+            [0.25, 0.75, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn conic_offset_goes_clockwise_from_angle() {
+        let conic = Conic::new(Point::new(1.0, 1.0), Radians(0.0));
+
+        assert_eq!(conic.offset(Point::new(2.0, 1.0)), 0.0);
+        assert_eq!(conic.offset(Point::new(1.0, 2.0)), 0.25);
+        assert_eq!(conic.offset(Point::new(0.0, 1.0)), 0.5);
+        assert_eq!(conic.offset(Point::new(1.0, 0.0)), 0.75);
+    }
+
+    #[test]
+    fn conic_offset_is_relative_to_angle() {
+        let conic = Conic::new(Point::ORIGIN, Radians(PI / 2.0));
+
+        assert_eq!(conic.offset(Point::new(0.0, 1.0)), 0.0);
+        assert_eq!(conic.offset(Point::new(1.0, 0.0)), 0.75);
+    }
+
+    #[test]
+    fn into_absolute_places_conic_center_in_bounds() {
+        let gradient = core::Gradient::Conic(core::gradient::Conic::new(
+            Point::new(0.5, 0.25),
+            Radians(1.0),
+        ));
+
+        let Gradient::Conic(conic) = into_absolute(
+            &gradient,
+            Rectangle::new(Point::new(10.0, 10.0), core::Size::new(20.0, 40.0)),
+        ) else {
+            panic!("Expected a conic gradient");
+        };
+
+        assert_eq!(conic.center, Point::new(20.0, 20.0));
+        assert_eq!(conic.angle, Radians(1.0));
+    }
+}
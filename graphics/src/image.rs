@@ -1,4 +1,14 @@
 //! Load and operate on images.
+#[cfg(feature = "image")]
+mod decoder;
+mod lru;
+
+#[cfg(feature = "image")]
+pub use decoder::{Decoded, Decoder, Process};
+pub use lru::Lru;
+
+pub use crate::core::image::Stats;
+
 #[cfg(feature = "image")]
 pub use ::image as image_rs;
 
@@ -6,6 +16,9 @@ use crate::core::image;
 use crate::core::svg;
 use crate::core::Rectangle;
 
+use std::fmt;
+use std::sync::Arc;
+
 /// A raster or vector image.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Image {
@@ -26,14 +39,52 @@ impl Image {
     }
 }
 
+/// A renderer that caches the images it draws.
+pub trait Cache {
+    /// Returns the [`Stats`] of the image caches of the renderer.
+    fn image_cache_stats(&self) -> Stats;
+}
+
+/// A closure called whenever an image finishes loading in the background.
+///
+/// Runners use it to redraw their windows as soon as images are ready, so
+/// every image is drawn without widgets having to poll for it.
+#[derive(Clone)]
+pub struct Waker(Arc<dyn Fn() + Send + Sync>);
+
+impl Waker {
+    /// Creates a new [`Waker`] calling the given closure.
+    pub fn new(wake: impl Fn() + Send + Sync + 'static) -> Self {
+        Self(Arc::new(wake))
+    }
+
+    /// Calls the closure of the [`Waker`].
+    pub fn wake(&self) {
+        (self.0)();
+    }
+}
+
+impl fmt::Debug for Waker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Waker(...)")
+    }
+}
+
+impl PartialEq for Waker {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(feature = "image")]
+/// The pixels of a loaded image, in RGBA format.
+pub type Buffer = ::image::ImageBuffer<::image::Rgba<u8>, image::Bytes>;
+
 #[cfg(feature = "image")]
 /// Tries to load an image by its [`Handle`].
 ///
 /// [`Handle`]: image::Handle
-pub fn load(
-    handle: &image::Handle,
-) -> ::image::ImageResult<::image::ImageBuffer<::image::Rgba<u8>, image::Bytes>>
-{
+pub fn load(handle: &image::Handle) -> ::image::ImageResult<Buffer> {
     use bitflags::bitflags;
 
     bitflags! {
@@ -134,3 +185,211 @@ pub fn load(
         ))
     }
 }
+
+#[cfg(feature = "image")]
+/// Tries to obtain the dimensions of an image by its [`Handle`].
+///
+/// Only the header of the image is decoded; so it is much faster than
+/// a [`load`].
+///
+/// [`Handle`]: image::Handle
+pub fn dimensions(
+    handle: &image::Handle,
+) -> ::image::ImageResult<crate::core::Size<u32>> {
+    let (width, height) = match handle {
+        image::Handle::Path(_, path) => ::image::io::Reader::open(path)?
+            .with_guessed_format()?
+            .into_dimensions()?,
+        image::Handle::Bytes(_, bytes) => {
+            ::image::io::Reader::new(std::io::Cursor::new(bytes))
+                .with_guessed_format()?
+                .into_dimensions()?
+        }
+        image::Handle::Rgba { width, height, .. } => (*width, *height),
+    };
+
+    Ok(crate::core::Size::new(width, height))
+}
+
+#[cfg(feature = "animated-image")]
+/// Tries to decode all the frames of an animated image by its [`Handle`].
+///
+/// GIF, APNG and animated WebP images are supported. Any other image is
+/// loaded with [`load`] as an [`Animation`] of a single frame.
+///
+/// Decoding happens eagerly and may take a while for long animations;
+/// consider running it in a background task.
+///
+/// [`Handle`]: image::Handle
+/// [`Animation`]: image::Animation
+pub fn load_animation(
+    handle: &image::Handle,
+) -> ::image::ImageResult<image::Animation> {
+    use ::image::codecs::gif::GifDecoder;
+    use ::image::codecs::png::PngDecoder;
+    use ::image::codecs::webp::WebPDecoder;
+    use ::image::AnimationDecoder;
+
+    use crate::core::time::Duration;
+
+    use std::io::Cursor;
+
+    /// Browsers play delays of 10 milliseconds or less at 100 milliseconds,
+    /// and most animations are authored with that in mind.
+    fn delay(delay: ::image::Delay) -> Duration {
+        let delay = Duration::from(delay);
+
+        if delay <= Duration::from_millis(10) {
+            Duration::from_millis(100)
+        } else {
+            delay
+        }
+    }
+
+    let bytes = match handle {
+        image::Handle::Path(_, path) => {
+            image::Bytes::from(std::fs::read(path)?)
+        }
+        image::Handle::Bytes(_, bytes) => bytes.clone(),
+        image::Handle::Rgba { .. } => {
+            return Ok(image::Animation::from(handle.clone()));
+        }
+    };
+
+    let frames = match ::image::guess_format(&bytes) {
+        Ok(::image::ImageFormat::Gif) => {
+            Some(GifDecoder::new(Cursor::new(&bytes))?.into_frames())
+        }
+        Ok(::image::ImageFormat::Png) => {
+            let decoder = PngDecoder::new(Cursor::new(&bytes))?;
+
+            decoder.is_apng().then(|| decoder.apng().into_frames())
+        }
+        Ok(::image::ImageFormat::WebP) => {
+            let decoder = WebPDecoder::new(Cursor::new(&bytes))?;
+
+            decoder.has_animation().then(|| decoder.into_frames())
+        }
+        _ => None,
+    };
+
+    let Some(frames) = frames else {
+        let image = load(handle)?;
+
+        return Ok(image::Animation::from(image::Handle::from_rgba(
+            image.width(),
+            image.height(),
+            image.into_raw(),
+        )));
+    };
+
+    let frames = frames
+        .map(|frame| {
+            let frame = frame?;
+            let delay = delay(frame.delay());
+            let buffer = frame.into_buffer();
+
+            Ok(image::Frame {
+                handle: image::Handle::from_rgba(
+                    buffer.width(),
+                    buffer.height(),
+                    buffer.into_raw(),
+                ),
+                delay,
+            })
+        })
+        .collect::<::image::ImageResult<Vec<_>>>()?;
+
+    if frames.is_empty() {
+        return Err(::image::error::ImageError::Limits(
+            ::image::error::LimitError::from_kind(
+                ::image::error::LimitErrorKind::DimensionError,
+            ),
+        ));
+    }
+
+    Ok(image::Animation::new(frames))
+}
+
+#[cfg(all(test, feature = "animated-image"))]
+mod tests {
+    use super::*;
+
+    use crate::core::time::Duration;
+
+    use ::image::codecs::gif::GifEncoder;
+    use ::image::{Delay, Frame, Rgba, RgbaImage};
+
+    fn gif(delays: &[u32]) -> image::Handle {
+        let mut bytes = Vec::new();
+
+        {
+            let mut encoder = GifEncoder::new(&mut bytes);
+
+            encoder
+                .encode_frames(delays.iter().enumerate().map(|(i, &delay)| {
+                    Frame::from_parts(
+                        RgbaImage::from_pixel(2, 2, Rgba([i as u8, 0, 0, 255])),
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(delay, 1),
+                    )
+                }))
+                .expect("Encode GIF");
+        }
+
+        image::Handle::from_bytes(bytes)
+    }
+
+    fn delays(animation: &image::Animation) -> Vec<Duration> {
+        animation.frames().iter().map(|frame| frame.delay).collect()
+    }
+
+    #[test]
+    fn short_delays_are_clamped() {
+        let animation = load_animation(&gif(&[0, 10])).unwrap();
+
+        assert_eq!(
+            delays(&animation),
+            [Duration::from_millis(100), Duration::from_millis(100)]
+        );
+    }
+
+    #[test]
+    fn longer_delays_are_kept() {
+        let animation = load_animation(&gif(&[20, 50, 1000])).unwrap();
+
+        assert_eq!(
+            delays(&animation),
+            [
+                Duration::from_millis(20),
+                Duration::from_millis(50),
+                Duration::from_millis(1000)
+            ]
+        );
+        assert_eq!(animation.duration(), Duration::from_millis(1070));
+    }
+
+    #[test]
+    fn mixed_delays_are_clamped_per_frame() {
+        let animation = load_animation(&gif(&[10, 30, 0])).unwrap();
+
+        assert_eq!(
+            delays(&animation),
+            [
+                Duration::from_millis(100),
+                Duration::from_millis(30),
+                Duration::from_millis(100)
+            ]
+        );
+    }
+
+    #[test]
+    fn rgba_is_a_single_frame() {
+        let handle = image::Handle::from_rgba(1, 1, vec![0, 0, 0, 255]);
+        let animation = load_animation(&handle).unwrap();
+
+        assert_eq!(animation.frames().len(), 1);
+        assert_eq!(animation.frames()[0].handle, handle);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::core::image;
+use crate::image::{load, Buffer, Waker};
+
+use rustc_hash::FxHashSet;
+
+/// The result of decoding an image, once processed.
+pub type Decoded<T> = (image::Id, T);
+
+/// A function processing the pixels of a loaded image, on the thread that
+/// loaded it.
+pub type Process<T> = fn(::image::ImageResult<Buffer>) -> T;
+
+/// Loads images on background threads, keeping rendering responsive.
+///
+/// Loaded images are processed on the same threads by a [`Process`]
+/// function, so renderers can convert them to their own pixel format off
+/// the render thread. The [`Waker`] set with [`set_waker`] is called
+/// whenever an image is ready.
+///
+/// On Wasm, images are loaded synchronously instead.
+///
+/// [`set_waker`]: Self::set_waker
+#[derive(Debug)]
+pub struct Decoder<T> {
+    process: Process<T>,
+    pending: FxHashSet<image::Id>,
+    is_blocking: bool,
+    waker: Option<Waker>,
+    #[cfg(not(target_arch = "wasm32"))]
+    workers: Option<Workers<T>>,
+    #[cfg(target_arch = "wasm32")]
+    decoded: Vec<Decoded<T>>,
+}
+
+impl<T> Decoder<T>
+where
+    T: Send + 'static,
+{
+    /// Creates a new [`Decoder`] with the given [`Process`] function.
+    ///
+    /// Its threads are only spawned once the first image is requested.
+    pub fn new(process: Process<T>) -> Self {
+        Self {
+            process,
+            pending: FxHashSet::default(),
+            is_blocking: false,
+            waker: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            workers: None,
+            #[cfg(target_arch = "wasm32")]
+            decoded: Vec::new(),
+        }
+    }
+
+    /// Sets whether the [`Decoder`] should load images synchronously with
+    /// [`load`](Self::load) instead of in the background.
+    ///
+    /// Useful to render a single frame offscreen, where there is no later
+    /// frame to draw the images in.
+    pub fn set_blocking(&mut self, is_blocking: bool) {
+        self.is_blocking = is_blocking;
+    }
+
+    /// Sets the [`Waker`] called whenever an image finishes loading in the
+    /// background.
+    ///
+    /// The background threads are spawned with the [`Waker`] set when the
+    /// first image is requested.
+    pub fn set_waker(&mut self, waker: Option<Waker>) {
+        self.waker = waker;
+    }
+
+    /// Returns true if the [`Decoder`] loads images synchronously.
+    pub fn is_blocking(&self) -> bool {
+        self.is_blocking
+    }
+
+    /// Loads and processes the image of the given [`Handle`] on the current
+    /// thread.
+    ///
+    /// [`Handle`]: image::Handle
+    pub fn load(&self, handle: &image::Handle) -> T {
+        (self.process)(load(handle))
+    }
+
+    /// Requests the image of the given [`Handle`] to be loaded, unless it is
+    /// already being loaded.
+    ///
+    /// [`Handle`]: image::Handle
+    pub fn request(&mut self, handle: &image::Handle) {
+        if !self.pending.insert(handle.id()) {
+            return;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let process = self.process;
+            let waker = &self.waker;
+
+            self.workers
+                .get_or_insert_with(|| Workers::spawn(process, waker.clone()))
+                .request(handle.clone());
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.decoded.push((handle.id(), self.load(handle)));
+
+            if let Some(waker) = &self.waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Returns true if the image with the given [`Id`] is being loaded.
+    ///
+    /// [`Id`]: image::Id
+    pub fn is_pending(&self, id: image::Id) -> bool {
+        self.pending.contains(&id)
+    }
+
+    /// Returns the images that have finished loading since the last poll.
+    pub fn poll(&mut self) -> Vec<Decoded<T>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let decoded: Vec<_> = self
+            .workers
+            .as_ref()
+            .map(|workers| workers.results.try_iter().collect())
+            .unwrap_or_default();
+
+        #[cfg(target_arch = "wasm32")]
+        let decoded = std::mem::take(&mut self.decoded);
+
+        for (id, _) in &decoded {
+            let _ = self.pending.remove(id);
+        }
+
+        decoded
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+struct Workers<T> {
+    requests: std::sync::mpsc::Sender<image::Handle>,
+    results: std::sync::mpsc::Receiver<Decoded<T>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T> Workers<T>
+where
+    T: Send + 'static,
+{
+    /// The maximum amount of threads decoding images at the same time.
+    const MAX_THREADS: usize = 4;
+
+    fn spawn(process: Process<T>, waker: Option<Waker>) -> Self {
+        use std::sync::{mpsc, Arc, Mutex};
+        use std::thread;
+
+        let (requests, receiver) = mpsc::channel::<image::Handle>();
+        let (sender, results) = mpsc::channel();
+
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let threads = thread::available_parallelism()
+            .map_or(1, usize::from)
+            .min(Self::MAX_THREADS);
+
+        for i in 0..threads {
+            let receiver = receiver.clone();
+            let sender = sender.clone();
+            let waker = waker.clone();
+
+            let worker = thread::Builder::new()
+                .name(format!("iced image decoder {i}"))
+                .spawn(move || loop {
+                    let Ok(handle) =
+                        receiver.lock().expect("Lock image requests").recv()
+                    else {
+                        break;
+                    };
+
+                    let decoded = process(load(&handle));
+
+                    if sender.send((handle.id(), decoded)).is_err() {
+                        break;
+                    }
+
+                    if let Some(waker) = &waker {
+                        waker.wake();
+                    }
+                });
+
+            if let Err(error) = worker {
+                log::warn!("Failed to spawn image decoder thread: {error}");
+            }
+        }
+
+        Self { requests, results }
+    }
+
+    fn request(&self, handle: image::Handle) {
+        let _ = self.requests.send(handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn is_loaded(result: ::image::ImageResult<Buffer>) -> bool {
+        result.is_ok()
+    }
+
+    fn pixel() -> image::Handle {
+        image::Handle::from_rgba(1, 1, vec![255; 4])
+    }
+
+    #[test]
+    fn load_processes_on_current_thread() {
+        let mut decoder = Decoder::new(is_loaded);
+        decoder.set_blocking(true);
+
+        assert!(decoder.is_blocking());
+        assert!(decoder.load(&pixel()));
+        assert!(!decoder.load(&image::Handle::from_bytes(vec![0; 4])));
+    }
+
+    #[test]
+    fn request_is_deduplicated() {
+        let handle = pixel();
+
+        let mut decoder = Decoder::new(is_loaded);
+        decoder.request(&handle);
+        decoder.request(&handle);
+
+        assert!(decoder.is_pending(handle.id()));
+        assert_eq!(decoder.pending.len(), 1);
+    }
+
+    #[test]
+    fn poll_clears_pending_and_wakes() {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+
+        let handle = pixel();
+
+        let mut decoder = Decoder::new(is_loaded);
+        decoder.set_waker(Some(Waker::new(move || {
+            let _ = sender.lock().expect("Lock sender").send(());
+        })));
+        decoder.request(&handle);
+
+        receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("Wake after decoding");
+
+        assert_eq!(decoder.poll(), vec![(handle.id(), true)]);
+        assert!(!decoder.is_pending(handle.id()));
+        assert!(decoder.poll().is_empty());
+    }
+}
@@ -0,0 +1,335 @@
+use crate::image::Stats;
+
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+/// A map of cached entries that evicts the least recently used ones
+/// once a memory budget is exceeded.
+///
+/// Entries used since the last [`trim`] are never evicted, since they are
+/// still being drawn.
+///
+/// [`trim`]: Self::trim
+#[derive(Debug)]
+pub struct Lru<K, V> {
+    entries: FxHashMap<K, Entry<V>>,
+    tick: u64,
+    last_trim: u64,
+    memory: usize,
+    hits: u64,
+    misses: u64,
+    should_trim: bool,
+}
+
+#[derive(Debug)]
+struct Entry<V> {
+    value: V,
+    size: usize,
+    last_used: u64,
+}
+
+impl<K, V> Lru<K, V>
+where
+    K: Copy + Eq + Hash,
+{
+    /// Creates a new empty [`Lru`].
+    pub fn new() -> Self {
+        Self {
+            entries: FxHashMap::default(),
+            tick: 0,
+            last_trim: 0,
+            memory: 0,
+            hits: 0,
+            misses: 0,
+            should_trim: false,
+        }
+    }
+
+    /// Returns true if the [`Lru`] contains an entry for the given key.
+    ///
+    /// It does not count as a use of the entry.
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns the entry of the given key, marking it as used and counting
+    /// a cache hit.
+    pub fn get(&mut self, key: &K) -> Option<&mut V> {
+        let entry = self.entries.get_mut(key)?;
+
+        self.tick += 1;
+        self.hits += 1;
+
+        entry.last_used = self.tick;
+
+        Some(&mut entry.value)
+    }
+
+    /// Returns the entry of the given key, marking it as used without
+    /// counting a cache hit.
+    ///
+    /// Useful to measure an entry that is not drawn.
+    pub fn touch(&mut self, key: &K) -> Option<&mut V> {
+        let entry = self.entries.get_mut(key)?;
+
+        self.tick += 1;
+
+        entry.last_used = self.tick;
+
+        Some(&mut entry.value)
+    }
+
+    /// Returns the entry of the given key, without marking it as used.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    /// Replaces the value of an existing entry, which now takes `size` bytes
+    /// of memory, marking it as used.
+    pub fn replace(&mut self, key: &K, value: V, size: usize) {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return;
+        };
+
+        self.tick += 1;
+        self.memory = self.memory - entry.size + size;
+        self.should_trim = true;
+
+        entry.value = value;
+        entry.size = size;
+        entry.last_used = self.tick;
+    }
+
+    /// Inserts a new entry taking `size` bytes of memory, marking it as used.
+    pub fn insert(&mut self, key: K, value: V, size: usize) -> &mut V {
+        self.tick += 1;
+        self.misses += 1;
+        self.memory += size;
+        self.should_trim = true;
+
+        let entry = Entry {
+            value,
+            size,
+            last_used: self.tick,
+        };
+
+        if let Some(old) = self.entries.insert(key, entry) {
+            self.memory -= old.size;
+        }
+
+        &mut self.entries.get_mut(&key).unwrap().value
+    }
+
+    /// Evicts the entries that have not been used since the last trim,
+    /// calling `evict` for each one of them.
+    ///
+    /// If a `budget` is given, only the least recently used entries are
+    /// evicted until the memory of the [`Lru`] fits in it.
+    pub fn trim(&mut self, budget: Option<usize>, mut evict: impl FnMut(V)) {
+        // Only trim if new entries have landed since the last trim
+        if !self.should_trim {
+            return;
+        }
+
+        let last_trim = self.last_trim;
+
+        let mut unused: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.last_used <= last_trim)
+            .map(|(key, entry)| (entry.last_used, *key))
+            .collect();
+
+        if budget.is_some() {
+            unused.sort_unstable_by_key(|(last_used, _)| *last_used);
+        }
+
+        for (_, key) in unused {
+            if budget.is_some_and(|budget| self.memory <= budget) {
+                break;
+            }
+
+            if let Some(entry) = self.entries.remove(&key) {
+                self.memory -= entry.size;
+
+                evict(entry.value);
+            }
+        }
+
+        self.last_trim = self.tick;
+        self.should_trim = false;
+    }
+
+    /// Returns the [`Stats`] of the [`Lru`].
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            memory: self.memory,
+            atlas_layers: 0,
+        }
+    }
+}
+
+impl<K, V> Default for Lru<K, V>
+where
+    K: Copy + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evicted(lru: &mut Lru<u32, u32>, budget: Option<usize>) -> Vec<u32> {
+        let mut evicted = Vec::new();
+        lru.trim(budget, |value| evicted.push(value));
+        evicted.sort_unstable();
+        evicted
+    }
+
+    #[test]
+    fn insert_counts_misses_and_memory() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+        let _ = lru.insert(2, 20, 50);
+
+        assert_eq!(
+            lru.stats(),
+            Stats {
+                hits: 0,
+                misses: 2,
+                memory: 150,
+                atlas_layers: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn insert_replaces_memory_of_existing_entry() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+        let _ = lru.insert(1, 11, 30);
+
+        assert_eq!(lru.peek(&1), Some(&11));
+        assert_eq!(lru.stats().memory, 30);
+    }
+
+    #[test]
+    fn get_counts_hits_but_touch_does_not() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+
+        assert_eq!(lru.get(&1), Some(&mut 10));
+        assert_eq!(lru.touch(&1), Some(&mut 10));
+        assert_eq!(lru.get(&2), None);
+        assert_eq!(lru.touch(&2), None);
+
+        assert_eq!(lru.stats().hits, 1);
+        assert_eq!(lru.stats().misses, 1);
+    }
+
+    #[test]
+    fn replace_updates_memory() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 0);
+        lru.replace(&1, 11, 400);
+        lru.replace(&2, 20, 400);
+
+        assert_eq!(lru.peek(&1), Some(&11));
+        assert!(!lru.contains(&2));
+        assert_eq!(lru.stats().memory, 400);
+    }
+
+    #[test]
+    fn replace_marks_entry_as_used() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 0);
+        let _ = lru.insert(2, 20, 0);
+        lru.trim(None, drop);
+
+        lru.replace(&1, 11, 400);
+
+        assert_eq!(evicted(&mut lru, None), [20]);
+        assert_eq!(lru.peek(&1), Some(&11));
+    }
+
+    #[test]
+    fn trim_keeps_entries_used_since_last_trim() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+        let _ = lru.insert(2, 20, 100);
+
+        assert!(evicted(&mut lru, Some(0)).is_empty());
+        assert_eq!(lru.stats().memory, 200);
+    }
+
+    #[test]
+    fn trim_without_budget_evicts_unused_entries() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+        let _ = lru.insert(2, 20, 100);
+        lru.trim(None, drop);
+
+        let _ = lru.get(&1);
+        let _ = lru.insert(3, 30, 100);
+
+        assert_eq!(evicted(&mut lru, None), [20]);
+        assert!(lru.contains(&1));
+        assert!(lru.contains(&3));
+        assert_eq!(lru.stats().memory, 200);
+    }
+
+    #[test]
+    fn trim_with_budget_evicts_least_recently_used_first() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+        let _ = lru.insert(2, 20, 100);
+        let _ = lru.insert(3, 30, 100);
+        let _ = lru.get(&1);
+        lru.trim(None, drop);
+
+        let _ = lru.insert(4, 40, 100);
+
+        assert_eq!(evicted(&mut lru, Some(250)), [20, 30]);
+        assert!(lru.contains(&1));
+        assert!(lru.contains(&4));
+        assert_eq!(lru.stats().memory, 200);
+    }
+
+    #[test]
+    fn trim_with_budget_stops_once_within_budget() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+        let _ = lru.insert(2, 20, 100);
+        lru.trim(None, drop);
+
+        let _ = lru.insert(3, 30, 100);
+
+        assert_eq!(evicted(&mut lru, Some(250)), [10]);
+        assert_eq!(lru.stats().memory, 200);
+    }
+
+    #[test]
+    fn trim_is_skipped_without_new_entries() {
+        let mut lru = Lru::new();
+
+        let _ = lru.insert(1, 10, 100);
+        lru.trim(None, drop);
+
+        assert!(evicted(&mut lru, None).is_empty());
+        assert!(lru.contains(&1));
+    }
+}
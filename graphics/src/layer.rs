@@ -1,6 +1,9 @@
 //! Draw and stack layers of graphical primitives.
+use crate::core::renderer::Effect;
 use crate::core::{Rectangle, Transformation};
 
+use std::ops::Range;
+
 /// A layer of graphical primitives.
 ///
 /// Layers normally dictate a set of primitives that are
@@ -30,6 +33,19 @@ pub struct Stack<T: Layer> {
     previous: Vec<usize>,
     current: usize,
     active_count: usize,
+    groups: Vec<Group>,
+    open_groups: Vec<Option<usize>>,
+}
+
+/// An [`Effect`] applied to a contiguous range of layers in a [`Stack`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// The indices of the layers affected by the [`Effect`].
+    pub layers: Range<usize>,
+    /// The bounds of the [`Group`], already transformed.
+    pub bounds: Rectangle,
+    /// The [`Effect`] of the [`Group`].
+    pub effect: Effect,
 }
 
 impl<T: Layer> Stack<T> {
@@ -41,6 +57,8 @@ impl<T: Layer> Stack<T> {
             previous: vec![],
             current: 0,
             active_count: 1,
+            groups: vec![],
+            open_groups: vec![],
         }
     }
 
@@ -85,6 +103,36 @@ impl<T: Layer> Stack<T> {
         self.current = self.previous.pop().unwrap();
     }
 
+    /// Pushes a new clipping region with an [`Effect`] in the [`Stack`].
+    ///
+    /// The [`Effect`] will apply to the new layer and any layers pushed
+    /// before the matching [`pop_effect`](Self::pop_effect). No [`Group`]
+    /// is recorded if the [`Effect`] leaves the layers untouched.
+    pub fn push_effect(&mut self, bounds: Rectangle, effect: Effect) {
+        self.push_clip(bounds);
+
+        if effect.is_none() {
+            self.open_groups.push(None);
+            return;
+        }
+
+        self.open_groups.push(Some(self.groups.len()));
+        self.groups.push(Group {
+            layers: self.current..self.current + 1,
+            bounds: bounds * self.transformation(),
+            effect,
+        });
+    }
+
+    /// Pops the current clipping region with an [`Effect`] from the [`Stack`].
+    pub fn pop_effect(&mut self) {
+        self.pop_clip();
+
+        if let Some(group) = self.open_groups.pop().flatten() {
+            self.groups[group].layers.end = self.active_count;
+        }
+    }
+
     /// Pushes a new [`Transformation`] in the [`Stack`].
     ///
     /// Future drawing operations will be affected by this new [`Transformation`] until
@@ -118,6 +166,15 @@ impl<T: Layer> Stack<T> {
         &self.layers[..self.active_count]
     }
 
+    /// Returns the effect groups of the [`Stack`], sorted by their first
+    /// layer.
+    ///
+    /// Groups are properly nested: a group starting inside of another
+    /// group also ends inside of it.
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
     /// Flushes and settles any primitives in the current layer of the [`Stack`].
     pub fn flush(&mut self) {
         self.layers[self.current].flush();
@@ -134,6 +191,8 @@ impl<T: Layer> Stack<T> {
         self.current = 0;
         self.active_count = 1;
         self.previous.clear();
+        self.groups.clear();
+        self.open_groups.clear();
     }
 }
 
@@ -142,3 +201,148 @@ impl<T: Layer> Default for Stack<T> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::{Point, Size};
+
+    #[derive(Debug, Default)]
+    struct Layer {
+        bounds: Rectangle,
+    }
+
+    impl super::Layer for Layer {
+        fn with_bounds(bounds: Rectangle) -> Self {
+            Self { bounds }
+        }
+
+        fn flush(&mut self) {}
+
+        fn resize(&mut self, bounds: Rectangle) {
+            self.bounds = bounds;
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    fn bounds(x: f32) -> Rectangle {
+        Rectangle::new(Point::new(x, 0.0), Size::new(10.0, 10.0))
+    }
+
+    fn faded() -> Effect {
+        Effect::NONE.opacity(0.5)
+    }
+
+    fn assert_nested(groups: &[Group]) {
+        for (i, outer) in groups.iter().enumerate() {
+            for inner in &groups[i + 1..] {
+                assert!(outer.layers.start <= inner.layers.start);
+
+                if inner.layers.start < outer.layers.end {
+                    assert!(inner.layers.end <= outer.layers.end);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_op_effects_record_no_group() {
+        let mut stack = Stack::<Layer>::new();
+
+        stack.push_effect(bounds(0.0), Effect::NONE);
+        stack.pop_effect();
+
+        stack.push_effect(bounds(0.0), Effect::NONE.blur(0.0));
+        stack.pop_effect();
+
+        assert!(stack.groups().is_empty());
+        assert_eq!(stack.as_slice().len(), 3);
+    }
+
+    #[test]
+    fn group_covers_layers_pushed_inside() {
+        let mut stack = Stack::<Layer>::new();
+
+        stack.push_effect(bounds(0.0), faded());
+        stack.push_clip(bounds(1.0));
+        stack.pop_clip();
+        stack.pop_effect();
+
+        stack.push_clip(bounds(2.0));
+        stack.pop_clip();
+
+        assert_eq!(
+            stack.groups(),
+            [Group {
+                layers: 1..3,
+                bounds: bounds(0.0),
+                effect: faded(),
+            }]
+        );
+    }
+
+    #[test]
+    fn groups_are_nested() {
+        let mut stack = Stack::<Layer>::new();
+
+        stack.push_effect(bounds(0.0), faded());
+        stack.push_clip(bounds(1.0));
+        stack.pop_clip();
+
+        stack.push_effect(bounds(2.0), Effect::NONE.blur(4.0));
+        stack.push_clip(bounds(3.0));
+        stack.pop_clip();
+        stack.pop_effect();
+
+        stack.push_effect(bounds(4.0), Effect::NONE);
+        stack.pop_effect();
+        stack.pop_effect();
+
+        stack.push_effect(bounds(5.0), Effect::NONE.backdrop_blur(2.0));
+        stack.pop_effect();
+
+        let groups = stack.groups();
+
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| group.layers.clone())
+                .collect::<Vec<_>>(),
+            vec![1..6, 3..5, 6..7]
+        );
+        assert_nested(groups);
+    }
+
+    #[test]
+    fn group_bounds_are_transformed() {
+        let mut stack = Stack::<Layer>::new();
+
+        stack.push_transformation(Transformation::scale(2.0));
+        stack.push_effect(bounds(1.0), faded());
+        stack.pop_effect();
+        stack.pop_transformation();
+
+        let expected = bounds(1.0) * Transformation::scale(2.0);
+
+        assert_eq!(stack.groups()[0].bounds, expected);
+        assert_eq!(stack.as_slice()[1].bounds, expected);
+    }
+
+    #[test]
+    fn clear_removes_groups() {
+        let mut stack = Stack::<Layer>::new();
+
+        stack.push_effect(bounds(0.0), faded());
+        stack.clear();
+
+        assert!(stack.groups().is_empty());
+
+        stack.push_effect(bounds(0.0), faded());
+        stack.pop_effect();
+
+        assert_eq!(stack.groups().len(), 1);
+        assert_eq!(stack.groups()[0].layers, 1..2);
+    }
+}
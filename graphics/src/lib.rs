@@ -26,6 +26,9 @@ pub mod text;
 #[cfg(feature = "geometry")]
 pub mod geometry;
 
+#[cfg(feature = "svg")]
+pub mod svg;
+
 pub use antialiasing::Antialiasing;
 pub use cache::Cache;
 pub use compositor::Compositor;
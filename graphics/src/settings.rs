@@ -1,8 +1,9 @@
 use crate::core::{Font, Pixels};
+use crate::image;
 use crate::Antialiasing;
 
 /// The settings of a renderer.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Settings {
     /// The default [`Font`] to use.
     pub default_font: Font,
@@ -16,6 +17,20 @@ pub struct Settings {
     ///
     /// By default, it is `None`.
     pub antialiasing: Option<Antialiasing>,
+
+    /// The maximum amount of memory, in bytes, that cached images may use
+    /// before the least recently drawn ones are evicted.
+    ///
+    /// By default, it is `None`; and images are evicted as soon as they
+    /// stop being drawn.
+    pub image_cache_budget: Option<usize>,
+
+    /// The [`image::Waker`] called whenever an image finishes loading in
+    /// the background.
+    ///
+    /// By default, it is `None`; and loaded images are only drawn once
+    /// something else redraws the window.
+    pub image_waker: Option<image::Waker>,
 }
 
 impl Default for Settings {
@@ -24,6 +39,8 @@ impl Default for Settings {
             default_font: Font::default(),
             default_text_size: Pixels(16.0),
             antialiasing: None,
+            image_cache_budget: None,
+            image_waker: None,
         }
     }
 }
@@ -0,0 +1,180 @@
+//! Load vector graphics with the fonts of the application.
+pub use resvg;
+pub use resvg::usvg;
+
+use crate::core::svg::{self, Data, Handle};
+use crate::core::Color;
+use crate::text;
+
+use rustc_hash::{FxHashSet, FxHasher};
+use std::borrow::Cow;
+use std::fs;
+use std::hash::{Hash, Hasher as _};
+use std::sync::{Arc, Mutex};
+
+/// The class [`Color`] overrides of an SVG.
+pub type Classes = [(Cow<'static, str>, Color)];
+
+/// Returns a key that uniquely identifies the tree of an SVG with the given
+/// [`Handle`] and [`Classes`].
+pub fn key(handle: &Handle, classes: &Classes) -> u64 {
+    if classes.is_empty() {
+        return handle.id();
+    }
+
+    let mut hasher = FxHasher::default();
+    handle.id().hash(&mut hasher);
+
+    for (class, color) in classes {
+        class.hash(&mut hasher);
+        color.into_rgba8().hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+/// Loads the tree of the SVG with the given [`Handle`], applying its
+/// stylesheet and the given [`Classes`].
+///
+/// Text in the SVG is laid out with the fonts of the global
+/// [`FontSystem`](text::FontSystem).
+pub fn load(handle: &Handle, classes: &Classes) -> Option<usvg::Tree> {
+    let data = match handle.data() {
+        Data::Path(path) => Cow::Owned(fs::read(path).ok()?),
+        Data::Bytes(bytes) => Cow::Borrowed(bytes.as_ref()),
+    };
+
+    let fontdb = fontdb();
+
+    let options = usvg::Options {
+        font_family: fontdb
+            .family_name(&usvg::fontdb::Family::SansSerif)
+            .to_owned(),
+        fontdb,
+        ..usvg::Options::default()
+    };
+
+    let Some(stylesheet) = svg::stylesheet(handle, classes) else {
+        return usvg::Tree::from_data(&data, &options).ok();
+    };
+
+    let data = if data.starts_with(&[0x1f, 0x8b]) {
+        Cow::Owned(usvg::decompress_svgz(&data).ok()?)
+    } else {
+        data
+    };
+
+    let svg = std::str::from_utf8(&data).ok()?;
+
+    match svg::inject_stylesheet(svg, &stylesheet) {
+        Some(svg) => usvg::Tree::from_str(&svg, &options).ok(),
+        None => usvg::Tree::from_str(svg, &options).ok(),
+    }
+}
+
+/// Estimates the amount of bytes of memory taken by the given tree.
+pub fn memory(tree: &usvg::Tree) -> usize {
+    use std::mem::{size_of, size_of_val};
+
+    fn group(group: &usvg::Group) -> usize {
+        size_of::<usvg::Group>()
+            + group.children().iter().map(node).sum::<usize>()
+    }
+
+    fn node(node: &usvg::Node) -> usize {
+        let mut memory = match node {
+            usvg::Node::Group(child) => group(child),
+            usvg::Node::Path(path) => {
+                let data = path.data();
+
+                size_of::<usvg::Path>()
+                    + size_of_val(data.points())
+                    + size_of_val(data.verbs())
+            }
+            usvg::Node::Image(image) => {
+                size_of::<usvg::Image>()
+                    + match image.kind() {
+                        usvg::ImageKind::JPEG(data)
+                        | usvg::ImageKind::PNG(data)
+                        | usvg::ImageKind::GIF(data) => data.len(),
+                        // Nested trees are visited as subroots
+                        usvg::ImageKind::SVG(_) => 0,
+                    }
+            }
+            usvg::Node::Text(_) => size_of::<usvg::Text>(),
+        };
+
+        // Clip paths, masks, patterns, nested trees and flattened text
+        node.subroots(|subroot| memory += group(subroot));
+
+        memory
+    }
+
+    group(tree.root())
+}
+
+/// Returns the font database of the global [`FontSystem`](text::FontSystem),
+/// rebuilding it only when new fonts have been loaded.
+fn fontdb() -> Arc<usvg::fontdb::Database> {
+    use cosmic_text::fontdb::{Family, Source};
+
+    static FONTDB: Mutex<Option<(text::Version, Arc<usvg::fontdb::Database>)>> =
+        Mutex::new(None);
+
+    let mut font_system =
+        text::font_system().write().expect("Write font system");
+
+    let mut cache = FONTDB.lock().expect("Lock SVG font database");
+
+    if let Some((version, fontdb)) = cache.as_ref() {
+        if *version == font_system.version() {
+            return fontdb.clone();
+        }
+    }
+
+    let version = font_system.version();
+    let source = font_system.raw().db();
+
+    let mut fontdb = usvg::fontdb::Database::new();
+    let mut binaries = FxHashSet::default();
+    let mut files = FxHashSet::default();
+
+    for face in source.faces() {
+        let source = match &face.source {
+            Source::Binary(data) => {
+                if !binaries.insert(Arc::as_ptr(data).cast::<()>() as usize) {
+                    continue;
+                }
+
+                usvg::fontdb::Source::Binary(data.clone())
+            }
+            Source::File(path) => {
+                if !files.insert(path) {
+                    continue;
+                }
+
+                usvg::fontdb::Source::File(path.clone())
+            }
+            Source::SharedFile(path, data) => {
+                if !files.insert(path) {
+                    continue;
+                }
+
+                usvg::fontdb::Source::SharedFile(path.clone(), data.clone())
+            }
+        };
+
+        let _ = fontdb.load_font_source(source);
+    }
+
+    fontdb.set_serif_family(source.family_name(&Family::Serif));
+    fontdb.set_sans_serif_family(source.family_name(&Family::SansSerif));
+    fontdb.set_cursive_family(source.family_name(&Family::Cursive));
+    fontdb.set_fantasy_family(source.family_name(&Family::Fantasy));
+    fontdb.set_monospace_family(source.family_name(&Family::Monospace));
+
+    let fontdb = Arc::new(fontdb);
+    *cache = Some((version, fontdb.clone()));
+
+    fontdb
+}
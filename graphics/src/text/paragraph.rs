@@ -1,6 +1,7 @@
 //! Draw paragraphs.
 use crate::core;
 use crate::core::alignment;
+use crate::core::text::paragraph::Position;
 use crate::core::text::{Hit, Shaping, Span, Text, Wrapping};
 use crate::core::{Font, Point, Rectangle, Size};
 use crate::text;
@@ -347,6 +348,66 @@ impl core::text::Paragraph for Paragraph {
             glyph.y - glyph.y_offset * glyph.font_size,
         ))
     }
+
+    fn hit_position(&self, point: Point) -> Option<Position> {
+        let cursor = self.internal().buffer.hit(point.x, point.y)?;
+
+        Some(Position {
+            line: cursor.line,
+            index: cursor.index,
+        })
+    }
+
+    fn range_bounds(&self, start: Position, end: Position) -> Vec<Rectangle> {
+        let internal = self.internal();
+
+        internal
+            .buffer
+            .layout_runs()
+            .filter(|run| start.line <= run.line_i && run.line_i <= end.line)
+            .filter_map(|run| {
+                let from = if run.line_i == start.line {
+                    start.index
+                } else {
+                    0
+                };
+
+                let to = if run.line_i == end.line {
+                    end.index
+                } else {
+                    usize::MAX
+                };
+
+                let (left, right) = run
+                    .glyphs
+                    .iter()
+                    .filter(|glyph| from < glyph.end && glyph.start < to)
+                    .fold(None, |range: Option<(f32, f32)>, glyph| {
+                        let (left, right) =
+                            range.unwrap_or((f32::INFINITY, f32::NEG_INFINITY));
+
+                        Some((left.min(glyph.x), right.max(glyph.x + glyph.w)))
+                    })?;
+
+                Some(Rectangle::new(
+                    Point::new(left, run.line_top),
+                    Size::new(right - left, run.line_height),
+                ))
+            })
+            .collect()
+    }
+
+    fn line(&self, index: usize) -> Option<&str> {
+        self.internal()
+            .buffer
+            .lines
+            .get(index)
+            .map(cosmic_text::BufferLine::text)
+    }
+
+    fn line_count(&self) -> usize {
+        self.internal().buffer.lines.len()
+    }
 }
 
 impl Default for Paragraph {
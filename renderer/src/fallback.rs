@@ -57,6 +57,14 @@ where
         delegate!(self, renderer, renderer.end_layer());
     }
 
+    fn start_effect(&mut self, bounds: Rectangle, effect: renderer::Effect) {
+        delegate!(self, renderer, renderer.start_effect(bounds, effect));
+    }
+
+    fn end_effect(&mut self) {
+        delegate!(self, renderer, renderer.end_effect());
+    }
+
     fn start_transformation(&mut self, transformation: Transformation) {
         delegate!(
             self,
@@ -152,6 +160,10 @@ where
     fn draw_image(&mut self, image: Image<A::Handle>, bounds: Rectangle) {
         delegate!(self, renderer, renderer.draw_image(image, bounds));
     }
+
+    fn is_image_loading(&self, handle: &Self::Handle) -> bool {
+        delegate!(self, renderer, renderer.is_image_loading(handle))
+    }
 }
 
 impl<A, B> svg::Renderer for Renderer<A, B>
@@ -168,6 +180,16 @@ where
     }
 }
 
+impl<A, B> graphics::image::Cache for Renderer<A, B>
+where
+    A: graphics::image::Cache,
+    B: graphics::image::Cache,
+{
+    fn image_cache_stats(&self) -> graphics::image::Stats {
+        delegate!(self, renderer, renderer.image_cache_stats())
+    }
+}
+
 impl<A, B> mesh::Renderer for Renderer<A, B>
 where
     A: mesh::Renderer,
@@ -241,8 +263,12 @@ where
         let mut errors = vec![];
 
         for backend in candidates.iter().map(Option::as_deref) {
-            match A::with_backend(settings, compatible_window.clone(), backend)
-                .await
+            match A::with_backend(
+                settings.clone(),
+                compatible_window.clone(),
+                backend,
+            )
+            .await
             {
                 Ok(compositor) => return Ok(Self::Primary(compositor)),
                 Err(error) => {
@@ -250,8 +276,12 @@ where
                 }
             }
 
-            match B::with_backend(settings, compatible_window.clone(), backend)
-                .await
+            match B::with_backend(
+                settings.clone(),
+                compatible_window.clone(),
+                backend,
+            )
+            .await
             {
                 Ok(compositor) => return Ok(Self::Secondary(compositor)),
                 Err(error) => {
@@ -274,6 +304,17 @@ where
         }
     }
 
+    fn create_offscreen_renderer(&self) -> Self::Renderer {
+        match self {
+            Self::Primary(compositor) => {
+                Renderer::Primary(compositor.create_offscreen_renderer())
+            }
+            Self::Secondary(compositor) => {
+                Renderer::Secondary(compositor.create_offscreen_renderer())
+            }
+        }
+    }
+
     fn create_surface<W: compositor::Window + Clone>(
         &mut self,
         window: W,
@@ -315,6 +356,21 @@ where
         delegate!(self, compositor, compositor.fetch_information())
     }
 
+    fn image_cache_stats(
+        &self,
+        renderer: &Self::Renderer,
+    ) -> graphics::image::Stats {
+        match (self, renderer) {
+            (Self::Primary(compositor), Renderer::Primary(renderer)) => {
+                compositor.image_cache_stats(renderer)
+            }
+            (Self::Secondary(compositor), Renderer::Secondary(renderer)) => {
+                compositor.image_cache_stats(renderer)
+            }
+            _ => unreachable!(),
+        }
+    }
+
     fn present<T: AsRef<str>>(
         &mut self,
         renderer: &mut Self::Renderer,
@@ -1,8 +1,13 @@
 //! Access the clipboard.
-use crate::core::clipboard::Kind;
+use crate::core::clipboard::{Content, Format, Kind};
+use crate::core::image;
+use crate::futures::event;
 use crate::futures::futures::channel::oneshot;
+use crate::futures::Subscription;
 use crate::task::{self, Task};
 
+use bytes::Bytes;
+
 /// A clipboard action to be performed by some [`Task`].
 ///
 /// [`Task`]: crate::Task
@@ -23,6 +28,32 @@ pub enum Action {
         /// The contents to be written.
         contents: String,
     },
+
+    /// Read the formats the clipboard contents are available in.
+    ReadFormats {
+        /// The clipboard target.
+        target: Kind,
+        /// The channel to send the available formats.
+        channel: oneshot::Sender<Vec<String>>,
+    },
+
+    /// Read the clipboard contents in the given [`Format`].
+    ReadContent {
+        /// The clipboard target.
+        target: Kind,
+        /// The format to read.
+        format: Format,
+        /// The channel to send the read contents.
+        channel: oneshot::Sender<Option<Content>>,
+    },
+
+    /// Write the given typed contents to the clipboard.
+    WriteContent {
+        /// The clipboard target.
+        target: Kind,
+        /// The contents to be written, each one in its own format.
+        contents: Vec<Content>,
+    },
 }
 
 /// Read the current contents of the clipboard.
@@ -60,3 +91,126 @@ pub fn write_primary<Message>(contents: String) -> Task<Message> {
         contents,
     }))
 }
+
+/// Subscribes to the changes of the contents of the clipboards, producing
+/// the [`Kind`] of clipboard that changed.
+///
+/// On X11 and Wayland, with the `dnd` feature of `iced` enabled, changes
+/// are notified by the platform. Wayland only notifies while a window of the
+/// application is focused, and it may notify again of the same contents when
+/// focus is gained.
+///
+/// Other platforms periodically poll the text contents of the clipboards;
+/// changes of other formats may go unnoticed.
+pub fn changes() -> Subscription<Kind> {
+    event::listen_clipboard()
+}
+
+/// Read the formats the current contents of the clipboard are available in.
+///
+/// Formats are MIME types in most platforms.
+pub fn read_formats() -> Task<Vec<String>> {
+    task::oneshot(|channel| {
+        crate::Action::Clipboard(Action::ReadFormats {
+            target: Kind::Standard,
+            channel,
+        })
+    })
+}
+
+/// Read the current contents of the clipboard in the given [`Format`].
+pub fn read_content(format: Format) -> Task<Option<Content>> {
+    task::oneshot(|channel| {
+        crate::Action::Clipboard(Action::ReadContent {
+            target: Kind::Standard,
+            format,
+            channel,
+        })
+    })
+}
+
+/// Read the current contents of the primary clipboard in the given
+/// [`Format`].
+pub fn read_primary_content(format: Format) -> Task<Option<Content>> {
+    task::oneshot(|channel| {
+        crate::Action::Clipboard(Action::ReadContent {
+            target: Kind::Primary,
+            format,
+            channel,
+        })
+    })
+}
+
+/// Read the current contents of the clipboard as HTML markup.
+pub fn read_html() -> Task<Option<String>> {
+    read_content(Format::Html).map(|content| match content {
+        Some(Content::Html(html)) => Some(html),
+        _ => None,
+    })
+}
+
+/// Read the current contents of the clipboard as an image.
+pub fn read_image() -> Task<Option<image::Handle>> {
+    read_content(Format::Image).map(|content| match content {
+        Some(Content::Image {
+            width,
+            height,
+            pixels,
+        }) => Some(image::Handle::from_rgba(width, height, pixels)),
+        _ => None,
+    })
+}
+
+/// Read the current contents of the clipboard with the given MIME type.
+pub fn read_mime(format: impl Into<String>) -> Task<Option<Bytes>> {
+    read_content(Format::Mime(format.into())).map(|content| match content {
+        Some(Content::Mime { bytes, .. }) => Some(bytes),
+        _ => None,
+    })
+}
+
+/// Write the given typed contents to the clipboard, each one in its own
+/// format.
+pub fn write_content<T>(
+    contents: impl IntoIterator<Item = Content>,
+) -> Task<T> {
+    task::effect(crate::Action::Clipboard(Action::WriteContent {
+        target: Kind::Standard,
+        contents: contents.into_iter().collect(),
+    }))
+}
+
+/// Write the given typed contents to the primary clipboard, each one in
+/// its own format.
+pub fn write_primary_content<T>(
+    contents: impl IntoIterator<Item = Content>,
+) -> Task<T> {
+    task::effect(crate::Action::Clipboard(Action::WriteContent {
+        target: Kind::Primary,
+        contents: contents.into_iter().collect(),
+    }))
+}
+
+/// Write the given HTML markup to the clipboard, together with its plain
+/// text alternative.
+pub fn write_html<T>(html: String, alt_text: String) -> Task<T> {
+    write_content([Content::Html(html), Content::Text(alt_text)])
+}
+
+/// Write an image with the given dimensions and RGBA pixels to the
+/// clipboard.
+pub fn write_image<T>(
+    width: u32,
+    height: u32,
+    pixels: impl Into<Bytes>,
+) -> Task<T> {
+    write_content([Content::image(width, height, pixels)])
+}
+
+/// Write the given bytes with the given MIME type to the clipboard.
+pub fn write_mime<T>(
+    format: impl Into<String>,
+    bytes: impl Into<Bytes>,
+) -> Task<T> {
+    write_content([Content::mime(format, bytes)])
+}
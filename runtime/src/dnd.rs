@@ -0,0 +1,45 @@
+//! Drag and drop data between applications.
+use crate::core::dnd::{Data, Outcome};
+use crate::core::window;
+use crate::futures::futures::channel::oneshot;
+use crate::task::{self, Task};
+
+/// A drag and drop action to be performed by some [`Task`].
+///
+/// [`Task`]: crate::Task
+#[derive(Debug)]
+pub enum Action {
+    /// Start dragging some [`Data`] out of a window.
+    StartDrag {
+        /// The window where the drag starts.
+        window: window::Id,
+        /// The dragged data.
+        data: Data,
+        /// The channel to send the [`Outcome`] of the drag.
+        channel: oneshot::Sender<Outcome>,
+    },
+}
+
+/// Starts dragging the given [`Data`] out of the given window, producing
+/// the [`Outcome`] of the drag once it is over.
+///
+/// The drag follows the mouse until its buttons are released; so this
+/// should normally be called while the user is dragging something inside
+/// the window.
+///
+/// ## Platform-specific
+///
+/// - **X11 / Wayland / macOS:** Supported with the `dnd` feature of `iced`.
+/// - **Windows:** Supported with the `dnd` feature of `iced`. The drag runs
+///   a modal loop, so the application does not update until it is over.
+/// - **Web:** Unsupported; the drag never starts and the [`Task`] produces
+///   [`Outcome::Unsupported`] right away.
+pub fn start_drag(window: window::Id, data: Data) -> Task<Outcome> {
+    task::oneshot(|channel| {
+        crate::Action::Dnd(Action::StartDrag {
+            window,
+            data,
+            channel,
+        })
+    })
+}
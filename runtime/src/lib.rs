@@ -10,6 +10,7 @@
 )]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 pub mod clipboard;
+pub mod dnd;
 pub mod font;
 pub mod keyboard;
 pub mod overlay;
@@ -64,6 +65,9 @@ pub enum Action<T> {
     /// Run a clipboard action.
     Clipboard(clipboard::Action),
 
+    /// Run a drag and drop action.
+    Dnd(dnd::Action),
+
     /// Run a window action.
     Window(window::Action),
 
@@ -91,6 +95,7 @@ impl<T> Action<T> {
             }
             Action::Widget(operation) => Err(Action::Widget(operation)),
             Action::Clipboard(action) => Err(Action::Clipboard(action)),
+            Action::Dnd(action) => Err(Action::Dnd(action)),
             Action::Window(action) => Err(Action::Window(action)),
             Action::System(action) => Err(Action::System(action)),
             Action::Exit => Err(Action::Exit),
@@ -114,6 +119,7 @@ where
             Action::Clipboard(action) => {
                 write!(f, "Action::Clipboard({action:?})")
             }
+            Action::Dnd(action) => write!(f, "Action::Dnd({action:?})"),
             Action::Window(_) => write!(f, "Action::Window"),
             Action::System(action) => write!(f, "Action::System({action:?})"),
             Action::Exit => write!(f, "Action::Exit"),
@@ -1,11 +1,14 @@
 //! Implement your own event loop to drive a user interface.
+use crate::core::dnd;
 use crate::core::event::{self, Event};
 use crate::core::layout;
 use crate::core::mouse;
 use crate::core::renderer;
 use crate::core::widget;
 use crate::core::window;
-use crate::core::{Clipboard, Element, Layout, Rectangle, Shell, Size, Vector};
+use crate::core::{
+    Clipboard, Element, InputMethod, Layout, Rectangle, Shell, Size, Vector,
+};
 use crate::overlay;
 
 /// A set of interactive graphical elements with a specific [`Layout`].
@@ -27,6 +30,7 @@ pub struct UserInterface<'a, Message, Theme, Renderer> {
     state: widget::Tree,
     overlay: Option<layout::Node>,
     bounds: Size,
+    drop_response: Option<dnd::Response>,
 }
 
 impl<'a, Message, Theme, Renderer> UserInterface<'a, Message, Theme, Renderer>
@@ -107,6 +111,7 @@ where
             state,
             overlay: None,
             bounds,
+            drop_response: None,
         }
     }
 
@@ -187,6 +192,9 @@ where
 
         let mut outdated = false;
         let mut redraw_request = None;
+        let mut input_method = InputMethod::Disabled;
+
+        self.drop_response = None;
 
         let mut manual_overlay = ManuallyDrop::new(
             self.root
@@ -219,7 +227,10 @@ where
                     &mut shell,
                 );
 
-                event_statuses.push(shell.event_status());
+                event_statuses.push((
+                    shell.event_status(),
+                    shell.drop_response().cloned(),
+                ));
 
                 match (redraw_request, shell.redraw_request()) {
                     (None, Some(at)) => {
@@ -231,6 +242,8 @@ where
                     _ => {}
                 }
 
+                input_method.merge(shell.input_method());
+
                 if shell.is_layout_invalid() {
                     let _ = ManuallyDrop::into_inner(manual_overlay);
 
@@ -290,7 +303,7 @@ where
 
             (base_cursor, event_statuses)
         } else {
-            (cursor, vec![event::Status::Ignored; events.len()])
+            (cursor, vec![(event::Status::Ignored, None); events.len()])
         };
 
         let viewport = Rectangle::with_size(self.bounds);
@@ -301,8 +314,17 @@ where
             .iter()
             .cloned()
             .zip(overlay_statuses)
-            .map(|(event, overlay_status)| {
+            .map(|(event, (overlay_status, overlay_response))| {
+                let is_drop_hovered = matches!(
+                    event,
+                    Event::Window(window::Event::DataHovered { .. })
+                );
+
                 if matches!(overlay_status, event::Status::Captured) {
+                    if is_drop_hovered {
+                        self.drop_response = overlay_response;
+                    }
+
                     return overlay_status;
                 }
 
@@ -323,6 +345,11 @@ where
                     self.overlay = None;
                 }
 
+                if is_drop_hovered {
+                    self.drop_response = overlay_response
+                        .or_else(|| shell.drop_response().cloned());
+                }
+
                 match (redraw_request, shell.redraw_request()) {
                     (None, Some(at)) => {
                         redraw_request = Some(at);
@@ -333,6 +360,8 @@ where
                     _ => {}
                 }
 
+                input_method.merge(shell.input_method());
+
                 shell.revalidate_layout(|| {
                     self.base = self.root.as_widget().layout(
                         &mut self.state,
@@ -355,12 +384,28 @@ where
             if outdated {
                 State::Outdated
             } else {
-                State::Updated { redraw_request }
+                State::Updated {
+                    redraw_request,
+                    input_method,
+                }
             },
             event_statuses,
         )
     }
 
+    /// Returns the [`dnd::Response`] of the widgets to the latest
+    /// [`DataHovered`] event processed by [`update`], if any.
+    ///
+    /// When no widget responds, the [preferred] format of the offer should be
+    /// accepted.
+    ///
+    /// [`DataHovered`]: window::Event::DataHovered
+    /// [`update`]: Self::update
+    /// [preferred]: dnd::Offer::preferred
+    pub fn drop_response(&self) -> Option<&dnd::Response> {
+        self.drop_response.as_ref()
+    }
+
     /// Draws the [`UserInterface`] with the provided [`Renderer`].
     ///
     /// It returns the current [`mouse::Interaction`]. You should update the
@@ -646,5 +691,7 @@ pub enum State {
     Updated {
         /// The [`window::RedrawRequest`] when a redraw should be performed.
         redraw_request: Option<window::RedrawRequest>,
+        /// The [`InputMethod`] requested by the widgets.
+        input_method: InputMethod,
     },
 }
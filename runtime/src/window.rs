@@ -3,11 +3,12 @@ pub mod screenshot;
 
 pub use screenshot::Screenshot;
 
+use crate::core;
 use crate::core::time::Instant;
 use crate::core::window::{
     Event, Icon, Id, Level, Mode, Settings, UserAttention,
 };
-use crate::core::{Point, Size};
+use crate::core::{Element, Point, Size};
 use crate::futures::event;
 use crate::futures::futures::channel::oneshot;
 use crate::futures::Subscription;
@@ -16,6 +17,7 @@ use crate::task::{self, Task};
 pub use raw_window_handle;
 
 use raw_window_handle::WindowHandle;
+use std::any::Any;
 
 /// An operation to be performed on some window.
 #[allow(missing_debug_implementations)]
@@ -148,6 +150,29 @@ pub enum Action {
     /// Screenshot the viewport of the window.
     Screenshot(Id, oneshot::Sender<Screenshot>),
 
+    /// Redraw all the windows.
+    RedrawAll,
+
+    /// Renders an element offscreen with the renderer and theme of the
+    /// window.
+    Render {
+        /// The window providing the theme.
+        id: Id,
+        /// The [`View`] producing the element, boxed as [`Any`] since it is
+        /// generic over the theme and the renderer of the application.
+        view: Box<dyn Any + Send>,
+        /// The logical size of the rendering.
+        size: Size,
+        /// The scale factor of the rendering.
+        scale_factor: f64,
+        /// The channel to send back the rendering.
+        channel: oneshot::Sender<Result<Screenshot, screenshot::RenderError>>,
+    },
+
+    /// Get the statistics of the image caches of the renderer of the
+    /// window.
+    GetImageCacheStats(Id, oneshot::Sender<core::image::Stats>),
+
     /// Enables mouse passthrough for the given window.
     ///
     /// This disables mouse events for the window and passes mouse events
@@ -412,6 +437,17 @@ where
     })
 }
 
+/// Gets the [`Stats`] of the image caches of the renderer of the window.
+///
+/// They can be used to tune the image cache budget of the application.
+///
+/// [`Stats`]: core::image::Stats
+pub fn get_image_cache_stats(id: Id) -> Task<core::image::Stats> {
+    task::oneshot(move |channel| {
+        crate::Action::Window(Action::GetImageCacheStats(id, channel))
+    })
+}
+
 /// Captures a [`Screenshot`] from the window.
 pub fn screenshot(id: Id) -> Task<Screenshot> {
     task::oneshot(move |channel| {
@@ -419,6 +455,51 @@ pub fn screenshot(id: Id) -> Task<Screenshot> {
     })
 }
 
+/// A closure producing an element to be rendered offscreen.
+pub type View<Theme, Renderer> =
+    Box<dyn FnOnce() -> Element<'static, (), Theme, Renderer> + Send>;
+
+/// Renders the element produced by the given closure offscreen, with the
+/// renderer and theme of the window.
+///
+/// Unlike [`screenshot`], the element does not need to be displayed. It is
+/// laid out with the given logical [`Size`] and rendered with the given
+/// scale factor on a transparent background.
+///
+/// The resulting [`Screenshot`] can be turned into an [`image::Handle`] to
+/// show thumbnails, drag previews, print previews...
+///
+/// The [`Task`] fails with a [`RenderError`] if the window does not exist
+/// or the element does not match the theme and renderer of the application.
+///
+/// [`image::Handle`]: crate::core::image::Handle
+/// [`RenderError`]: screenshot::RenderError
+pub fn render<Message, Theme, Renderer>(
+    id: Id,
+    size: Size,
+    scale_factor: f64,
+    view: impl FnOnce() -> Element<'static, Message, Theme, Renderer>
+        + Send
+        + 'static,
+) -> Task<Result<Screenshot, screenshot::RenderError>>
+where
+    Message: 'static,
+    Theme: 'static,
+    Renderer: core::Renderer + 'static,
+{
+    let view: View<Theme, Renderer> = Box::new(move || view().map(|_| ()));
+
+    task::oneshot(move |channel| {
+        crate::Action::Window(Action::Render {
+            id,
+            view: Box::new(view),
+            size,
+            scale_factor,
+            channel,
+        })
+    })
+}
+
 /// Enables mouse passthrough for the given window.
 ///
 /// This disables mouse events for the window and passes mouse events
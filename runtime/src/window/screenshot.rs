@@ -1,4 +1,5 @@
 //! Take screenshots of a window.
+use crate::core::image;
 use crate::core::{Rectangle, Size};
 
 use bytes::Bytes;
@@ -96,6 +97,16 @@ impl From<Screenshot> for Bytes {
     }
 }
 
+impl From<Screenshot> for image::Handle {
+    fn from(screenshot: Screenshot) -> Self {
+        image::Handle::from_rgba(
+            screenshot.size.width,
+            screenshot.size.height,
+            screenshot.bytes,
+        )
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 /// Errors that can occur when cropping a [`Screenshot`].
 pub enum CropError {
@@ -106,3 +117,59 @@ pub enum CropError {
     /// The cropped region's size is zero.
     Zero,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+/// Errors that can occur when rendering an element offscreen.
+pub enum RenderError {
+    #[error("The window to render with does not exist.")]
+    /// The window providing the theme does not exist.
+    WindowNotFound,
+    #[error(
+        "The element does not match the theme and renderer of the application."
+    )]
+    /// The element is not generic over the theme and renderer of the
+    /// application.
+    Mismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_handle_keeps_size_and_pixels() {
+        let screenshot =
+            Screenshot::new(vec![255; 2 * 3 * 4], Size::new(2, 3), 2.0);
+
+        let image::Handle::Rgba {
+            width,
+            height,
+            pixels,
+            ..
+        } = image::Handle::from(screenshot.clone())
+        else {
+            panic!("Screenshot should become an RGBA handle");
+        };
+
+        assert_eq!((width, height), (2, 3));
+        assert_eq!(pixels, screenshot.bytes);
+    }
+
+    #[test]
+    fn crop_keeps_region_rows() {
+        let bytes: Vec<u8> = (0..4 * 2 * 2).collect();
+        let screenshot = Screenshot::new(bytes, Size::new(2, 2), 1.0);
+
+        let cropped = screenshot
+            .crop(Rectangle {
+                x: 1,
+                y: 1,
+                width: 1,
+                height: 1,
+            })
+            .expect("Crop screenshot");
+
+        assert_eq!(cropped.size, Size::new(1, 1));
+        assert_eq!(cropped.bytes.as_ref(), &[12, 13, 14, 15]);
+    }
+}
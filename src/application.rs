@@ -31,6 +31,7 @@
 //! }
 //! ```
 use crate::program::{self, Program};
+use crate::tray::Tray;
 use crate::window;
 use crate::{
     Element, Executor, Font, Result, Settings, Size, Subscription, Task,
@@ -182,6 +183,17 @@ impl<P: Program> Application<P> {
         Self { settings, ..self }
     }
 
+    /// Sets the [`Settings::image_cache_budget`] of the [`Application`].
+    pub fn image_cache_budget(self, image_cache_budget: usize) -> Self {
+        Self {
+            settings: Settings {
+                image_cache_budget: Some(image_cache_budget),
+                ..self.settings
+            },
+            ..self
+        }
+    }
+
     /// Sets the [`Settings::antialiasing`] of the [`Application`].
     pub fn antialiasing(self, antialiasing: bool) -> Self {
         Self {
@@ -379,6 +391,20 @@ impl<P: Program> Application<P> {
         }
     }
 
+    /// Sets the [`Tray`] logic of the [`Application`].
+    pub fn tray(
+        self,
+        f: impl Fn(&P::State) -> Option<Tray<P::Message>>,
+    ) -> Application<
+        impl Program<State = P::State, Message = P::Message, Theme = P::Theme>,
+    > {
+        Application {
+            raw: program::with_tray(self.raw, f),
+            settings: self.settings,
+            window: self.window,
+        }
+    }
+
     /// Sets the executor of the [`Application`].
     pub fn executor<E>(
         self,
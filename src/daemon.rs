@@ -1,6 +1,7 @@
 //! Create and run daemons that run in the background.
 use crate::application;
 use crate::program::{self, Program};
+use crate::tray::Tray;
 use crate::window;
 use crate::{Element, Executor, Font, Result, Settings, Subscription, Task};
 
@@ -129,6 +130,17 @@ impl<P: Program> Daemon<P> {
         Self { settings, ..self }
     }
 
+    /// Sets the [`Settings::image_cache_budget`] of the [`Daemon`].
+    pub fn image_cache_budget(self, image_cache_budget: usize) -> Self {
+        Self {
+            settings: Settings {
+                image_cache_budget: Some(image_cache_budget),
+                ..self.settings
+            },
+            ..self
+        }
+    }
+
     /// Sets the [`Settings::antialiasing`] of the [`Daemon`].
     pub fn antialiasing(self, antialiasing: bool) -> Self {
         Self {
@@ -224,6 +236,24 @@ impl<P: Program> Daemon<P> {
         }
     }
 
+    /// Sets the [`Tray`] logic of the [`Daemon`].
+    ///
+    /// The [`Tray`] is rebuilt after every update, and its interactions
+    /// produce messages that are handled by the update logic of the
+    /// [`Daemon`]. The system tray is only supported on Linux with the
+    /// `tray` feature enabled.
+    pub fn tray(
+        self,
+        f: impl Fn(&P::State) -> Option<Tray<P::Message>>,
+    ) -> Daemon<
+        impl Program<State = P::State, Message = P::Message, Theme = P::Theme>,
+    > {
+        Daemon {
+            raw: program::with_tray(self.raw, f),
+            settings: self.settings,
+        }
+    }
+
     /// Sets the executor of the [`Daemon`].
     pub fn executor<E>(
         self,
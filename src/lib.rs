@@ -524,11 +524,21 @@ pub mod task {
 
 pub mod clipboard {
     //! Access the clipboard.
+    pub use crate::core::clipboard::{Content, Format, Kind};
     pub use crate::runtime::clipboard::{
-        read, read_primary, write, write_primary,
+        changes, read, read_content, read_formats, read_html, read_image,
+        read_mime, read_primary, read_primary_content, write, write_content,
+        write_html, write_image, write_mime, write_primary,
+        write_primary_content,
     };
 }
 
+pub mod dnd {
+    //! Drag and drop data between applications.
+    pub use crate::core::dnd::{Data, Offer, Outcome, Payload, Response};
+    pub use crate::runtime::dnd::start_drag;
+}
+
 pub mod executor {
     //! Choose your preferred executor to power your application.
     pub use iced_futures::Executor;
@@ -604,6 +614,11 @@ pub mod touch {
     pub use crate::core::touch::{Event, Finger};
 }
 
+pub mod tray {
+    //! Show an icon with a menu in the system tray.
+    pub use crate::core::tray::{Icon, Item, Menu, Tray};
+}
+
 #[allow(hidden_glob_reexports)]
 pub mod widget {
     //! Use the built-in widgets or create your own.
@@ -1,4 +1,5 @@
 use crate::core::text;
+use crate::core::tray::Tray;
 use crate::graphics::compositor;
 use crate::shell;
 use crate::window;
@@ -62,6 +63,10 @@ pub trait Program: Sized {
         1.0
     }
 
+    fn tray(&self, _state: &Self::State) -> Option<Tray<Self::Message>> {
+        None
+    }
+
     /// Runs the [`Program`].
     ///
     /// The state of the [`Program`] must implement [`Default`].
@@ -160,6 +165,10 @@ pub trait Program: Sized {
             fn scale_factor(&self, window: window::Id) -> f64 {
                 self.program.scale_factor(&self.state, window)
             }
+
+            fn tray(&self) -> Option<Tray<Self::Message>> {
+                self.program.tray(&self.state)
+            }
         }
 
         #[allow(clippy::needless_update)]
@@ -171,6 +180,7 @@ pub trait Program: Sized {
             } else {
                 None
             },
+            image_cache_budget: settings.image_cache_budget,
             ..crate::graphics::Settings::default()
         };
 
@@ -184,6 +194,7 @@ pub trait Program: Sized {
                 default_font: settings.default_font,
                 default_text_size: settings.default_text_size,
                 antialiasing: settings.antialiasing,
+                image_cache_budget: settings.image_cache_budget,
             }
             .into(),
             renderer_settings,
@@ -259,6 +270,10 @@ pub fn with_title<P: Program>(
         fn scale_factor(&self, state: &Self::State, window: window::Id) -> f64 {
             self.program.scale_factor(state, window)
         }
+
+        fn tray(&self, state: &Self::State) -> Option<Tray<Self::Message>> {
+            self.program.tray(state)
+        }
     }
 
     WithTitle { program, title }
@@ -329,6 +344,10 @@ pub fn with_subscription<P: Program>(
         fn scale_factor(&self, state: &Self::State, window: window::Id) -> f64 {
             self.program.scale_factor(state, window)
         }
+
+        fn tray(&self, state: &Self::State) -> Option<Tray<Self::Message>> {
+            self.program.tray(state)
+        }
     }
 
     WithSubscription {
@@ -402,6 +421,10 @@ pub fn with_theme<P: Program>(
         fn scale_factor(&self, state: &Self::State, window: window::Id) -> f64 {
             self.program.scale_factor(state, window)
         }
+
+        fn tray(&self, state: &Self::State) -> Option<Tray<Self::Message>> {
+            self.program.tray(state)
+        }
     }
 
     WithTheme { program, theme: f }
@@ -472,6 +495,10 @@ pub fn with_style<P: Program>(
         fn scale_factor(&self, state: &Self::State, window: window::Id) -> f64 {
             self.program.scale_factor(state, window)
         }
+
+        fn tray(&self, state: &Self::State) -> Option<Tray<Self::Message>> {
+            self.program.tray(state)
+        }
     }
 
     WithStyle { program, style: f }
@@ -542,6 +569,10 @@ pub fn with_scale_factor<P: Program>(
         fn scale_factor(&self, state: &Self::State, window: window::Id) -> f64 {
             (self.scale_factor)(state, window)
         }
+
+        fn tray(&self, state: &Self::State) -> Option<Tray<Self::Message>> {
+            self.program.tray(state)
+        }
     }
 
     WithScaleFactor {
@@ -550,6 +581,80 @@ pub fn with_scale_factor<P: Program>(
     }
 }
 
+pub fn with_tray<P: Program>(
+    program: P,
+    f: impl Fn(&P::State) -> Option<Tray<P::Message>>,
+) -> impl Program<State = P::State, Message = P::Message, Theme = P::Theme> {
+    struct WithTray<P, F> {
+        program: P,
+        tray: F,
+    }
+
+    impl<P: Program, F> Program for WithTray<P, F>
+    where
+        F: Fn(&P::State) -> Option<Tray<P::Message>>,
+    {
+        type State = P::State;
+        type Message = P::Message;
+        type Theme = P::Theme;
+        type Renderer = P::Renderer;
+        type Executor = P::Executor;
+
+        fn tray(&self, state: &Self::State) -> Option<Tray<Self::Message>> {
+            (self.tray)(state)
+        }
+
+        fn title(&self, state: &Self::State, window: window::Id) -> String {
+            self.program.title(state, window)
+        }
+
+        fn update(
+            &self,
+            state: &mut Self::State,
+            message: Self::Message,
+        ) -> Task<Self::Message> {
+            self.program.update(state, message)
+        }
+
+        fn view<'a>(
+            &self,
+            state: &'a Self::State,
+            window: window::Id,
+        ) -> Element<'a, Self::Message, Self::Theme, Self::Renderer> {
+            self.program.view(state, window)
+        }
+
+        fn subscription(
+            &self,
+            state: &Self::State,
+        ) -> Subscription<Self::Message> {
+            self.program.subscription(state)
+        }
+
+        fn theme(
+            &self,
+            state: &Self::State,
+            window: window::Id,
+        ) -> Self::Theme {
+            self.program.theme(state, window)
+        }
+
+        fn style(
+            &self,
+            state: &Self::State,
+            theme: &Self::Theme,
+        ) -> Appearance {
+            self.program.style(state, theme)
+        }
+
+        fn scale_factor(&self, state: &Self::State, window: window::Id) -> f64 {
+            self.program.scale_factor(state, window)
+        }
+    }
+
+    WithTray { program, tray: f }
+}
+
 pub fn with_executor<P: Program, E: Executor>(
     program: P,
 ) -> impl Program<State = P::State, Message = P::Message, Theme = P::Theme> {
@@ -616,6 +721,10 @@ pub fn with_executor<P: Program, E: Executor>(
         fn scale_factor(&self, state: &Self::State, window: window::Id) -> f64 {
             self.program.scale_factor(state, window)
         }
+
+        fn tray(&self, state: &Self::State) -> Option<Tray<Self::Message>> {
+            self.program.tray(state)
+        }
     }
 
     WithExecutor {
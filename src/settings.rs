@@ -35,6 +35,13 @@ pub struct Settings {
     ///
     /// [`Canvas`]: crate::widget::Canvas
     pub antialiasing: bool,
+
+    /// The maximum amount of memory, in bytes, that the renderer may use to
+    /// cache images before evicting the least recently drawn ones.
+    ///
+    /// By default, it is `None`; and images are evicted as soon as they
+    /// stop being drawn.
+    pub image_cache_budget: Option<usize>,
 }
 
 impl Default for Settings {
@@ -45,6 +52,7 @@ impl Default for Settings {
             default_font: Font::default(),
             default_text_size: Pixels(16.0),
             antialiasing: false,
+            image_cache_budget: None,
         }
     }
 }
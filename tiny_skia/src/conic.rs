@@ -0,0 +1,308 @@
+//! Paint with conic gradients, which `tiny-skia` does not support, by
+//! rasterizing them into pixmaps.
+use crate::core::gradient::ColorStop;
+use crate::core::{Color, Point, Rectangle};
+use crate::engine::into_color;
+use crate::graphics::gradient;
+
+/// A cache of rasterized [`gradient::Conic`]s.
+///
+/// Entries that are not used between two calls to [`Cache::trim`] are
+/// dropped.
+#[derive(Debug, Default)]
+pub struct Cache {
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    gradient: gradient::Conic,
+    transform: tiny_skia::Transform,
+    bounds: Rectangle,
+    pattern: Option<Pattern>,
+    is_used: bool,
+}
+
+/// A [`gradient::Conic`] rasterized into a pixmap, alongside the transform
+/// that maps it back into the space of the path it paints.
+#[derive(Debug)]
+struct Pattern {
+    pixmap: tiny_skia::Pixmap,
+    transform: tiny_skia::Transform,
+}
+
+impl Cache {
+    /// Returns the `paint` with its shader replaced by the given
+    /// [`gradient::Conic`], rasterized within the given physical bounds
+    /// using the `transform` of the path that will be painted.
+    ///
+    /// The paint is transparent if the gradient cannot be rasterized.
+    pub fn paint<'a>(
+        &'a mut self,
+        gradient: &gradient::Conic,
+        transform: tiny_skia::Transform,
+        bounds: Rectangle,
+        paint: &tiny_skia::Paint<'_>,
+    ) -> tiny_skia::Paint<'a> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| {
+                entry.gradient == *gradient
+                    && entry.transform == transform
+                    && entry.bounds == bounds
+            })
+            .unwrap_or_else(|| {
+                self.entries.push(Entry {
+                    gradient: *gradient,
+                    transform,
+                    bounds,
+                    pattern: rasterize(gradient, transform, bounds),
+                    is_used: false,
+                });
+
+                self.entries.len() - 1
+            });
+
+        let entry = &mut self.entries[index];
+        entry.is_used = true;
+
+        let shader = match &entry.pattern {
+            Some(pattern) => tiny_skia::Pattern::new(
+                pattern.pixmap.as_ref(),
+                tiny_skia::SpreadMode::Pad,
+                tiny_skia::FilterQuality::Nearest,
+                1.0,
+                pattern.transform,
+            ),
+            None => {
+                tiny_skia::Shader::SolidColor(tiny_skia::Color::TRANSPARENT)
+            }
+        };
+
+        tiny_skia::Paint {
+            shader,
+            blend_mode: paint.blend_mode,
+            anti_alias: paint.anti_alias,
+            force_hq_pipeline: paint.force_hq_pipeline,
+        }
+    }
+
+    /// Drops the entries that were not used since the last trim.
+    pub fn trim(&mut self) {
+        self.entries.retain(|entry| entry.is_used);
+
+        for entry in &mut self.entries {
+            entry.is_used = false;
+        }
+    }
+}
+
+/// Rasterizes the [`gradient::Conic`] within the given physical bounds,
+/// using the `transform` of the path that will be painted with it.
+fn rasterize(
+    gradient: &gradient::Conic,
+    transform: tiny_skia::Transform,
+    bounds: Rectangle,
+) -> Option<Pattern> {
+    let inverse = transform.invert()?;
+
+    let x = bounds.x.floor();
+    let y = bounds.y.floor();
+    let width = (bounds.x + bounds.width).ceil() - x;
+    let height = (bounds.y + bounds.height).ceil() - y;
+
+    let size = tiny_skia::IntSize::from_wh(width as u32, height as u32)?;
+
+    let colors: Vec<tiny_skia::PremultipliedColorU8> = (0..size.height())
+        .flat_map(|row| (0..size.width()).map(move |column| (column, row)))
+        .map(|(column, row)| {
+            let mut point = tiny_skia::Point {
+                x: x + column as f32 + 0.5,
+                y: y + row as f32 + 0.5,
+            };
+
+            inverse.map_point(&mut point);
+
+            let offset = gradient.offset(Point::new(point.x, point.y));
+
+            into_color(sample(&gradient.stops, offset))
+                .to_color_u8()
+                .premultiply()
+        })
+        .collect();
+
+    let pixmap = tiny_skia::Pixmap::from_vec(bytemuck::cast_vec(colors), size)?;
+
+    Some(Pattern {
+        pixmap,
+        transform: inverse.pre_translate(x, y),
+    })
+}
+
+/// Returns the color of the stops at the given offset, interpolating
+/// linearly between them.
+fn sample(stops: &[Option<ColorStop>; 8], offset: f32) -> Color {
+    let mut stops = stops.iter().flatten();
+
+    let Some(mut previous) = stops.next() else {
+        return Color::BLACK;
+    };
+
+    if offset <= previous.offset {
+        return previous.color;
+    }
+
+    for stop in stops {
+        if offset <= stop.offset {
+            let range = stop.offset - previous.offset;
+
+            if range <= f32::EPSILON {
+                return stop.color;
+            }
+
+            let t = (offset - previous.offset) / range;
+            let mix = |a: f32, b: f32| a + (b - a) * t;
+
+            return Color {
+                r: mix(previous.color.r, stop.color.r),
+                g: mix(previous.color.g, stop.color.g),
+                b: mix(previous.color.b, stop.color.b),
+                a: mix(previous.color.a, stop.color.a),
+            };
+        }
+
+        previous = stop;
+    }
+
+    previous.color
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::Size;
+
+    fn gradient() -> gradient::Conic {
+        gradient::Conic::new(Point::new(2.0, 2.0), 0.0)
+            .add_stop(0.0, Color::BLACK)
+            .add_stop(0.5, Color::WHITE)
+            .add_stop(1.0, Color::BLACK)
+    }
+
+    fn bounds() -> Rectangle {
+        Rectangle::new(Point::ORIGIN, Size::new(4.0, 4.0))
+    }
+
+    fn paint(cache: &mut Cache, bounds: Rectangle) {
+        let _ = cache.paint(
+            &gradient(),
+            tiny_skia::Transform::identity(),
+            bounds,
+            &tiny_skia::Paint::default(),
+        );
+    }
+
+    #[test]
+    fn sample_interpolates_between_stops() {
+        let stops = gradient().stops;
+
+        assert_eq!(sample(&stops, 0.0), Color::BLACK);
+        assert_eq!(sample(&stops, 0.5), Color::WHITE);
+        assert_eq!(sample(&stops, 0.25), Color::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!(sample(&stops, 0.75), Color::from_rgb(0.5, 0.5, 0.5));
+    }
+
+    #[test]
+    fn sample_clamps_to_outer_stops() {
+        let stops = gradient::Conic::new(Point::ORIGIN, 0.0)
+            .add_stop(0.25, Color::WHITE)
+            .add_stop(0.75, Color::BLACK)
+            .stops;
+
+        assert_eq!(sample(&stops, 0.0), Color::WHITE);
+        assert_eq!(sample(&stops, 1.0), Color::BLACK);
+    }
+
+    #[test]
+    fn sample_without_stops_is_black() {
+        assert_eq!(sample(&[None; 8], 0.5), Color::BLACK);
+    }
+
+    #[test]
+    fn rasterize_covers_pixel_aligned_bounds() {
+        let pattern = rasterize(
+            &gradient(),
+            tiny_skia::Transform::identity(),
+            Rectangle::new(Point::new(1.5, 1.5), Size::new(2.0, 2.0)),
+        )
+        .expect("Rasterize gradient");
+
+        assert_eq!(pattern.pixmap.width(), 3);
+        assert_eq!(pattern.pixmap.height(), 3);
+        assert_eq!(
+            pattern.transform,
+            tiny_skia::Transform::from_translate(1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn rasterize_samples_around_center() {
+        let pattern =
+            rasterize(&gradient(), tiny_skia::Transform::identity(), bounds())
+                .expect("Rasterize gradient");
+
+        let pixel = |x, y| {
+            let color = pattern.pixmap.pixel(x, y).expect("Read pixel");
+
+            (color.red(), color.green(), color.blue())
+        };
+
+        // Right of the center the offset is close to 0, and left of it
+        // close to 0.5
+        assert!(pixel(3, 2).0 < 64);
+        assert!(pixel(0, 2).0 > 192);
+    }
+
+    #[test]
+    fn rasterize_rejects_empty_bounds() {
+        assert!(rasterize(
+            &gradient(),
+            tiny_skia::Transform::identity(),
+            Rectangle::new(Point::ORIGIN, Size::ZERO),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn cache_reuses_entries() {
+        let mut cache = Cache::default();
+
+        paint(&mut cache, bounds());
+        paint(&mut cache, bounds());
+
+        assert_eq!(cache.entries.len(), 1);
+
+        paint(
+            &mut cache,
+            Rectangle::new(Point::ORIGIN, Size::new(2.0, 2.0)),
+        );
+
+        assert_eq!(cache.entries.len(), 2);
+    }
+
+    #[test]
+    fn cache_trims_unused_entries() {
+        let mut cache = Cache::default();
+
+        paint(&mut cache, bounds());
+        cache.trim();
+
+        assert_eq!(cache.entries.len(), 1);
+
+        cache.trim();
+
+        assert!(cache.entries.is_empty());
+    }
+}
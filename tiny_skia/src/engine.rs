@@ -1,10 +1,13 @@
+use crate::conic;
+use crate::core::gradient::ColorStop;
 use crate::core::renderer::Quad;
 use crate::core::{
-    Background, Color, Gradient, Rectangle, Size, Transformation, Vector,
+    Background, Color, Point, Rectangle, Size, Transformation, Vector,
 };
+use crate::graphics::gradient::{self, Gradient};
 use crate::graphics::{Image, Text};
 use crate::text;
-use crate::Primitive;
+use crate::{Layer, Primitive};
 
 #[derive(Debug)]
 pub struct Engine {
@@ -14,6 +17,9 @@ pub struct Engine {
     pub(crate) raster_pipeline: crate::raster::Pipeline,
     #[cfg(feature = "svg")]
     pub(crate) vector_pipeline: crate::vector::Pipeline,
+    #[cfg(any(feature = "image", feature = "svg"))]
+    pub(crate) image_cache_budget: Option<usize>,
+    conics: conic::Cache,
 }
 
 impl Engine {
@@ -24,6 +30,77 @@ impl Engine {
             raster_pipeline: crate::raster::Pipeline::new(),
             #[cfg(feature = "svg")]
             vector_pipeline: crate::vector::Pipeline::new(),
+            #[cfg(any(feature = "image", feature = "svg"))]
+            image_cache_budget: None,
+            conics: conic::Cache::default(),
+        }
+    }
+
+    pub fn draw_layer(
+        &mut self,
+        layer: &Layer,
+        scale_factor: f32,
+        pixels: &mut tiny_skia::PixmapMut<'_>,
+        clip_mask: &mut tiny_skia::Mask,
+        clip_bounds: Rectangle,
+    ) {
+        adjust_clip_mask(clip_mask, clip_bounds);
+
+        for (quad, background) in &layer.quads {
+            self.draw_quad(
+                quad,
+                background,
+                Transformation::scale(scale_factor),
+                pixels,
+                clip_mask,
+                clip_bounds,
+            );
+        }
+
+        for group in &layer.primitives {
+            let Some(new_clip_bounds) =
+                (group.clip_bounds() * scale_factor).intersection(&clip_bounds)
+            else {
+                continue;
+            };
+
+            adjust_clip_mask(clip_mask, new_clip_bounds);
+
+            for primitive in group.as_slice() {
+                self.draw_primitive(
+                    primitive,
+                    group.transformation()
+                        * Transformation::scale(scale_factor),
+                    pixels,
+                    clip_mask,
+                    clip_bounds,
+                );
+            }
+
+            adjust_clip_mask(clip_mask, clip_bounds);
+        }
+
+        for image in &layer.images {
+            self.draw_image(
+                image,
+                Transformation::scale(scale_factor),
+                pixels,
+                clip_mask,
+                clip_bounds,
+            );
+        }
+
+        for group in &layer.text {
+            for text in group.as_slice() {
+                self.draw_text(
+                    text,
+                    group.transformation()
+                        * Transformation::scale(scale_factor),
+                    pixels,
+                    clip_mask,
+                    clip_bounds,
+                );
+            }
         }
     }
 
@@ -153,62 +230,67 @@ impl Engine {
             }
         }
 
-        pixels.fill_path(
-            &path,
-            &tiny_skia::Paint {
-                shader: match background {
-                    Background::Color(color) => {
-                        tiny_skia::Shader::SolidColor(into_color(*color))
-                    }
-                    Background::Gradient(Gradient::Linear(linear)) => {
-                        let (start, end) =
-                            linear.angle.to_distance(&quad.bounds);
-
-                        let stops: Vec<tiny_skia::GradientStop> = linear
-                            .stops
-                            .into_iter()
-                            .flatten()
-                            .map(|stop| {
-                                tiny_skia::GradientStop::new(
-                                    stop.offset,
-                                    tiny_skia::Color::from_rgba(
-                                        stop.color.b,
-                                        stop.color.g,
-                                        stop.color.r,
-                                        stop.color.a,
-                                    )
-                                    .expect("Create color"),
-                                )
-                            })
-                            .collect();
-
-                        tiny_skia::LinearGradient::new(
-                            tiny_skia::Point {
-                                x: start.x,
-                                y: start.y,
-                            },
-                            tiny_skia::Point { x: end.x, y: end.y },
-                            if stops.is_empty() {
-                                vec![tiny_skia::GradientStop::new(
-                                    0.0,
-                                    tiny_skia::Color::BLACK,
-                                )]
-                            } else {
-                                stops
-                            },
-                            tiny_skia::SpreadMode::Pad,
-                            tiny_skia::Transform::identity(),
-                        )
-                        .expect("Create linear gradient")
+        match background {
+            Background::Color(color) => {
+                pixels.fill_path(
+                    &path,
+                    &tiny_skia::Paint {
+                        shader: tiny_skia::Shader::SolidColor(into_color(
+                            *color,
+                        )),
+                        anti_alias: true,
+                        ..tiny_skia::Paint::default()
+                    },
+                    tiny_skia::FillRule::EvenOdd,
+                    transform,
+                    clip_mask,
+                );
+            }
+            Background::Gradient(gradient) => {
+                let gradient = gradient::into_absolute(gradient, quad.bounds);
+
+                let paint = tiny_skia::Paint {
+                    shader: into_shader(&gradient),
+                    anti_alias: true,
+                    ..tiny_skia::Paint::default()
+                };
+
+                // `tiny-skia` has no conic gradients, so we rasterize them ourselves
+                let paint = match (
+                    &gradient,
+                    clip_bounds.intersection(&physical_bounds),
+                ) {
+                    (Gradient::Conic(conic), Some(bounds)) => {
+                        self.conics.paint(conic, transform, bounds, &paint)
                     }
-                },
-                anti_alias: true,
-                ..tiny_skia::Paint::default()
-            },
-            tiny_skia::FillRule::EvenOdd,
-            transform,
-            clip_mask,
-        );
+                    _ => paint,
+                };
+
+                pixels.fill_path(
+                    &path,
+                    &paint,
+                    tiny_skia::FillRule::EvenOdd,
+                    transform,
+                    clip_mask,
+                );
+            }
+            Background::Image(image) => {
+                #[cfg(feature = "image")]
+                self.raster_pipeline.draw_background(
+                    image,
+                    quad.bounds,
+                    &path,
+                    pixels,
+                    transform,
+                    clip_mask,
+                );
+
+                #[cfg(not(feature = "image"))]
+                log::warn!(
+                    "Unsupported background in `iced_tiny_skia`: {image:?}",
+                );
+            }
+        }
 
         if border_width > 0.0 {
             // Border path is offset by half the border width
@@ -475,7 +557,12 @@ impl Engine {
         layer_bounds: Rectangle,
     ) {
         match primitive {
-            Primitive::Fill { path, paint, rule } => {
+            Primitive::Fill {
+                path,
+                paint,
+                rule,
+                conic,
+            } => {
                 let physical_bounds = {
                     let bounds = path.bounds();
 
@@ -496,9 +583,18 @@ impl Engine {
                 let clip_mask =
                     (physical_bounds != clip_bounds).then_some(clip_mask as &_);
 
+                let pattern = conic.as_ref().map(|conic| {
+                    self.conics.paint(
+                        conic,
+                        into_transform(transformation),
+                        clip_bounds,
+                        paint,
+                    )
+                });
+
                 pixels.fill_path(
                     path,
-                    paint,
+                    pattern.as_ref().unwrap_or(paint),
                     *rule,
                     into_transform(transformation),
                     clip_mask,
@@ -508,6 +604,7 @@ impl Engine {
                 path,
                 paint,
                 stroke,
+                conic,
             } => {
                 let physical_bounds = {
                     let bounds = path.bounds();
@@ -529,9 +626,18 @@ impl Engine {
                 let clip_mask =
                     (physical_bounds != clip_bounds).then_some(clip_mask as &_);
 
+                let pattern = conic.as_ref().map(|conic| {
+                    self.conics.paint(
+                        conic,
+                        into_transform(transformation),
+                        clip_bounds,
+                        paint,
+                    )
+                });
+
                 pixels.stroke_path(
                     path,
-                    paint,
+                    pattern.as_ref().unwrap_or(paint),
                     stroke,
                     into_transform(transformation),
                     clip_mask,
@@ -569,15 +675,8 @@ impl Engine {
                     center.y,
                 );
 
-                self.raster_pipeline.draw(
-                    &raster.handle,
-                    raster.filter_method,
-                    *bounds,
-                    raster.opacity,
-                    _pixels,
-                    transform,
-                    clip_mask,
-                );
+                self.raster_pipeline
+                    .draw(raster, *bounds, _pixels, transform, clip_mask);
             }
             #[cfg(feature = "svg")]
             Image::Vector(svg, bounds) => {
@@ -602,6 +701,7 @@ impl Engine {
                 self.vector_pipeline.draw(
                     &svg.handle,
                     svg.color,
+                    &svg.classes,
                     physical_bounds,
                     svg.opacity,
                     _pixels,
@@ -624,14 +724,46 @@ impl Engine {
         }
     }
 
+    #[cfg(any(feature = "image", feature = "svg"))]
+    pub fn image_cache_stats(&self) -> crate::graphics::image::Stats {
+        let stats = crate::graphics::image::Stats::default();
+
+        #[cfg(feature = "image")]
+        let stats = stats + self.raster_pipeline.stats();
+
+        #[cfg(feature = "svg")]
+        let stats = stats + self.vector_pipeline.stats();
+
+        stats
+    }
+
     pub fn trim(&mut self) {
         self.text_pipeline.trim_cache();
+        self.conics.trim();
 
+        // The budget is shared, so each cache is trimmed to fit next to
+        // the memory used by the rest
         #[cfg(feature = "image")]
-        self.raster_pipeline.trim_cache();
+        {
+            let rest = self.image_cache_stats().memory
+                - self.raster_pipeline.stats().memory;
+
+            self.raster_pipeline.trim_cache(
+                self.image_cache_budget
+                    .map(|budget| budget.saturating_sub(rest)),
+            );
+        }
 
         #[cfg(feature = "svg")]
-        self.vector_pipeline.trim_cache();
+        {
+            let rest = self.image_cache_stats().memory
+                - self.vector_pipeline.stats().memory;
+
+            self.vector_pipeline.trim_cache(
+                self.image_cache_budget
+                    .map(|budget| budget.saturating_sub(rest)),
+            );
+        }
     }
 }
 
@@ -640,6 +772,65 @@ pub fn into_color(color: Color) -> tiny_skia::Color {
         .expect("Convert color from iced to tiny_skia")
 }
 
+pub fn into_shader(gradient: &Gradient) -> tiny_skia::Shader<'static> {
+    match gradient {
+        Gradient::Linear(linear) => tiny_skia::LinearGradient::new(
+            tiny_skia::Point {
+                x: linear.start.x,
+                y: linear.start.y,
+            },
+            tiny_skia::Point {
+                x: linear.end.x,
+                y: linear.end.y,
+            },
+            into_gradient_stops(&linear.stops),
+            tiny_skia::SpreadMode::Pad,
+            tiny_skia::Transform::identity(),
+        )
+        .expect("Create linear gradient"),
+        Gradient::Radial(radial) => {
+            let center = tiny_skia::Point {
+                x: radial.center.x,
+                y: radial.center.y,
+            };
+
+            tiny_skia::RadialGradient::new(
+                center,
+                center,
+                radial.radius,
+                into_gradient_stops(&radial.stops),
+                tiny_skia::SpreadMode::Pad,
+                tiny_skia::Transform::identity(),
+            )
+            .unwrap_or(tiny_skia::Shader::SolidColor(
+                tiny_skia::Color::TRANSPARENT,
+            ))
+        }
+        // Conic gradients are rasterized by the `Engine` when drawn
+        Gradient::Conic(_) => {
+            tiny_skia::Shader::SolidColor(tiny_skia::Color::TRANSPARENT)
+        }
+    }
+}
+
+fn into_gradient_stops(
+    stops: &[Option<ColorStop>; 8],
+) -> Vec<tiny_skia::GradientStop> {
+    let stops: Vec<_> = stops
+        .iter()
+        .flatten()
+        .map(|stop| {
+            tiny_skia::GradientStop::new(stop.offset, into_color(stop.color))
+        })
+        .collect();
+
+    if stops.is_empty() {
+        vec![tiny_skia::GradientStop::new(0.0, tiny_skia::Color::BLACK)]
+    } else {
+        stops
+    }
+}
+
 fn into_transform(transformation: Transformation) -> tiny_skia::Transform {
     let translation = transformation.translation();
 
@@ -846,3 +1037,131 @@ pub fn adjust_clip_mask(clip_mask: &mut tiny_skia::Mask, bounds: Rectangle) {
         tiny_skia::Transform::default(),
     );
 }
+
+/// Blurs the pixels inside the given bounds with the given radius.
+///
+/// The gaussian blur is approximated with three successive box blurs, and
+/// only the pixels inside the bounds are read and written.
+pub fn blur(
+    pixels: &mut tiny_skia::PixmapMut<'_>,
+    bounds: Rectangle,
+    radius: f32,
+) {
+    let sigma = radius / 2.0;
+
+    if sigma < 0.5 {
+        return;
+    }
+
+    let Some(bounds) = bounds
+        .intersection(&Rectangle::new(
+            Point::ORIGIN,
+            Size::new(pixels.width() as f32, pixels.height() as f32),
+        ))
+        .and_then(Rectangle::snap)
+    else {
+        return;
+    };
+
+    let stride = pixels.width() as usize;
+    let x = bounds.x as usize;
+    let y = bounds.y as usize;
+    let width = bounds.width as usize;
+    let height = bounds.height as usize;
+
+    let data: &mut [[u8; 4]] = bytemuck::cast_slice_mut(pixels.data_mut());
+
+    let mut source: Vec<[u8; 4]> = (0..height)
+        .flat_map(|row| {
+            let start = (y + row) * stride + x;
+
+            data[start..start + width].iter().copied()
+        })
+        .collect();
+
+    let mut target = source.clone();
+
+    for size in box_sizes(sigma) {
+        let radius = (size - 1) / 2;
+
+        // Horizontal pass
+        box_blur(&source, &mut target, width, height, 1, width, radius);
+
+        // Vertical pass
+        box_blur(&target, &mut source, height, width, width, 1, radius);
+    }
+
+    for row in 0..height {
+        let start = (y + row) * stride + x;
+
+        data[start..start + width]
+            .copy_from_slice(&source[row * width..(row + 1) * width]);
+    }
+}
+
+/// Computes the sizes of three box blurs approximating a gaussian blur
+/// with the given standard deviation.
+fn box_sizes(sigma: f32) -> [usize; 3] {
+    let ideal = (12.0 * sigma * sigma / 3.0 + 1.0).sqrt();
+
+    let mut lower = ideal.floor() as usize;
+
+    if lower % 2 == 0 {
+        lower = lower.saturating_sub(1).max(1);
+    }
+
+    let upper = lower + 2;
+    let lower_f = lower as f32;
+
+    let lower_count = ((12.0 * sigma * sigma
+        - 3.0 * lower_f * lower_f
+        - 12.0 * lower_f
+        - 9.0)
+        / (-4.0 * lower_f - 4.0))
+        .round()
+        .max(0.0) as usize;
+
+    [0, 1, 2].map(|i| if i < lower_count { lower } else { upper })
+}
+
+/// Blurs each line of the source into the target with a moving average,
+/// repeating the pixels at the edges of each line.
+fn box_blur(
+    source: &[[u8; 4]],
+    target: &mut [[u8; 4]],
+    length: usize,
+    lines: usize,
+    step: usize,
+    line_step: usize,
+    radius: usize,
+) {
+    let window = (radius * 2 + 1) as u32;
+
+    for line in 0..lines {
+        let pixel =
+            |i: usize| source[line * line_step + i.min(length - 1) * step];
+
+        let mut sum = [0u32; 4];
+
+        for i in 0..=radius * 2 {
+            let color = pixel(i.saturating_sub(radius));
+
+            for channel in 0..4 {
+                sum[channel] += u32::from(color[channel]);
+            }
+        }
+
+        for i in 0..length {
+            target[line * line_step + i * step] =
+                sum.map(|channel| ((channel + window / 2) / window) as u8);
+
+            let added = pixel(i + radius + 1);
+            let removed = pixel(i.saturating_sub(radius));
+
+            for channel in 0..4 {
+                sum[channel] += u32::from(added[channel]);
+                sum[channel] -= u32::from(removed[channel]);
+            }
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use crate::core::text::LineHeight;
 use crate::core::{self, Pixels, Point, Radians, Rectangle, Size, Svg, Vector};
+use crate::engine;
 use crate::graphics::cache::{self, Cached};
 use crate::graphics::geometry::fill::{self, Fill};
 use crate::graphics::geometry::stroke::{self, Stroke};
 use crate::graphics::geometry::{self, Path, Style};
-use crate::graphics::{self, Gradient, Image, Text};
+use crate::graphics::gradient::{self, Gradient};
+use crate::graphics::{self, Image, Text};
 use crate::Primitive;
 
 use std::rc::Rc;
@@ -81,6 +83,24 @@ impl Frame {
             ),
         }
     }
+
+    fn conic(&self, style: Style) -> Option<gradient::Conic> {
+        let Style::Gradient(Gradient::Conic(mut conic)) = style else {
+            return None;
+        };
+
+        let mut center = [tiny_skia::Point {
+            x: conic.center.x,
+            y: conic.center.y,
+        }];
+
+        self.transform.map_points(&mut center);
+
+        conic.center = Point::new(center[0].x, center[0].y);
+        conic.angle.0 += self.transform.ky.atan2(self.transform.sx);
+
+        Some(conic)
+    }
 }
 
 impl geometry::frame::Backend for Frame {
@@ -118,6 +138,7 @@ impl geometry::frame::Backend for Frame {
             path,
             paint,
             rule: into_fill_rule(fill.rule),
+            conic: self.conic(fill.style),
         });
     }
 
@@ -145,17 +166,19 @@ impl geometry::frame::Backend for Frame {
             path,
             paint,
             rule: into_fill_rule(fill.rule),
+            conic: self.conic(fill.style),
         });
     }
 
     fn stroke<'a>(&mut self, path: &Path, stroke: impl Into<Stroke<'a>>) {
-        let Some(path) =
-            convert_path(path).and_then(|path| path.transform(self.transform))
+        let stroke = stroke.into();
+
+        let Some(path) = convert_path(&stroke.apply(path))
+            .and_then(|path| path.transform(self.transform))
         else {
             return;
         };
 
-        let stroke = stroke.into();
         let skia_stroke = into_stroke(&stroke);
 
         let mut paint = into_paint(stroke.style);
@@ -165,6 +188,7 @@ impl geometry::frame::Backend for Frame {
             path,
             paint,
             stroke: skia_stroke,
+            conic: self.conic(stroke.style),
         });
     }
 
@@ -182,7 +206,8 @@ impl geometry::frame::Backend for Frame {
 
         let (scale_x, scale_y) = self.transform.get_scale();
 
-        if !self.transform.has_skew()
+        if text.is_plain()
+            && !self.transform.has_skew()
             && scale_x == scale_y
             && scale_x > 0.0
             && scale_y > 0.0
@@ -415,49 +440,7 @@ pub fn into_paint(style: Style) -> tiny_skia::Paint<'static> {
                 tiny_skia::Color::from_rgba(color.b, color.g, color.r, color.a)
                     .expect("Create color"),
             ),
-            Style::Gradient(gradient) => match gradient {
-                Gradient::Linear(linear) => {
-                    let stops: Vec<tiny_skia::GradientStop> = linear
-                        .stops
-                        .into_iter()
-                        .flatten()
-                        .map(|stop| {
-                            tiny_skia::GradientStop::new(
-                                stop.offset,
-                                tiny_skia::Color::from_rgba(
-                                    stop.color.b,
-                                    stop.color.g,
-                                    stop.color.r,
-                                    stop.color.a,
-                                )
-                                .expect("Create color"),
-                            )
-                        })
-                        .collect();
-
-                    tiny_skia::LinearGradient::new(
-                        tiny_skia::Point {
-                            x: linear.start.x,
-                            y: linear.start.y,
-                        },
-                        tiny_skia::Point {
-                            x: linear.end.x,
-                            y: linear.end.y,
-                        },
-                        if stops.is_empty() {
-                            vec![tiny_skia::GradientStop::new(
-                                0.0,
-                                tiny_skia::Color::BLACK,
-                            )]
-                        } else {
-                            stops
-                        },
-                        tiny_skia::SpreadMode::Pad,
-                        tiny_skia::Transform::identity(),
-                    )
-                    .expect("Create linear gradient")
-                }
-            },
+            Style::Gradient(gradient) => engine::into_shader(&gradient),
         },
         anti_alias: true,
         ..Default::default()
@@ -484,14 +467,7 @@ pub fn into_stroke(stroke: &Stroke<'_>) -> tiny_skia::Stroke {
             stroke::LineJoin::Round => tiny_skia::LineJoin::Round,
             stroke::LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
         },
-        dash: if stroke.line_dash.segments.is_empty() {
-            None
-        } else {
-            tiny_skia::StrokeDash::new(
-                stroke.line_dash.segments.into(),
-                stroke.line_dash.offset as f32,
-            )
-        },
+        miter_limit: stroke.miter_limit.max(2.0),
         ..Default::default()
     }
 }
@@ -2,6 +2,7 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 pub mod window;
 
+mod conic;
 mod engine;
 mod layer;
 mod primitive;
@@ -58,11 +59,41 @@ impl Renderer {
         }
     }
 
+    /// Sets the maximum amount of memory, in bytes, that the cached images
+    /// of the [`Renderer`] may use before the least recently drawn ones are
+    /// evicted.
+    ///
+    /// If `None`, images are evicted as soon as they stop being drawn.
+    #[cfg(any(feature = "image", feature = "svg"))]
+    pub fn set_image_cache_budget(&mut self, budget: Option<usize>) {
+        self.engine.image_cache_budget = budget;
+    }
+
+    /// Sets whether the [`Renderer`] decodes images synchronously when they
+    /// are first drawn, instead of in the background.
+    ///
+    /// Useful to render a single frame offscreen.
+    #[cfg(feature = "image")]
+    pub fn set_blocking_image_decoding(&mut self, is_blocking: bool) {
+        self.engine.raster_pipeline.set_blocking(is_blocking);
+    }
+
+    /// Sets the [`graphics::image::Waker`] called whenever an image of the
+    /// [`Renderer`] finishes loading in the background.
+    #[cfg(feature = "image")]
+    pub fn set_image_waker(&mut self, waker: Option<graphics::image::Waker>) {
+        self.engine.raster_pipeline.set_waker(waker);
+    }
+
     pub fn layers(&mut self) -> &[Layer] {
         self.layers.flush();
         self.layers.as_slice()
     }
 
+    pub fn groups(&self) -> &[graphics::layer::Group] {
+        self.layers.groups()
+    }
+
     pub fn draw<T: AsRef<str>>(
         &mut self,
         pixels: &mut tiny_skia::PixmapMut<'_>,
@@ -134,71 +165,110 @@ impl Renderer {
                 None,
             );
 
-            for layer in self.layers.iter() {
-                let Some(clip_bounds) =
-                    region.intersection(&(layer.bounds * scale_factor))
-                else {
-                    continue;
-                };
-
-                engine::adjust_clip_mask(clip_mask, clip_bounds);
-
-                for (quad, background) in &layer.quads {
-                    self.engine.draw_quad(
-                        quad,
-                        background,
-                        Transformation::scale(scale_factor),
-                        pixels,
-                        clip_mask,
-                        clip_bounds,
-                    );
-                }
+            let mut groups = self.layers.groups().iter().peekable();
+            let mut offscreens: Vec<(
+                &graphics::layer::Group,
+                tiny_skia::Pixmap,
+            )> = Vec::new();
+
+            for (index, layer) in self.layers.iter().enumerate() {
+                if let Some(group) =
+                    groups.next_if(|group| group.layers.start == index)
+                {
+                    let bounds = group.bounds * scale_factor;
+
+                    // Blur whatever lies behind the group in place, since
+                    // its contents will be drawn on top anyway
+                    if group.effect.backdrop_blur > 0.0 {
+                        if let Some(bounds) = region.intersection(&bounds) {
+                            let radius =
+                                group.effect.backdrop_blur * scale_factor;
+
+                            match offscreens.last_mut() {
+                                Some((_, pixmap)) => engine::blur(
+                                    &mut pixmap.as_mut(),
+                                    bounds,
+                                    radius,
+                                ),
+                                None => engine::blur(pixels, bounds, radius),
+                            }
+                        }
+                    }
 
-                for group in &layer.primitives {
-                    let Some(new_clip_bounds) = (group.clip_bounds()
-                        * scale_factor)
-                        .intersection(&clip_bounds)
+                    let Some(pixmap) =
+                        tiny_skia::Pixmap::new(pixels.width(), pixels.height())
                     else {
                         continue;
                     };
 
-                    engine::adjust_clip_mask(clip_mask, new_clip_bounds);
+                    offscreens.push((group, pixmap));
+                }
 
-                    for primitive in group.as_slice() {
-                        self.engine.draw_primitive(
-                            primitive,
-                            group.transformation()
-                                * Transformation::scale(scale_factor),
+                if let Some(clip_bounds) =
+                    region.intersection(&(layer.bounds * scale_factor))
+                {
+                    match offscreens.last_mut() {
+                        Some((_, pixmap)) => self.engine.draw_layer(
+                            layer,
+                            scale_factor,
+                            &mut pixmap.as_mut(),
+                            clip_mask,
+                            clip_bounds,
+                        ),
+                        None => self.engine.draw_layer(
+                            layer,
+                            scale_factor,
                             pixels,
                             clip_mask,
                             clip_bounds,
-                        );
+                        ),
                     }
-
-                    engine::adjust_clip_mask(clip_mask, clip_bounds);
                 }
 
-                for image in &layer.images {
-                    self.engine.draw_image(
-                        image,
-                        Transformation::scale(scale_factor),
-                        pixels,
-                        clip_mask,
-                        clip_bounds,
-                    );
-                }
+                while offscreens
+                    .last()
+                    .is_some_and(|(group, _)| group.layers.end == index + 1)
+                {
+                    let (group, mut pixmap) = offscreens.pop().unwrap();
+                    let bounds = group.bounds * scale_factor;
 
-                for group in &layer.text {
-                    for text in group.as_slice() {
-                        self.engine.draw_text(
-                            text,
-                            group.transformation()
-                                * Transformation::scale(scale_factor),
-                            pixels,
-                            clip_mask,
+                    let Some(clip_bounds) = region.intersection(&bounds) else {
+                        continue;
+                    };
+
+                    if group.effect.blur > 0.0 {
+                        engine::blur(
+                            &mut pixmap.as_mut(),
                             clip_bounds,
+                            group.effect.blur * scale_factor,
                         );
                     }
+
+                    engine::adjust_clip_mask(clip_mask, clip_bounds);
+
+                    let paint = tiny_skia::PixmapPaint {
+                        opacity: group.effect.opacity,
+                        ..tiny_skia::PixmapPaint::default()
+                    };
+
+                    match offscreens.last_mut() {
+                        Some((_, target)) => target.draw_pixmap(
+                            0,
+                            0,
+                            pixmap.as_ref(),
+                            &paint,
+                            tiny_skia::Transform::identity(),
+                            Some(clip_mask),
+                        ),
+                        None => pixels.draw_pixmap(
+                            0,
+                            0,
+                            pixmap.as_ref(),
+                            &paint,
+                            tiny_skia::Transform::identity(),
+                            Some(clip_mask),
+                        ),
+                    }
                 }
             }
 
@@ -235,6 +305,14 @@ impl core::Renderer for Renderer {
         self.layers.pop_clip();
     }
 
+    fn start_effect(&mut self, bounds: Rectangle, effect: renderer::Effect) {
+        self.layers.push_effect(bounds, effect);
+    }
+
+    fn end_effect(&mut self) {
+        self.layers.pop_effect();
+    }
+
     fn start_transformation(&mut self, transformation: Transformation) {
         self.layers.push_transformation(transformation);
     }
@@ -373,6 +451,13 @@ impl graphics::mesh::Renderer for Renderer {
     }
 }
 
+#[cfg(any(feature = "image", feature = "svg"))]
+impl graphics::image::Cache for Renderer {
+    fn image_cache_stats(&self) -> graphics::image::Stats {
+        self.engine.image_cache_stats()
+    }
+}
+
 #[cfg(feature = "image")]
 impl core::image::Renderer for Renderer {
     type Handle = core::image::Handle;
@@ -385,6 +470,10 @@ impl core::image::Renderer for Renderer {
         let (layer, transformation) = self.layers.current_mut();
         layer.draw_raster(image, bounds, transformation);
     }
+
+    fn is_image_loading(&self, handle: &Self::Handle) -> bool {
+        self.engine.raster_pipeline.is_loading(handle)
+    }
 }
 
 #[cfg(feature = "svg")]
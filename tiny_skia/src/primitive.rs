@@ -1,4 +1,5 @@
 use crate::core::Rectangle;
+use crate::graphics::gradient;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Primitive {
@@ -10,6 +11,11 @@ pub enum Primitive {
         paint: tiny_skia::Paint<'static>,
         /// The fill rule to follow.
         rule: tiny_skia::FillRule,
+        /// The conic gradient to paint with, if any.
+        ///
+        /// `tiny-skia` does not support conic gradients, so they are
+        /// rasterized when the primitive is drawn.
+        conic: Option<gradient::Conic>,
     },
     /// A path stroked with some paint.
     Stroke {
@@ -19,6 +25,11 @@ pub enum Primitive {
         paint: tiny_skia::Paint<'static>,
         /// The stroke settings.
         stroke: tiny_skia::Stroke,
+        /// The conic gradient to paint with, if any.
+        ///
+        /// `tiny-skia` does not support conic gradients, so they are
+        /// rasterized when the primitive is drawn.
+        conic: Option<gradient::Conic>,
     },
 }
 
@@ -1,10 +1,10 @@
+use crate::core::background;
 use crate::core::image as raster;
-use crate::core::{Rectangle, Size};
+use crate::core::{Color, Rectangle, Size};
 use crate::graphics;
+use crate::graphics::image::{Buffer, Decoder, Lru, Stats};
 
-use rustc_hash::{FxHashMap, FxHashSet};
 use std::cell::RefCell;
-use std::collections::hash_map;
 
 #[derive(Debug)]
 pub struct Pipeline {
@@ -19,106 +19,394 @@ impl Pipeline {
     }
 
     pub fn dimensions(&self, handle: &raster::Handle) -> Size<u32> {
-        if let Some(image) = self.cache.borrow_mut().allocate(handle) {
-            Size::new(image.width(), image.height())
-        } else {
-            Size::new(0, 0)
-        }
+        self.cache.borrow_mut().measure(handle)
+    }
+
+    pub fn set_blocking(&mut self, is_blocking: bool) {
+        self.cache.get_mut().decoder.set_blocking(is_blocking);
+    }
+
+    pub fn set_waker(&mut self, waker: Option<graphics::image::Waker>) {
+        self.cache.get_mut().decoder.set_waker(waker);
+    }
+
+    pub fn is_loading(&self, handle: &raster::Handle) -> bool {
+        self.cache.borrow_mut().is_loading(handle)
     }
 
     pub fn draw(
         &mut self,
-        handle: &raster::Handle,
-        filter_method: raster::FilterMethod,
+        image: &raster::Image,
         bounds: Rectangle,
-        opacity: f32,
         pixels: &mut tiny_skia::PixmapMut<'_>,
         transform: tiny_skia::Transform,
         clip_mask: Option<&tiny_skia::Mask>,
     ) {
-        if let Some(image) = self.cache.borrow_mut().allocate(handle) {
-            let width_scale = bounds.width / image.width() as f32;
-            let height_scale = bounds.height / image.height() as f32;
+        let mut cache = self.cache.borrow_mut();
 
-            let transform = transform.pre_scale(width_scale, height_scale);
+        let Some(pixmap) =
+            cache.adjust(&image.handle, image.tint, image.adjustments)
+        else {
+            return;
+        };
 
-            let quality = match filter_method {
-                raster::FilterMethod::Linear => {
-                    tiny_skia::FilterQuality::Bilinear
-                }
-                raster::FilterMethod::Nearest => {
-                    tiny_skia::FilterQuality::Nearest
-                }
-            };
+        let Some(region) =
+            image.region(Size::new(pixmap.width(), pixmap.height()))
+        else {
+            return;
+        };
+
+        let Some(rect) = tiny_skia::Rect::from_xywh(
+            bounds.x,
+            bounds.y,
+            bounds.width,
+            bounds.height,
+        ) else {
+            return;
+        };
 
-            pixels.draw_pixmap(
-                (bounds.x / width_scale) as i32,
-                (bounds.y / height_scale) as i32,
-                image,
-                &tiny_skia::PixmapPaint {
+        let region = Rectangle::<f32>::from(region);
+
+        let quality = match image.filter_method {
+            raster::FilterMethod::Linear => tiny_skia::FilterQuality::Bilinear,
+            raster::FilterMethod::Nearest => tiny_skia::FilterQuality::Nearest,
+        };
+
+        // Map the cropped region of the image to the bounds
+        let paint = tiny_skia::Paint {
+            shader: tiny_skia::Pattern::new(
+                pixmap,
+                tiny_skia::SpreadMode::Pad,
+                quality,
+                image.opacity,
+                tiny_skia::Transform::from_translate(bounds.x, bounds.y)
+                    .pre_scale(
+                        bounds.width / region.width,
+                        bounds.height / region.height,
+                    )
+                    .pre_translate(-region.x, -region.y),
+            ),
+            anti_alias: false,
+            ..tiny_skia::Paint::default()
+        };
+
+        pixels.fill_rect(rect, &paint, transform, clip_mask);
+    }
+
+    pub fn draw_background(
+        &mut self,
+        background: &background::Image,
+        bounds: Rectangle,
+        path: &tiny_skia::Path,
+        pixels: &mut tiny_skia::PixmapMut<'_>,
+        transform: tiny_skia::Transform,
+        clip_mask: Option<&tiny_skia::Mask>,
+    ) {
+        let mut cache = self.cache.borrow_mut();
+
+        let Some(image) = cache.allocate(&background.handle) else {
+            return;
+        };
+
+        let image_size = Size::new(image.width() as f32, image.height() as f32);
+
+        let quality = match background.filter_method {
+            raster::FilterMethod::Linear => tiny_skia::FilterQuality::Bilinear,
+            raster::FilterMethod::Nearest => tiny_skia::FilterQuality::Nearest,
+        };
+
+        let pattern = |source: Rectangle, target: Rectangle, spread_mode| {
+            tiny_skia::Paint {
+                shader: tiny_skia::Pattern::new(
+                    image,
+                    spread_mode,
                     quality,
-                    opacity,
-                    ..Default::default()
-                },
-                transform,
-                clip_mask,
-            );
+                    background.opacity,
+                    tiny_skia::Transform::from_translate(target.x, target.y)
+                        .pre_scale(
+                            target.width / source.width,
+                            target.height / source.height,
+                        )
+                        .pre_translate(-source.x, -source.y),
+                ),
+                anti_alias: true,
+                ..tiny_skia::Paint::default()
+            }
+        };
+
+        let slices = background.fit.slices(image_size, bounds);
+
+        match background.fit {
+            background::Fit::Cover | background::Fit::Tile => {
+                let spread_mode = if background.fit == background::Fit::Tile {
+                    tiny_skia::SpreadMode::Repeat
+                } else {
+                    tiny_skia::SpreadMode::Pad
+                };
+
+                let (source, target) = slices[0];
+
+                pixels.fill_path(
+                    path,
+                    &pattern(source, target, spread_mode),
+                    tiny_skia::FillRule::EvenOdd,
+                    transform,
+                    clip_mask,
+                );
+            }
+            background::Fit::Contain | background::Fit::NineSlice(_) => {
+                // The slices of the image may not cover the whole path,
+                // so we clip them to the path with a mask instead
+                let mut mask = if let Some(clip_mask) = clip_mask {
+                    clip_mask.clone()
+                } else if let Some(mask) =
+                    tiny_skia::Mask::new(pixels.width(), pixels.height())
+                {
+                    mask
+                } else {
+                    return;
+                };
+
+                if clip_mask.is_some() {
+                    mask.intersect_path(
+                        path,
+                        tiny_skia::FillRule::EvenOdd,
+                        true,
+                        transform,
+                    );
+                } else {
+                    mask.fill_path(
+                        path,
+                        tiny_skia::FillRule::EvenOdd,
+                        true,
+                        transform,
+                    );
+                }
+
+                for (source, target) in slices {
+                    let Some(rect) = tiny_skia::Rect::from_xywh(
+                        target.x,
+                        target.y,
+                        target.width,
+                        target.height,
+                    ) else {
+                        continue;
+                    };
+
+                    pixels.fill_rect(
+                        rect,
+                        &pattern(source, target, tiny_skia::SpreadMode::Pad),
+                        transform,
+                        Some(&mask),
+                    );
+                }
+            }
         }
     }
 
-    pub fn trim_cache(&mut self) {
-        self.cache.borrow_mut().trim();
+    pub fn trim_cache(&mut self, budget: Option<usize>) {
+        self.cache.get_mut().trim(budget);
+    }
+
+    pub fn stats(&self) -> Stats {
+        let cache = self.cache.borrow();
+
+        cache.entries.stats() + cache.adjusted.stats()
     }
 }
 
-#[derive(Debug, Default)]
+/// The key of an adjusted image: its [`raster::Id`] along with the bits of
+/// its tint and color adjustments.
+type Adjusted = (raster::Id, [u32; 4], [u32; 3]);
+
+#[derive(Debug)]
 struct Cache {
-    entries: FxHashMap<raster::Id, Option<Entry>>,
-    hits: FxHashSet<raster::Id>,
+    entries: Lru<raster::Id, Memory>,
+    adjusted: Lru<Adjusted, Entry>,
+    decoder: Decoder<Memory>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self {
+            entries: Lru::new(),
+            adjusted: Lru::new(),
+            // Images are converted to premultiplied BGRA by the workers
+            decoder: Decoder::new(Memory::from_load),
+        }
+    }
 }
 
 impl Cache {
+    fn load(&mut self, handle: &raster::Handle) -> &Memory {
+        self.receive();
+
+        let id = handle.id();
+
+        if self.entries.contains(&id) {
+            return self.entries.get(&id).unwrap();
+        }
+
+        self.insert(handle)
+    }
+
+    /// Measures the image of the given handle, loading it without counting
+    /// a cache hit.
+    fn measure(&mut self, handle: &raster::Handle) -> Size<u32> {
+        self.receive();
+
+        if let Some(memory) = self.entries.touch(&handle.id()) {
+            return memory.dimensions();
+        }
+
+        self.insert(handle).dimensions()
+    }
+
+    fn insert(&mut self, handle: &raster::Handle) -> &Memory {
+        let memory = if matches!(handle, raster::Handle::Rgba { .. })
+            || self.decoder.is_blocking()
+        {
+            self.decoder.load(handle)
+        } else {
+            match graphics::image::dimensions(handle) {
+                Ok(size) => {
+                    self.decoder.request(handle);
+
+                    Memory::Decoding(size)
+                }
+                Err(_) => Memory::Invalid,
+            }
+        };
+
+        let size = memory.size();
+
+        self.entries.insert(handle.id(), memory, size)
+    }
+
     pub fn allocate(
         &mut self,
         handle: &raster::Handle,
     ) -> Option<tiny_skia::PixmapRef<'_>> {
-        let id = handle.id();
+        let Memory::Loaded(entry) = self.load(handle) else {
+            return None;
+        };
 
-        if let hash_map::Entry::Vacant(entry) = self.entries.entry(id) {
-            let image = graphics::image::load(handle).ok()?;
+        Some(entry.pixmap())
+    }
 
-            let mut buffer =
-                vec![0u32; image.width() as usize * image.height() as usize];
+    /// Allocates the image of the given handle with the given tint and
+    /// color adjustments applied.
+    ///
+    /// The adjusted pixels are cached, so they are only processed once.
+    fn adjust(
+        &mut self,
+        handle: &raster::Handle,
+        tint: Color,
+        adjustments: raster::Adjustments,
+    ) -> Option<tiny_skia::PixmapRef<'_>> {
+        if tint == Color::WHITE && adjustments.is_none() {
+            return self.allocate(handle);
+        }
 
-            for (i, pixel) in image.pixels().enumerate() {
-                let [r, g, b, a] = pixel.0;
+        let key = (
+            handle.id(),
+            [tint.r, tint.g, tint.b, tint.a].map(f32::to_bits),
+            [
+                adjustments.brightness,
+                adjustments.contrast,
+                adjustments.saturation,
+            ]
+            .map(f32::to_bits),
+        );
 
-                buffer[i] = bytemuck::cast(
-                    tiny_skia::ColorU8::from_rgba(b, g, r, a).premultiply(),
-                );
-            }
+        if self.adjusted.contains(&key) {
+            return self.adjusted.get(&key).map(|entry| entry.pixmap());
+        }
+
+        let Memory::Loaded(original) = self.load(handle) else {
+            return None;
+        };
+
+        let entry = original.adjust(tint, adjustments);
+        let size = entry.pixels.len() * 4;
+
+        Some(self.adjusted.insert(key, entry, size).pixmap())
+    }
 
-            let _ = entry.insert(Some(Entry {
-                width: image.width(),
-                height: image.height(),
-                pixels: buffer,
-            }));
+    fn is_loading(&mut self, handle: &raster::Handle) -> bool {
+        self.receive();
+
+        matches!(self.entries.peek(&handle.id()), Some(Memory::Decoding(_)))
+    }
+
+    /// Stores the images decoded since the last call.
+    fn receive(&mut self) {
+        for (id, memory) in self.decoder.poll() {
+            let size = memory.size();
+
+            self.entries.replace(&id, memory, size);
         }
+    }
+
+    fn trim(&mut self, budget: Option<usize>) {
+        let adjusted = self.adjusted.stats().memory;
+
+        self.entries
+            .trim(budget.map(|budget| budget.saturating_sub(adjusted)), drop);
 
-        let _ = self.hits.insert(id);
-        self.entries.get(&id).unwrap().as_ref().map(|entry| {
-            tiny_skia::PixmapRef::from_bytes(
-                bytemuck::cast_slice(&entry.pixels),
-                entry.width,
-                entry.height,
-            )
-            .expect("Build pixmap from image bytes")
+        let rest = self.entries.stats().memory;
+
+        self.adjusted
+            .trim(budget.map(|budget| budget.saturating_sub(rest)), drop);
+    }
+}
+
+#[derive(Debug)]
+enum Memory {
+    Loaded(Entry),
+    Decoding(Size<u32>),
+    Invalid,
+}
+
+impl Memory {
+    fn from_load(
+        result: graphics::image::image_rs::ImageResult<Buffer>,
+    ) -> Self {
+        let Ok(image) = result else {
+            return Memory::Invalid;
+        };
+
+        let mut buffer =
+            vec![0u32; image.width() as usize * image.height() as usize];
+
+        for (i, pixel) in image.pixels().enumerate() {
+            let [r, g, b, a] = pixel.0;
+
+            buffer[i] = bytemuck::cast(
+                tiny_skia::ColorU8::from_rgba(b, g, r, a).premultiply(),
+            );
+        }
+
+        Memory::Loaded(Entry {
+            width: image.width(),
+            height: image.height(),
+            pixels: buffer,
         })
     }
 
-    fn trim(&mut self) {
-        self.entries.retain(|key, _| self.hits.contains(key));
-        self.hits.clear();
+    fn dimensions(&self) -> Size<u32> {
+        match self {
+            Memory::Loaded(entry) => Size::new(entry.width, entry.height),
+            Memory::Decoding(size) => *size,
+            Memory::Invalid => Size::new(0, 0),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Memory::Loaded(entry) => entry.pixels.len() * 4,
+            Memory::Decoding(_) | Memory::Invalid => 0,
+        }
     }
 }
 
@@ -128,3 +416,53 @@ struct Entry {
     height: u32,
     pixels: Vec<u32>,
 }
+
+impl Entry {
+    fn pixmap(&self) -> tiny_skia::PixmapRef<'_> {
+        tiny_skia::PixmapRef::from_bytes(
+            bytemuck::cast_slice(&self.pixels),
+            self.width,
+            self.height,
+        )
+        .expect("Build pixmap from image bytes")
+    }
+
+    /// Returns a new [`Entry`] with the given tint and color adjustments
+    /// applied to its pixels.
+    fn adjust(&self, tint: Color, adjustments: raster::Adjustments) -> Self {
+        let pixels = self
+            .pixels
+            .iter()
+            .map(|pixel| {
+                let color = bytemuck::cast::<
+                    u32,
+                    tiny_skia::PremultipliedColorU8,
+                >(*pixel)
+                .demultiply();
+
+                // Pixels are stored in BGRA order
+                let color = adjustments.apply(
+                    Color::from_rgba8(
+                        color.blue(),
+                        color.green(),
+                        color.red(),
+                        f32::from(color.alpha()) / 255.0,
+                    ),
+                    tint,
+                );
+
+                let [r, g, b, a] = color.into_rgba8();
+
+                bytemuck::cast(
+                    tiny_skia::ColorU8::from_rgba(b, g, r, a).premultiply(),
+                )
+            })
+            .collect();
+
+        Self {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+}
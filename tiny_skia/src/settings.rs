@@ -4,7 +4,7 @@ use crate::graphics;
 /// The settings of a [`Compositor`].
 ///
 /// [`Compositor`]: crate::window::Compositor
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Settings {
     /// The default [`Font`] to use.
     pub default_font: Font,
@@ -13,6 +13,19 @@ pub struct Settings {
     ///
     /// By default, it will be set to `16.0`.
     pub default_text_size: Pixels,
+
+    /// The maximum amount of memory, in bytes, that cached images may use
+    /// before the least recently drawn ones are evicted.
+    ///
+    /// By default, it is `None`; and images are evicted as soon as they
+    /// stop being drawn.
+    pub image_cache_budget: Option<usize>,
+
+    /// The [`graphics::image::Waker`] called whenever an image finishes
+    /// loading in the background.
+    ///
+    /// By default, it is `None`.
+    pub image_waker: Option<graphics::image::Waker>,
 }
 
 impl Default for Settings {
@@ -20,6 +33,8 @@ impl Default for Settings {
         Settings {
             default_font: Font::default(),
             default_text_size: Pixels(16.0),
+            image_cache_budget: None,
+            image_waker: None,
         }
     }
 }
@@ -29,6 +44,8 @@ impl From<graphics::Settings> for Settings {
         Self {
             default_font: settings.default_font,
             default_text_size: settings.default_text_size,
+            image_cache_budget: settings.image_cache_budget,
+            image_waker: settings.image_waker,
         }
     }
 }
@@ -1,14 +1,12 @@
-use crate::core::svg::{Data, Handle};
+use crate::core::svg::Handle;
 use crate::core::{Color, Rectangle, Size};
+use crate::graphics::image::{Lru, Stats};
+use crate::graphics::svg::{self, Classes};
 
 use resvg::usvg;
-use rustc_hash::{FxHashMap, FxHashSet};
 use tiny_skia::Transform;
 
 use std::cell::RefCell;
-use std::collections::hash_map;
-use std::fs;
-use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct Pipeline {
@@ -33,6 +31,7 @@ impl Pipeline {
         &mut self,
         handle: &Handle,
         color: Option<Color>,
+        classes: &Classes,
         bounds: Rectangle,
         opacity: f32,
         pixels: &mut tiny_skia::PixmapMut<'_>,
@@ -42,6 +41,7 @@ impl Pipeline {
         if let Some(image) = self.cache.borrow_mut().draw(
             handle,
             color,
+            classes,
             Size::new(bounds.width as u32, bounds.height as u32),
         ) {
             pixels.draw_pixmap(
@@ -58,18 +58,27 @@ impl Pipeline {
         }
     }
 
-    pub fn trim_cache(&mut self) {
-        self.cache.borrow_mut().trim();
+    pub fn trim_cache(&mut self, budget: Option<usize>) {
+        self.cache.get_mut().trim(budget);
+    }
+
+    pub fn stats(&self) -> Stats {
+        let cache = self.cache.borrow();
+        let rasters = cache.rasters.stats();
+
+        // Trees are loaded once per rasterization miss, so only their
+        // memory is counted
+        Stats {
+            memory: rasters.memory + cache.trees.stats().memory,
+            ..rasters
+        }
     }
 }
 
 #[derive(Default)]
 struct Cache {
-    trees: FxHashMap<u64, Option<resvg::usvg::Tree>>,
-    tree_hits: FxHashSet<u64>,
-    rasters: FxHashMap<RasterKey, tiny_skia::Pixmap>,
-    raster_hits: FxHashSet<RasterKey>,
-    fontdb: Option<Arc<usvg::fontdb::Database>>,
+    trees: Lru<u64, Option<resvg::usvg::Tree>>,
+    rasters: Lru<RasterKey, tiny_skia::Pixmap>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -80,47 +89,43 @@ struct RasterKey {
 }
 
 impl Cache {
-    fn load(&mut self, handle: &Handle) -> Option<&usvg::Tree> {
-        let id = handle.id();
-
-        // TODO: Reuse `cosmic-text` font database
-        if self.fontdb.is_none() {
-            let mut fontdb = usvg::fontdb::Database::new();
-            fontdb.load_system_fonts();
+    fn load(
+        &mut self,
+        handle: &Handle,
+        classes: &Classes,
+    ) -> Option<&usvg::Tree> {
+        let key = svg::key(handle, classes);
 
-            self.fontdb = Some(Arc::new(fontdb));
+        if !self.trees.contains(&key) {
+            return self.insert(key, handle, classes);
         }
 
-        let options = usvg::Options {
-            fontdb: self
-                .fontdb
-                .as_ref()
-                .expect("fontdb must be initialized")
-                .clone(),
-            ..usvg::Options::default()
-        };
-
-        if let hash_map::Entry::Vacant(entry) = self.trees.entry(id) {
-            let svg = match handle.data() {
-                Data::Path(path) => {
-                    fs::read_to_string(path).ok().and_then(|contents| {
-                        usvg::Tree::from_str(&contents, &options).ok()
-                    })
-                }
-                Data::Bytes(bytes) => {
-                    usvg::Tree::from_data(bytes, &options).ok()
-                }
-            };
+        self.trees.get(&key).unwrap().as_ref()
+    }
 
-            let _ = entry.insert(svg);
-        }
+    fn insert(
+        &mut self,
+        key: u64,
+        handle: &Handle,
+        classes: &Classes,
+    ) -> Option<&usvg::Tree> {
+        let tree = svg::load(handle, classes);
+        let size = tree.as_ref().map_or(0, svg::memory);
 
-        let _ = self.tree_hits.insert(id);
-        self.trees.get(&id).unwrap().as_ref()
+        self.trees.insert(key, tree, size).as_ref()
     }
 
+    /// Measures the viewport of the SVG of the given handle, loading it
+    /// without counting a cache hit.
     fn viewport_dimensions(&mut self, handle: &Handle) -> Option<Size<u32>> {
-        let tree = self.load(handle)?;
+        let key = svg::key(handle, &[]);
+
+        let tree = if self.trees.contains(&key) {
+            self.trees.touch(&key).unwrap().as_ref()
+        } else {
+            self.insert(key, handle, &[])
+        }?;
+
         let size = tree.size();
 
         Some(Size::new(size.width() as u32, size.height() as u32))
@@ -130,6 +135,7 @@ impl Cache {
         &mut self,
         handle: &Handle,
         color: Option<Color>,
+        classes: &Classes,
         size: Size<u32>,
     ) -> Option<tiny_skia::PixmapRef<'_>> {
         if size.width == 0 || size.height == 0 {
@@ -137,14 +143,13 @@ impl Cache {
         }
 
         let key = RasterKey {
-            id: handle.id(),
+            id: svg::key(handle, classes),
             color: color.map(Color::into_rgba8),
             size,
         };
 
-        #[allow(clippy::map_entry)]
-        if !self.rasters.contains_key(&key) {
-            let tree = self.load(handle)?;
+        if !self.rasters.contains(&key) {
+            let tree = self.load(handle, classes)?;
 
             let mut image = tiny_skia::Pixmap::new(size.width, size.height)?;
 
@@ -196,28 +201,28 @@ impl Cache {
                 }
             }
 
-            let _ = self.rasters.insert(key, image);
+            let size = image.data().len();
+
+            return Some(self.rasters.insert(key, image, size).as_ref());
         }
 
-        let _ = self.raster_hits.insert(key);
-        self.rasters.get(&key).map(tiny_skia::Pixmap::as_ref)
+        self.rasters.get(&key).map(|image| image.as_ref())
     }
 
-    fn trim(&mut self) {
-        self.trees.retain(|key, _| self.tree_hits.contains(key));
-        self.rasters.retain(|key, _| self.raster_hits.contains(key));
+    fn trim(&mut self, budget: Option<usize>) {
+        self.trees.trim(None, drop);
+
+        let trees = self.trees.stats().memory;
 
-        self.tree_hits.clear();
-        self.raster_hits.clear();
+        self.rasters
+            .trim(budget.map(|budget| budget.saturating_sub(trees)), drop);
     }
 }
 
 impl std::fmt::Debug for Cache {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Cache")
-            .field("tree_hits", &self.tree_hits)
             .field("rasters", &self.rasters)
-            .field("raster_hits", &self.raster_hits)
             .finish_non_exhaustive()
     }
 }
@@ -2,6 +2,7 @@ use crate::core::{Color, Rectangle, Size};
 use crate::graphics::compositor::{self, Information};
 use crate::graphics::damage;
 use crate::graphics::error::{self, Error};
+use crate::graphics::layer;
 use crate::graphics::{self, Viewport};
 use crate::{Layer, Renderer, Settings};
 
@@ -21,7 +22,7 @@ pub struct Surface {
         Box<dyn compositor::Window>,
     >,
     clip_mask: tiny_skia::Mask,
-    layer_stack: VecDeque<Vec<Layer>>,
+    layer_stack: VecDeque<(Vec<Layer>, Vec<layer::Group>)>,
     background_color: Color,
     max_age: u8,
 }
@@ -49,10 +50,29 @@ impl crate::graphics::Compositor for Compositor {
     }
 
     fn create_renderer(&self) -> Self::Renderer {
-        Renderer::new(
+        #[allow(unused_mut)]
+        let mut renderer = Renderer::new(
             self.settings.default_font,
             self.settings.default_text_size,
-        )
+        );
+
+        #[cfg(any(feature = "image", feature = "svg"))]
+        renderer.set_image_cache_budget(self.settings.image_cache_budget);
+
+        #[cfg(feature = "image")]
+        renderer.set_image_waker(self.settings.image_waker.clone());
+
+        renderer
+    }
+
+    fn create_offscreen_renderer(&self) -> Self::Renderer {
+        #[allow(unused_mut)]
+        let mut renderer = self.create_renderer();
+
+        #[cfg(feature = "image")]
+        renderer.set_blocking_image_decoding(true);
+
+        renderer
     }
 
     fn create_surface<W: compositor::Window + Clone>(
@@ -107,6 +127,16 @@ impl crate::graphics::Compositor for Compositor {
         }
     }
 
+    #[cfg(any(feature = "image", feature = "svg"))]
+    fn image_cache_stats(
+        &self,
+        renderer: &Self::Renderer,
+    ) -> graphics::image::Stats {
+        use graphics::image::Cache as _;
+
+        renderer.image_cache_stats()
+    }
+
     fn present<T: AsRef<str>>(
         &mut self,
         renderer: &mut Self::Renderer,
@@ -168,8 +198,10 @@ pub fn present<T: AsRef<str>>(
     };
 
     let damage = last_layers
-        .and_then(|last_layers| {
-            (surface.background_color == background_color).then(|| {
+        .and_then(|(last_layers, last_groups)| {
+            (surface.background_color == background_color
+                && last_groups.as_slice() == renderer.groups())
+            .then(|| {
                 damage::diff(
                     last_layers,
                     renderer.layers(),
@@ -184,11 +216,15 @@ pub fn present<T: AsRef<str>>(
         return Ok(());
     }
 
-    surface.layer_stack.push_front(renderer.layers().to_vec());
+    surface
+        .layer_stack
+        .push_front((renderer.layers().to_vec(), renderer.groups().to_vec()));
     surface.background_color = background_color;
 
-    let damage =
-        damage::group(damage, Rectangle::with_size(viewport.logical_size()));
+    let damage = damage::group(
+        expand_damage(damage, renderer.groups()),
+        Rectangle::with_size(viewport.logical_size()),
+    );
 
     let mut pixels = tiny_skia::PixmapMut::from_bytes(
         bytemuck::cast_slice_mut(&mut buffer),
@@ -258,3 +294,35 @@ pub fn screenshot<T: AsRef<str>>(
         },
     )
 }
+
+/// Extends the damage regions to cover any effect group they touch.
+///
+/// Effects blur and blend the contents of a whole group at once, so any
+/// damage in a group needs the whole group to be redrawn.
+fn expand_damage(
+    mut damage: Vec<Rectangle>,
+    groups: &[layer::Group],
+) -> Vec<Rectangle> {
+    let mut expanded = vec![false; groups.len()];
+
+    loop {
+        let mut changed = false;
+
+        for (group, expanded) in groups.iter().zip(&mut expanded) {
+            if !*expanded
+                && damage
+                    .iter()
+                    .any(|region| region.intersection(&group.bounds).is_some())
+            {
+                damage.push(group.bounds);
+
+                *expanded = true;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return damage;
+        }
+    }
+}
@@ -0,0 +1,494 @@
+use crate::core::{Rectangle, Size};
+use crate::graphics::layer::Group;
+use crate::Buffer;
+
+use std::num::NonZeroU64;
+
+/// The amount of instances drawn for each [`Group`]: two for the
+/// backdrop blur, two for the content blur and one for the composition.
+const INSTANCES_PER_GROUP: usize = 5;
+
+/// Renders the effect groups of a frame with offscreen passes.
+///
+/// When a frame has effect groups, the whole frame is rendered into an
+/// offscreen root target first, so backdrops can be read and blurred.
+/// Each nested group renders into its own target, which is then blurred
+/// and composited into the target of its parent.
+#[derive(Debug)]
+pub struct Pipeline {
+    format: wgpu::TextureFormat,
+    blur: wgpu::RenderPipeline,
+    composite: wgpu::RenderPipeline,
+    constants: wgpu::BindGroup,
+    globals: wgpu::Buffer,
+    texture_layout: wgpu::BindGroupLayout,
+    instances: Buffer<Instance>,
+    targets: Vec<Target>,
+    scratch: Option<Target>,
+    size: Size<u32>,
+}
+
+impl Pipeline {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            min_filter: wgpu::FilterMode::Linear,
+            mag_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let globals = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("iced_wgpu::effect globals"),
+            size: std::mem::size_of::<Globals>() as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        });
+
+        let constant_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("iced_wgpu::effect uniforms layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(
+                            wgpu::SamplerBindingType::Filtering,
+                        ),
+                        count: None,
+                    },
+                ],
+            });
+
+        let constants = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("iced_wgpu::effect uniforms bind group"),
+            layout: &constant_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: globals.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        let texture_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("iced_wgpu::effect texture layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float {
+                            filterable: true,
+                        },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                }],
+            });
+
+        let layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("iced_wgpu::effect pipeline layout"),
+                push_constant_ranges: &[],
+                bind_group_layouts: &[&constant_layout, &texture_layout],
+            });
+
+        let shader =
+            device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("iced_wgpu effect shader"),
+                source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(
+                    concat!(
+                        include_str!("shader/vertex.wgsl"),
+                        "\n",
+                        include_str!("shader/effect.wgsl"),
+                    ),
+                )),
+            });
+
+        let pipeline = |label, blend| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<Instance>() as u64,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array!(
+                            // Bounds
+                            0 => Float32x4,
+                            // Direction
+                            1 => Float32x2,
+                            // Sigma
+                            2 => Float32,
+                            // Opacity
+                            3 => Float32,
+                        ),
+                    }],
+                    compilation_options:
+                        wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options:
+                        wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    front_face: wgpu::FrontFace::Cw,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        // Offscreen targets contain premultiplied colors, since they are
+        // rendered on top of a transparent background
+        let blur = pipeline("iced_wgpu::effect blur pipeline", None);
+        let composite = pipeline(
+            "iced_wgpu::effect composite pipeline",
+            Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+        );
+
+        let instances = Buffer::new(
+            device,
+            "iced_wgpu::effect instance buffer",
+            INSTANCES_PER_GROUP + 1,
+            wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        );
+
+        Self {
+            format,
+            blur,
+            composite,
+            constants,
+            globals,
+            texture_layout,
+            instances,
+            targets: Vec::new(),
+            scratch: None,
+            size: Size::new(0, 0),
+        }
+    }
+
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        belt: &mut wgpu::util::StagingBelt,
+        groups: &[Group],
+        scale_factor: f32,
+        size: Size<u32>,
+    ) {
+        if groups.is_empty() {
+            return;
+        }
+
+        if self.size != size {
+            self.targets.clear();
+            self.scratch = None;
+            self.size = size;
+
+            belt.write_buffer(
+                encoder,
+                &self.globals,
+                0,
+                NonZeroU64::new(std::mem::size_of::<Globals>() as u64)
+                    .expect("non-empty globals"),
+                device,
+            )
+            .copy_from_slice(bytemuck::bytes_of(&Globals {
+                size: [size.width as f32, size.height as f32],
+                _padding: [0.0; 2],
+            }));
+        }
+
+        // One target for the root, plus one for each level of nesting
+        let depth = max_depth(groups) + 1;
+
+        while self.targets.len() < depth {
+            self.targets.push(Target::new(
+                device,
+                self.format,
+                &self.texture_layout,
+                size,
+            ));
+        }
+
+        if self.scratch.is_none() {
+            self.scratch = Some(Target::new(
+                device,
+                self.format,
+                &self.texture_layout,
+                size,
+            ));
+        }
+
+        let viewport = Rectangle::with_size(Size::new(
+            size.width as f32,
+            size.height as f32,
+        ));
+
+        let mut instances =
+            Vec::with_capacity(groups.len() * INSTANCES_PER_GROUP + 1);
+
+        for group in groups {
+            let bounds = (group.bounds * scale_factor)
+                .intersection(&viewport)
+                .unwrap_or(Rectangle::with_size(Size::ZERO));
+
+            let bounds = [bounds.x, bounds.y, bounds.width, bounds.height];
+
+            let backdrop = group.effect.backdrop_blur * scale_factor / 2.0;
+            let blur = group.effect.blur * scale_factor / 2.0;
+
+            instances.extend([
+                Instance::blur(bounds, [1.0, 0.0], backdrop),
+                Instance::blur(bounds, [0.0, 1.0], backdrop),
+                Instance::blur(bounds, [1.0, 0.0], blur),
+                Instance::blur(bounds, [0.0, 1.0], blur),
+                Instance::composite(bounds, group.effect.opacity),
+            ]);
+        }
+
+        instances.push(Instance::composite(
+            [viewport.x, viewport.y, viewport.width, viewport.height],
+            1.0,
+        ));
+
+        let _ = self.instances.resize(device, instances.len());
+        let _ = self.instances.write(device, encoder, belt, 0, &instances);
+    }
+
+    /// Returns the offscreen target at the given depth.
+    ///
+    /// The root of the frame is at depth 0.
+    pub fn target(&self, depth: usize) -> &wgpu::TextureView {
+        &self.targets[depth].view
+    }
+
+    /// Blurs the backdrop of the group with the given index, which lies in
+    /// the target at the given depth.
+    pub fn blur_backdrop(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth: usize,
+        group: usize,
+    ) {
+        self.blur(encoder, depth, group * INSTANCES_PER_GROUP);
+    }
+
+    /// Blurs the contents of the group with the given index, which lie in
+    /// the target at the given depth.
+    pub fn blur_contents(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth: usize,
+        group: usize,
+    ) {
+        self.blur(encoder, depth, group * INSTANCES_PER_GROUP + 2);
+    }
+
+    /// Composites the target of the group with the given index, at the
+    /// given depth, into the current render pass.
+    pub fn composite<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        depth: usize,
+        group: usize,
+    ) {
+        let instance = group * INSTANCES_PER_GROUP + 4;
+
+        self.draw(render_pass, &self.composite, depth, instance);
+    }
+
+    /// Composites the root target, with the given amount of groups, into
+    /// the current render pass.
+    pub fn composite_root<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        groups: usize,
+    ) {
+        let instance = groups * INSTANCES_PER_GROUP;
+
+        self.draw(render_pass, &self.composite, 0, instance);
+    }
+
+    fn blur(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        depth: usize,
+        instance: usize,
+    ) {
+        let target = &self.targets[depth];
+        let scratch = self.scratch.as_ref().expect("Scratch target");
+
+        for (source, destination, instance) in
+            [(target, scratch, instance), (scratch, target, instance + 1)]
+        {
+            let mut render_pass =
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("iced_wgpu::effect blur render pass"),
+                    color_attachments: &[Some(
+                        wgpu::RenderPassColorAttachment {
+                            view: &destination.view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Load,
+                                store: wgpu::StoreOp::Store,
+                            },
+                        },
+                    )],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+            render_pass.set_pipeline(&self.blur);
+            render_pass.set_bind_group(0, &self.constants, &[]);
+            render_pass.set_bind_group(1, &source.bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.instances.slice(..));
+            render_pass.draw(0..6, instance as u32..instance as u32 + 1);
+        }
+    }
+
+    fn draw<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        pipeline: &'a wgpu::RenderPipeline,
+        depth: usize,
+        instance: usize,
+    ) {
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &self.constants, &[]);
+        render_pass.set_bind_group(1, &self.targets[depth].bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.instances.slice(..));
+        render_pass.draw(0..6, instance as u32..instance as u32 + 1);
+    }
+}
+
+/// Returns the maximum nesting depth of the given groups.
+fn max_depth(groups: &[Group]) -> usize {
+    let mut ends: Vec<usize> = Vec::new();
+    let mut depth = 0;
+
+    for group in groups {
+        while ends.last().is_some_and(|end| *end <= group.layers.start) {
+            let _ = ends.pop();
+        }
+
+        ends.push(group.layers.end);
+        depth = depth.max(ends.len());
+    }
+
+    depth
+}
+
+#[derive(Debug)]
+struct Target {
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Target {
+    fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        texture_layout: &wgpu::BindGroupLayout,
+        size: Size<u32>,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("iced_wgpu::effect target"),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("iced_wgpu::effect target bind group"),
+            layout: texture_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&view),
+            }],
+        });
+
+        Self { view, bind_group }
+    }
+}
+
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Instance {
+    bounds: [f32; 4],
+    direction: [f32; 2],
+    sigma: f32,
+    opacity: f32,
+}
+
+impl Instance {
+    fn blur(bounds: [f32; 4], direction: [f32; 2], sigma: f32) -> Self {
+        Self {
+            bounds,
+            direction,
+            sigma,
+            opacity: 1.0,
+        }
+    }
+
+    fn composite(bounds: [f32; 4], opacity: f32) -> Self {
+        Self {
+            bounds,
+            direction: [0.0, 0.0],
+            sigma: 0.0,
+            opacity,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct Globals {
+    size: [f32; 2],
+    // Uniforms must be aligned to 16 bytes in some platforms
+    _padding: [f32; 2],
+}
@@ -1,4 +1,5 @@
 use crate::buffer;
+use crate::effect;
 use crate::graphics::Antialiasing;
 use crate::primitive;
 use crate::quad;
@@ -13,6 +14,7 @@ pub struct Engine {
     pub(crate) quad_pipeline: quad::Pipeline,
     pub(crate) text_pipeline: text::Pipeline,
     pub(crate) triangle_pipeline: triangle::Pipeline,
+    pub(crate) effect_pipeline: effect::Pipeline,
     #[cfg(any(feature = "image", feature = "svg"))]
     pub(crate) image_pipeline: crate::image::Pipeline,
     pub(crate) primitive_storage: primitive::Storage,
@@ -26,11 +28,6 @@ impl Engine {
         format: wgpu::TextureFormat,
         antialiasing: Option<Antialiasing>, // TODO: Initialize AA pipelines lazily
     ) -> Self {
-        let text_pipeline = text::Pipeline::new(device, queue, format);
-        let quad_pipeline = quad::Pipeline::new(device, format);
-        let triangle_pipeline =
-            triangle::Pipeline::new(device, format, antialiasing);
-
         #[cfg(any(feature = "image", feature = "svg"))]
         let image_pipeline = {
             let backend = _adapter.get_info().backend;
@@ -38,6 +35,17 @@ impl Engine {
             crate::image::Pipeline::new(device, format, backend)
         };
 
+        let text_pipeline = text::Pipeline::new(device, queue, format);
+        let quad_pipeline = quad::Pipeline::new(
+            device,
+            format,
+            #[cfg(feature = "image")]
+            image_pipeline.texture_layout(),
+        );
+        let triangle_pipeline =
+            triangle::Pipeline::new(device, format, antialiasing);
+        let effect_pipeline = effect::Pipeline::new(device, format);
+
         Self {
             // TODO: Resize belt smartly (?)
             // It would be great if the `StagingBelt` API exposed methods
@@ -50,6 +58,7 @@ impl Engine {
             quad_pipeline,
             text_pipeline,
             triangle_pipeline,
+            effect_pipeline,
 
             #[cfg(any(feature = "image", feature = "svg"))]
             image_pipeline,
@@ -7,7 +7,7 @@ use crate::graphics::cache::{self, Cached};
 use crate::graphics::color;
 use crate::graphics::geometry::fill::{self, Fill};
 use crate::graphics::geometry::{
-    self, LineCap, LineDash, LineJoin, Path, Stroke, Style,
+    self, stroke, LineCap, LineJoin, Path, Stroke, Style,
 };
 use crate::graphics::gradient::{self, Gradient};
 use crate::graphics::mesh::{self, Mesh};
@@ -18,7 +18,6 @@ use crate::triangle;
 use lyon::geom::euclid;
 use lyon::tessellation;
 
-use std::borrow::Cow;
 use std::sync::Arc;
 
 #[derive(Debug)]
@@ -223,17 +222,8 @@ impl geometry::frame::Backend for Frame {
             .buffers
             .get_stroke(&self.transforms.current.transform_style(stroke.style));
 
-        let mut options = tessellation::StrokeOptions::default();
-        options.line_width = stroke.width;
-        options.start_cap = into_line_cap(stroke.line_cap);
-        options.end_cap = into_line_cap(stroke.line_cap);
-        options.line_join = into_line_join(stroke.line_join);
-
-        let path = if stroke.line_dash.segments.is_empty() {
-            Cow::Borrowed(path)
-        } else {
-            Cow::Owned(dashed(path, stroke.line_dash))
-        };
+        let options = into_stroke_options(&stroke);
+        let path = stroke.apply(path);
 
         if self.transforms.current.is_identity() {
             self.stroke_tessellator.tessellate_path(
@@ -261,6 +251,13 @@ impl geometry::frame::Backend for Frame {
     ) {
         let stroke = stroke.into();
 
+        if !stroke.line_dash.segments.is_empty()
+            || stroke.alignment != stroke::Alignment::Center
+        {
+            self.stroke(&Path::rectangle(top_left, size), stroke);
+            return;
+        }
+
         let mut buffer = self
             .buffers
             .get_stroke(&self.transforms.current.transform_style(stroke.style));
@@ -276,11 +273,7 @@ impl geometry::frame::Backend for Frame {
                 lyon::math::Vector::new(size.width, size.height),
             );
 
-        let mut options = tessellation::StrokeOptions::default();
-        options.line_width = stroke.width;
-        options.start_cap = into_line_cap(stroke.line_cap);
-        options.end_cap = into_line_cap(stroke.line_cap);
-        options.line_join = into_line_join(stroke.line_join);
+        let options = into_stroke_options(&stroke);
 
         self.stroke_tessellator
             .tessellate_rectangle(
@@ -296,7 +289,8 @@ impl geometry::frame::Backend for Frame {
 
         let (scale_x, scale_y) = self.transforms.current.scale();
 
-        if self.transforms.current.is_scale_translation()
+        if text.is_plain()
+            && self.transforms.current.is_scale_translation()
             && scale_x == scale_y
             && scale_x > 0.0
             && scale_y > 0.0
@@ -598,6 +592,19 @@ impl Transform {
                 linear.start = self.transform_point(linear.start);
                 linear.end = self.transform_point(linear.end);
             }
+            Gradient::Radial(radial) => {
+                let center = self.transform_point(radial.center);
+                let edge = self.transform_point(
+                    radial.center + Vector::new(radial.radius, 0.0),
+                );
+
+                radial.center = center;
+                radial.radius = center.distance(edge);
+            }
+            Gradient::Conic(conic) => {
+                conic.center = self.transform_point(conic.center);
+                conic.angle.0 += self.0.m12.atan2(self.0.m11);
+            }
         }
 
         gradient
@@ -688,6 +695,18 @@ impl tessellation::StrokeVertexConstructor<mesh::SolidVertex2D>
     }
 }
 
+fn into_stroke_options(stroke: &Stroke<'_>) -> tessellation::StrokeOptions {
+    let mut options = tessellation::StrokeOptions::default();
+    options.line_width = stroke.width;
+    options.start_cap = into_line_cap(stroke.line_cap);
+    options.end_cap = into_line_cap(stroke.line_cap);
+    options.line_join = into_line_join(stroke.line_join);
+
+    options.miter_limit = stroke.miter_limit.max(2.0);
+
+    options
+}
+
 fn into_line_join(line_join: LineJoin) -> lyon::tessellation::LineJoin {
     match line_join {
         LineJoin::Miter => lyon::tessellation::LineJoin::Miter,
@@ -710,47 +729,3 @@ fn into_fill_rule(rule: fill::Rule) -> lyon::tessellation::FillRule {
         fill::Rule::EvenOdd => lyon::tessellation::FillRule::EvenOdd,
     }
 }
-
-pub(super) fn dashed(path: &Path, line_dash: LineDash<'_>) -> Path {
-    use lyon::algorithms::walk::{
-        walk_along_path, RepeatedPattern, WalkerEvent,
-    };
-    use lyon::path::iterator::PathIterator;
-
-    Path::new(|builder| {
-        let segments_odd = (line_dash.segments.len() % 2 == 1)
-            .then(|| [line_dash.segments, line_dash.segments].concat());
-
-        let mut draw_line = false;
-
-        walk_along_path(
-            path.raw().iter().flattened(
-                lyon::tessellation::StrokeOptions::DEFAULT_TOLERANCE,
-            ),
-            0.0,
-            lyon::tessellation::StrokeOptions::DEFAULT_TOLERANCE,
-            &mut RepeatedPattern {
-                callback: |event: WalkerEvent<'_>| {
-                    let point = Point {
-                        x: event.position.x,
-                        y: event.position.y,
-                    };
-
-                    if draw_line {
-                        builder.line_to(point);
-                    } else {
-                        builder.move_to(point);
-                    }
-
-                    draw_line = !draw_line;
-
-                    true
-                },
-                index: line_dash.offset,
-                intervals: segments_odd
-                    .as_deref()
-                    .unwrap_or(line_dash.segments),
-            },
-        );
-    })
-}
@@ -1,4 +1,5 @@
 use crate::core::{self, Size};
+use crate::graphics::image::Stats;
 use crate::image::atlas::{self, Atlas};
 
 use std::sync::Arc;
@@ -10,6 +11,7 @@ pub struct Cache {
     raster: crate::image::raster::Cache,
     #[cfg(feature = "svg")]
     vector: crate::image::vector::Cache,
+    budget: Option<usize>,
 }
 
 impl Cache {
@@ -24,6 +26,7 @@ impl Cache {
             raster: crate::image::raster::Cache::default(),
             #[cfg(feature = "svg")]
             vector: crate::image::vector::Cache::default(),
+            budget: None,
         }
     }
 
@@ -35,14 +38,48 @@ impl Cache {
         self.atlas.layer_count()
     }
 
+    pub fn set_budget(&mut self, budget: Option<usize>) {
+        self.budget = budget;
+    }
+
+    #[cfg(feature = "image")]
+    pub fn set_blocking(&mut self, is_blocking: bool) {
+        self.raster.set_blocking(is_blocking);
+    }
+
+    #[cfg(feature = "image")]
+    pub fn set_waker(&mut self, waker: Option<crate::graphics::image::Waker>) {
+        self.raster.set_waker(waker);
+    }
+
+    pub fn stats(&self) -> Stats {
+        let stats = Stats {
+            atlas_layers: self.atlas.layer_count(),
+            ..Stats::default()
+        };
+
+        #[cfg(feature = "image")]
+        let stats = stats + self.raster.stats();
+
+        #[cfg(feature = "svg")]
+        let stats = stats + self.vector.stats();
+
+        stats
+    }
+
     #[cfg(feature = "image")]
     pub fn measure_image(&mut self, handle: &core::image::Handle) -> Size<u32> {
-        self.raster.load(handle).dimensions()
+        self.raster.measure(handle)
+    }
+
+    #[cfg(feature = "image")]
+    pub fn is_image_loading(&mut self, handle: &core::image::Handle) -> bool {
+        self.raster.is_loading(handle)
     }
 
     #[cfg(feature = "svg")]
     pub fn measure_svg(&mut self, handle: &core::svg::Handle) -> Size<u32> {
-        self.vector.load(handle).viewport_dimensions()
+        self.vector.measure(handle)
     }
 
     #[cfg(feature = "image")]
@@ -62,6 +99,7 @@ impl Cache {
         encoder: &mut wgpu::CommandEncoder,
         handle: &core::svg::Handle,
         color: Option<core::Color>,
+        classes: &crate::graphics::svg::Classes,
         size: [f32; 2],
         scale: f32,
     ) -> Option<&atlas::Entry> {
@@ -70,6 +108,7 @@ impl Cache {
             encoder,
             handle,
             color,
+            classes,
             size,
             scale,
             &mut self.atlas,
@@ -77,10 +116,22 @@ impl Cache {
     }
 
     pub fn trim(&mut self) {
+        // The budget is shared, so each cache is trimmed to fit next to
+        // the memory used by the rest
         #[cfg(feature = "image")]
-        self.raster.trim(&mut self.atlas);
+        {
+            let rest = self.stats().memory - self.raster.stats().memory;
+            let budget = self.budget.map(|budget| budget.saturating_sub(rest));
+
+            self.raster.trim(&mut self.atlas, budget);
+        }
 
         #[cfg(feature = "svg")]
-        self.vector.trim(&mut self.atlas);
+        {
+            let rest = self.stats().memory - self.vector.stats().memory;
+            let budget = self.budget.map(|budget| budget.saturating_sub(rest));
+
+            self.vector.trim(&mut self.atlas, budget);
+        }
     }
 }
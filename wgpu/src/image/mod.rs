@@ -1,7 +1,7 @@
 pub(crate) mod cache;
 pub(crate) use cache::Cache;
 
-mod atlas;
+pub(crate) mod atlas;
 
 #[cfg(feature = "image")]
 mod raster;
@@ -9,7 +9,8 @@ mod raster;
 #[cfg(feature = "svg")]
 mod vector;
 
-use crate::core::{Rectangle, Size, Transformation};
+use crate::core::{self, Rectangle, Size, Transformation};
+use crate::graphics::color;
 use crate::Buffer;
 
 use bytemuck::{Pod, Zeroable};
@@ -114,11 +115,23 @@ impl Pipeline {
             device.create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: Some("iced_wgpu image shader"),
                 source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(
-                    concat!(
-                        include_str!("../shader/vertex.wgsl"),
-                        "\n",
-                        include_str!("../shader/image.wgsl"),
-                    ),
+                    if color::GAMMA_CORRECTION {
+                        concat!(
+                            include_str!("../shader/vertex.wgsl"),
+                            "\n",
+                            include_str!("../shader/image.wgsl"),
+                            "\n",
+                            include_str!("../shader/image/srgb.wgsl"),
+                        )
+                    } else {
+                        concat!(
+                            include_str!("../shader/vertex.wgsl"),
+                            "\n",
+                            include_str!("../shader/image.wgsl"),
+                            "\n",
+                            include_str!("../shader/image/passthrough.wgsl"),
+                        )
+                    },
                 )),
             });
 
@@ -151,6 +164,14 @@ impl Pipeline {
                             7 => Sint32,
                             // Snap
                             8 => Uint32,
+                            // Tint
+                            9 => Float32x4,
+                            // Adjustments
+                            10 => Float32x4,
+                            11 => Float32x4,
+                            12 => Float32x4,
+                            // Adjust
+                            13 => Uint32,
                         ),
                     }],
                     compilation_options:
@@ -209,6 +230,11 @@ impl Pipeline {
         Cache::new(device, self.backend, self.texture_layout.clone())
     }
 
+    #[cfg(feature = "image")]
+    pub fn texture_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.texture_layout
+    }
+
     pub fn prepare(
         &mut self,
         device: &wgpu::Device,
@@ -232,9 +258,19 @@ impl Pipeline {
                         add_instances(
                             [bounds.x, bounds.y],
                             [bounds.width, bounds.height],
+                            image.crop,
                             f32::from(image.rotation),
                             image.opacity,
                             image.snap,
+                            [
+                                image.tint.r,
+                                image.tint.g,
+                                image.tint.b,
+                                image.tint.a,
+                            ],
+                            image.adjustments.matrix(),
+                            image.tint != core::Color::WHITE
+                                || !image.adjustments.is_none(),
                             atlas_entry,
                             match image.filter_method {
                                 crate::core::image::FilterMethod::Nearest => {
@@ -259,15 +295,20 @@ impl Pipeline {
                         encoder,
                         &svg.handle,
                         svg.color,
+                        &svg.classes,
                         size,
                         scale,
                     ) {
                         add_instances(
                             [bounds.x, bounds.y],
                             size,
+                            None,
                             f32::from(svg.rotation),
                             svg.opacity,
                             true,
+                            [1.0; 4],
+                            core::image::Adjustments::NONE.matrix(),
+                            false,
                             atlas_entry,
                             nearest_instances,
                         );
@@ -497,6 +538,9 @@ struct Instance {
     _size_in_atlas: [f32; 2],
     _layer: u32,
     _snap: u32,
+    _tint: [f32; 4],
+    _adjustments: [[f32; 4]; 3],
+    _adjust: u32,
 }
 
 impl Instance {
@@ -516,58 +560,85 @@ struct Uniforms {
 fn add_instances(
     image_position: [f32; 2],
     image_size: [f32; 2],
+    crop: Option<Rectangle<u32>>,
     rotation: f32,
     opacity: f32,
     snap: bool,
+    tint: [f32; 4],
+    adjustments: [[f32; 4]; 3],
+    adjust: bool,
     entry: &atlas::Entry,
     instances: &mut Vec<Instance>,
 ) {
+    let Some(region) = core::image::region(crop, entry.size()) else {
+        return;
+    };
+
     let center = [
         image_position[0] + image_size[0] / 2.0,
         image_position[1] + image_size[1] / 2.0,
     ];
 
-    match entry {
-        atlas::Entry::Contiguous(allocation) => {
+    let scaling_x = image_size[0] / region.width as f32;
+    let scaling_y = image_size[1] / region.height as f32;
+
+    // Draw the part of each fragment that falls inside the cropped region
+    let mut add_fragment =
+        |(fragment_x, fragment_y): (u32, u32),
+         allocation: &atlas::Allocation| {
+            let Size { width, height } = allocation.size();
+
+            let left = fragment_x.max(region.x);
+            let top = fragment_y.max(region.y);
+            let right = (fragment_x + width).min(region.x + region.width);
+            let bottom = (fragment_y + height).min(region.y + region.height);
+
+            if left >= right || top >= bottom {
+                return;
+            }
+
+            let [x, y] = image_position;
+
+            let position = [
+                x + (left - region.x) as f32 * scaling_x,
+                y + (top - region.y) as f32 * scaling_y,
+            ];
+
+            let size = [
+                (right - left) as f32 * scaling_x,
+                (bottom - top) as f32 * scaling_y,
+            ];
+
+            let (atlas_x, atlas_y) = allocation.position();
+
             add_instance(
-                image_position,
+                position,
                 center,
-                image_size,
+                size,
                 rotation,
                 opacity,
                 snap,
-                allocation,
+                tint,
+                adjustments,
+                adjust,
+                Rectangle {
+                    x: atlas_x + left - fragment_x,
+                    y: atlas_y + top - fragment_y,
+                    width: right - left,
+                    height: bottom - top,
+                },
+                allocation.layer(),
                 instances,
             );
-        }
-        atlas::Entry::Fragmented { fragments, size } => {
-            let scaling_x = image_size[0] / size.width as f32;
-            let scaling_y = image_size[1] / size.height as f32;
+        };
 
+    match entry {
+        atlas::Entry::Contiguous(allocation) => {
+            add_fragment((0, 0), allocation);
+        }
+        atlas::Entry::Fragmented { fragments, .. } => {
             for fragment in fragments {
-                let allocation = &fragment.allocation;
-
-                let [x, y] = image_position;
-                let (fragment_x, fragment_y) = fragment.position;
-                let Size {
-                    width: fragment_width,
-                    height: fragment_height,
-                } = allocation.size();
-
-                let position = [
-                    x + fragment_x as f32 * scaling_x,
-                    y + fragment_y as f32 * scaling_y,
-                ];
-
-                let size = [
-                    fragment_width as f32 * scaling_x,
-                    fragment_height as f32 * scaling_y,
-                ];
-
-                add_instance(
-                    position, center, size, rotation, opacity, snap,
-                    allocation, instances,
-                );
+                add_fragment(fragment.position, &fragment.allocation);
             }
         }
     }
@@ -581,12 +652,19 @@ fn add_instance(
     rotation: f32,
     opacity: f32,
     snap: bool,
-    allocation: &atlas::Allocation,
+    tint: [f32; 4],
+    adjustments: [[f32; 4]; 3],
+    adjust: bool,
+    region_in_atlas: Rectangle<u32>,
+    layer: usize,
     instances: &mut Vec<Instance>,
 ) {
-    let (x, y) = allocation.position();
-    let Size { width, height } = allocation.size();
-    let layer = allocation.layer();
+    let Rectangle {
+        x,
+        y,
+        width,
+        height,
+    } = region_in_atlas;
 
     let instance = Instance {
         _position: position,
@@ -604,6 +682,9 @@ fn add_instance(
         ],
         _layer: layer as u32,
         _snap: snap as u32,
+        _tint: tint,
+        _adjustments: adjustments,
+        _adjust: adjust as u32,
     };
 
     instances.push(instance);
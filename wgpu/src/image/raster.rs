@@ -1,11 +1,9 @@
 use crate::core::image;
 use crate::core::Size;
 use crate::graphics;
-use crate::graphics::image::image_rs;
+use crate::graphics::image::{image_rs, Decoder, Lru, Stats};
 use crate::image::atlas::{self, Atlas};
 
-use rustc_hash::{FxHashMap, FxHashSet};
-
 /// Entry in cache corresponding to an image handle
 #[derive(Debug)]
 pub enum Memory {
@@ -13,6 +11,8 @@ pub enum Memory {
     Host(image_rs::ImageBuffer<image_rs::Rgba<u8>, image::Bytes>),
     /// Storage entry
     Device(atlas::Entry),
+    /// Image data being decoded, with its known dimensions
+    Decoding(Size<u32>),
     /// Image not found
     NotFound,
     /// Invalid image data
@@ -20,6 +20,17 @@ pub enum Memory {
 }
 
 impl Memory {
+    /// Memory corresponding to the result of loading an image
+    fn from_load(
+        result: image_rs::ImageResult<graphics::image::Buffer>,
+    ) -> Self {
+        match result {
+            Ok(image) => Memory::Host(image),
+            Err(image_rs::error::ImageError::IoError(_)) => Memory::NotFound,
+            Err(_) => Memory::Invalid,
+        }
+    }
+
     /// Width and height of image
     pub fn dimensions(&self) -> Size<u32> {
         match self {
@@ -29,37 +40,103 @@ impl Memory {
                 Size::new(width, height)
             }
             Memory::Device(entry) => entry.size(),
+            Memory::Decoding(size) => *size,
             Memory::NotFound => Size::new(1, 1),
             Memory::Invalid => Size::new(1, 1),
         }
     }
+
+    /// Amount of bytes taken by the image data
+    pub fn size(&self) -> usize {
+        match self {
+            Memory::Host(image) => image.as_raw().len(),
+            Memory::Device(entry) => {
+                let Size { width, height } = entry.size();
+
+                width as usize * height as usize * 4
+            }
+            Memory::Decoding(_) | Memory::NotFound | Memory::Invalid => 0,
+        }
+    }
 }
 
 /// Caches image raster data
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Cache {
-    map: FxHashMap<image::Id, Memory>,
-    hits: FxHashSet<image::Id>,
-    should_trim: bool,
+    map: Lru<image::Id, Memory>,
+    decoder: Decoder<Memory>,
 }
 
 impl Cache {
-    /// Load image
+    /// Sets whether images are decoded synchronously when first loaded
+    pub fn set_blocking(&mut self, is_blocking: bool) {
+        self.decoder.set_blocking(is_blocking);
+    }
+
+    /// Sets the waker called whenever an image is decoded in the background
+    pub fn set_waker(&mut self, waker: Option<graphics::image::Waker>) {
+        self.decoder.set_waker(waker);
+    }
+
+    /// Load image for drawing, decoding encoded data in the background
     pub fn load(&mut self, handle: &image::Handle) -> &mut Memory {
-        if self.contains(handle) {
-            return self.get(handle).unwrap();
+        self.receive();
+
+        let id = handle.id();
+
+        if self.map.contains(&id) {
+            return self.map.get(&id).unwrap();
         }
 
-        let memory = match graphics::image::load(handle) {
-            Ok(image) => Memory::Host(image),
-            Err(image_rs::error::ImageError::IoError(_)) => Memory::NotFound,
-            Err(_) => Memory::Invalid,
+        self.insert(handle)
+    }
+
+    /// Measure image, loading it without counting a cache hit
+    pub fn measure(&mut self, handle: &image::Handle) -> Size<u32> {
+        self.receive();
+
+        if let Some(memory) = self.map.touch(&handle.id()) {
+            return memory.dimensions();
+        }
+
+        self.insert(handle).dimensions()
+    }
+
+    fn insert(&mut self, handle: &image::Handle) -> &mut Memory {
+        let memory = if matches!(handle, image::Handle::Rgba { .. })
+            || self.decoder.is_blocking()
+        {
+            self.decoder.load(handle)
+        } else {
+            match graphics::image::dimensions(handle) {
+                Ok(size) => {
+                    self.decoder.request(handle);
+
+                    Memory::Decoding(size)
+                }
+                Err(error) => Memory::from_load(Err(error)),
+            }
         };
 
-        self.should_trim = true;
+        let size = memory.size();
+
+        self.map.insert(handle.id(), memory, size)
+    }
+
+    /// Returns true if the image is still being decoded
+    pub fn is_loading(&mut self, handle: &image::Handle) -> bool {
+        self.receive();
+
+        matches!(self.map.peek(&handle.id()), Some(Memory::Decoding(_)))
+    }
+
+    /// Store the images decoded since the last call
+    fn receive(&mut self) {
+        for (id, memory) in self.decoder.poll() {
+            let size = memory.size();
 
-        self.insert(handle, memory);
-        self.get(handle).unwrap()
+            self.map.replace(&id, memory, size);
+        }
     }
 
     /// Load image and upload raster data
@@ -87,42 +164,27 @@ impl Cache {
         }
     }
 
-    /// Trim cache misses from cache
-    pub fn trim(&mut self, atlas: &mut Atlas) {
-        // Only trim if new entries have landed in the `Cache`
-        if !self.should_trim {
-            return;
-        }
-
-        let hits = &self.hits;
-
-        self.map.retain(|k, memory| {
-            let retain = hits.contains(k);
-
-            if !retain {
-                if let Memory::Device(entry) = memory {
-                    atlas.remove(entry);
-                }
+    /// Trim cache misses from cache, keeping the most recently used
+    /// entries within the given memory `budget`
+    pub fn trim(&mut self, atlas: &mut Atlas, budget: Option<usize>) {
+        self.map.trim(budget, |memory| {
+            if let Memory::Device(entry) = memory {
+                atlas.remove(&entry);
             }
-
-            retain
         });
-
-        self.hits.clear();
-        self.should_trim = false;
-    }
-
-    fn get(&mut self, handle: &image::Handle) -> Option<&mut Memory> {
-        let _ = self.hits.insert(handle.id());
-
-        self.map.get_mut(&handle.id())
     }
 
-    fn insert(&mut self, handle: &image::Handle, memory: Memory) {
-        let _ = self.map.insert(handle.id(), memory);
+    /// Returns the [`Stats`] of the cache
+    pub fn stats(&self) -> Stats {
+        self.map.stats()
     }
+}
 
-    fn contains(&self, handle: &image::Handle) -> bool {
-        self.map.contains_key(&handle.id())
+impl Default for Cache {
+    fn default() -> Self {
+        Self {
+            map: Lru::new(),
+            decoder: Decoder::new(Memory::from_load),
+        }
     }
 }
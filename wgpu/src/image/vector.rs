@@ -1,12 +1,11 @@
 use crate::core::svg;
 use crate::core::{Color, Size};
+use crate::graphics::image::{Lru, Stats};
+use crate::graphics::svg::{key, load, memory, Classes};
 use crate::image::atlas::{self, Atlas};
 
 use resvg::tiny_skia;
 use resvg::usvg;
-use rustc_hash::{FxHashMap, FxHashSet};
-use std::fs;
-use std::sync::Arc;
 
 /// Entry in cache corresponding to an svg handle
 pub enum Svg {
@@ -28,65 +27,58 @@ impl Svg {
             Svg::NotFound => Size::new(1, 1),
         }
     }
+
+    /// Amount of bytes taken by the parsed svg
+    pub fn size(&self) -> usize {
+        match self {
+            Svg::Loaded(tree) => memory(tree),
+            Svg::NotFound => 0,
+        }
+    }
 }
 
 /// Caches svg vector and raster data
 #[derive(Debug, Default)]
 pub struct Cache {
-    svgs: FxHashMap<u64, Svg>,
-    rasterized: FxHashMap<(u64, u32, u32, ColorFilter), atlas::Entry>,
-    svg_hits: FxHashSet<u64>,
-    rasterized_hits: FxHashSet<(u64, u32, u32, ColorFilter)>,
-    should_trim: bool,
-    fontdb: Option<Arc<usvg::fontdb::Database>>,
+    svgs: Lru<u64, Svg>,
+    rasterized: Lru<(u64, u32, u32, ColorFilter), atlas::Entry>,
 }
 
 type ColorFilter = Option<[u8; 4]>;
 
 impl Cache {
     /// Load svg
-    pub fn load(&mut self, handle: &svg::Handle) -> &Svg {
-        if self.svgs.contains_key(&handle.id()) {
-            return self.svgs.get(&handle.id()).unwrap();
+    pub fn load(&mut self, handle: &svg::Handle, classes: &Classes) -> &Svg {
+        let key = key(handle, classes);
+
+        if self.svgs.contains(&key) {
+            return self.svgs.get(&key).unwrap();
         }
 
-        // TODO: Reuse `cosmic-text` font database
-        if self.fontdb.is_none() {
-            let mut fontdb = usvg::fontdb::Database::new();
-            fontdb.load_system_fonts();
+        self.insert(key, handle, classes)
+    }
+
+    /// Measure svg, loading it without counting a cache hit
+    pub fn measure(&mut self, handle: &svg::Handle) -> Size<u32> {
+        let key = key(handle, &[]);
 
-            self.fontdb = Some(Arc::new(fontdb));
+        if let Some(svg) = self.svgs.touch(&key) {
+            return svg.viewport_dimensions();
         }
 
-        let options = usvg::Options {
-            fontdb: self
-                .fontdb
-                .as_ref()
-                .expect("fontdb must be initialized")
-                .clone(),
-            ..usvg::Options::default()
-        };
-
-        let svg = match handle.data() {
-            svg::Data::Path(path) => fs::read_to_string(path)
-                .ok()
-                .and_then(|contents| {
-                    usvg::Tree::from_str(&contents, &options).ok()
-                })
-                .map(Svg::Loaded)
-                .unwrap_or(Svg::NotFound),
-            svg::Data::Bytes(bytes) => {
-                match usvg::Tree::from_data(bytes, &options) {
-                    Ok(tree) => Svg::Loaded(tree),
-                    Err(_) => Svg::NotFound,
-                }
-            }
-        };
+        self.insert(key, handle, &[]).viewport_dimensions()
+    }
 
-        self.should_trim = true;
+    fn insert(
+        &mut self,
+        key: u64,
+        handle: &svg::Handle,
+        classes: &Classes,
+    ) -> &Svg {
+        let svg = load(handle, classes).map_or(Svg::NotFound, Svg::Loaded);
+        let size = svg.size();
 
-        let _ = self.svgs.insert(handle.id(), svg);
-        self.svgs.get(&handle.id()).unwrap()
+        self.svgs.insert(key, svg, size)
     }
 
     /// Load svg and upload raster data
@@ -96,11 +88,12 @@ impl Cache {
         encoder: &mut wgpu::CommandEncoder,
         handle: &svg::Handle,
         color: Option<Color>,
+        classes: &Classes,
         [width, height]: [f32; 2],
         scale: f32,
         atlas: &mut Atlas,
     ) -> Option<&atlas::Entry> {
-        let id = handle.id();
+        let id = key(handle, classes);
 
         let (width, height) = (
             (scale * width).ceil() as u32,
@@ -114,14 +107,13 @@ impl Cache {
         // We currently rerasterize the SVG when its size changes. This is slow
         // as heck. A GPU rasterizer like `pathfinder` may perform better.
         // It would be cool to be able to smooth resize the `svg` example.
-        if self.rasterized.contains_key(&key) {
-            let _ = self.svg_hits.insert(id);
-            let _ = self.rasterized_hits.insert(key);
+        if self.rasterized.contains(&key) {
+            let _ = self.svgs.touch(&id);
 
-            return self.rasterized.get(&key);
+            return self.rasterized.get(&key).map(|entry| &*entry);
         }
 
-        match self.load(handle) {
+        match self.load(handle, classes) {
             Svg::Loaded(tree) => {
                 if width == 0 || height == 0 {
                     return None;
@@ -172,38 +164,39 @@ impl Cache {
 
                 log::debug!("allocating {id} {width}x{height}");
 
-                let _ = self.svg_hits.insert(id);
-                let _ = self.rasterized_hits.insert(key);
-                let _ = self.rasterized.insert(key, allocation);
+                let size = width as usize * height as usize * 4;
 
-                self.rasterized.get(&key)
+                Some(&*self.rasterized.insert(key, allocation, size))
             }
             Svg::NotFound => None,
         }
     }
 
-    /// Load svg and upload raster data
-    pub fn trim(&mut self, atlas: &mut Atlas) {
-        if !self.should_trim {
-            return;
-        }
+    /// Trim cache misses from cache, keeping the most recently used
+    /// rasterizations within the given memory `budget`
+    pub fn trim(&mut self, atlas: &mut Atlas, budget: Option<usize>) {
+        self.svgs.trim(None, drop);
 
-        let svg_hits = &self.svg_hits;
-        let rasterized_hits = &self.rasterized_hits;
+        let trees = self.svgs.stats().memory;
 
-        self.svgs.retain(|k, _| svg_hits.contains(k));
-        self.rasterized.retain(|k, entry| {
-            let retain = rasterized_hits.contains(k);
+        self.rasterized.trim(
+            budget.map(|budget| budget.saturating_sub(trees)),
+            |entry| {
+                atlas.remove(&entry);
+            },
+        );
+    }
 
-            if !retain {
-                atlas.remove(entry);
-            }
+    /// Returns the [`Stats`] of the cache
+    pub fn stats(&self) -> Stats {
+        let rasterized = self.rasterized.stats();
 
-            retain
-        });
-        self.svg_hits.clear();
-        self.rasterized_hits.clear();
-        self.should_trim = false;
+        // Parsed trees are loaded once per rasterization miss, so only
+        // their memory is counted
+        Stats {
+            memory: rasterized.memory + self.svgs.stats().memory,
+            ..rasterized
+        }
     }
 }
 
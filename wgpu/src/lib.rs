@@ -32,6 +32,7 @@ pub mod geometry;
 
 mod buffer;
 mod color;
+mod effect;
 mod engine;
 mod quad;
 mod text;
@@ -109,6 +110,32 @@ impl Renderer {
         }
     }
 
+    /// Sets the maximum amount of memory, in bytes, that the cached images
+    /// of the [`Renderer`] may use before the least recently drawn ones are
+    /// evicted.
+    ///
+    /// If `None`, images are evicted as soon as they stop being drawn.
+    #[cfg(any(feature = "svg", feature = "image"))]
+    pub fn set_image_cache_budget(&mut self, budget: Option<usize>) {
+        self.image_cache.get_mut().set_budget(budget);
+    }
+
+    /// Sets whether the [`Renderer`] decodes images synchronously when they
+    /// are first drawn, instead of in the background.
+    ///
+    /// Useful to render a single frame offscreen.
+    #[cfg(feature = "image")]
+    pub fn set_blocking_image_decoding(&mut self, is_blocking: bool) {
+        self.image_cache.get_mut().set_blocking(is_blocking);
+    }
+
+    /// Sets the [`graphics::image::Waker`] called whenever an image of the
+    /// [`Renderer`] finishes loading in the background.
+    #[cfg(feature = "image")]
+    pub fn set_image_waker(&mut self, waker: Option<graphics::image::Waker>) {
+        self.image_cache.get_mut().set_waker(waker);
+    }
+
     pub fn present<T: AsRef<str>>(
         &mut self,
         engine: &mut Engine,
@@ -145,12 +172,23 @@ impl Renderer {
 
         self.text_viewport.update(queue, viewport.physical_size());
 
+        engine.effect_pipeline.prepare(
+            device,
+            encoder,
+            &mut engine.staging_belt,
+            self.layers.groups(),
+            scale_factor,
+            viewport.physical_size(),
+        );
+
         for layer in self.layers.iter_mut() {
             if !layer.quads.is_empty() {
                 engine.quad_pipeline.prepare(
                     device,
                     encoder,
                     &mut engine.staging_belt,
+                    #[cfg(feature = "image")]
+                    &mut self.image_cache.borrow_mut(),
                     &layer.quads,
                     viewport.projection(),
                     scale_factor,
@@ -220,34 +258,39 @@ impl Renderer {
     ) {
         use std::mem::ManuallyDrop;
 
-        let mut render_pass = ManuallyDrop::new(encoder.begin_render_pass(
-            &wgpu::RenderPassDescriptor {
-                label: Some("iced_wgpu render pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: frame,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: match clear_color {
-                            Some(background_color) => wgpu::LoadOp::Clear({
-                                let [r, g, b, a] =
-                                    graphics::color::pack(background_color)
-                                        .components();
-
-                                wgpu::Color {
-                                    r: f64::from(r),
-                                    g: f64::from(g),
-                                    b: f64::from(b),
-                                    a: f64::from(a),
-                                }
-                            }),
-                            None => wgpu::LoadOp::Load,
-                        },
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
+        let clear = match clear_color {
+            Some(background_color) => wgpu::LoadOp::Clear({
+                let [r, g, b, a] =
+                    graphics::color::pack(background_color).components();
+
+                wgpu::Color {
+                    r: f64::from(r),
+                    g: f64::from(g),
+                    b: f64::from(b),
+                    a: f64::from(a),
+                }
+            }),
+            None => wgpu::LoadOp::Load,
+        };
+
+        // Effects need to read what has been drawn so far, so the whole
+        // frame is drawn offscreen when there are any
+        let groups = self.layers.groups();
+        let effects = &engine.effect_pipeline;
+
+        let mut target = if groups.is_empty() {
+            frame
+        } else {
+            effects.target(0)
+        };
+
+        let mut render_pass = ManuallyDrop::new(begin_render_pass(
+            encoder,
+            target,
+            if groups.is_empty() {
+                clear
+            } else {
+                wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT)
             },
         ));
 
@@ -267,123 +310,151 @@ impl Renderer {
 
         let scale = Transformation::scale(scale_factor);
 
-        for layer in self.layers.iter() {
-            let Some(physical_bounds) =
-                physical_bounds.intersection(&(layer.bounds * scale))
-            else {
-                continue;
-            };
+        let mut pending_groups = groups.iter().enumerate().peekable();
+        let mut open_groups: Vec<(usize, &graphics::layer::Group)> = Vec::new();
 
-            let Some(scissor_rect) = physical_bounds.snap() else {
-                continue;
-            };
-
-            if !layer.quads.is_empty() {
-                engine.quad_pipeline.render(
-                    quad_layer,
-                    scissor_rect,
-                    &layer.quads,
-                    &mut render_pass,
-                );
+        for (index, layer) in self.layers.iter().enumerate() {
+            if let Some((group_index, group)) =
+                pending_groups.next_if(|(_, group)| group.layers.start == index)
+            {
+                let _ = ManuallyDrop::into_inner(render_pass);
 
-                quad_layer += 1;
-            }
+                if group.effect.backdrop_blur > 0.0 {
+                    effects.blur_backdrop(
+                        encoder,
+                        open_groups.len(),
+                        group_index,
+                    );
+                }
 
-            if !layer.triangles.is_empty() {
-                let _ = ManuallyDrop::into_inner(render_pass);
+                open_groups.push((group_index, group));
+                target = effects.target(open_groups.len());
 
-                mesh_layer += engine.triangle_pipeline.render(
+                render_pass = ManuallyDrop::new(begin_render_pass(
                     encoder,
-                    frame,
-                    &self.triangle_storage,
-                    mesh_layer,
-                    &layer.triangles,
-                    physical_bounds,
-                    scale,
-                );
-
-                render_pass = ManuallyDrop::new(encoder.begin_render_pass(
-                    &wgpu::RenderPassDescriptor {
-                        label: Some("iced_wgpu render pass"),
-                        color_attachments: &[Some(
-                            wgpu::RenderPassColorAttachment {
-                                view: frame,
-                                resolve_target: None,
-                                ops: wgpu::Operations {
-                                    load: wgpu::LoadOp::Load,
-                                    store: wgpu::StoreOp::Store,
-                                },
-                            },
-                        )],
-                        depth_stencil_attachment: None,
-                        timestamp_writes: None,
-                        occlusion_query_set: None,
-                    },
+                    target,
+                    wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
                 ));
             }
 
-            if !layer.primitives.is_empty() {
-                let _ = ManuallyDrop::into_inner(render_pass);
+            if let Some(physical_bounds) =
+                physical_bounds.intersection(&(layer.bounds * scale))
+            {
+                if let Some(scissor_rect) = physical_bounds.snap() {
+                    if !layer.quads.is_empty() {
+                        engine.quad_pipeline.render(
+                            quad_layer,
+                            scissor_rect,
+                            &layer.quads,
+                            #[cfg(feature = "image")]
+                            image_cache.bind_group(),
+                            &mut render_pass,
+                        );
 
-                for instance in &layer.primitives {
-                    if let Some(clip_bounds) = (instance.bounds * scale)
-                        .intersection(&physical_bounds)
-                        .and_then(Rectangle::snap)
-                    {
-                        instance.primitive.render(
+                        quad_layer += 1;
+                    }
+
+                    if !layer.triangles.is_empty() {
+                        let _ = ManuallyDrop::into_inner(render_pass);
+
+                        mesh_layer += engine.triangle_pipeline.render(
                             encoder,
-                            &engine.primitive_storage,
-                            frame,
-                            &clip_bounds,
+                            target,
+                            &self.triangle_storage,
+                            mesh_layer,
+                            &layer.triangles,
+                            physical_bounds,
+                            scale,
                         );
+
+                        render_pass = ManuallyDrop::new(begin_render_pass(
+                            encoder,
+                            target,
+                            wgpu::LoadOp::Load,
+                        ));
                     }
-                }
 
-                render_pass = ManuallyDrop::new(encoder.begin_render_pass(
-                    &wgpu::RenderPassDescriptor {
-                        label: Some("iced_wgpu render pass"),
-                        color_attachments: &[Some(
-                            wgpu::RenderPassColorAttachment {
-                                view: frame,
-                                resolve_target: None,
-                                ops: wgpu::Operations {
-                                    load: wgpu::LoadOp::Load,
-                                    store: wgpu::StoreOp::Store,
-                                },
-                            },
-                        )],
-                        depth_stencil_attachment: None,
-                        timestamp_writes: None,
-                        occlusion_query_set: None,
-                    },
-                ));
-            }
+                    if !layer.primitives.is_empty() {
+                        let _ = ManuallyDrop::into_inner(render_pass);
+
+                        for instance in &layer.primitives {
+                            if let Some(clip_bounds) = (instance.bounds * scale)
+                                .intersection(&physical_bounds)
+                                .and_then(Rectangle::snap)
+                            {
+                                instance.primitive.render(
+                                    encoder,
+                                    &engine.primitive_storage,
+                                    target,
+                                    &clip_bounds,
+                                );
+                            }
+                        }
+
+                        render_pass = ManuallyDrop::new(begin_render_pass(
+                            encoder,
+                            target,
+                            wgpu::LoadOp::Load,
+                        ));
+                    }
 
-            #[cfg(any(feature = "svg", feature = "image"))]
-            if !layer.images.is_empty() {
-                engine.image_pipeline.render(
-                    &image_cache,
-                    image_layer,
-                    scissor_rect,
-                    &mut render_pass,
-                );
+                    #[cfg(any(feature = "svg", feature = "image"))]
+                    if !layer.images.is_empty() {
+                        engine.image_pipeline.render(
+                            &image_cache,
+                            image_layer,
+                            scissor_rect,
+                            &mut render_pass,
+                        );
 
-                image_layer += 1;
+                        image_layer += 1;
+                    }
+
+                    if !layer.text.is_empty() {
+                        text_layer += engine.text_pipeline.render(
+                            &self.text_viewport,
+                            &self.text_storage,
+                            text_layer,
+                            &layer.text,
+                            scissor_rect,
+                            &mut render_pass,
+                        );
+                    }
+                }
             }
 
-            if !layer.text.is_empty() {
-                text_layer += engine.text_pipeline.render(
-                    &self.text_viewport,
-                    &self.text_storage,
-                    text_layer,
-                    &layer.text,
-                    scissor_rect,
-                    &mut render_pass,
-                );
+            while open_groups
+                .last()
+                .is_some_and(|(_, group)| group.layers.end == index + 1)
+            {
+                let _ = ManuallyDrop::into_inner(render_pass);
+
+                let depth = open_groups.len();
+                let (group_index, group) = open_groups.pop().unwrap();
+
+                if group.effect.blur > 0.0 {
+                    effects.blur_contents(encoder, depth, group_index);
+                }
+
+                target = effects.target(open_groups.len());
+
+                render_pass = ManuallyDrop::new(begin_render_pass(
+                    encoder,
+                    target,
+                    wgpu::LoadOp::Load,
+                ));
+
+                effects.composite(&mut render_pass, depth, group_index);
             }
         }
 
         let _ = ManuallyDrop::into_inner(render_pass);
+
+        if !groups.is_empty() {
+            let mut render_pass = begin_render_pass(encoder, frame, clear);
+
+            effects.composite_root(&mut render_pass, groups.len());
+        }
     }
 
     fn draw_overlay(
@@ -440,6 +511,18 @@ impl core::Renderer for Renderer {
         self.layers.pop_clip();
     }
 
+    fn start_effect(
+        &mut self,
+        bounds: Rectangle,
+        effect: core::renderer::Effect,
+    ) {
+        self.layers.push_effect(bounds, effect);
+    }
+
+    fn end_effect(&mut self) {
+        self.layers.pop_effect();
+    }
+
     fn start_transformation(&mut self, transformation: Transformation) {
         self.layers.push_transformation(transformation);
     }
@@ -532,6 +615,10 @@ impl core::image::Renderer for Renderer {
         let (layer, transformation) = self.layers.current_mut();
         layer.draw_raster(image, bounds, transformation);
     }
+
+    fn is_image_loading(&self, handle: &Self::Handle) -> bool {
+        self.image_cache.borrow_mut().is_image_loading(handle)
+    }
 }
 
 #[cfg(feature = "svg")]
@@ -546,6 +633,13 @@ impl core::svg::Renderer for Renderer {
     }
 }
 
+#[cfg(any(feature = "svg", feature = "image"))]
+impl graphics::image::Cache for Renderer {
+    fn image_cache_stats(&self) -> graphics::image::Stats {
+        self.image_cache.borrow().stats()
+    }
+}
+
 impl graphics::mesh::Renderer for Renderer {
     fn draw_mesh(&mut self, mesh: graphics::Mesh) {
         let (layer, transformation) = self.layers.current_mut();
@@ -608,3 +702,24 @@ impl primitive::Renderer for Renderer {
 impl graphics::compositor::Default for crate::Renderer {
     type Compositor = window::Compositor;
 }
+
+fn begin_render_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    target: &wgpu::TextureView,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("iced_wgpu render pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
@@ -1,9 +1,14 @@
 mod gradient;
 mod solid;
 
+#[cfg(feature = "image")]
+mod image;
+
 use gradient::Gradient;
 use solid::Solid;
 
+#[cfg(feature = "image")]
+use crate::core::{background, Color};
 use crate::core::{Background, Rectangle, Transformation};
 use crate::graphics;
 use crate::graphics::color;
@@ -47,13 +52,19 @@ pub struct Quad {
 pub struct Pipeline {
     solid: solid::Pipeline,
     gradient: gradient::Pipeline,
+    #[cfg(feature = "image")]
+    image: image::Pipeline,
     constant_layout: wgpu::BindGroupLayout,
     layers: Vec<Layer>,
     prepare_layer: usize,
 }
 
 impl Pipeline {
-    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Pipeline {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        #[cfg(feature = "image")] texture_layout: &wgpu::BindGroupLayout,
+    ) -> Pipeline {
         let constant_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("iced_wgpu::quad uniforms layout"),
@@ -74,6 +85,13 @@ impl Pipeline {
         Self {
             solid: solid::Pipeline::new(device, format, &constant_layout),
             gradient: gradient::Pipeline::new(device, format, &constant_layout),
+            #[cfg(feature = "image")]
+            image: image::Pipeline::new(
+                device,
+                format,
+                &constant_layout,
+                texture_layout,
+            ),
             layers: Vec::new(),
             prepare_layer: 0,
             constant_layout,
@@ -85,6 +103,7 @@ impl Pipeline {
         device: &wgpu::Device,
         encoder: &mut wgpu::CommandEncoder,
         belt: &mut wgpu::util::StagingBelt,
+        #[cfg(feature = "image")] images: &mut crate::image::Cache,
         quads: &Batch,
         transformation: Transformation,
         scale: f32,
@@ -94,7 +113,16 @@ impl Pipeline {
         }
 
         let layer = &mut self.layers[self.prepare_layer];
-        layer.prepare(device, encoder, belt, quads, transformation, scale);
+        layer.prepare(
+            device,
+            encoder,
+            belt,
+            #[cfg(feature = "image")]
+            images,
+            quads,
+            transformation,
+            scale,
+        );
 
         self.prepare_layer += 1;
     }
@@ -104,6 +132,7 @@ impl Pipeline {
         layer: usize,
         bounds: Rectangle<u32>,
         quads: &Batch,
+        #[cfg(feature = "image")] atlas: &'a wgpu::BindGroup,
         render_pass: &mut wgpu::RenderPass<'a>,
     ) {
         if let Some(layer) = self.layers.get(layer) {
@@ -116,6 +145,8 @@ impl Pipeline {
 
             let mut solid_offset = 0;
             let mut gradient_offset = 0;
+            #[cfg(feature = "image")]
+            let mut image_offset = 0;
 
             for (kind, count) in &quads.order {
                 match kind {
@@ -139,6 +170,18 @@ impl Pipeline {
 
                         gradient_offset += count;
                     }
+                    #[cfg(feature = "image")]
+                    Kind::Image => {
+                        self.image.render(
+                            render_pass,
+                            &layer.constants,
+                            atlas,
+                            &layer.image,
+                            image_offset..(image_offset + count),
+                        );
+
+                        image_offset += count;
+                    }
                 }
             }
         }
@@ -155,6 +198,8 @@ pub struct Layer {
     constants_buffer: wgpu::Buffer,
     solid: solid::Layer,
     gradient: gradient::Layer,
+    #[cfg(feature = "image")]
+    image: image::Layer,
 }
 
 impl Layer {
@@ -183,6 +228,8 @@ impl Layer {
             constants_buffer,
             solid: solid::Layer::new(device),
             gradient: gradient::Layer::new(device),
+            #[cfg(feature = "image")]
+            image: image::Layer::new(device),
         }
     }
 
@@ -191,6 +238,7 @@ impl Layer {
         device: &wgpu::Device,
         encoder: &mut wgpu::CommandEncoder,
         belt: &mut wgpu::util::StagingBelt,
+        #[cfg(feature = "image")] images: &mut crate::image::Cache,
         quads: &Batch,
         transformation: Transformation,
         scale: f32,
@@ -205,6 +253,12 @@ impl Layer {
             self.gradient
                 .prepare(device, encoder, belt, &quads.gradients);
         }
+
+        #[cfg(feature = "image")]
+        if !quads.images.is_empty() {
+            self.image
+                .prepare(device, encoder, belt, images, &quads.images);
+        }
     }
 
     pub fn update(
@@ -238,6 +292,10 @@ pub struct Batch {
     /// The gradient quads of the [`Layer`].
     gradients: Vec<Gradient>,
 
+    /// The image quads of the [`Layer`], uploaded to the atlas on prepare.
+    #[cfg(feature = "image")]
+    images: Vec<(Quad, background::Image)>,
+
     /// The quad order of the [`Layer`].
     order: Order,
 }
@@ -248,7 +306,7 @@ type Order = Vec<(Kind, usize)>;
 impl Batch {
     /// Returns true if there are no quads of any type in [`Quads`].
     pub fn is_empty(&self) -> bool {
-        self.solids.is_empty() && self.gradients.is_empty()
+        self.order.is_empty()
     }
 
     /// Adds a [`Quad`] with the provided `Background` type to the quad [`Layer`].
@@ -273,6 +331,39 @@ impl Batch {
 
                 Kind::Gradient
             }
+            #[cfg(feature = "image")]
+            Background::Image(image) => {
+                // Image quads do not support shadows, so we draw the shadow
+                // with a transparent solid quad underneath
+                if quad.shadow_color.components()[3] > 0.0 {
+                    self.add(
+                        Quad {
+                            border_width: 0.0,
+                            ..quad
+                        },
+                        &Background::Color(Color::TRANSPARENT),
+                    );
+                }
+
+                self.images.push((
+                    Quad {
+                        shadow_color: color::pack(Color::TRANSPARENT),
+                        ..quad
+                    },
+                    image.clone(),
+                ));
+
+                Kind::Image
+            }
+            #[cfg(not(feature = "image"))]
+            Background::Image(image) => {
+                log::warn!(
+                    "Unsupported background in `iced_wgpu`: {image:?}; \
+                    enable the `image` feature to draw image backgrounds",
+                );
+
+                return;
+            }
         };
 
         match self.order.last_mut() {
@@ -288,6 +379,8 @@ impl Batch {
     pub fn clear(&mut self) {
         self.solids.clear();
         self.gradients.clear();
+        #[cfg(feature = "image")]
+        self.images.clear();
         self.order.clear();
     }
 }
@@ -299,6 +392,9 @@ enum Kind {
     Solid,
     /// A gradient quad
     Gradient,
+    /// An image quad
+    #[cfg(feature = "image")]
+    Image,
 }
 
 fn color_target_state(
@@ -0,0 +1,396 @@
+use crate::core::background;
+use crate::core::image::FilterMethod;
+use crate::core::{Rectangle, Size};
+use crate::image::atlas;
+use crate::quad::{self, Quad};
+use crate::Buffer;
+
+use bytemuck::{Pod, Zeroable};
+use std::ops::Range;
+
+/// A quad filled with an image from the texture atlas.
+///
+/// A single image background may be split into many [`Image`] instances:
+/// one per slice of its [`background::Fit`] and atlas fragment.
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[repr(C)]
+pub struct Image {
+    /// The origin of the tiling of the piece.
+    origin: [f32; 2],
+
+    /// The tiling period of the piece; zero if it does not repeat.
+    period: [f32; 2],
+
+    /// The bounds of the piece, relative to its origin.
+    target: [f32; 4],
+
+    /// The bounds of the piece in the texture atlas, in normalized
+    /// coordinates.
+    atlas: [f32; 4],
+
+    /// The layer of the texture atlas containing the piece.
+    layer: u32,
+
+    /// The filter method of the piece; 0 for nearest and 1 for linear.
+    filter_method: u32,
+
+    /// The opacity of the piece.
+    opacity: f32,
+
+    /// The [`Quad`] data of the [`Image`].
+    quad: Quad,
+}
+
+#[derive(Debug)]
+pub struct Layer {
+    instances: Buffer<Image>,
+    instance_count: usize,
+    offsets: Vec<usize>,
+}
+
+impl Layer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let instances = Buffer::new(
+            device,
+            "iced_wgpu.quad.image.buffer",
+            quad::INITIAL_INSTANCES,
+            wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        );
+
+        Self {
+            instances,
+            instance_count: 0,
+            offsets: Vec::new(),
+        }
+    }
+
+    /// Uploads the images of the given backgrounds to the atlas and prepares
+    /// their instances.
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        belt: &mut wgpu::util::StagingBelt,
+        cache: &mut crate::image::Cache,
+        backgrounds: &[(Quad, background::Image)],
+    ) {
+        let mut instances = Vec::with_capacity(backgrounds.len());
+
+        self.offsets.clear();
+        self.offsets.push(0);
+
+        for (quad, background) in backgrounds {
+            if let Some(entry) =
+                cache.upload_raster(device, encoder, &background.handle)
+            {
+                add_instances(*quad, background, entry, &mut instances);
+            }
+
+            self.offsets.push(instances.len());
+        }
+
+        let _ = self.instances.resize(device, instances.len());
+        let _ = self.instances.write(device, encoder, belt, 0, &instances);
+
+        self.instance_count = instances.len();
+    }
+
+    /// Returns the range of instances of the given range of backgrounds.
+    fn instances(&self, backgrounds: Range<usize>) -> Range<usize> {
+        self.offsets[backgrounds.start]..self.offsets[backgrounds.end]
+    }
+}
+
+#[derive(Debug)]
+pub struct Pipeline {
+    #[cfg(not(target_arch = "wasm32"))]
+    pipeline: wgpu::RenderPipeline,
+    #[cfg(not(target_arch = "wasm32"))]
+    samplers: wgpu::BindGroup,
+}
+
+impl Pipeline {
+    #[allow(unused_variables)]
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        constants_layout: &wgpu::BindGroupLayout,
+        texture_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let sampler = |filter| {
+                device.create_sampler(&wgpu::SamplerDescriptor {
+                    address_mode_u: wgpu::AddressMode::ClampToEdge,
+                    address_mode_v: wgpu::AddressMode::ClampToEdge,
+                    address_mode_w: wgpu::AddressMode::ClampToEdge,
+                    min_filter: filter,
+                    mag_filter: filter,
+                    mipmap_filter: filter,
+                    ..Default::default()
+                })
+            };
+
+            let nearest_sampler = sampler(wgpu::FilterMode::Nearest);
+            let linear_sampler = sampler(wgpu::FilterMode::Linear);
+
+            let samplers_layout = device.create_bind_group_layout(
+                &wgpu::BindGroupLayoutDescriptor {
+                    label: Some("iced_wgpu.quad.image.samplers layout"),
+                    entries: &[
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(
+                                wgpu::SamplerBindingType::Filtering,
+                            ),
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(
+                                wgpu::SamplerBindingType::Filtering,
+                            ),
+                            count: None,
+                        },
+                    ],
+                },
+            );
+
+            let samplers =
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("iced_wgpu.quad.image.samplers bind group"),
+                    layout: &samplers_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Sampler(
+                                &nearest_sampler,
+                            ),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(
+                                &linear_sampler,
+                            ),
+                        },
+                    ],
+                });
+
+            let layout = device.create_pipeline_layout(
+                &wgpu::PipelineLayoutDescriptor {
+                    label: Some("iced_wgpu.quad.image.pipeline"),
+                    push_constant_ranges: &[],
+                    bind_group_layouts: &[
+                        constants_layout,
+                        &samplers_layout,
+                        texture_layout,
+                    ],
+                },
+            );
+
+            let shader =
+                device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("iced_wgpu.quad.image.shader"),
+                    source: wgpu::ShaderSource::Wgsl(
+                        std::borrow::Cow::Borrowed(concat!(
+                            include_str!("../shader/quad.wgsl"),
+                            "\n",
+                            include_str!("../shader/vertex.wgsl"),
+                            "\n",
+                            include_str!("../shader/quad/image.wgsl"),
+                        )),
+                    ),
+                });
+
+            let pipeline = device.create_render_pipeline(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some("iced_wgpu.quad.image.pipeline"),
+                    layout: Some(&layout),
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: Some("image_vs_main"),
+                        buffers: &[wgpu::VertexBufferLayout {
+                            array_stride: std::mem::size_of::<Image>() as u64,
+                            step_mode: wgpu::VertexStepMode::Instance,
+                            attributes: &wgpu::vertex_attr_array!(
+                                // Origin
+                                0 => Float32x2,
+                                // Period
+                                1 => Float32x2,
+                                // Target bounds
+                                2 => Float32x4,
+                                // Atlas bounds
+                                3 => Float32x4,
+                                // Layer
+                                4 => Uint32,
+                                // Filter method
+                                5 => Uint32,
+                                // Opacity
+                                6 => Float32,
+                                // Position & Scale
+                                7 => Float32x4,
+                                // Border color
+                                8 => Float32x4,
+                                // Border radius
+                                9 => Float32x4,
+                                // Border width
+                                10 => Float32
+                            ),
+                        }],
+                        compilation_options:
+                            wgpu::PipelineCompilationOptions::default(),
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point: Some("image_fs_main"),
+                        targets: &quad::color_target_state(format),
+                        compilation_options:
+                            wgpu::PipelineCompilationOptions::default(),
+                    }),
+                    primitive: wgpu::PrimitiveState {
+                        topology: wgpu::PrimitiveTopology::TriangleList,
+                        front_face: wgpu::FrontFace::Cw,
+                        ..Default::default()
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: 1,
+                        mask: !0,
+                        alpha_to_coverage_enabled: false,
+                    },
+                    multiview: None,
+                    cache: None,
+                },
+            );
+
+            Self { pipeline, samplers }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        Self {}
+    }
+
+    #[allow(unused_variables)]
+    pub fn render<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        constants: &'a wgpu::BindGroup,
+        atlas: &'a wgpu::BindGroup,
+        layer: &'a Layer,
+        range: Range<usize>,
+    ) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let instances = layer.instances(range);
+
+            if instances.is_empty() {
+                return;
+            }
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, constants, &[]);
+            render_pass.set_bind_group(1, &self.samplers, &[]);
+            render_pass.set_bind_group(2, atlas, &[]);
+            render_pass.set_vertex_buffer(0, layer.instances.slice(..));
+
+            render_pass
+                .draw(0..6, instances.start as u32..instances.end as u32);
+        }
+    }
+}
+
+/// Splits the image background of a [`Quad`] into pieces, one for each
+/// slice of its fit and fragment of its atlas entry.
+fn add_instances(
+    quad: Quad,
+    background: &background::Image,
+    entry: &atlas::Entry,
+    instances: &mut Vec<Image>,
+) {
+    let image_size = entry.size();
+    let image_size =
+        Size::new(image_size.width as f32, image_size.height as f32);
+
+    let bounds = Rectangle::new(quad.position.into(), quad.size.into());
+    let slices = background.fit.slices(image_size, bounds);
+
+    let (origin, period) = if background.fit == background::Fit::Tile {
+        (quad.position, [image_size.width, image_size.height])
+    } else {
+        ([0.0, 0.0], [0.0, 0.0])
+    };
+
+    let fragments: Vec<_> = match entry {
+        atlas::Entry::Contiguous(allocation) => vec![((0, 0), allocation)],
+        atlas::Entry::Fragmented { fragments, .. } => fragments
+            .iter()
+            .map(|fragment| (fragment.position, &fragment.allocation))
+            .collect(),
+    };
+
+    let start = instances.len();
+
+    for (source, target) in slices {
+        let target = Rectangle {
+            x: target.x - origin[0],
+            y: target.y - origin[1],
+            ..target
+        };
+
+        for &((x, y), allocation) in &fragments {
+            let size = allocation.size();
+
+            let fragment = Rectangle {
+                x: x as f32,
+                y: y as f32,
+                width: size.width as f32,
+                height: size.height as f32,
+            };
+
+            let Some(piece) = source.intersection(&fragment) else {
+                continue;
+            };
+
+            let scale_x = target.width / source.width;
+            let scale_y = target.height / source.height;
+
+            let (atlas_x, atlas_y) = allocation.position();
+
+            instances.push(Image {
+                origin,
+                period,
+                target: [
+                    target.x + (piece.x - source.x) * scale_x,
+                    target.y + (piece.y - source.y) * scale_y,
+                    piece.width * scale_x,
+                    piece.height * scale_y,
+                ],
+                atlas: [
+                    (atlas_x as f32 + piece.x - fragment.x + 0.5)
+                        / atlas::SIZE as f32,
+                    (atlas_y as f32 + piece.y - fragment.y + 0.5)
+                        / atlas::SIZE as f32,
+                    (piece.width - 1.0).max(0.0) / atlas::SIZE as f32,
+                    (piece.height - 1.0).max(0.0) / atlas::SIZE as f32,
+                ],
+                layer: allocation.layer() as u32,
+                filter_method: match background.filter_method {
+                    FilterMethod::Nearest => 0,
+                    FilterMethod::Linear => 1,
+                },
+                opacity: background.opacity,
+                quad: Quad {
+                    border_width: 0.0,
+                    ..quad
+                },
+            });
+        }
+    }
+
+    // Only the last piece draws the border, so it stays on top of the image
+    if let Some(last) = instances[start..].last_mut() {
+        last.quad.border_width = quad.border_width;
+    }
+}
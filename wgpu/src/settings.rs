@@ -5,7 +5,7 @@ use crate::graphics::{self, Antialiasing};
 /// The settings of a [`Renderer`].
 ///
 /// [`Renderer`]: crate::Renderer
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Settings {
     /// The present mode of the [`Renderer`].
     ///
@@ -27,6 +27,19 @@ pub struct Settings {
     ///
     /// By default, it is `None`.
     pub antialiasing: Option<Antialiasing>,
+
+    /// The maximum amount of memory, in bytes, that cached images may use
+    /// before the least recently drawn ones are evicted.
+    ///
+    /// By default, it is `None`; and images are evicted as soon as they
+    /// stop being drawn.
+    pub image_cache_budget: Option<usize>,
+
+    /// The [`graphics::image::Waker`] called whenever an image finishes
+    /// loading in the background.
+    ///
+    /// By default, it is `None`.
+    pub image_waker: Option<graphics::image::Waker>,
 }
 
 impl Default for Settings {
@@ -37,6 +50,8 @@ impl Default for Settings {
             default_font: Font::default(),
             default_text_size: Pixels(16.0),
             antialiasing: None,
+            image_cache_budget: None,
+            image_waker: None,
         }
     }
 }
@@ -47,6 +62,8 @@ impl From<graphics::Settings> for Settings {
             default_font: settings.default_font,
             default_text_size: settings.default_text_size,
             antialiasing: settings.antialiasing,
+            image_cache_budget: settings.image_cache_budget,
+            image_waker: settings.image_waker,
             ..Settings::default()
         }
     }
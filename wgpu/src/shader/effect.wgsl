@@ -0,0 +1,69 @@
+struct Globals {
+    size: vec2<f32>,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(0) @binding(1) var u_sampler: sampler;
+@group(1) @binding(0) var u_texture: texture_2d<f32>;
+
+struct VertexInput {
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) bounds: vec4<f32>,
+    @location(1) direction: vec2<f32>,
+    @location(2) sigma: f32,
+    @location(3) opacity: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) bounds: vec4<f32>,
+    @location(1) @interpolate(flat) direction: vec2<f32>,
+    @location(2) @interpolate(flat) sigma: f32,
+    @location(3) @interpolate(flat) opacity: f32,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let position = input.bounds.xy + vertex_position(input.vertex_index) * input.bounds.zw;
+
+    out.position = vec4<f32>(position / globals.size * vec2(2.0, -2.0) + vec2(-1.0, 1.0), 0.0, 1.0);
+    out.bounds = input.bounds;
+    out.direction = input.direction;
+    out.sigma = input.sigma;
+    out.opacity = input.opacity;
+
+    return out;
+}
+
+/// Samples the texture at the given position, repeating the pixels at the
+/// edges of the bounds
+fn sample(position: vec2<f32>, bounds: vec4<f32>) -> vec4<f32> {
+    let clamped = clamp(position, bounds.xy + 0.5, bounds.xy + bounds.zw - 0.5);
+
+    return textureSampleLevel(u_texture, u_sampler, clamped / globals.size, 0.0);
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    if (input.sigma <= 0.0) {
+        return sample(input.position.xy, input.bounds) * input.opacity;
+    }
+
+    let radius = i32(ceil(input.sigma * 3.0));
+    let factor = -0.5 / (input.sigma * input.sigma);
+
+    var color = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    var total = 0.0;
+
+    for (var i = -radius; i <= radius; i++) {
+        let offset = f32(i);
+        let weight = exp(offset * offset * factor);
+
+        color += sample(input.position.xy + input.direction * offset, input.bounds) * weight;
+        total += weight;
+    }
+
+    return color / total * input.opacity;
+}
@@ -18,6 +18,11 @@ struct VertexInput {
     @location(6) atlas_scale: vec2<f32>,
     @location(7) layer: i32,
     @location(8) snap: u32,
+    @location(9) tint: vec4<f32>,
+    @location(10) red: vec4<f32>,
+    @location(11) green: vec4<f32>,
+    @location(12) blue: vec4<f32>,
+    @location(13) adjust: u32,
 }
 
 struct VertexOutput {
@@ -25,6 +30,11 @@ struct VertexOutput {
     @location(0) uv: vec2<f32>,
     @location(1) layer: f32, // this should be an i32, but naga currently reads that as requiring interpolation.
     @location(2) opacity: f32,
+    @location(3) tint: vec4<f32>,
+    @location(4) red: vec4<f32>,
+    @location(5) green: vec4<f32>,
+    @location(6) blue: vec4<f32>,
+    @location(7) @interpolate(flat) adjust: u32,
 }
 
 @vertex
@@ -38,6 +48,11 @@ fn vs_main(input: VertexInput) -> VertexOutput {
     out.uv = vec2<f32>(v_pos * input.atlas_scale + input.atlas_pos);
     out.layer = f32(input.layer);
     out.opacity = input.opacity;
+    out.tint = input.tint;
+    out.red = input.red;
+    out.green = input.green;
+    out.blue = input.blue;
+    out.adjust = input.adjust;
 
     // Calculate the vertex position and move the center to the origin
     v_pos = input.pos + v_pos * input.scale - input.center;
@@ -67,5 +82,23 @@ fn vs_main(input: VertexInput) -> VertexOutput {
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     // Sample the texture at the given UV coordinate and layer.
-    return textureSample(u_texture, u_sampler, input.uv, i32(input.layer)) * vec4<f32>(1.0, 1.0, 1.0, input.opacity);
+    let color = textureSample(u_texture, u_sampler, input.uv, i32(input.layer));
+
+    // Untinted images without adjustments skip the sRGB round trip
+    if input.adjust == 0u {
+        return color * vec4<f32>(1.0, 1.0, 1.0, input.opacity);
+    }
+
+    // Apply the color matrix of the adjustments in sRGB space
+    let srgb = vec4<f32>(into_srgb(color.rgb), 1.0);
+
+    let adjusted = clamp(
+        vec3<f32>(dot(input.red, srgb), dot(input.green, srgb), dot(input.blue, srgb)),
+        vec3<f32>(0.0),
+        vec3<f32>(1.0),
+    );
+
+    let tinted = vec4<f32>(adjusted, color.a) * input.tint;
+
+    return vec4<f32>(from_srgb(tinted.rgb), tinted.a * input.opacity);
 }
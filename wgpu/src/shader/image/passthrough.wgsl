@@ -0,0 +1,7 @@
+fn into_srgb(color: vec3<f32>) -> vec3<f32> {
+    return color;
+}
+
+fn from_srgb(color: vec3<f32>) -> vec3<f32> {
+    return color;
+}
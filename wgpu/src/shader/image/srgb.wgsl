@@ -0,0 +1,15 @@
+fn into_srgb(linear: vec3<f32>) -> vec3<f32> {
+    let cutoff = linear <= vec3<f32>(0.0031308);
+    let lower = linear * 12.92;
+    let higher = 1.055 * pow(linear, vec3<f32>(1.0 / 2.4)) - 0.055;
+
+    return select(higher, lower, cutoff);
+}
+
+fn from_srgb(srgb: vec3<f32>) -> vec3<f32> {
+    let cutoff = srgb <= vec3<f32>(0.04045);
+    let lower = srgb / 12.92;
+    let higher = pow((srgb + 0.055) / 1.055, vec3<f32>(2.4));
+
+    return select(higher, lower, cutoff);
+}
@@ -0,0 +1,149 @@
+@group(1) @binding(0) var nearest_sampler: sampler;
+@group(1) @binding(1) var linear_sampler: sampler;
+@group(2) @binding(0) var atlas: texture_2d_array<f32>;
+
+struct ImageVertexInput {
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) origin: vec2<f32>,
+    @location(1) period: vec2<f32>,
+    @location(2) target_bounds: vec4<f32>,
+    @location(3) atlas_bounds: vec4<f32>,
+    @location(4) layer: u32,
+    @location(5) filter_method: u32,
+    @location(6) opacity: f32,
+    @location(7) position_and_scale: vec4<f32>,
+    @location(8) border_color: vec4<f32>,
+    @location(9) border_radius: vec4<f32>,
+    @location(10) border_width: f32,
+}
+
+struct ImageVertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) origin: vec2<f32>,
+    @location(1) period: vec2<f32>,
+    @location(2) target_bounds: vec4<f32>,
+    @location(3) atlas_bounds: vec4<f32>,
+    @location(4) @interpolate(flat) layer: u32,
+    @location(5) @interpolate(flat) filter_method: u32,
+    @location(6) opacity: f32,
+    @location(7) position_and_scale: vec4<f32>,
+    @location(8) border_color: vec4<f32>,
+    @location(9) border_radius: vec4<f32>,
+    @location(10) border_width: f32,
+}
+
+@vertex
+fn image_vs_main(input: ImageVertexInput) -> ImageVertexOutput {
+    var out: ImageVertexOutput;
+
+    var pos: vec2<f32> = input.position_and_scale.xy * globals.scale;
+    var scale: vec2<f32> = input.position_and_scale.zw * globals.scale;
+
+    var min_border_radius = min(input.position_and_scale.z, input.position_and_scale.w) * 0.5;
+    var border_radius: vec4<f32> = vec4<f32>(
+        min(input.border_radius.x, min_border_radius),
+        min(input.border_radius.y, min_border_radius),
+        min(input.border_radius.z, min_border_radius),
+        min(input.border_radius.w, min_border_radius)
+    );
+
+    var transform: mat4x4<f32> = mat4x4<f32>(
+        vec4<f32>(scale.x + 1.0, 0.0, 0.0, 0.0),
+        vec4<f32>(0.0, scale.y + 1.0, 0.0, 0.0),
+        vec4<f32>(0.0, 0.0, 1.0, 0.0),
+        vec4<f32>(pos - vec2<f32>(0.5, 0.5), 0.0, 1.0)
+    );
+
+    out.position = globals.transform * transform * vec4<f32>(vertex_position(input.vertex_index), 0.0, 1.0);
+    out.origin = input.origin * globals.scale;
+    out.period = input.period * globals.scale;
+    out.target_bounds = input.target_bounds * globals.scale;
+    out.atlas_bounds = input.atlas_bounds;
+    out.layer = input.layer;
+    out.filter_method = input.filter_method;
+    out.opacity = input.opacity;
+    out.position_and_scale = vec4<f32>(pos, scale);
+    out.border_color = input.border_color;
+    out.border_radius = border_radius * globals.scale;
+    out.border_width = input.border_width * globals.scale;
+
+    return out;
+}
+
+/// Returns the color of the image piece at the given position, or a
+/// transparent color if the position falls outside of the piece
+fn image_color(input: ImageVertexOutput) -> vec4<f32> {
+    var relative = input.position.xy - input.origin;
+
+    // Tiled pieces repeat every period
+    if (input.period.x > 0.0 && input.period.y > 0.0) {
+        relative = relative - floor(relative / input.period) * input.period;
+    }
+
+    let local = (relative - input.target_bounds.xy) / input.target_bounds.zw;
+
+    if (any(local < vec2<f32>(0.0, 0.0)) || any(local >= vec2<f32>(1.0, 1.0))) {
+        return vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    }
+
+    let uv = input.atlas_bounds.xy + local * input.atlas_bounds.zw;
+    let layer = i32(input.layer);
+
+    var color: vec4<f32>;
+
+    // Sampling an explicit level allows us to choose the sampler per instance
+    if (input.filter_method == 0u) {
+        color = textureSampleLevel(atlas, nearest_sampler, uv, layer, 0.0);
+    } else {
+        color = textureSampleLevel(atlas, linear_sampler, uv, layer, 0.0);
+    }
+
+    return vec4<f32>(color.x, color.y, color.z, color.w * input.opacity);
+}
+
+@fragment
+fn image_fs_main(input: ImageVertexOutput) -> @location(0) vec4<f32> {
+    var mixed_color: vec4<f32> = image_color(input);
+
+    let pos = input.position_and_scale.xy;
+    let scale = input.position_and_scale.zw;
+
+    var border_radius = select_border_radius(
+        input.border_radius,
+        input.position.xy,
+        (pos + scale * 0.5).xy
+    );
+
+    if (input.border_width > 0.0) {
+        var internal_border: f32 = max(border_radius - input.border_width, 0.0);
+
+        var internal_distance: f32 = distance_alg(
+            input.position.xy,
+            pos + vec2<f32>(input.border_width, input.border_width),
+            scale - vec2<f32>(input.border_width * 2.0, input.border_width * 2.0),
+            internal_border
+        );
+
+        var border_mix: f32 = smoothstep(
+            max(internal_border - 0.5, 0.0),
+            internal_border + 0.5,
+            internal_distance
+        );
+
+        mixed_color = mix(mixed_color, input.border_color, vec4<f32>(border_mix, border_mix, border_mix, border_mix));
+    }
+
+    var dist: f32 = distance_alg(
+        input.position.xy,
+        pos,
+        scale,
+        border_radius
+    );
+
+    var radius_alpha: f32 = 1.0 - smoothstep(
+        max(border_radius - 0.5, 0.0),
+        border_radius + 0.5,
+        dist);
+
+    return vec4<f32>(mixed_color.x, mixed_color.y, mixed_color.z, mixed_color.w * radius_alpha);
+}
@@ -300,12 +300,31 @@ impl graphics::Compositor for Compositor {
     }
 
     fn create_renderer(&self) -> Self::Renderer {
-        Renderer::new(
+        #[allow(unused_mut)]
+        let mut renderer = Renderer::new(
             &self.device,
             &self.engine,
             self.settings.default_font,
             self.settings.default_text_size,
-        )
+        );
+
+        #[cfg(any(feature = "svg", feature = "image"))]
+        renderer.set_image_cache_budget(self.settings.image_cache_budget);
+
+        #[cfg(feature = "image")]
+        renderer.set_image_waker(self.settings.image_waker.clone());
+
+        renderer
+    }
+
+    fn create_offscreen_renderer(&self) -> Self::Renderer {
+        #[allow(unused_mut)]
+        let mut renderer = self.create_renderer();
+
+        #[cfg(feature = "image")]
+        renderer.set_blocking_image_decoding(true);
+
+        renderer
     }
 
     fn create_surface<W: compositor::Window>(
@@ -356,6 +375,16 @@ impl graphics::Compositor for Compositor {
         }
     }
 
+    #[cfg(any(feature = "image", feature = "svg"))]
+    fn image_cache_stats(
+        &self,
+        renderer: &Self::Renderer,
+    ) -> graphics::image::Stats {
+        use graphics::image::Cache as _;
+
+        renderer.image_cache_stats()
+    }
+
     fn present<T: AsRef<str>>(
         &mut self,
         renderer: &mut Self::Renderer,
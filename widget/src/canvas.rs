@@ -48,12 +48,16 @@
 //!     canvas(Circle { radius: 50.0 }).into()
 //! }
 //! ```
+pub mod scene;
+
 mod program;
 
 pub use program::Program;
+pub use scene::Scene;
 
 pub use crate::core::event::Event;
 pub use crate::graphics::cache::Group;
+pub use crate::graphics::geometry::export;
 pub use crate::graphics::geometry::{
     fill, gradient, path, stroke, Fill, Gradient, Image, LineCap, LineDash,
     LineJoin, Path, Stroke, Style, Text,
@@ -86,6 +90,37 @@ pub type Geometry<Renderer = crate::Renderer> =
 /// The frame supported by a renderer.
 pub type Frame<Renderer = crate::Renderer> = geometry::Frame<Renderer>;
 
+/// Draws a [`Program`] with a default state and exports the resulting
+/// geometry as an [`export::Drawing`] of the given [`Size`].
+///
+/// The [`Program`] must be implemented for [`export::Renderer`]; making the
+/// implementation generic over any [`geometry::Renderer`] allows the same
+/// drawing logic to be used both on screen and for exporting.
+pub fn export<P, Message, Theme>(
+    program: &P,
+    theme: &Theme,
+    size: Size,
+) -> export::Drawing
+where
+    P: Program<Message, Theme, export::Renderer>,
+{
+    let mut renderer = export::Renderer::new();
+
+    let layers = program.draw(
+        &P::State::default(),
+        &renderer,
+        theme,
+        Rectangle::with_size(size),
+        mouse::Cursor::Unavailable,
+    );
+
+    for layer in layers {
+        geometry::Renderer::draw_geometry(&mut renderer, layer);
+    }
+
+    renderer.into_drawing(size)
+}
+
 /// A widget capable of drawing 2D graphics.
 ///
 /// # Example: Drawing a Simple Circle
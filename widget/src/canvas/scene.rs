@@ -0,0 +1,637 @@
+//! Retain shapes in a [`Canvas`] and find out which one is under the cursor.
+//!
+//! [`Canvas`]: crate::Canvas
+use crate::canvas::{Fill, Frame, Path, Stroke};
+use crate::core::mouse;
+use crate::core::{Point, Radians, Rectangle, Vector};
+use crate::graphics::geometry;
+
+/// A set of retained shapes that can be drawn and hit-tested.
+///
+/// Shapes are stacked by their [`Shape::z_index`]; shapes with the same
+/// index are stacked in insertion order.
+#[derive(Debug, Clone)]
+pub struct Scene<Id> {
+    shapes: Vec<Shape<Id>>,
+}
+
+impl<Id> Scene<Id>
+where
+    Id: Clone + PartialEq,
+{
+    /// Creates a new empty [`Scene`].
+    pub fn new() -> Self {
+        Self { shapes: Vec::new() }
+    }
+
+    /// Adds a [`Shape`] to the [`Scene`], replacing any other [`Shape`] with
+    /// the same id.
+    ///
+    /// A replaced [`Shape`] keeps its place in the stack, unless its
+    /// [`Shape::z_index`] changes.
+    pub fn push(&mut self, shape: Shape<Id>) {
+        if let Some(index) = self
+            .shapes
+            .iter()
+            .position(|current| current.id == shape.id)
+        {
+            if self.shapes[index].z_index == shape.z_index {
+                self.shapes[index] = shape;
+                return;
+            }
+
+            let _ = self.shapes.remove(index);
+        }
+
+        let index = self
+            .shapes
+            .partition_point(|current| current.z_index <= shape.z_index);
+
+        self.shapes.insert(index, shape);
+    }
+
+    /// Removes the [`Shape`] with the given id from the [`Scene`].
+    pub fn remove(&mut self, id: &Id) -> Option<Shape<Id>> {
+        let index = self.shapes.iter().position(|shape| &shape.id == id)?;
+
+        Some(self.shapes.remove(index))
+    }
+
+    /// Removes all the shapes of the [`Scene`].
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    /// Returns the [`Shape`] with the given id.
+    pub fn get(&self, id: &Id) -> Option<&Shape<Id>> {
+        self.shapes.iter().find(|shape| &shape.id == id)
+    }
+
+    /// Returns a mutable reference to the [`Shape`] with the given id.
+    ///
+    /// Changing the [`Shape::z_index`] through this reference does not
+    /// restack the [`Shape`]; [`push`] it again instead.
+    ///
+    /// [`push`]: Self::push
+    pub fn get_mut(&mut self, id: &Id) -> Option<&mut Shape<Id>> {
+        self.shapes.iter_mut().find(|shape| &shape.id == id)
+    }
+
+    /// Returns true if the [`Scene`] has no shapes.
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// Returns the shapes of the [`Scene`], from bottom to top.
+    pub fn shapes(&self) -> impl DoubleEndedIterator<Item = &Shape<Id>> {
+        self.shapes.iter()
+    }
+
+    /// Returns the topmost [`Shape`] at the given [`Point`], if any.
+    pub fn hit_test(&self, point: Point) -> Option<&Shape<Id>> {
+        self.shapes().rev().find(|shape| shape.contains(point))
+    }
+
+    /// Draws all the shapes of the [`Scene`] in the given [`Frame`].
+    pub fn draw<Renderer>(&self, frame: &mut Frame<Renderer>)
+    where
+        Renderer: geometry::Renderer,
+    {
+        for shape in self.shapes() {
+            shape.draw(frame);
+        }
+    }
+
+    /// Processes a canvas [`Event`] and returns the resulting events of the
+    /// shapes of the [`Scene`].
+    ///
+    /// The [`State`] keeps track of the hovered and pressed shapes, and it
+    /// should be stored in the [`Program::State`] of the canvas.
+    ///
+    /// [`Event`]: crate::canvas::Event
+    /// [`Program::State`]: crate::canvas::Program::State
+    pub fn update(
+        &self,
+        state: &mut State<Id>,
+        event: &crate::canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Event<Id>> {
+        let mut events = Vec::new();
+
+        let crate::canvas::Event::Mouse(event) = event else {
+            return events;
+        };
+
+        let position = cursor
+            .position()
+            .map(|position| position - Vector::new(bounds.x, bounds.y));
+
+        let hovered = cursor
+            .position_in(bounds)
+            .and_then(|position| self.hit_test(position))
+            .map(|shape| shape.id.clone());
+
+        if hovered != state.hovered {
+            if let Some(id) = state.hovered.take() {
+                events.push(Event::Left(id));
+            }
+
+            if let Some(id) = hovered.clone() {
+                events.push(Event::Entered(id));
+            }
+
+            state.hovered = hovered;
+        }
+
+        match event {
+            mouse::Event::CursorMoved { .. } => {
+                if let (Some((id, _)), Some(position)) =
+                    (&state.pressed, position)
+                {
+                    events.push(Event::Dragged {
+                        id: id.clone(),
+                        position,
+                    });
+                }
+            }
+            mouse::Event::ButtonPressed(button) => {
+                if let (Some(id), Some(position)) = (&state.hovered, position) {
+                    state.pressed = Some((id.clone(), *button));
+
+                    events.push(Event::Pressed {
+                        id: id.clone(),
+                        button: *button,
+                        position,
+                    });
+                }
+            }
+            mouse::Event::ButtonReleased(button) => {
+                if state
+                    .pressed
+                    .as_ref()
+                    .is_some_and(|(_, pressed)| pressed == button)
+                {
+                    let (id, _) = state.pressed.take().expect("Pressed shape");
+
+                    events.push(Event::Released {
+                        id,
+                        button: *button,
+                        position,
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        events
+    }
+
+    /// Returns the [`mouse::Interaction`] of the pressed or hovered [`Shape`].
+    pub fn mouse_interaction(&self, state: &State<Id>) -> mouse::Interaction {
+        state
+            .pressed
+            .as_ref()
+            .map(|(id, _)| id)
+            .or(state.hovered.as_ref())
+            .and_then(|id| self.get(id))
+            .map(|shape| shape.interaction)
+            .unwrap_or_default()
+    }
+}
+
+impl<Id> Default for Scene<Id>
+where
+    Id: Clone + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A retained shape of a [`Scene`].
+#[derive(Debug, Clone)]
+pub struct Shape<Id> {
+    /// The identifier of the [`Shape`].
+    pub id: Id,
+    /// The [`Path`] of the [`Shape`], in local coordinates.
+    pub path: Path,
+    /// The [`Fill`] of the [`Shape`], if any.
+    pub fill: Option<Fill>,
+    /// The [`Stroke`] of the [`Shape`], if any.
+    pub stroke: Option<Stroke<'static>>,
+    /// The stacking order of the [`Shape`]; higher indices are on top.
+    pub z_index: i32,
+    /// The [`Transform`] from the local coordinates of the [`Shape`] to the
+    /// coordinates of the [`Scene`].
+    pub transform: Transform,
+    /// The [`mouse::Interaction`] of the [`Shape`] when hovered or pressed.
+    pub interaction: mouse::Interaction,
+}
+
+impl<Id> Shape<Id> {
+    /// Creates a new [`Shape`] with the given id and [`Path`].
+    ///
+    /// By default, it has no fill nor stroke and it will not be hit.
+    pub fn new(id: Id, path: Path) -> Self {
+        Self {
+            id,
+            path,
+            fill: None,
+            stroke: None,
+            z_index: 0,
+            transform: Transform::IDENTITY,
+            interaction: mouse::Interaction::default(),
+        }
+    }
+
+    /// Sets the [`Fill`] of the [`Shape`].
+    pub fn fill(mut self, fill: impl Into<Fill>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    /// Sets the [`Stroke`] of the [`Shape`].
+    pub fn stroke(mut self, stroke: impl Into<Stroke<'static>>) -> Self {
+        self.stroke = Some(stroke.into());
+        self
+    }
+
+    /// Sets the z-index of the [`Shape`].
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    /// Sets the [`Transform`] of the [`Shape`].
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Sets the [`mouse::Interaction`] of the [`Shape`].
+    pub fn interaction(mut self, interaction: mouse::Interaction) -> Self {
+        self.interaction = interaction;
+        self
+    }
+
+    /// Returns true if the given [`Point`], in the coordinates of the
+    /// [`Scene`], is covered by the fill or the stroke of the [`Shape`].
+    pub fn contains(&self, point: Point) -> bool {
+        let point = self.transform.to_local(point);
+
+        self.fill
+            .is_some_and(|fill| self.path.contains(point, fill.rule))
+            || self
+                .stroke
+                .is_some_and(|stroke| self.path.stroke_contains(point, &stroke))
+    }
+
+    /// Draws the [`Shape`] in the given [`Frame`].
+    pub fn draw<Renderer>(&self, frame: &mut Frame<Renderer>)
+    where
+        Renderer: geometry::Renderer,
+    {
+        frame.with_save(|frame| {
+            frame.translate(self.transform.translation);
+            frame.rotate(self.transform.rotation);
+            frame.scale(self.transform.scale);
+
+            if let Some(fill) = self.fill {
+                frame.fill(&self.path, fill);
+            }
+
+            if let Some(stroke) = self.stroke {
+                frame.stroke(&self.path, stroke);
+            }
+        });
+    }
+}
+
+/// The placement of a [`Shape`] in a [`Scene`].
+///
+/// The local coordinates of a [`Shape`] are scaled, then rotated and then
+/// translated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// The translation of the [`Transform`].
+    pub translation: Vector,
+    /// The rotation of the [`Transform`].
+    pub rotation: Radians,
+    /// The uniform scale of the [`Transform`].
+    pub scale: f32,
+}
+
+impl Transform {
+    /// The identity [`Transform`].
+    pub const IDENTITY: Self = Self {
+        translation: Vector::ZERO,
+        rotation: Radians(0.0),
+        scale: 1.0,
+    };
+
+    /// Creates a [`Transform`] that translates by the given [`Vector`].
+    pub fn translate(translation: Vector) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Sets the rotation of the [`Transform`].
+    pub fn rotate(self, rotation: impl Into<Radians>) -> Self {
+        Self {
+            rotation: rotation.into(),
+            ..self
+        }
+    }
+
+    /// Sets the scale of the [`Transform`].
+    pub fn scale(self, scale: f32) -> Self {
+        Self { scale, ..self }
+    }
+
+    /// Maps the given [`Point`] from the coordinates of the [`Scene`] to the
+    /// local coordinates of a [`Shape`].
+    pub fn to_local(&self, point: Point) -> Point {
+        let Vector { x, y } = point - Point::ORIGIN - self.translation;
+        let (sin, cos) = self.rotation.0.sin_cos();
+
+        Point::new(
+            (x * cos + y * sin) / self.scale,
+            (y * cos - x * sin) / self.scale,
+        )
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// The interaction state of a [`Scene`].
+#[derive(Debug, Clone)]
+pub struct State<Id> {
+    hovered: Option<Id>,
+    pressed: Option<(Id, mouse::Button)>,
+}
+
+impl<Id> State<Id> {
+    /// Returns the id of the hovered [`Shape`], if any.
+    pub fn hovered(&self) -> Option<&Id> {
+        self.hovered.as_ref()
+    }
+
+    /// Returns the id of the pressed [`Shape`], if any.
+    pub fn pressed(&self) -> Option<&Id> {
+        self.pressed.as_ref().map(|(id, _)| id)
+    }
+}
+
+impl<Id> Default for State<Id> {
+    fn default() -> Self {
+        Self {
+            hovered: None,
+            pressed: None,
+        }
+    }
+}
+
+/// An interaction with a [`Shape`] of a [`Scene`].
+///
+/// Positions are relative to the bounds of the canvas.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<Id> {
+    /// The cursor entered the [`Shape`].
+    Entered(Id),
+    /// The cursor left the [`Shape`].
+    Left(Id),
+    /// A mouse button was pressed over the [`Shape`].
+    Pressed {
+        /// The id of the [`Shape`].
+        id: Id,
+        /// The pressed button.
+        button: mouse::Button,
+        /// The position of the cursor.
+        position: Point,
+    },
+    /// The cursor moved while the [`Shape`] was pressed.
+    Dragged {
+        /// The id of the [`Shape`].
+        id: Id,
+        /// The position of the cursor.
+        position: Point,
+    },
+    /// The mouse button pressing the [`Shape`] was released.
+    Released {
+        /// The id of the [`Shape`].
+        id: Id,
+        /// The released button.
+        button: mouse::Button,
+        /// The position of the cursor, if it is still available.
+        position: Option<Point>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::{Color, Size};
+
+    use std::f32::consts::FRAC_PI_2;
+
+    fn square(id: &'static str, size: f32) -> Shape<&'static str> {
+        Shape::new(id, Path::rectangle(Point::ORIGIN, Size::new(size, size)))
+            .fill(Color::BLACK)
+    }
+
+    fn is_close(a: Point, b: Point) -> bool {
+        a.distance(b) < 1e-4
+    }
+
+    #[test]
+    fn to_local_is_identity_by_default() {
+        let point = Point::new(3.0, 4.0);
+
+        assert_eq!(Transform::IDENTITY.to_local(point), point);
+    }
+
+    #[test]
+    fn to_local_undoes_translation() {
+        let transform = Transform::translate(Vector::new(10.0, 20.0));
+
+        assert_eq!(
+            transform.to_local(Point::new(15.0, 25.0)),
+            Point::new(5.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn to_local_undoes_rotation() {
+        let transform = Transform::IDENTITY.rotate(Radians(FRAC_PI_2));
+
+        assert!(is_close(
+            transform.to_local(Point::new(0.0, 1.0)),
+            Point::new(1.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn to_local_undoes_scale_rotation_and_translation() {
+        let transform = Transform::translate(Vector::new(10.0, 0.0))
+            .rotate(Radians(FRAC_PI_2))
+            .scale(2.0);
+
+        // (1, 2) is scaled to (2, 4), rotated to (-4, 2) and translated
+        // to (6, 2)
+        assert!(is_close(
+            transform.to_local(Point::new(6.0, 2.0)),
+            Point::new(1.0, 2.0)
+        ));
+    }
+
+    #[test]
+    fn hit_test_finds_topmost_shape() {
+        let mut scene = Scene::new();
+        scene.push(square("top", 10.0).z_index(1));
+        scene.push(square("bottom", 20.0));
+
+        let hit = |x, y| scene.hit_test(Point::new(x, y)).map(|shape| shape.id);
+
+        assert_eq!(hit(5.0, 5.0), Some("top"));
+        assert_eq!(hit(15.0, 15.0), Some("bottom"));
+        assert_eq!(hit(25.0, 25.0), None);
+    }
+
+    #[test]
+    fn hit_test_stacks_equal_indices_in_insertion_order() {
+        let mut scene = Scene::new();
+        scene.push(square("first", 10.0));
+        scene.push(square("second", 10.0));
+
+        assert_eq!(
+            scene.hit_test(Point::new(5.0, 5.0)).map(|shape| shape.id),
+            Some("second")
+        );
+    }
+
+    #[test]
+    fn hit_test_applies_transform() {
+        let mut scene = Scene::new();
+        scene.push(
+            square("moved", 10.0)
+                .transform(Transform::translate(Vector::new(100.0, 0.0))),
+        );
+
+        assert!(scene.hit_test(Point::new(5.0, 5.0)).is_none());
+        assert!(scene.hit_test(Point::new(105.0, 5.0)).is_some());
+    }
+
+    #[test]
+    fn hit_test_ignores_unpainted_shapes() {
+        let mut scene = Scene::new();
+        scene.push(Shape::new(
+            "invisible",
+            Path::rectangle(Point::ORIGIN, Size::new(10.0, 10.0)),
+        ));
+
+        assert!(scene.hit_test(Point::new(5.0, 5.0)).is_none());
+    }
+
+    #[test]
+    fn hit_test_stroke_only_covers_outline() {
+        let mut scene = Scene::new();
+        scene.push(
+            Shape::new(
+                "outline",
+                Path::rectangle(Point::ORIGIN, Size::new(10.0, 10.0)),
+            )
+            .stroke(Stroke::default().with_width(2.0)),
+        );
+
+        assert!(scene.hit_test(Point::new(0.5, 5.0)).is_some());
+        assert!(scene.hit_test(Point::new(5.0, 5.0)).is_none());
+    }
+
+    #[test]
+    fn push_replaces_shape_with_same_id() {
+        let mut scene = Scene::new();
+        scene.push(square("shape", 10.0));
+        scene.push(square("shape", 20.0));
+
+        assert_eq!(scene.shapes().count(), 1);
+        assert!(scene.hit_test(Point::new(15.0, 15.0)).is_some());
+    }
+
+    #[test]
+    fn push_keeps_shapes_sorted_by_z_index() {
+        let mut scene = Scene::new();
+        scene.push(square("middle", 10.0).z_index(1));
+        scene.push(square("top", 10.0).z_index(2));
+        scene.push(square("bottom", 10.0));
+        scene.push(square("middle_2", 10.0).z_index(1));
+
+        assert_eq!(
+            scene.shapes().map(|shape| shape.id).collect::<Vec<_>>(),
+            ["bottom", "middle", "middle_2", "top"]
+        );
+    }
+
+    #[test]
+    fn push_restacks_shape_when_z_index_changes() {
+        let mut scene = Scene::new();
+        scene.push(square("a", 10.0));
+        scene.push(square("b", 10.0));
+        scene.push(square("a", 20.0));
+
+        assert_eq!(
+            scene.shapes().map(|shape| shape.id).collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+
+        scene.push(square("a", 20.0).z_index(1));
+
+        assert_eq!(
+            scene.shapes().map(|shape| shape.id).collect::<Vec<_>>(),
+            ["b", "a"]
+        );
+    }
+
+    #[test]
+    fn released_without_cursor_has_no_position() {
+        let mut scene = Scene::new();
+        scene.push(square("shape", 10.0));
+
+        let mut state = State::default();
+        let bounds = Rectangle::new(Point::ORIGIN, Size::new(100.0, 100.0));
+
+        let _ = scene.update(
+            &mut state,
+            &crate::canvas::Event::Mouse(mouse::Event::ButtonPressed(
+                mouse::Button::Left,
+            )),
+            bounds,
+            mouse::Cursor::Available(Point::new(5.0, 5.0)),
+        );
+
+        let events = scene.update(
+            &mut state,
+            &crate::canvas::Event::Mouse(mouse::Event::ButtonReleased(
+                mouse::Button::Left,
+            )),
+            bounds,
+            mouse::Cursor::Unavailable,
+        );
+
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::Released {
+                id: "shape",
+                position: None,
+                ..
+            }
+        )));
+    }
+}
@@ -61,7 +61,7 @@ use crate::core::mouse;
 use crate::core::overlay;
 use crate::core::renderer;
 use crate::core::text;
-use crate::core::time::Instant;
+use crate::core::time::{Duration, Instant};
 use crate::core::widget::{self, Widget};
 use crate::core::window;
 use crate::core::{
@@ -151,11 +151,17 @@ pub struct ComboBox<
     on_open: Option<Message>,
     on_close: Option<Message>,
     on_input: Option<Box<dyn Fn(String) -> Message>>,
+    on_query: Option<Box<dyn Fn(String) -> Message>>,
+    debounce: Duration,
+    loading: String,
     menu_class: <Theme as menu::Catalog>::Class<'a>,
     padding: Padding,
     size: Option<f32>,
 }
 
+/// The default debounce [`Duration`] of the queries of a [`ComboBox`].
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
 impl<'a, T, Message, Theme, Renderer> ComboBox<'a, T, Message, Theme, Renderer>
 where
     T: std::fmt::Display + Clone,
@@ -185,6 +191,9 @@ where
             on_selected: Box::new(on_selected),
             on_option_hovered: None,
             on_input: None,
+            on_query: None,
+            debounce: DEFAULT_DEBOUNCE,
+            loading: String::from("Loading..."),
             on_open: None,
             on_close: None,
             menu_class: <Theme as Catalog>::default_menu(),
@@ -203,6 +212,43 @@ where
         self
     }
 
+    /// Sets the message that should be produced to query new options when
+    /// some text is typed into the [`ComboBox`].
+    ///
+    /// The message is produced once the text has not changed for the
+    /// debounce [`Duration`] of the [`ComboBox`]. Until then, the current
+    /// options are filtered locally and a loading indicator is displayed.
+    /// No query is produced when the text is cleared.
+    ///
+    /// The results of the query can be fetched with a [`Task`] and provided
+    /// with [`State::set_suggestions`], which replaces the options of the
+    /// [`State`] while keeping the typed text. A query that fails, or has
+    /// no results to provide, must be ended with [`State::finish_query`].
+    ///
+    /// [`Task`]: crate::runtime::Task
+    pub fn on_query(
+        mut self,
+        on_query: impl Fn(String) -> Message + 'static,
+    ) -> Self {
+        self.on_query = Some(Box::new(on_query));
+        self
+    }
+
+    /// Sets the debounce [`Duration`] of the queries of the [`ComboBox`].
+    ///
+    /// By default, it is [`DEFAULT_DEBOUNCE`].
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Sets the label displayed in the menu of the [`ComboBox`] while a
+    /// query is loading.
+    pub fn loading_label(mut self, label: impl Into<String>) -> Self {
+        self.loading = label.into();
+        self
+    }
+
     /// Sets the message that will be produced when an option of the
     /// [`ComboBox`] is hovered using the arrow keys.
     pub fn on_option_hovered(
@@ -334,6 +380,7 @@ struct Inner<T> {
     value: String,
     option_matchers: Vec<String>,
     filtered_options: Filtered<T>,
+    is_loading: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -371,6 +418,7 @@ where
                 value,
                 option_matchers,
                 filtered_options,
+                is_loading: false,
             }),
         }
     }
@@ -383,6 +431,46 @@ where
         &self.options
     }
 
+    /// Replaces the options of the [`State`] with the suggestions for the
+    /// given query, produced by [`ComboBox::on_query`].
+    ///
+    /// The typed text is kept and the suggestions are displayed as they
+    /// are, without any local filtering. Suggestions for a query that does
+    /// not match the current text are stale and will be ignored.
+    pub fn set_suggestions(&mut self, query: &str, suggestions: Vec<T>) {
+        let inner = self.inner.get_mut();
+
+        if inner.value != query {
+            return;
+        }
+
+        inner.option_matchers = build_matchers(&suggestions);
+        inner.filtered_options.update(suggestions.clone());
+        inner.is_loading = false;
+
+        self.options = suggestions;
+    }
+
+    /// Ends the given query without replacing the options of the [`State`],
+    /// hiding the loading indicator.
+    ///
+    /// Useful when a query fails or produces no suggestions. Like
+    /// [`State::set_suggestions`], a query that does not match the current
+    /// text is stale and will be ignored.
+    pub fn finish_query(&mut self, query: &str) {
+        let inner = self.inner.get_mut();
+
+        if inner.value == query {
+            inner.is_loading = false;
+        }
+    }
+
+    /// Returns whether the [`State`] is waiting for the suggestions of a
+    /// query.
+    pub fn is_loading(&self) -> bool {
+        self.inner.borrow().is_loading
+    }
+
     fn value(&self) -> String {
         let inner = self.inner.borrow();
 
@@ -452,6 +540,7 @@ struct Menu<T> {
     hovered_option: Option<usize>,
     new_selection: Option<T>,
     filtered_options: Filtered<T>,
+    query_at: Option<Instant>,
 }
 
 #[derive(Debug, Clone)]
@@ -503,6 +592,7 @@ where
             filtered_options: Filtered::empty(),
             hovered_option: Some(0),
             new_selection: None,
+            query_at: None,
         })
     }
 
@@ -580,6 +670,20 @@ where
                 menu.hovered_option = Some(0);
                 state.value = new_value;
 
+                if self.on_query.is_some() {
+                    if state.value.is_empty() {
+                        state.is_loading = false;
+                        menu.query_at = None;
+                    } else {
+                        let query_at = Instant::now() + self.debounce;
+
+                        state.is_loading = true;
+                        menu.query_at = Some(query_at);
+
+                        shell.request_redraw_at(query_at);
+                    }
+                }
+
                 state.filtered_options.update(
                     search(
                         &self.state.options,
@@ -594,6 +698,20 @@ where
             shell.request_redraw();
         }
 
+        if let Event::Window(window::Event::RedrawRequested(now)) = event {
+            if let Some(query_at) = menu.query_at {
+                if now >= query_at {
+                    menu.query_at = None;
+
+                    if let Some(on_query) = &self.on_query {
+                        shell.publish(on_query(self.state.value()));
+                    }
+                } else {
+                    shell.request_redraw_at(query_at);
+                }
+            }
+        }
+
         let is_focused = {
             let text_input_state = tree.children[0]
                 .state
@@ -735,7 +853,9 @@ where
                 // Clear the value and reset the options and menu
                 state.value = String::new();
                 state.filtered_options.update(self.state.options.clone());
+                state.is_loading = false;
                 menu.menu = menu::State::default();
+                menu.query_at = None;
 
                 // Notify the selection
                 shell.publish((self.on_selected)(selection));
@@ -858,7 +978,9 @@ where
 
             self.state.sync_filtered_options(filtered_options);
 
-            if filtered_options.options.is_empty() {
+            let is_loading = self.state.is_loading();
+
+            if filtered_options.options.is_empty() && !is_loading {
                 None
             } else {
                 let bounds = layout.bounds();
@@ -890,6 +1012,10 @@ where
                     menu = menu.text_size(size);
                 }
 
+                if is_loading {
+                    menu = menu.loading(&self.loading);
+                }
+
                 Some(
                     menu.overlay(
                         layout.position() + translation,
@@ -974,3 +1100,205 @@ where
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::clipboard;
+    use crate::core::input_method;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Selected(String),
+        Query(String),
+    }
+
+    type ComboBox<'a> = super::ComboBox<'a, String, Message, Theme, ()>;
+
+    const DEBOUNCE: Duration = Duration::from_millis(100);
+
+    fn combo_box(state: &State<String>) -> ComboBox<'_> {
+        ComboBox::new(state, "", None, Message::Selected)
+            .on_query(Message::Query)
+            .debounce(DEBOUNCE)
+    }
+
+    fn tree(combo_box: &ComboBox<'_>) -> widget::Tree {
+        let mut tree =
+            widget::Tree::new(combo_box as &dyn Widget<Message, Theme, ()>);
+
+        tree.children[0]
+            .state
+            .downcast_mut::<text_input::State<()>>()
+            .focus();
+
+        tree
+    }
+
+    fn update(
+        combo_box: &mut ComboBox<'_>,
+        tree: &mut widget::Tree,
+        event: Event,
+    ) -> Vec<Message> {
+        let size = Size::new(100.0, 30.0);
+        let node =
+            layout::Node::with_children(size, vec![layout::Node::new(size)]);
+        let mut messages = Vec::new();
+        let mut shell = Shell::new(&mut messages);
+
+        combo_box.update(
+            tree,
+            event,
+            Layout::new(&node),
+            mouse::Cursor::Unavailable,
+            &(),
+            &mut clipboard::Null,
+            &mut shell,
+            &Rectangle::with_size(size),
+        );
+
+        messages
+    }
+
+    fn type_text(text: &str) -> Event {
+        Event::InputMethod(input_method::Event::Commit(text.to_owned()))
+    }
+
+    fn backspace() -> Event {
+        let key = keyboard::Key::Named(key::Named::Backspace);
+
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key: key.clone(),
+            modified_key: key,
+            physical_key: key::Physical::Unidentified(
+                key::NativeCode::Unidentified,
+            ),
+            location: keyboard::Location::Standard,
+            modifiers: keyboard::Modifiers::empty(),
+            text: None,
+        })
+    }
+
+    fn redraw(at: Instant) -> Event {
+        Event::Window(window::Event::RedrawRequested(at))
+    }
+
+    fn options(state: &State<String>) -> Vec<String> {
+        state.with_inner(|inner| inner.filtered_options.options.clone())
+    }
+
+    #[test]
+    fn query_is_published_after_debounce() {
+        let state = State::new(Vec::new());
+        let mut combo_box = combo_box(&state);
+        let mut tree = tree(&combo_box);
+
+        assert!(update(&mut combo_box, &mut tree, type_text("ap")).is_empty());
+        assert!(state.is_loading());
+
+        let now = Instant::now();
+
+        assert!(update(&mut combo_box, &mut tree, redraw(now)).is_empty());
+        assert_eq!(
+            update(&mut combo_box, &mut tree, redraw(now + DEBOUNCE)),
+            vec![Message::Query("ap".to_owned())]
+        );
+        assert!(
+            update(&mut combo_box, &mut tree, redraw(now + DEBOUNCE * 2))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn typing_postpones_query() {
+        let state = State::new(Vec::new());
+        let mut combo_box = combo_box(&state);
+        let mut tree = tree(&combo_box);
+
+        let _ = update(&mut combo_box, &mut tree, type_text("a"));
+        let _ = update(&mut combo_box, &mut tree, type_text("p"));
+
+        assert_eq!(
+            update(
+                &mut combo_box,
+                &mut tree,
+                redraw(Instant::now() + DEBOUNCE)
+            ),
+            vec![Message::Query("ap".to_owned())]
+        );
+    }
+
+    #[test]
+    fn clearing_input_ends_loading_without_query() {
+        let state = State::new(Vec::new());
+        let mut combo_box = combo_box(&state);
+        let mut tree = tree(&combo_box);
+
+        let _ = update(&mut combo_box, &mut tree, type_text("a"));
+        let _ = update(&mut combo_box, &mut tree, backspace());
+
+        assert!(!state.is_loading());
+        assert!(update(
+            &mut combo_box,
+            &mut tree,
+            redraw(Instant::now() + DEBOUNCE)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn set_suggestions_rejects_stale_queries() {
+        let mut state = State::new(vec!["banana".to_owned()]);
+
+        {
+            let mut combo_box = combo_box(&state);
+            let mut tree = tree(&combo_box);
+
+            let _ = update(&mut combo_box, &mut tree, type_text("ap"));
+        }
+
+        state.set_suggestions("a", vec!["avocado".to_owned()]);
+
+        assert!(state.is_loading());
+        assert_eq!(state.options(), ["banana".to_owned()]);
+
+        state.set_suggestions("ap", vec!["apple".to_owned()]);
+
+        assert!(!state.is_loading());
+        assert_eq!(state.options(), ["apple".to_owned()]);
+        assert_eq!(options(&state), vec!["apple".to_owned()]);
+    }
+
+    #[test]
+    fn finish_query_ends_loading_of_current_query() {
+        let mut state = State::new(vec!["banana".to_owned()]);
+
+        {
+            let mut combo_box = combo_box(&state);
+            let mut tree = tree(&combo_box);
+
+            let _ = update(&mut combo_box, &mut tree, type_text("ap"));
+        }
+
+        state.finish_query("a");
+
+        assert!(state.is_loading());
+
+        state.finish_query("ap");
+
+        assert!(!state.is_loading());
+        assert_eq!(state.options(), ["banana".to_owned()]);
+    }
+
+    #[test]
+    fn loading_requires_on_query() {
+        let state = State::new(Vec::new());
+        let mut combo_box = ComboBox::new(&state, "", None, Message::Selected);
+        let mut tree = tree(&combo_box);
+
+        let _ = update(&mut combo_box, &mut tree, type_text("ap"));
+
+        assert!(!state.is_loading());
+    }
+}
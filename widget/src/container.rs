@@ -451,6 +451,7 @@ pub fn draw_background<Renderer>(
             },
             style
                 .background
+                .clone()
                 .unwrap_or(Background::Color(Color::TRANSPARENT)),
         );
     }
@@ -571,7 +572,7 @@ pub fn visible_bounds(id: Id) -> Task<Option<Rectangle>> {
 }
 
 /// The appearance of a container.
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Style {
     /// The text [`Color`] of the container.
     pub text_color: Option<Color>,
@@ -635,6 +636,18 @@ impl From<gradient::Linear> for Style {
     }
 }
 
+impl From<gradient::Radial> for Style {
+    fn from(gradient: gradient::Radial) -> Self {
+        Self::default().background(gradient)
+    }
+}
+
+impl From<gradient::Conic> for Style {
+    fn from(gradient: gradient::Conic) -> Self {
+        Self::default().background(gradient)
+    }
+}
+
 /// The theme catalog of a [`Container`].
 pub trait Catalog {
     /// The item class of the [`Catalog`].
@@ -652,7 +665,7 @@ pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
 
 impl<Theme> From<Style> for StyleFn<'_, Theme> {
     fn from(style: Style) -> Self {
-        Box::new(move |_theme| style)
+        Box::new(move |_theme| style.clone())
     }
 }
 
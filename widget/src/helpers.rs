@@ -8,6 +8,7 @@ use crate::core::widget::operation::{self, Operation};
 use crate::core::window;
 use crate::core::{Element, Length, Pixels, Widget};
 use crate::keyed;
+use crate::multi_pick_list::{self, MultiPickList};
 use crate::overlay;
 use crate::pane_grid::{self, PaneGrid};
 use crate::pick_list::{self, PickList};
@@ -1456,6 +1457,28 @@ where
     PickList::new(options, selected, on_selected)
 }
 
+/// Creates a new [`MultiPickList`].
+///
+/// Multi pick lists display a dropdown list of options that can be toggled,
+/// showing the selected ones as removable chips.
+///
+/// See [`MultiPickList`] for an example.
+pub fn multi_pick_list<'a, T, L, V, Message, Theme, Renderer>(
+    options: L,
+    selected: V,
+    on_change: impl Fn(Vec<T>) -> Message + 'a,
+) -> MultiPickList<'a, T, L, V, Message, Theme, Renderer>
+where
+    T: ToString + PartialEq + Clone + 'a,
+    L: Borrow<[T]> + 'a,
+    V: Borrow<[T]> + 'a,
+    Message: Clone,
+    Theme: multi_pick_list::Catalog + overlay::menu::Catalog,
+    Renderer: core::text::Renderer,
+{
+    MultiPickList::new(options, selected, on_change)
+}
+
 /// Creates a new [`ComboBox`].
 ///
 /// Combo boxes display a dropdown list of searchable and selectable options.
@@ -16,7 +16,9 @@
 //! }
 //! ```
 //! <img src="https://github.com/iced-rs/iced/blob/9712b319bb7a32848001b96bd84977430f14b623/examples/resources/ferris.png?raw=true" width="300">
+pub mod animated;
 pub mod viewer;
+pub use animated::Animated;
 pub use viewer::Viewer;
 
 use crate::core::image;
@@ -25,17 +27,30 @@ use crate::core::mouse;
 use crate::core::renderer;
 use crate::core::widget::Tree;
 use crate::core::{
-    ContentFit, Element, Layout, Length, Point, Rectangle, Rotation, Size,
-    Vector, Widget,
+    Color, ContentFit, Element, Layout, Length, Point, Rectangle, Rotation,
+    Size, Vector, Widget,
 };
 
-pub use image::{FilterMethod, Handle};
+pub use image::{Adjustments, Animation, FilterMethod, Frame, Handle};
+
+#[cfg(feature = "animated-image")]
+pub use crate::graphics::image::load_animation;
 
 /// Creates a new [`Viewer`] with the given image `Handle`.
-pub fn viewer<Handle>(handle: Handle) -> Viewer<Handle> {
+pub fn viewer<'a, Handle, Message, Theme>(
+    handle: Handle,
+) -> Viewer<'a, Handle, Message, Theme>
+where
+    Theme: viewer::Catalog,
+{
     Viewer::new(handle)
 }
 
+/// Creates a new [`Animated`] image playing the given [`Animation`].
+pub fn animated(animation: &Animation) -> Animated {
+    Animated::new(animation)
+}
+
 /// A frame that displays an image while keeping aspect ratio.
 ///
 /// # Example
@@ -57,12 +72,16 @@ pub fn viewer<Handle>(handle: Handle) -> Viewer<Handle> {
 #[derive(Debug)]
 pub struct Image<Handle = image::Handle> {
     handle: Handle,
+    placeholder: Option<Handle>,
     width: Length,
     height: Length,
     content_fit: ContentFit,
     filter_method: FilterMethod,
     rotation: Rotation,
     opacity: f32,
+    crop: Option<Rectangle<u32>>,
+    tint: Color,
+    adjustments: Adjustments,
 }
 
 impl<Handle> Image<Handle> {
@@ -70,15 +89,28 @@ impl<Handle> Image<Handle> {
     pub fn new(handle: impl Into<Handle>) -> Self {
         Image {
             handle: handle.into(),
+            placeholder: None,
             width: Length::Shrink,
             height: Length::Shrink,
             content_fit: ContentFit::default(),
             filter_method: FilterMethod::default(),
             rotation: Rotation::default(),
             opacity: 1.0,
+            crop: None,
+            tint: Color::WHITE,
+            adjustments: Adjustments::NONE,
         }
     }
 
+    /// Sets the placeholder of the [`Image`], drawn while its image is
+    /// still being loaded.
+    ///
+    /// A small, already decoded preview of the image is a good placeholder.
+    pub fn placeholder(mut self, placeholder: impl Into<Handle>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
     /// Sets the width of the [`Image`] boundaries.
     pub fn width(mut self, width: impl Into<Length>) -> Self {
         self.width = width.into();
@@ -119,6 +151,66 @@ impl<Handle> Image<Handle> {
         self.opacity = opacity.into();
         self
     }
+
+    /// Crops the [`Image`] to the given region, in physical pixels of the
+    /// image.
+    ///
+    /// The [`Image`] is laid out as if the region was the whole image.
+    pub fn crop(mut self, region: Rectangle<u32>) -> Self {
+        self.crop = Some(region);
+        self
+    }
+
+    /// Sets the tint of the [`Image`], which multiplies its pixels.
+    pub fn tint(mut self, tint: impl Into<Color>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+
+    /// Sets the brightness of the [`Image`].
+    ///
+    /// `0.0` makes it black and `1.0` leaves it untouched.
+    pub fn brightness(mut self, brightness: f32) -> Self {
+        self.adjustments.brightness = brightness;
+        self
+    }
+
+    /// Sets the contrast of the [`Image`].
+    ///
+    /// `0.0` makes it completely gray and `1.0` leaves it untouched.
+    pub fn contrast(mut self, contrast: f32) -> Self {
+        self.adjustments.contrast = contrast;
+        self
+    }
+
+    /// Sets the saturation of the [`Image`].
+    ///
+    /// `0.0` makes it grayscale and `1.0` leaves it untouched.
+    pub fn saturation(mut self, saturation: f32) -> Self {
+        self.adjustments.saturation = saturation;
+        self
+    }
+
+    /// Turns the [`Image`] grayscale.
+    pub fn grayscale(self) -> Self {
+        self.saturation(0.0)
+    }
+}
+
+/// Returns the size of the image of the given handle, cropped to the given
+/// region.
+fn measure<Renderer, Handle>(
+    renderer: &Renderer,
+    handle: &Handle,
+    crop: Option<Rectangle<u32>>,
+) -> Size
+where
+    Renderer: image::Renderer<Handle = Handle>,
+{
+    image::region(crop, renderer.measure_image(handle))
+        .map_or(Size::ZERO, |region| {
+            Size::new(region.width as f32, region.height as f32)
+        })
 }
 
 /// Computes the layout of an [`Image`].
@@ -130,14 +222,13 @@ pub fn layout<Renderer, Handle>(
     height: Length,
     content_fit: ContentFit,
     rotation: Rotation,
+    crop: Option<Rectangle<u32>>,
 ) -> layout::Node
 where
     Renderer: image::Renderer<Handle = Handle>,
 {
-    // The raw w/h of the underlying image
-    let image_size = renderer.measure_image(handle);
-    let image_size =
-        Size::new(image_size.width as f32, image_size.height as f32);
+    // The raw w/h of the underlying image, once cropped
+    let image_size = measure(renderer, handle, crop);
 
     // The rotated size of the image
     let rotated_size = rotation.apply(image_size);
@@ -172,12 +263,14 @@ pub fn draw<Renderer, Handle>(
     filter_method: FilterMethod,
     rotation: Rotation,
     opacity: f32,
+    crop: Option<Rectangle<u32>>,
+    tint: Color,
+    adjustments: Adjustments,
 ) where
     Renderer: image::Renderer<Handle = Handle>,
     Handle: Clone,
 {
-    let Size { width, height } = renderer.measure_image(handle);
-    let image_size = Size::new(width as f32, height as f32);
+    let image_size = measure(renderer, handle, crop);
     let rotated_size = rotation.apply(image_size);
 
     let bounds = layout.bounds();
@@ -204,16 +297,17 @@ pub fn draw<Renderer, Handle>(
     let drawing_bounds = Rectangle::new(position, final_size);
 
     let render = |renderer: &mut Renderer| {
-        renderer.draw_image(
-            image::Image {
-                handle: handle.clone(),
-                filter_method,
-                rotation: rotation.radians(),
-                opacity,
-                snap: true,
-            },
-            drawing_bounds,
-        );
+        let mut image = image::Image::from_handle(handle.clone())
+            .filter_method(filter_method)
+            .rotation(rotation.radians())
+            .opacity(opacity)
+            .snap(true)
+            .tint(tint)
+            .adjustments(adjustments);
+
+        image.crop = crop;
+
+        renderer.draw_image(image, drawing_bounds);
     };
 
     if adjusted_fit.width > bounds.width || adjusted_fit.height > bounds.height
@@ -251,6 +345,7 @@ where
             self.height,
             self.content_fit,
             self.rotation,
+            self.crop,
         )
     }
 
@@ -264,14 +359,47 @@ where
         _cursor: mouse::Cursor,
         _viewport: &Rectangle,
     ) {
+        let (handle, crop) = match &self.placeholder {
+            Some(placeholder) if renderer.is_image_loading(&self.handle) => {
+                // Crop the same relative region of the placeholder
+                let crop = self.crop.map(|crop| {
+                    let image = renderer.measure_image(&self.handle);
+                    let preview = renderer.measure_image(placeholder);
+
+                    let scale = |value: u32, from: u32, to: u32| {
+                        (u64::from(value) * u64::from(to)
+                            / u64::from(from.max(1)))
+                            as u32
+                    };
+
+                    Rectangle {
+                        x: scale(crop.x, image.width, preview.width),
+                        y: scale(crop.y, image.height, preview.height),
+                        width: scale(crop.width, image.width, preview.width),
+                        height: scale(
+                            crop.height,
+                            image.height,
+                            preview.height,
+                        ),
+                    }
+                });
+
+                (placeholder, crop)
+            }
+            _ => (&self.handle, self.crop),
+        };
+
         draw(
             renderer,
             layout,
-            &self.handle,
+            handle,
             self.content_fit,
             self.filter_method,
             self.rotation,
             self.opacity,
+            crop,
+            self.tint,
+            self.adjustments,
         );
     }
 }
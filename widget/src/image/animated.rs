@@ -0,0 +1,251 @@
+//! Play animated images frame by frame.
+use crate::core::image::{self, Animation, FilterMethod};
+use crate::core::layout;
+use crate::core::mouse;
+use crate::core::renderer;
+use crate::core::time::Instant;
+use crate::core::widget::tree::{self, Tree};
+use crate::core::window;
+use crate::core::{
+    Clipboard, Color, ContentFit, Element, Event, Layout, Length, Rectangle,
+    Rotation, Shell, Size, Widget,
+};
+
+/// A frame that plays an [`Animation`] while keeping aspect ratio.
+///
+/// Redraws are only requested when the next frame is due.
+#[derive(Debug)]
+pub struct Animated {
+    animation: Animation,
+    width: Length,
+    height: Length,
+    content_fit: ContentFit,
+    filter_method: FilterMethod,
+    rotation: Rotation,
+    opacity: f32,
+    is_paused: bool,
+    is_looping: bool,
+}
+
+impl Animated {
+    /// Creates a new [`Animated`] image playing the given [`Animation`].
+    pub fn new(animation: &Animation) -> Self {
+        Animated {
+            animation: animation.clone(),
+            width: Length::Shrink,
+            height: Length::Shrink,
+            content_fit: ContentFit::default(),
+            filter_method: FilterMethod::default(),
+            rotation: Rotation::default(),
+            opacity: 1.0,
+            is_paused: false,
+            is_looping: true,
+        }
+    }
+
+    /// Sets the width of the [`Animated`] boundaries.
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the height of the [`Animated`] boundaries.
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Sets the [`ContentFit`] of the [`Animated`] image.
+    ///
+    /// Defaults to [`ContentFit::Contain`]
+    pub fn content_fit(mut self, content_fit: ContentFit) -> Self {
+        self.content_fit = content_fit;
+        self
+    }
+
+    /// Sets the [`FilterMethod`] of the [`Animated`] image.
+    pub fn filter_method(mut self, filter_method: FilterMethod) -> Self {
+        self.filter_method = filter_method;
+        self
+    }
+
+    /// Applies the given [`Rotation`] to the [`Animated`] image.
+    pub fn rotation(mut self, rotation: impl Into<Rotation>) -> Self {
+        self.rotation = rotation.into();
+        self
+    }
+
+    /// Sets the opacity of the [`Animated`] image.
+    ///
+    /// It should be in the [0.0, 1.0] range—`0.0` meaning completely transparent,
+    /// and `1.0` meaning completely opaque.
+    pub fn opacity(mut self, opacity: impl Into<f32>) -> Self {
+        self.opacity = opacity.into();
+        self
+    }
+
+    /// Sets whether the [`Animated`] image is paused.
+    ///
+    /// A paused animation keeps showing its current frame and resumes
+    /// from it once unpaused.
+    pub fn paused(mut self, is_paused: bool) -> Self {
+        self.is_paused = is_paused;
+        self
+    }
+
+    /// Sets whether the [`Animated`] image starts over after its last frame.
+    ///
+    /// Defaults to `true`.
+    pub fn looping(mut self, is_looping: bool) -> Self {
+        self.is_looping = is_looping;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    animation: image::Id,
+    frame: usize,
+    next_frame: Option<Instant>,
+}
+
+impl State {
+    fn new(animation: &Animation) -> Self {
+        Self {
+            animation: animation.id(),
+            frame: 0,
+            next_frame: None,
+        }
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer> for Animated
+where
+    Renderer: image::Renderer<Handle = image::Handle>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::new(&self.animation))
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        let state = tree.state.downcast_mut::<State>();
+
+        if state.animation != self.animation.id() {
+            *state = State::new(&self.animation);
+        }
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        super::layout(
+            renderer,
+            limits,
+            &self.animation.frames()[0].handle,
+            self.width,
+            self.height,
+            self.content_fit,
+            self.rotation,
+            None,
+        )
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let Event::Window(window::Event::RedrawRequested(now)) = event else {
+            return;
+        };
+
+        let state = tree.state.downcast_mut::<State>();
+        let frames = self.animation.frames();
+        let duration = self.animation.duration();
+
+        let is_over = state.frame + 1 >= frames.len() && !self.is_looping;
+
+        if self.is_paused || is_over || duration.is_zero() {
+            state.next_frame = None;
+            return;
+        }
+
+        let mut next_frame = *state
+            .next_frame
+            .get_or_insert_with(|| now + frames[state.frame].delay);
+
+        // Skip the missed loops, instead of fast-forwarding through them
+        if now >= next_frame + duration {
+            next_frame = now;
+        }
+
+        while now >= next_frame {
+            if state.frame + 1 >= frames.len() && !self.is_looping {
+                state.next_frame = None;
+                return;
+            }
+
+            state.frame = (state.frame + 1) % frames.len();
+            next_frame += frames[state.frame].delay;
+        }
+
+        state.next_frame = Some(next_frame);
+        shell.request_redraw_at(next_frame);
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+
+        super::draw(
+            renderer,
+            layout,
+            &self.animation.frames()[state.frame].handle,
+            self.content_fit,
+            self.filter_method,
+            self.rotation,
+            self.opacity,
+            None,
+            Color::WHITE,
+            image::Adjustments::NONE,
+        );
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Animated>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Renderer: image::Renderer<Handle = image::Handle>,
+{
+    fn from(animated: Animated) -> Element<'a, Message, Theme, Renderer> {
+        Element::new(animated)
+    }
+}
@@ -1,17 +1,50 @@
-//! Zoom and pan on an image.
+//! Zoom, pan and rotate an image.
+use crate::core::border::{self, Border};
 use crate::core::image::{self, FilterMethod};
+use crate::core::keyboard;
+use crate::core::keyboard::key;
 use crate::core::layout;
 use crate::core::mouse;
 use crate::core::renderer;
+use crate::core::touch;
+use crate::core::widget;
+use crate::core::widget::operation::{self, Operation};
 use crate::core::widget::tree::{self, Tree};
 use crate::core::{
-    Clipboard, ContentFit, Element, Event, Image, Layout, Length, Pixels,
-    Point, Radians, Rectangle, Shell, Size, Vector, Widget,
+    Background, Clipboard, Color, ContentFit, Element, Event, Image, Layout,
+    Length, Pixels, Point, Radians, Rectangle, Shell, Size, Theme, Vector,
+    Widget,
 };
+use crate::runtime::task::{self, Task};
+use crate::runtime::window;
+use crate::runtime::Action;
+
+/// The maximum size of the minimap of a [`Viewer`].
+const MINIMAP_SIZE: f32 = 128.0;
+
+/// The spacing between the minimap of a [`Viewer`] and its edges.
+const MINIMAP_SPACING: f32 = 8.0;
+
+/// The distance panned by the arrow keys.
+const PAN_STEP: f32 = 32.0;
 
 /// A frame that displays an image with the ability to zoom in/out and pan.
+///
+/// While hovered, a [`Viewer`] can also be controlled with the keyboard:
+///
+/// - `+` and `-` zoom in and out.
+/// - `0` zooms to fit the image.
+/// - `1` zooms to the actual size of the image.
+/// - The arrow keys pan the image.
+///
+/// On touch devices, the image can be panned with one finger and zoomed by
+/// pinching it with two.
 #[allow(missing_debug_implementations)]
-pub struct Viewer<Handle> {
+pub struct Viewer<'a, Handle, Message = (), Theme = crate::Theme>
+where
+    Theme: Catalog,
+{
+    id: Option<Id>,
     padding: f32,
     width: Length,
     height: Length,
@@ -21,12 +54,20 @@ pub struct Viewer<Handle> {
     handle: Handle,
     filter_method: FilterMethod,
     content_fit: ContentFit,
+    rotation: Radians,
+    has_minimap: bool,
+    on_zoom: Option<Box<dyn Fn(Zoom) -> Message + 'a>>,
+    class: Theme::Class<'a>,
 }
 
-impl<Handle> Viewer<Handle> {
+impl<'a, Handle, Message, Theme> Viewer<'a, Handle, Message, Theme>
+where
+    Theme: Catalog,
+{
     /// Creates a new [`Viewer`] with the given [`State`].
     pub fn new<T: Into<Handle>>(handle: T) -> Self {
         Viewer {
+            id: None,
             handle: handle.into(),
             padding: 0.0,
             width: Length::Shrink,
@@ -36,9 +77,19 @@ impl<Handle> Viewer<Handle> {
             scale_step: 0.10,
             filter_method: FilterMethod::default(),
             content_fit: ContentFit::default(),
+            rotation: Radians(0.0),
+            has_minimap: false,
+            on_zoom: None,
+            class: Theme::default(),
         }
     }
 
+    /// Sets the [`Id`] of the [`Viewer`].
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
     /// Sets the [`FilterMethod`] of the [`Viewer`].
     pub fn filter_method(mut self, filter_method: image::FilterMethod) -> Self {
         self.filter_method = filter_method;
@@ -93,13 +144,90 @@ impl<Handle> Viewer<Handle> {
         self.scale_step = scale_step;
         self
     }
+
+    /// Rotates the image of the [`Viewer`] around its center.
+    ///
+    /// The image is fit to the [`Viewer`] once rotated.
+    pub fn rotation(mut self, rotation: impl Into<Radians>) -> Self {
+        self.rotation = rotation.into();
+        self
+    }
+
+    /// Sets whether the [`Viewer`] shows a minimap of its image, with the
+    /// visible region highlighted, while the image does not fit.
+    pub fn minimap(mut self, has_minimap: bool) -> Self {
+        self.has_minimap = has_minimap;
+        self
+    }
+
+    /// Sets the message that should be produced when the [`Zoom`] of the
+    /// [`Viewer`] changes.
+    ///
+    /// Besides the user zooming, this includes the zoom [`Task`]s of this
+    /// module and any resize of the [`Viewer`] or its image.
+    pub fn on_zoom(mut self, on_zoom: impl Fn(Zoom) -> Message + 'a) -> Self {
+        self.on_zoom = Some(Box::new(on_zoom));
+        self
+    }
+
+    /// Sets the style of the [`Viewer`].
+    #[must_use]
+    pub fn style(mut self, style: impl Fn(&Theme) -> Style + 'a) -> Self
+    where
+        Theme::Class<'a>: From<StyleFn<'a, Theme>>,
+    {
+        self.class = (Box::new(style) as StyleFn<'a, Theme>).into();
+        self
+    }
+
+    /// Sets the style class of the [`Viewer`].
+    #[cfg(feature = "advanced")]
+    #[must_use]
+    pub fn class(mut self, class: impl Into<Theme::Class<'a>>) -> Self {
+        self.class = class.into();
+        self
+    }
+
+    fn geometry<Renderer>(
+        &self,
+        renderer: &Renderer,
+        bounds: Rectangle,
+    ) -> Geometry
+    where
+        Renderer: image::Renderer<Handle = Handle>,
+    {
+        let Size { width, height } = renderer.measure_image(&self.handle);
+        let image = Size::new(width as f32, height as f32);
+
+        Geometry {
+            bounds,
+            image,
+            rotation: self.rotation,
+            fitted: self
+                .content_fit
+                .fit(image.rotate(self.rotation), bounds.size()),
+            min_scale: self.min_scale,
+            max_scale: self.max_scale,
+        }
+    }
+
+    fn image(&self, filter_method: FilterMethod) -> Image<Handle>
+    where
+        Handle: Clone,
+    {
+        Image::from_handle(self.handle.clone())
+            .filter_method(filter_method)
+            .rotation(self.rotation)
+            .snap(true)
+    }
 }
 
-impl<Message, Theme, Renderer, Handle> Widget<Message, Theme, Renderer>
-    for Viewer<Handle>
+impl<'a, Message, Theme, Renderer, Handle> Widget<Message, Theme, Renderer>
+    for Viewer<'a, Handle, Message, Theme>
 where
     Renderer: image::Renderer<Handle = Handle>,
     Handle: Clone,
+    Theme: Catalog,
 {
     fn tag(&self) -> tree::Tag {
         tree::Tag::of::<State>()
@@ -122,10 +250,11 @@ where
         renderer: &Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        // The raw w/h of the underlying image
+        // The raw w/h of the underlying image, once rotated
         let image_size = renderer.measure_image(&self.handle);
         let image_size =
-            Size::new(image_size.width as f32, image_size.height as f32);
+            Size::new(image_size.width as f32, image_size.height as f32)
+                .rotate(self.rotation);
 
         // The size to be available to the widget prior to `Shrink`ing
         let raw_size = limits.resolve(self.width, self.height, image_size);
@@ -148,6 +277,19 @@ where
         layout::Node::new(final_size)
     }
 
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        state.geometry = self.geometry(renderer, layout.bounds());
+
+        operation.custom(state, self.id.as_ref().map(|id| &id.0));
+    }
+
     fn update(
         &mut self,
         tree: &mut Tree,
@@ -161,6 +303,9 @@ where
     ) {
         let bounds = layout.bounds();
 
+        let state = tree.state.downcast_mut::<State>();
+        state.geometry = self.geometry(renderer, bounds);
+
         match event {
             Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
                 let Some(cursor_position) = cursor.position_over(bounds) else {
@@ -170,46 +315,15 @@ where
                 match delta {
                     mouse::ScrollDelta::Lines { y, .. }
                     | mouse::ScrollDelta::Pixels { y, .. } => {
-                        let state = tree.state.downcast_mut::<State>();
-                        let previous_scale = state.scale;
-
-                        if y < 0.0 && previous_scale > self.min_scale
-                            || y > 0.0 && previous_scale < self.max_scale
-                        {
-                            state.scale = (if y > 0.0 {
-                                state.scale * (1.0 + self.scale_step)
-                            } else {
-                                state.scale / (1.0 + self.scale_step)
-                            })
-                            .clamp(self.min_scale, self.max_scale);
-
-                            let scaled_size = scaled_image_size(
-                                renderer,
-                                &self.handle,
-                                state,
-                                bounds.size(),
-                                self.content_fit,
+                        if y > 0.0 {
+                            state.zoom_at(
+                                state.scale * (1.0 + self.scale_step),
+                                cursor_position,
                             );
-
-                            let factor = state.scale / previous_scale - 1.0;
-
-                            let cursor_to_center =
-                                cursor_position - bounds.center();
-
-                            let adjustment = cursor_to_center * factor
-                                + state.current_offset * factor;
-
-                            state.current_offset = Vector::new(
-                                if scaled_size.width > bounds.width {
-                                    state.current_offset.x + adjustment.x
-                                } else {
-                                    0.0
-                                },
-                                if scaled_size.height > bounds.height {
-                                    state.current_offset.y + adjustment.y
-                                } else {
-                                    0.0
-                                },
+                        } else if y < 0.0 {
+                            state.zoom_at(
+                                state.scale / (1.0 + self.scale_step),
+                                cursor_position,
                             );
                         }
                     }
@@ -222,62 +336,149 @@ where
                     return;
                 };
 
-                let state = tree.state.downcast_mut::<State>();
-
-                state.cursor_grabbed_at = Some(cursor_position);
-                state.starting_offset = state.current_offset;
+                state.grab(cursor_position);
                 shell.capture_event();
             }
             Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
-                let state = tree.state.downcast_mut::<State>();
-
                 if state.cursor_grabbed_at.is_some() {
                     state.cursor_grabbed_at = None;
                     shell.capture_event();
                 }
             }
             Event::Mouse(mouse::Event::CursorMoved { position }) => {
-                let state = tree.state.downcast_mut::<State>();
-
-                if let Some(origin) = state.cursor_grabbed_at {
-                    let scaled_size = scaled_image_size(
-                        renderer,
-                        &self.handle,
-                        state,
-                        bounds.size(),
-                        self.content_fit,
-                    );
-                    let hidden_width = (scaled_size.width - bounds.width / 2.0)
-                        .max(0.0)
-                        .round();
-
-                    let hidden_height = (scaled_size.height
-                        - bounds.height / 2.0)
-                        .max(0.0)
-                        .round();
-
-                    let delta = position - origin;
-
-                    let x = if bounds.width < scaled_size.width {
-                        (state.starting_offset.x - delta.x)
-                            .clamp(-hidden_width, hidden_width)
-                    } else {
-                        0.0
-                    };
-
-                    let y = if bounds.height < scaled_size.height {
-                        (state.starting_offset.y - delta.y)
-                            .clamp(-hidden_height, hidden_height)
-                    } else {
-                        0.0
-                    };
-
-                    state.current_offset = Vector::new(x, y);
+                if state.cursor_grabbed_at.is_some() {
+                    state.drag(position);
                     shell.capture_event();
                 }
             }
+            Event::Touch(touch::Event::FingerPressed { id, position }) => {
+                if !bounds.contains(position) {
+                    return;
+                }
+
+                let Some(slot) =
+                    state.fingers.iter_mut().find(|finger| finger.is_none())
+                else {
+                    return;
+                };
+
+                *slot = Some((id, position));
+
+                match state.fingers {
+                    [Some(_), Some(_)] => {
+                        state.cursor_grabbed_at = None;
+                    }
+                    _ => {
+                        state.grab(position);
+                    }
+                }
+
+                shell.capture_event();
+            }
+            Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                let previous = state.fingers;
+
+                let Some((_, finger)) = state
+                    .fingers
+                    .iter_mut()
+                    .flatten()
+                    .find(|(finger, _)| *finger == id)
+                else {
+                    return;
+                };
+
+                *finger = position;
+
+                match (previous, state.fingers) {
+                    (
+                        [Some((_, a)), Some((_, b))],
+                        [Some((_, new_a)), Some((_, new_b))],
+                    ) => {
+                        state.pinch([a, b], [new_a, new_b]);
+                    }
+                    _ => {
+                        state.drag(position);
+                    }
+                }
+
+                shell.capture_event();
+            }
+            Event::Touch(
+                touch::Event::FingerLifted { id, .. }
+                | touch::Event::FingerLost { id, .. },
+            ) => {
+                let Some(slot) = state.fingers.iter_mut().find(|finger| {
+                    finger.is_some_and(|(finger, _)| finger == id)
+                }) else {
+                    return;
+                };
+
+                *slot = None;
+
+                // Keep panning with the remaining finger, if any
+                match state.fingers {
+                    [Some((_, position)), None]
+                    | [None, Some((_, position))] => {
+                        state.grab(position);
+                    }
+                    _ => {
+                        state.cursor_grabbed_at = None;
+                    }
+                }
+
+                shell.capture_event();
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => {
+                if !cursor.is_over(bounds) {
+                    return;
+                }
+
+                match key.as_ref() {
+                    keyboard::Key::Character("+" | "=") => {
+                        state.zoom_to(state.scale * (1.0 + self.scale_step));
+                    }
+                    keyboard::Key::Character("-") => {
+                        state.zoom_to(state.scale / (1.0 + self.scale_step));
+                    }
+                    keyboard::Key::Character("0") => {
+                        state.zoom_to_fit();
+                    }
+                    keyboard::Key::Character("1") => {
+                        state.zoom_to_actual_size();
+                    }
+                    keyboard::Key::Named(key::Named::ArrowLeft) => {
+                        state.pan(Vector::new(-PAN_STEP, 0.0));
+                    }
+                    keyboard::Key::Named(key::Named::ArrowRight) => {
+                        state.pan(Vector::new(PAN_STEP, 0.0));
+                    }
+                    keyboard::Key::Named(key::Named::ArrowUp) => {
+                        state.pan(Vector::new(0.0, -PAN_STEP));
+                    }
+                    keyboard::Key::Named(key::Named::ArrowDown) => {
+                        state.pan(Vector::new(0.0, PAN_STEP));
+                    }
+                    _ => return,
+                }
+
+                shell.capture_event();
+            }
             _ => {}
         }
+
+        // Zoom tasks change the state outside of `update`, so changes are
+        // detected against the last known zoom instead
+        let zoom = state.zoom();
+
+        if state
+            .last_zoom
+            .replace(zoom)
+            .is_some_and(|last| last != zoom)
+        {
+            if let Some(on_zoom) = &self.on_zoom {
+                shell.publish(on_zoom(zoom));
+            }
+        }
     }
 
     fn mouse_interaction(
@@ -305,7 +506,7 @@ where
         &self,
         tree: &Tree,
         renderer: &mut Renderer,
-        _theme: &Theme,
+        theme: &Theme,
         _style: &renderer::Style,
         layout: Layout<'_>,
         _cursor: mouse::Cursor,
@@ -313,14 +514,14 @@ where
     ) {
         let state = tree.state.downcast_ref::<State>();
         let bounds = layout.bounds();
+        let geometry = self.geometry(renderer, bounds);
 
-        let final_size = scaled_image_size(
-            renderer,
-            &self.handle,
-            state,
-            bounds.size(),
-            self.content_fit,
-        );
+        let rotated_size = geometry.image.rotate(self.rotation);
+        let final_size = geometry.fitted * state.scale;
+
+        if rotated_size.width <= 0.0 || rotated_size.height <= 0.0 {
+            return;
+        }
 
         let translation = {
             let diff_w = bounds.width - final_size.width;
@@ -336,24 +537,90 @@ where
             image_top_left - state.offset(bounds, final_size)
         };
 
-        let drawing_bounds = Rectangle::new(bounds.position(), final_size);
-
-        let render = |renderer: &mut Renderer| {
-            renderer.with_translation(translation, |renderer| {
-                renderer.draw_image(
-                    Image {
-                        handle: self.handle.clone(),
-                        filter_method: self.filter_method,
-                        rotation: Radians(0.0),
-                        opacity: 1.0,
-                        snap: true,
+        // The bounds of the rotated image, and the unrotated image inside
+        let image_bounds =
+            Rectangle::new(bounds.position() + translation, final_size);
+
+        let drawing_bounds = centered(
+            image_bounds.center(),
+            geometry.image * (final_size.width / rotated_size.width),
+        );
+
+        renderer.with_layer(bounds, |renderer| {
+            renderer.draw_image(self.image(self.filter_method), drawing_bounds);
+        });
+
+        if !self.has_minimap
+            || final_size.width <= bounds.width
+                && final_size.height <= bounds.height
+        {
+            return;
+        }
+
+        let Some(visible) = bounds.intersection(&image_bounds) else {
+            return;
+        };
+
+        let style = theme.style(&self.class);
+
+        let minimap_size = ContentFit::Contain.fit(
+            rotated_size,
+            Size::new(
+                MINIMAP_SIZE.min(bounds.width / 3.0),
+                MINIMAP_SIZE.min(bounds.height / 3.0),
+            ),
+        );
+
+        let minimap = Rectangle::new(
+            Point::new(
+                bounds.x + bounds.width - minimap_size.width - MINIMAP_SPACING,
+                bounds.y + bounds.height
+                    - minimap_size.height
+                    - MINIMAP_SPACING,
+            ),
+            minimap_size,
+        );
+
+        let ratio = minimap_size.width / final_size.width;
+
+        let region = Rectangle {
+            x: minimap.x + (visible.x - image_bounds.x) * ratio,
+            y: minimap.y + (visible.y - image_bounds.y) * ratio,
+            width: visible.width * ratio,
+            height: visible.height * ratio,
+        };
+
+        renderer.with_layer(bounds, |renderer| {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: minimap,
+                    border: style.minimap_border,
+                    ..renderer::Quad::default()
+                },
+                style.minimap_background,
+            );
+
+            renderer.draw_image(
+                self.image(FilterMethod::Linear),
+                centered(
+                    minimap.center(),
+                    geometry.image * (minimap_size.width / rotated_size.width),
+                ),
+            );
+
+            // Images are drawn on top of quads, so the highlighted region
+            // needs its own layer
+            renderer.with_layer(minimap, |renderer| {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: region,
+                        border: style.region_border,
+                        ..renderer::Quad::default()
                     },
-                    drawing_bounds,
+                    Color::TRANSPARENT,
                 );
             });
-        };
-
-        renderer.with_layer(bounds, render);
+        });
     }
 }
 
@@ -364,6 +631,9 @@ pub struct State {
     starting_offset: Vector,
     current_offset: Vector,
     cursor_grabbed_at: Option<Point>,
+    fingers: [Option<(touch::Finger, Point)>; 2],
+    geometry: Geometry,
+    last_zoom: Option<Zoom>,
 }
 
 impl Default for State {
@@ -373,6 +643,9 @@ impl Default for State {
             starting_offset: Vector::default(),
             current_offset: Vector::default(),
             cursor_grabbed_at: None,
+            fingers: [None; 2],
+            geometry: Geometry::default(),
+            last_zoom: None,
         }
     }
 }
@@ -383,6 +656,85 @@ impl State {
         State::default()
     }
 
+    /// Returns the scale applied to the image of the [`Viewer`] once fit.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Returns the current [`Zoom`] of the [`Viewer`].
+    pub fn zoom(&self) -> Zoom {
+        let Geometry {
+            image,
+            rotation,
+            fitted,
+            ..
+        } = self.geometry;
+
+        let rotated = image.rotate(rotation);
+
+        Zoom {
+            scale: self.scale,
+            ratio: if rotated.width > 0.0 {
+                fitted.width * self.scale / rotated.width
+            } else {
+                self.scale
+            },
+        }
+    }
+
+    /// Zooms the image to the given scale, keeping the center of the
+    /// [`Viewer`] in place.
+    pub fn zoom_to(&mut self, scale: f32) {
+        self.zoom_at(scale, self.geometry.bounds.center());
+    }
+
+    /// Zooms the image to fit the [`Viewer`] and centers it.
+    pub fn zoom_to_fit(&mut self) {
+        self.scale = 1.0;
+        self.current_offset = Vector::default();
+    }
+
+    /// Zooms the image to its actual size, where every pixel of the image
+    /// takes a logical pixel.
+    pub fn zoom_to_actual_size(&mut self) {
+        let Geometry {
+            image,
+            rotation,
+            fitted,
+            ..
+        } = self.geometry;
+
+        if fitted.width > 0.0 {
+            self.zoom_to(image.rotate(rotation).width / fitted.width);
+        }
+    }
+
+    /// Centers the [`Viewer`] on the given point of the image, in pixels of
+    /// the image.
+    pub fn center_on(&mut self, point: Point) {
+        let Geometry {
+            image, rotation, ..
+        } = self.geometry;
+
+        let ratio = self.zoom().ratio;
+        let (sin, cos) = rotation.0.sin_cos();
+
+        let distance =
+            point - Point::new(image.width / 2.0, image.height / 2.0);
+
+        self.current_offset = self.clamp(
+            Vector::new(
+                distance.x * cos - distance.y * sin,
+                distance.x * sin + distance.y * cos,
+            ) * ratio,
+        );
+    }
+
+    /// Returns if the cursor is currently grabbed by the [`Viewer`].
+    pub fn is_cursor_grabbed(&self) -> bool {
+        self.cursor_grabbed_at.is_some()
+    }
+
     /// Returns the current offset of the [`State`], given the bounds
     /// of the [`Viewer`] and its image.
     fn offset(&self, bounds: Rectangle, image_size: Size) -> Vector {
@@ -398,20 +750,139 @@ impl State {
         )
     }
 
-    /// Returns if the cursor is currently grabbed by the [`Viewer`].
-    pub fn is_cursor_grabbed(&self) -> bool {
-        self.cursor_grabbed_at.is_some()
+    /// Clamps the given offset, so the image cannot leave the [`Viewer`]
+    /// and stays centered along any axis in which it fits.
+    fn clamp(&self, offset: Vector) -> Vector {
+        let bounds = self.geometry.bounds;
+        let scaled_size = self.geometry.fitted * self.scale;
+
+        let hidden_width =
+            (scaled_size.width - bounds.width / 2.0).max(0.0).round();
+
+        let hidden_height =
+            (scaled_size.height - bounds.height / 2.0).max(0.0).round();
+
+        Vector::new(
+            if bounds.width < scaled_size.width {
+                offset.x.clamp(-hidden_width, hidden_width)
+            } else {
+                0.0
+            },
+            if bounds.height < scaled_size.height {
+                offset.y.clamp(-hidden_height, hidden_height)
+            } else {
+                0.0
+            },
+        )
     }
+
+    /// Zooms the image to the given scale, keeping the given point in place.
+    fn zoom_at(&mut self, scale: f32, point: Point) {
+        let Geometry {
+            bounds,
+            min_scale,
+            max_scale,
+            ..
+        } = self.geometry;
+
+        let previous_scale = self.scale;
+        self.scale = scale.clamp(min_scale, max_scale);
+
+        let factor = self.scale / previous_scale - 1.0;
+
+        let adjustment =
+            (point - bounds.center()) * factor + self.current_offset * factor;
+
+        self.current_offset = self.clamp(self.current_offset + adjustment);
+    }
+
+    /// Moves the image in the opposite direction of the given delta.
+    fn pan(&mut self, delta: Vector) {
+        self.current_offset = self.clamp(self.current_offset + delta);
+    }
+
+    fn grab(&mut self, position: Point) {
+        self.cursor_grabbed_at = Some(position);
+        self.starting_offset = self.current_offset;
+    }
+
+    fn drag(&mut self, position: Point) {
+        let Some(origin) = self.cursor_grabbed_at else {
+            return;
+        };
+
+        self.current_offset =
+            self.clamp(self.starting_offset - (position - origin));
+    }
+
+    /// Zooms and pans the image following two fingers.
+    fn pinch(&mut self, from: [Point; 2], to: [Point; 2]) {
+        let distance = |[a, b]: [Point; 2]| a.distance(b);
+        let middle = |[a, b]: [Point; 2]| {
+            Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+        };
+
+        if distance(from) > 0.0 {
+            self.zoom_at(
+                self.scale * distance(to) / distance(from),
+                middle(to),
+            );
+        }
+
+        self.pan(middle(from) - middle(to));
+    }
+}
+
+/// The last known geometry of a [`Viewer`].
+#[derive(Debug, Clone, Copy)]
+struct Geometry {
+    bounds: Rectangle,
+    image: Size,
+    rotation: Radians,
+    fitted: Size,
+    min_scale: f32,
+    max_scale: f32,
 }
 
-impl<'a, Message, Theme, Renderer, Handle> From<Viewer<Handle>>
+impl Default for Geometry {
+    fn default() -> Self {
+        Self {
+            bounds: Rectangle::default(),
+            image: Size::ZERO,
+            rotation: Radians(0.0),
+            fitted: Size::ZERO,
+            min_scale: 0.25,
+            max_scale: 10.0,
+        }
+    }
+}
+
+/// The zoom of a [`Viewer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Zoom {
+    /// The scale applied to the image once fit to the [`Viewer`].
+    ///
+    /// `1.0` means the image is fit.
+    pub scale: f32,
+
+    /// The amount of logical pixels taken by every pixel of the image.
+    ///
+    /// `1.0` means the image is shown at its actual size.
+    pub ratio: f32,
+}
+
+impl<'a, Message, Theme, Renderer, Handle>
+    From<Viewer<'a, Handle, Message, Theme>>
     for Element<'a, Message, Theme, Renderer>
 where
     Renderer: 'a + image::Renderer<Handle = Handle>,
     Message: 'a,
+    Theme: Catalog + 'a,
     Handle: Clone + 'a,
 {
-    fn from(viewer: Viewer<Handle>) -> Element<'a, Message, Theme, Renderer> {
+    fn from(
+        viewer: Viewer<'a, Handle, Message, Theme>,
+    ) -> Element<'a, Message, Theme, Renderer> {
         Element::new(viewer)
     }
 }
@@ -439,3 +910,479 @@ where
         adjusted_fit.height * state.scale,
     )
 }
+
+fn centered(center: Point, size: Size) -> Rectangle {
+    Rectangle::new(
+        Point::new(center.x - size.width / 2.0, center.y - size.height / 2.0),
+        size,
+    )
+}
+
+/// The identifier of a [`Viewer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Id(widget::Id);
+
+impl Id {
+    /// Creates a custom [`Id`].
+    pub fn new(id: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        Self(widget::Id::new(id))
+    }
+
+    /// Creates a unique [`Id`].
+    ///
+    /// This function produces a different [`Id`] every time it is called.
+    pub fn unique() -> Self {
+        Self(widget::Id::unique())
+    }
+}
+
+impl From<Id> for widget::Id {
+    fn from(id: Id) -> Self {
+        id.0
+    }
+}
+
+/// Produces a [`Task`] that zooms the [`Viewer`] with the given [`Id`] to
+/// the given scale.
+pub fn zoom_to<T>(id: Id, scale: f32) -> Task<T>
+where
+    T: Send + 'static,
+{
+    apply(id, move |state| state.zoom_to(scale))
+}
+
+/// Produces a [`Task`] that zooms the [`Viewer`] with the given [`Id`] to
+/// fit its image.
+pub fn zoom_to_fit<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    apply(id, State::zoom_to_fit)
+}
+
+/// Produces a [`Task`] that zooms the [`Viewer`] with the given [`Id`] to
+/// the actual size of its image.
+pub fn zoom_to_actual_size<T>(id: Id) -> Task<T>
+where
+    T: Send + 'static,
+{
+    apply(id, State::zoom_to_actual_size)
+}
+
+/// Produces a [`Task`] that centers the [`Viewer`] with the given [`Id`] on
+/// the given point of its image, in pixels of the image.
+pub fn center_on<T>(id: Id, point: Point) -> Task<T>
+where
+    T: Send + 'static,
+{
+    apply(id, move |state| state.center_on(point))
+}
+
+/// Produces a [`Task`] that returns the current [`Zoom`] of the [`Viewer`]
+/// with the given [`Id`].
+pub fn zoom(id: Id) -> Task<Zoom> {
+    struct Query {
+        target: widget::Id,
+        zoom: Option<Zoom>,
+    }
+
+    impl Operation<Zoom> for Query {
+        fn container(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<Zoom>),
+        ) {
+            operate_on_children(self);
+        }
+
+        fn custom(
+            &mut self,
+            state: &mut dyn std::any::Any,
+            id: Option<&widget::Id>,
+        ) {
+            if id != Some(&self.target) {
+                return;
+            }
+
+            if let Some(state) = state.downcast_mut::<State>() {
+                self.zoom = Some(state.zoom());
+            }
+        }
+
+        fn finish(&self) -> operation::Outcome<Zoom> {
+            self.zoom
+                .map_or(operation::Outcome::None, operation::Outcome::Some)
+        }
+    }
+
+    task::widget(Query {
+        target: id.0,
+        zoom: None,
+    })
+}
+
+fn apply<T>(id: Id, f: impl FnMut(&mut State) + Send + 'static) -> Task<T>
+where
+    T: Send + 'static,
+{
+    // The viewer is only redrawn, and its zoom published, if the operation
+    // actually changed its state
+    task::widget(Apply {
+        target: id.0,
+        f,
+        is_changed: false,
+    })
+    .then(|()| task::effect(Action::Window(window::Action::RedrawAll)))
+}
+
+/// An [`Operation`] applying a closure to the [`State`] of a [`Viewer`],
+/// which only outputs if the [`State`] changed.
+struct Apply<F> {
+    target: widget::Id,
+    f: F,
+    is_changed: bool,
+}
+
+impl<F> Operation<()> for Apply<F>
+where
+    F: FnMut(&mut State) + Send,
+{
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<()>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn custom(
+        &mut self,
+        state: &mut dyn std::any::Any,
+        id: Option<&widget::Id>,
+    ) {
+        if id != Some(&self.target) {
+            return;
+        }
+
+        if let Some(state) = state.downcast_mut::<State>() {
+            let previous = (state.scale, state.current_offset);
+
+            (self.f)(state);
+
+            self.is_changed |= previous != (state.scale, state.current_offset);
+        }
+    }
+
+    fn finish(&self) -> operation::Outcome<()> {
+        if self.is_changed {
+            operation::Outcome::Some(())
+        } else {
+            operation::Outcome::None
+        }
+    }
+}
+
+/// The appearance of a [`Viewer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style {
+    /// The [`Background`] of the minimap.
+    pub minimap_background: Background,
+    /// The [`Border`] of the minimap.
+    pub minimap_border: Border,
+    /// The [`Border`] of the visible region highlighted in the minimap.
+    pub region_border: Border,
+}
+
+/// The theme catalog of a [`Viewer`].
+pub trait Catalog {
+    /// The item class of the [`Catalog`].
+    type Class<'a>;
+
+    /// The default class produced by the [`Catalog`].
+    fn default<'a>() -> Self::Class<'a>;
+
+    /// The [`Style`] of a class.
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+/// A styling function for a [`Viewer`].
+///
+/// This is just a boxed closure: `Fn(&Theme) -> Style`.
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+/// The default style of a [`Viewer`].
+pub fn default(theme: &Theme) -> Style {
+    let palette = theme.extended_palette();
+
+    Style {
+        minimap_background: palette
+            .background
+            .base
+            .color
+            .scale_alpha(0.8)
+            .into(),
+        minimap_border: border::rounded(2)
+            .width(1)
+            .color(palette.background.strong.color),
+        region_border: border::width(2).color(palette.primary.strong.color),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::clipboard;
+
+    /// A renderer whose image handles are their own size.
+    struct Renderer;
+
+    impl crate::core::Renderer for Renderer {
+        fn start_layer(&mut self, _bounds: Rectangle) {}
+
+        fn end_layer(&mut self) {}
+
+        fn start_transformation(
+            &mut self,
+            _transformation: crate::core::Transformation,
+        ) {
+        }
+
+        fn end_transformation(&mut self) {}
+
+        fn clear(&mut self) {}
+
+        fn fill_quad(
+            &mut self,
+            _quad: renderer::Quad,
+            _background: impl Into<Background>,
+        ) {
+        }
+    }
+
+    impl image::Renderer for Renderer {
+        type Handle = Size<u32>;
+
+        fn measure_image(&self, handle: &Self::Handle) -> Size<u32> {
+            *handle
+        }
+
+        fn draw_image(
+            &mut self,
+            _image: Image<Self::Handle>,
+            _bounds: Rectangle,
+        ) {
+        }
+    }
+
+    const BOUNDS: Size = Size::new(100.0, 100.0);
+
+    fn state() -> State {
+        State {
+            geometry: Geometry {
+                bounds: Rectangle::new(Point::ORIGIN, BOUNDS),
+                image: Size::new(200.0, 100.0),
+                rotation: Radians(0.0),
+                fitted: Size::new(100.0, 50.0),
+                min_scale: 0.25,
+                max_scale: 10.0,
+            },
+            ..State::default()
+        }
+    }
+
+    fn update(
+        viewer: &mut Viewer<'_, Size<u32>, Zoom>,
+        tree: &mut Tree,
+        event: Event,
+    ) -> Vec<Zoom> {
+        let node = layout::Node::new(BOUNDS);
+        let mut messages = Vec::new();
+        let mut shell = Shell::new(&mut messages);
+
+        Widget::<Zoom, Theme, Renderer>::update(
+            viewer,
+            tree,
+            event,
+            Layout::new(&node),
+            mouse::Cursor::Available(Point::new(50.0, 50.0)),
+            &Renderer,
+            &mut clipboard::Null,
+            &mut shell,
+            &Rectangle::new(Point::ORIGIN, BOUNDS),
+        );
+
+        messages
+    }
+
+    fn redraw() -> Event {
+        Event::Window(crate::core::window::Event::RedrawRequested(
+            crate::core::time::Instant::now(),
+        ))
+    }
+
+    #[test]
+    fn zoom_ratio_follows_fitted_size() {
+        let mut state = state();
+
+        assert_eq!(
+            state.zoom(),
+            Zoom {
+                scale: 1.0,
+                ratio: 0.5
+            }
+        );
+
+        state.zoom_to_actual_size();
+
+        assert_eq!(
+            state.zoom(),
+            Zoom {
+                scale: 2.0,
+                ratio: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn zoom_to_clamps_scale() {
+        let mut state = state();
+
+        state.zoom_to(100.0);
+        assert_eq!(state.scale(), 10.0);
+
+        state.zoom_to(0.0);
+        assert_eq!(state.scale(), 0.25);
+    }
+
+    #[test]
+    fn zoom_to_fit_resets_scale_and_offset() {
+        let mut state = state();
+
+        state.zoom_to(4.0);
+        state.pan(Vector::new(30.0, 10.0));
+        state.zoom_to_fit();
+
+        assert_eq!(state.scale(), 1.0);
+        assert_eq!(state.current_offset, Vector::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn pan_keeps_fitting_axes_centered() {
+        let mut state = state();
+
+        state.zoom_to(1.5);
+        state.pan(Vector::new(30.0, 30.0));
+
+        // The scaled image is 150x75, so it only overflows horizontally
+        assert_eq!(state.current_offset, Vector::new(30.0, 0.0));
+    }
+
+    #[test]
+    fn center_on_moves_to_image_point() {
+        let mut state = state();
+
+        state.zoom_to(4.0);
+        state.center_on(Point::new(150.0, 50.0));
+
+        assert_eq!(state.current_offset, Vector::new(100.0, 0.0));
+    }
+
+    #[test]
+    fn pinch_scales_by_finger_distance() {
+        let mut state = state();
+
+        state.pinch(
+            [Point::new(40.0, 50.0), Point::new(60.0, 50.0)],
+            [Point::new(30.0, 50.0), Point::new(70.0, 50.0)],
+        );
+
+        assert_eq!(state.scale(), 2.0);
+    }
+
+    #[test]
+    fn on_zoom_is_published_for_user_zoom() {
+        let mut viewer = Viewer::new(Size::new(200, 100))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .on_zoom(std::convert::identity);
+
+        let mut tree = Tree::new(&viewer as &dyn Widget<_, Theme, Renderer>);
+
+        assert!(update(&mut viewer, &mut tree, redraw()).is_empty());
+
+        let messages = update(
+            &mut viewer,
+            &mut tree,
+            Event::Mouse(mouse::Event::WheelScrolled {
+                delta: mouse::ScrollDelta::Lines { x: 0.0, y: 1.0 },
+            }),
+        );
+
+        assert_eq!(messages.len(), 1);
+        assert!((messages[0].scale - 1.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn on_zoom_is_published_for_programmatic_zoom() {
+        let mut viewer = Viewer::new(Size::new(200, 100))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .on_zoom(std::convert::identity);
+
+        let mut tree = Tree::new(&viewer as &dyn Widget<_, Theme, Renderer>);
+
+        assert!(update(&mut viewer, &mut tree, redraw()).is_empty());
+
+        tree.state.downcast_mut::<State>().zoom_to_actual_size();
+
+        assert_eq!(
+            update(&mut viewer, &mut tree, redraw()),
+            [Zoom {
+                scale: 2.0,
+                ratio: 1.0
+            }]
+        );
+        assert!(update(&mut viewer, &mut tree, redraw()).is_empty());
+    }
+
+    #[test]
+    fn apply_only_outputs_when_state_changes() {
+        let target = widget::Id::new("viewer");
+
+        let run = |state: &mut State, id: &widget::Id, scale: f32| {
+            let mut operation = Apply {
+                target: target.clone(),
+                f: move |state: &mut State| state.zoom_to(scale),
+                is_changed: false,
+            };
+
+            operation.custom(state, Some(id));
+
+            matches!(operation.finish(), operation::Outcome::Some(()))
+        };
+
+        let mut state = state();
+
+        assert!(run(&mut state, &target, 2.0));
+        assert_eq!(state.scale(), 2.0);
+
+        assert!(!run(&mut state, &target, 2.0));
+        assert!(!run(&mut state, &widget::Id::new("other"), 4.0));
+        assert_eq!(state.scale(), 2.0);
+    }
+}
@@ -1,5 +1,6 @@
 //! Build and reuse custom widgets using The Elm Architecture.
 #![allow(deprecated)]
+use crate::core::dnd;
 use crate::core::layout::{self, Layout};
 use crate::core::mouse;
 use crate::core::overlay;
@@ -345,6 +346,12 @@ where
 
         local_shell.revalidate_layout(|| shell.invalidate_layout());
 
+        match local_shell.drop_response() {
+            Some(dnd::Response::Accept(format)) => shell.accept_drop(format),
+            Some(dnd::Response::Reject) => shell.reject_drop(),
+            None => {}
+        }
+
         if let Some(redraw_request) = local_shell.redraw_request() {
             match redraw_request {
                 window::RedrawRequest::NextFrame => {
@@ -630,6 +637,12 @@ where
 
         local_shell.revalidate_layout(|| shell.invalidate_layout());
 
+        match local_shell.drop_response() {
+            Some(dnd::Response::Accept(format)) => shell.accept_drop(format),
+            Some(dnd::Response::Reject) => shell.reject_drop(),
+            None => {}
+        }
+
         if let Some(redraw_request) = local_shell.redraw_request() {
             match redraw_request {
                 window::RedrawRequest::NextFrame => {
@@ -22,6 +22,7 @@ pub mod checkbox;
 pub mod combo_box;
 pub mod container;
 pub mod keyed;
+pub mod multi_pick_list;
 pub mod overlay;
 pub mod pane_grid;
 pub mod pick_list;
@@ -60,6 +61,8 @@ pub use container::Container;
 #[doc(no_inline)]
 pub use mouse_area::MouseArea;
 #[doc(no_inline)]
+pub use multi_pick_list::MultiPickList;
+#[doc(no_inline)]
 pub use pane_grid::PaneGrid;
 #[doc(no_inline)]
 pub use pick_list::PickList;
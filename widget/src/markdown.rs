@@ -56,7 +56,7 @@ use crate::core::{
 };
 use crate::{column, container, rich_text, row, scrollable, span, text};
 
-use std::cell::{Cell, RefCell};
+use std::cell::RefCell;
 use std::sync::Arc;
 
 pub use core::text::Highlight;
@@ -87,7 +87,7 @@ pub enum Item {
 #[derive(Debug, Clone)]
 pub struct Text {
     spans: Vec<Span>,
-    last_style: Cell<Option<Style>>,
+    last_style: RefCell<Option<Style>>,
     last_styled_spans: RefCell<Arc<[text::Span<'static, Url>]>>,
 }
 
@@ -95,7 +95,7 @@ impl Text {
     fn new(spans: Vec<Span>) -> Self {
         Self {
             spans,
-            last_style: Cell::default(),
+            last_style: RefCell::default(),
             last_styled_spans: RefCell::default(),
         }
     }
@@ -105,11 +105,11 @@ impl Text {
     /// This method performs caching for you. It will only reallocate if the [`Style`]
     /// provided changes.
     pub fn spans(&self, style: Style) -> Arc<[text::Span<'static, Url>]> {
-        if Some(style) != self.last_style.get() {
+        if self.last_style.borrow().as_ref() != Some(&style) {
             *self.last_styled_spans.borrow_mut() =
                 self.spans.iter().map(|span| span.view(&style)).collect();
 
-            self.last_style.set(Some(style));
+            *self.last_style.borrow_mut() = Some(style);
         }
 
         self.last_styled_spans.borrow().clone()
@@ -150,7 +150,9 @@ impl Span {
                 let span = if *code {
                     span.font(Font::MONOSPACE)
                         .color(style.inline_code_color)
-                        .background(style.inline_code_highlight.background)
+                        .background(
+                            style.inline_code_highlight.background.clone(),
+                        )
                         .border(style.inline_code_highlight.border)
                         .padding(style.inline_code_padding)
                 } else if *strong || *emphasis {
@@ -540,7 +542,7 @@ impl Default for Settings {
 }
 
 /// The text styling of some Markdown rendering in [`view`].
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Style {
     /// The [`Highlight`] to be applied to the background of inline code.
     pub inline_code_highlight: Highlight,
@@ -636,30 +638,33 @@ where
     let spacing = text_size * 0.625;
 
     let blocks = items.into_iter().enumerate().map(|(i, item)| match item {
-        Item::Heading(level, heading) => {
-            container(rich_text(heading.spans(style)).size(match level {
+        Item::Heading(level, heading) => container(
+            rich_text(heading.spans(style.clone())).size(match level {
                 pulldown_cmark::HeadingLevel::H1 => h1_size,
                 pulldown_cmark::HeadingLevel::H2 => h2_size,
                 pulldown_cmark::HeadingLevel::H3 => h3_size,
                 pulldown_cmark::HeadingLevel::H4 => h4_size,
                 pulldown_cmark::HeadingLevel::H5 => h5_size,
                 pulldown_cmark::HeadingLevel::H6 => h6_size,
-            }))
-            .padding(padding::top(if i > 0 {
-                text_size / 2.0
-            } else {
-                Pixels::ZERO
-            }))
-            .into()
-        }
-        Item::Paragraph(paragraph) => {
-            rich_text(paragraph.spans(style)).size(text_size).into()
-        }
+            }),
+        )
+        .padding(padding::top(if i > 0 {
+            text_size / 2.0
+        } else {
+            Pixels::ZERO
+        }))
+        .into(),
+        Item::Paragraph(paragraph) => rich_text(paragraph.spans(style.clone()))
+            .size(text_size)
+            .into(),
         Item::List { start: None, items } => {
             column(items.iter().map(|items| {
-                row![text("•").size(text_size), view(items, settings, style)]
-                    .spacing(spacing)
-                    .into()
+                row![
+                    text("•").size(text_size),
+                    view(items, settings, style.clone())
+                ]
+                .spacing(spacing)
+                .into()
             }))
             .spacing(spacing)
             .into()
@@ -670,7 +675,7 @@ where
         } => column(items.iter().enumerate().map(|(i, items)| {
             row![
                 text!("{}.", i as u64 + *start).size(text_size),
-                view(items, settings, style)
+                view(items, settings, style.clone())
             ]
             .spacing(spacing)
             .into()
@@ -680,7 +685,7 @@ where
         Item::CodeBlock(code) => container(
             scrollable(
                 container(
-                    rich_text(code.spans(style))
+                    rich_text(code.spans(style.clone()))
                         .font(Font::MONOSPACE)
                         .size(code_size),
                 )
@@ -0,0 +1,1082 @@
+//! Multi pick lists display a dropdown list of options that can be toggled,
+//! showing the selected ones as removable chips.
+//!
+//! See [`MultiPickList`] for an example.
+use crate::core::alignment;
+use crate::core::keyboard;
+use crate::core::keyboard::key;
+use crate::core::layout;
+use crate::core::mouse;
+use crate::core::overlay;
+use crate::core::renderer;
+use crate::core::text::paragraph;
+use crate::core::text::{self, Text};
+use crate::core::touch;
+use crate::core::widget::tree::{self, Tree};
+use crate::core::window;
+use crate::core::{
+    Background, Border, Clipboard, Color, Element, Event, Layout, Length,
+    Padding, Pixels, Point, Rectangle, Shell, Size, Theme, Vector, Widget,
+};
+use crate::overlay::menu::{self, Menu};
+
+pub use crate::pick_list::{Handle, Icon, Status};
+
+use std::borrow::Borrow;
+use std::f32;
+use std::fmt;
+
+/// A widget for selecting multiple values from a list of options.
+///
+/// The selected values are displayed as chips, which can be removed by
+/// clicking on their close button. While open, the hovered option can be
+/// toggled with the arrow keys and `Space` or `Enter`.
+///
+/// # Example
+/// ```no_run
+/// # mod iced { pub mod widget { pub use iced_widget::*; } pub use iced_widget::Renderer; pub use iced_widget::core::*; }
+/// # pub type Element<'a, Message> = iced_widget::core::Element<'a, Message, iced_widget::Theme, iced_widget::Renderer>;
+/// #
+/// use iced::widget::multi_pick_list;
+///
+/// struct State {
+///    tags: Vec<Tag>,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Tag {
+///     Bug,
+///     Feature,
+///     Question,
+/// }
+///
+/// #[derive(Debug, Clone)]
+/// enum Message {
+///     TagsChanged(Vec<Tag>),
+/// }
+///
+/// fn view(state: &State) -> Element<'_, Message> {
+///     let tags = [Tag::Bug, Tag::Feature, Tag::Question];
+///
+///     multi_pick_list(tags, &state.tags[..], Message::TagsChanged)
+///         .placeholder("Select some tags...")
+///         .into()
+/// }
+///
+/// fn update(state: &mut State, message: Message) {
+///     match message {
+///         Message::TagsChanged(tags) => {
+///             state.tags = tags;
+///         }
+///     }
+/// }
+///
+/// impl std::fmt::Display for Tag {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str(match self {
+///             Self::Bug => "Bug",
+///             Self::Feature => "Feature",
+///             Self::Question => "Question",
+///         })
+///     }
+/// }
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct MultiPickList<
+    'a,
+    T,
+    L,
+    V,
+    Message,
+    Theme = crate::Theme,
+    Renderer = crate::Renderer,
+> where
+    T: ToString + PartialEq + Clone,
+    L: Borrow<[T]> + 'a,
+    V: Borrow<[T]> + 'a,
+    Theme: Catalog,
+    Renderer: text::Renderer,
+{
+    on_change: Box<dyn Fn(Vec<T>) -> Message + 'a>,
+    options: L,
+    selected: V,
+    placeholder: Option<String>,
+    select_all: Option<String>,
+    width: Length,
+    padding: Padding,
+    text_size: Option<Pixels>,
+    text_line_height: text::LineHeight,
+    text_shaping: text::Shaping,
+    font: Option<Renderer::Font>,
+    handle: Handle<Renderer::Font>,
+    class: <Theme as Catalog>::Class<'a>,
+    menu_class: <Theme as menu::Catalog>::Class<'a>,
+    last_status: Option<Status>,
+}
+
+impl<'a, T, L, V, Message, Theme, Renderer>
+    MultiPickList<'a, T, L, V, Message, Theme, Renderer>
+where
+    T: ToString + PartialEq + Clone,
+    L: Borrow<[T]> + 'a,
+    V: Borrow<[T]> + 'a,
+    Message: Clone,
+    Theme: Catalog,
+    Renderer: text::Renderer,
+{
+    /// Creates a new [`MultiPickList`] with the given list of options, the
+    /// current selected values, and the message to produce when the
+    /// selection changes.
+    pub fn new(
+        options: L,
+        selected: V,
+        on_change: impl Fn(Vec<T>) -> Message + 'a,
+    ) -> Self {
+        Self {
+            on_change: Box::new(on_change),
+            options,
+            selected,
+            placeholder: None,
+            select_all: Some(String::from("Select all")),
+            width: Length::Shrink,
+            padding: crate::button::DEFAULT_PADDING,
+            text_size: None,
+            text_line_height: text::LineHeight::default(),
+            text_shaping: text::Shaping::default(),
+            font: None,
+            handle: Handle::default(),
+            class: <Theme as Catalog>::default(),
+            menu_class: <Theme as Catalog>::default_menu(),
+            last_status: None,
+        }
+    }
+
+    /// Sets the placeholder of the [`MultiPickList`].
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Sets the label of the first option of the menu, which selects all
+    /// the options or none of them if they are all selected already.
+    ///
+    /// If `None`, the option will not be displayed.
+    pub fn select_all(mut self, label: Option<impl Into<String>>) -> Self {
+        self.select_all = label.map(Into::into);
+        self
+    }
+
+    /// Sets the width of the [`MultiPickList`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the [`Padding`] of the [`MultiPickList`].
+    pub fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Sets the text size of the [`MultiPickList`].
+    pub fn text_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.text_size = Some(size.into());
+        self
+    }
+
+    /// Sets the text [`text::LineHeight`] of the [`MultiPickList`].
+    pub fn text_line_height(
+        mut self,
+        line_height: impl Into<text::LineHeight>,
+    ) -> Self {
+        self.text_line_height = line_height.into();
+        self
+    }
+
+    /// Sets the [`text::Shaping`] strategy of the [`MultiPickList`].
+    pub fn text_shaping(mut self, shaping: text::Shaping) -> Self {
+        self.text_shaping = shaping;
+        self
+    }
+
+    /// Sets the font of the [`MultiPickList`].
+    pub fn font(mut self, font: impl Into<Renderer::Font>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// Sets the [`Handle`] of the [`MultiPickList`].
+    pub fn handle(mut self, handle: Handle<Renderer::Font>) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Sets the style of the [`MultiPickList`].
+    #[must_use]
+    pub fn style(mut self, style: impl Fn(&Theme, Status) -> Style + 'a) -> Self
+    where
+        <Theme as Catalog>::Class<'a>: From<StyleFn<'a, Theme>>,
+    {
+        self.class = (Box::new(style) as StyleFn<'a, Theme>).into();
+        self
+    }
+
+    /// Sets the style of the [`Menu`].
+    #[must_use]
+    pub fn menu_style(
+        mut self,
+        style: impl Fn(&Theme) -> menu::Style + 'a,
+    ) -> Self
+    where
+        <Theme as menu::Catalog>::Class<'a>: From<menu::StyleFn<'a, Theme>>,
+    {
+        self.menu_class = (Box::new(style) as menu::StyleFn<'a, Theme>).into();
+        self
+    }
+
+    /// Sets the style class of the [`MultiPickList`].
+    #[cfg(feature = "advanced")]
+    #[must_use]
+    pub fn class(
+        mut self,
+        class: impl Into<<Theme as Catalog>::Class<'a>>,
+    ) -> Self {
+        self.class = class.into();
+        self
+    }
+
+    /// Sets the style class of the [`Menu`].
+    #[cfg(feature = "advanced")]
+    #[must_use]
+    pub fn menu_class(
+        mut self,
+        class: impl Into<<Theme as menu::Catalog>::Class<'a>>,
+    ) -> Self {
+        self.menu_class = class.into();
+        self
+    }
+
+    /// Returns the new selection after toggling the given [`Entry`].
+    fn toggle(&self, entry: Entry) -> Vec<T> {
+        let options = self.options.borrow();
+        let selected = self.selected.borrow();
+
+        match entry.index {
+            Some(index) => {
+                let Some(toggled) = options.get(index) else {
+                    return selected.to_vec();
+                };
+
+                if selected.contains(toggled) {
+                    selected
+                        .iter()
+                        .filter(|option| *option != toggled)
+                        .cloned()
+                        .collect()
+                } else {
+                    selected.iter().chain([toggled]).cloned().collect()
+                }
+            }
+            None => {
+                if options.iter().all(|option| selected.contains(option)) {
+                    Vec::new()
+                } else {
+                    options.to_vec()
+                }
+            }
+        }
+    }
+
+    fn entries(&self) -> Vec<Entry> {
+        let select_all = self.select_all.iter().map(|label| Entry {
+            index: None,
+            label: label.clone(),
+        });
+
+        let options =
+            self.options
+                .borrow()
+                .iter()
+                .enumerate()
+                .map(|(i, option)| Entry {
+                    index: Some(i),
+                    label: option.to_string(),
+                });
+
+        select_all.chain(options).collect()
+    }
+}
+
+impl<'a, T, L, V, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for MultiPickList<'a, T, L, V, Message, Theme, Renderer>
+where
+    T: Clone + ToString + PartialEq + 'a,
+    L: Borrow<[T]>,
+    V: Borrow<[T]>,
+    Message: Clone + 'a,
+    Theme: Catalog + 'a,
+    Renderer: text::Renderer + 'a,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State<Renderer::Paragraph>>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::<Renderer::Paragraph>::new())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: self.width,
+            height: Length::Shrink,
+        }
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let state = tree.state.downcast_mut::<State<Renderer::Paragraph>>();
+
+        let font = self.font.unwrap_or_else(|| renderer.default_font());
+        let text_size =
+            self.text_size.unwrap_or_else(|| renderer.default_size());
+        let line_height =
+            f32::from(self.text_line_height.to_absolute(text_size));
+        let options = self.options.borrow();
+        let selected = self.selected.borrow();
+
+        let option_text = Text {
+            content: "",
+            bounds: Size::new(f32::INFINITY, line_height),
+            size: text_size,
+            line_height: self.text_line_height,
+            font,
+            horizontal_alignment: alignment::Horizontal::Left,
+            vertical_alignment: alignment::Vertical::Center,
+            shaping: self.text_shaping,
+            wrapping: text::Wrapping::default(),
+        };
+
+        state.options.resize_with(options.len(), Default::default);
+        state.chips.resize_with(selected.len(), Default::default);
+
+        for (option, paragraph) in options.iter().zip(state.options.iter_mut())
+        {
+            paragraph.update(Text {
+                content: &option.to_string(),
+                ..option_text
+            });
+        }
+
+        for (option, paragraph) in selected.iter().zip(state.chips.iter_mut()) {
+            paragraph.update(Text {
+                content: &option.to_string(),
+                ..option_text
+            });
+        }
+
+        if let Some(placeholder) = &self.placeholder {
+            state.placeholder.update(Text {
+                content: placeholder,
+                ..option_text
+            });
+        }
+
+        let close_width = line_height;
+        let chip_width = |paragraph: &paragraph::Plain<Renderer::Paragraph>| {
+            CHIP_PADDING + paragraph.min_width() + close_width
+        };
+
+        let max_width = match self.width {
+            Length::Shrink => {
+                let labels_width =
+                    state.options.iter().fold(0.0, |width, paragraph| {
+                        f32::max(width, chip_width(paragraph))
+                    });
+
+                labels_width.max(
+                    self.placeholder
+                        .as_ref()
+                        .map(|_| state.placeholder.min_width())
+                        .unwrap_or(0.0),
+                )
+            }
+            _ => 0.0,
+        };
+
+        let handle_width = match &self.handle {
+            Handle::Arrow { size } => size.unwrap_or(text_size).0,
+            Handle::Static(icon) => icon.size.unwrap_or(text_size).0,
+            Handle::Dynamic { open, closed } => f32::max(
+                open.size.unwrap_or(text_size).0,
+                closed.size.unwrap_or(text_size).0,
+            ),
+            Handle::None => 0.0,
+        } + self.padding.left;
+
+        let limits = limits.width(self.width).shrink(self.padding);
+
+        // Chips must wrap before reaching the handle
+        let available_width = (limits
+            .resolve(
+                self.width,
+                Length::Shrink,
+                Size::new(max_width + handle_width, 0.0),
+            )
+            .width
+            - handle_width)
+            .max(0.0);
+
+        let mut chips = Vec::with_capacity(state.chips.len());
+        let mut position = Point::new(self.padding.left, self.padding.top);
+
+        for paragraph in &state.chips {
+            let width = chip_width(paragraph);
+
+            if position.x > self.padding.left
+                && position.x - self.padding.left + width > available_width
+            {
+                position = Point::new(
+                    self.padding.left,
+                    position.y + line_height + CHIP_SPACING,
+                );
+            }
+
+            chips.push(
+                layout::Node::new(Size::new(width, line_height))
+                    .move_to(position),
+            );
+
+            position.x += width + CHIP_SPACING;
+        }
+
+        let size = {
+            let intrinsic = Size::new(
+                max_width + handle_width,
+                position.y - self.padding.top + line_height,
+            );
+
+            limits
+                .resolve(self.width, Length::Shrink, intrinsic)
+                .expand(self.padding)
+        };
+
+        layout::Node::with_children(size, chips)
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State<Renderer::Paragraph>>();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                let removed = layout.children().position(|chip| {
+                    let bounds = chip.bounds();
+                    let close_width = bounds.height;
+
+                    cursor.is_over(Rectangle {
+                        x: bounds.x + bounds.width - close_width,
+                        width: close_width,
+                        ..bounds
+                    })
+                });
+
+                if let Some(index) = removed {
+                    let selected = self.selected.borrow();
+
+                    shell.publish((self.on_change)(
+                        selected
+                            .iter()
+                            .enumerate()
+                            .filter(|(i, _)| *i != index)
+                            .map(|(_, option)| option.clone())
+                            .collect(),
+                    ));
+
+                    shell.capture_event();
+                } else if state.is_open {
+                    // Event wasn't processed by overlay, so cursor was clicked either outside its
+                    // bounds or on the drop-down, either way we close the overlay.
+                    state.is_open = false;
+
+                    shell.capture_event();
+                } else if cursor.is_over(layout.bounds()) {
+                    state.is_open = true;
+                    state.hovered_option = Some(0);
+
+                    shell.capture_event();
+                }
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(named_key),
+                ..
+            }) if state.is_open => {
+                let entries = self.options.borrow().len()
+                    + usize::from(self.select_all.is_some());
+
+                match named_key {
+                    key::Named::ArrowUp => {
+                        state.hovered_option =
+                            Some(match state.hovered_option {
+                                Some(index) if index > 0 => index - 1,
+                                _ => entries.saturating_sub(1),
+                            });
+                    }
+                    key::Named::ArrowDown => {
+                        state.hovered_option =
+                            Some(match state.hovered_option {
+                                Some(index) if index + 1 < entries => index + 1,
+                                _ => 0,
+                            });
+                    }
+                    key::Named::Space | key::Named::Enter => {
+                        let entry = state.hovered_option.and_then(|index| {
+                            self.entries().get(index).cloned()
+                        });
+
+                        if let Some(entry) = entry {
+                            shell.publish((self.on_change)(self.toggle(entry)));
+                        }
+                    }
+                    key::Named::Escape => {
+                        state.is_open = false;
+                    }
+                    _ => return,
+                }
+
+                shell.capture_event();
+                shell.request_redraw();
+            }
+            _ => {}
+        };
+
+        let status = {
+            let is_hovered = cursor.is_over(layout.bounds());
+
+            if state.is_open {
+                Status::Opened { is_hovered }
+            } else if is_hovered {
+                Status::Hovered
+            } else {
+                Status::Active
+            }
+        };
+
+        if let Event::Window(window::Event::RedrawRequested(_now)) = event {
+            self.last_status = Some(status);
+        } else if self
+            .last_status
+            .is_some_and(|last_status| last_status != status)
+        {
+            shell.request_redraw();
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let bounds = layout.bounds();
+        let is_mouse_over = cursor.is_over(bounds);
+
+        if is_mouse_over {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let font = self.font.unwrap_or_else(|| renderer.default_font());
+        let state = tree.state.downcast_ref::<State<Renderer::Paragraph>>();
+
+        let bounds = layout.bounds();
+
+        let style = Catalog::style(
+            theme,
+            &self.class,
+            self.last_status.unwrap_or(Status::Active),
+        );
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: style.border,
+                ..renderer::Quad::default()
+            },
+            style.background,
+        );
+
+        let handle = match &self.handle {
+            Handle::Arrow { size } => Some((
+                Renderer::ICON_FONT,
+                Renderer::ARROW_DOWN_ICON,
+                *size,
+                text::LineHeight::default(),
+                text::Shaping::Basic,
+            )),
+            Handle::Static(Icon {
+                font,
+                code_point,
+                size,
+                line_height,
+                shaping,
+            }) => Some((*font, *code_point, *size, *line_height, *shaping)),
+            Handle::Dynamic { open, closed } => {
+                let icon = if state.is_open { open } else { closed };
+
+                Some((
+                    icon.font,
+                    icon.code_point,
+                    icon.size,
+                    icon.line_height,
+                    icon.shaping,
+                ))
+            }
+            Handle::None => None,
+        };
+
+        if let Some((font, code_point, size, line_height, shaping)) = handle {
+            let size = size.unwrap_or_else(|| renderer.default_size());
+
+            renderer.fill_text(
+                Text {
+                    content: code_point.to_string(),
+                    size,
+                    line_height,
+                    font,
+                    bounds: Size::new(
+                        bounds.width,
+                        f32::from(line_height.to_absolute(size)),
+                    ),
+                    horizontal_alignment: alignment::Horizontal::Right,
+                    vertical_alignment: alignment::Vertical::Center,
+                    shaping,
+                    wrapping: text::Wrapping::default(),
+                },
+                Point::new(
+                    bounds.x + bounds.width - self.padding.right,
+                    bounds.center_y(),
+                ),
+                style.handle_color,
+                *viewport,
+            );
+        }
+
+        let text_size =
+            self.text_size.unwrap_or_else(|| renderer.default_size());
+
+        if self.selected.borrow().is_empty() {
+            if let Some(placeholder) = &self.placeholder {
+                renderer.fill_text(
+                    Text {
+                        content: placeholder.clone(),
+                        size: text_size,
+                        line_height: self.text_line_height,
+                        font,
+                        bounds: Size::new(
+                            bounds.width - self.padding.horizontal(),
+                            f32::from(
+                                self.text_line_height.to_absolute(text_size),
+                            ),
+                        ),
+                        horizontal_alignment: alignment::Horizontal::Left,
+                        vertical_alignment: alignment::Vertical::Center,
+                        shaping: self.text_shaping,
+                        wrapping: text::Wrapping::default(),
+                    },
+                    Point::new(bounds.x + self.padding.left, bounds.center_y()),
+                    style.placeholder_color,
+                    *viewport,
+                );
+            }
+
+            return;
+        }
+
+        for (chip, paragraph) in layout.children().zip(&state.chips) {
+            let chip_bounds = chip.bounds();
+            let close_width = chip_bounds.height;
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: chip_bounds,
+                    border: style.chip_border,
+                    ..renderer::Quad::default()
+                },
+                style.chip_background.clone(),
+            );
+
+            renderer.fill_paragraph(
+                paragraph.raw(),
+                Point::new(
+                    chip_bounds.x + CHIP_PADDING,
+                    chip_bounds.center_y(),
+                ),
+                style.chip_text_color,
+                *viewport,
+            );
+
+            let close_bounds = Rectangle {
+                x: chip_bounds.x + chip_bounds.width - close_width,
+                width: close_width,
+                ..chip_bounds
+            };
+
+            renderer.fill_text(
+                Text {
+                    content: String::from("×"),
+                    size: text_size,
+                    line_height: self.text_line_height,
+                    font,
+                    bounds: close_bounds.size(),
+                    horizontal_alignment: alignment::Horizontal::Center,
+                    vertical_alignment: alignment::Vertical::Center,
+                    shaping: text::Shaping::Basic,
+                    wrapping: text::Wrapping::default(),
+                },
+                close_bounds.center(),
+                if cursor.is_over(close_bounds) {
+                    style.chip_text_color
+                } else {
+                    style.chip_text_color.scale_alpha(0.7)
+                },
+                *viewport,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let state = tree.state.downcast_mut::<State<Renderer::Paragraph>>();
+        let font = self.font.unwrap_or_else(|| renderer.default_font());
+
+        if !state.is_open {
+            return None;
+        }
+
+        let bounds = layout.bounds();
+
+        let options = self.options.borrow();
+        let selected = self.selected.borrow();
+
+        state.entries = self.entries();
+        state.checked = state
+            .entries
+            .iter()
+            .map(|entry| match entry.index {
+                Some(index) => selected.contains(&options[index]),
+                None => {
+                    !options.is_empty()
+                        && options
+                            .iter()
+                            .all(|option| selected.contains(option))
+                }
+            })
+            .collect();
+
+        let this = &*self;
+
+        let mut menu = Menu::new(
+            &mut state.menu,
+            &state.entries,
+            &mut state.hovered_option,
+            |entry| (this.on_change)(this.toggle(entry)),
+            None,
+            &this.menu_class,
+        )
+        .width(bounds.width)
+        .padding(this.padding)
+        .font(font)
+        .text_shaping(this.text_shaping)
+        .checkboxes(&state.checked);
+
+        if let Some(text_size) = this.text_size {
+            menu = menu.text_size(text_size);
+        }
+
+        Some(menu.overlay(layout.position() + translation, bounds.height))
+    }
+}
+
+impl<'a, T, L, V, Message, Theme, Renderer>
+    From<MultiPickList<'a, T, L, V, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    T: Clone + ToString + PartialEq + 'a,
+    L: Borrow<[T]> + 'a,
+    V: Borrow<[T]> + 'a,
+    Message: Clone + 'a,
+    Theme: Catalog + 'a,
+    Renderer: text::Renderer + 'a,
+{
+    fn from(
+        multi_pick_list: MultiPickList<'a, T, L, V, Message, Theme, Renderer>,
+    ) -> Self {
+        Self::new(multi_pick_list)
+    }
+}
+
+const CHIP_PADDING: f32 = 8.0;
+const CHIP_SPACING: f32 = 4.0;
+
+#[derive(Debug, Clone)]
+struct Entry {
+    index: Option<usize>,
+    label: String,
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+#[derive(Debug)]
+struct State<P: text::Paragraph> {
+    menu: menu::State,
+    is_open: bool,
+    hovered_option: Option<usize>,
+    entries: Vec<Entry>,
+    checked: Vec<bool>,
+    options: Vec<paragraph::Plain<P>>,
+    chips: Vec<paragraph::Plain<P>>,
+    placeholder: paragraph::Plain<P>,
+}
+
+impl<P: text::Paragraph> State<P> {
+    /// Creates a new [`State`] for a [`MultiPickList`].
+    fn new() -> Self {
+        Self {
+            menu: menu::State::default(),
+            is_open: false,
+            hovered_option: None,
+            entries: Vec::new(),
+            checked: Vec::new(),
+            options: Vec::new(),
+            chips: Vec::new(),
+            placeholder: paragraph::Plain::default(),
+        }
+    }
+}
+
+impl<P: text::Paragraph> Default for State<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The appearance of a multi pick list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style {
+    /// The placeholder [`Color`] of the multi pick list.
+    pub placeholder_color: Color,
+    /// The handle [`Color`] of the multi pick list.
+    pub handle_color: Color,
+    /// The [`Background`] of the multi pick list.
+    pub background: Background,
+    /// The [`Border`] of the multi pick list.
+    pub border: Border,
+    /// The text [`Color`] of the chips of the multi pick list.
+    pub chip_text_color: Color,
+    /// The [`Background`] of the chips of the multi pick list.
+    pub chip_background: Background,
+    /// The [`Border`] of the chips of the multi pick list.
+    pub chip_border: Border,
+}
+
+/// The theme catalog of a [`MultiPickList`].
+pub trait Catalog: menu::Catalog {
+    /// The item class of the [`Catalog`].
+    type Class<'a>;
+
+    /// The default class produced by the [`Catalog`].
+    fn default<'a>() -> <Self as Catalog>::Class<'a>;
+
+    /// The default class for the menu of the [`MultiPickList`].
+    fn default_menu<'a>() -> <Self as menu::Catalog>::Class<'a> {
+        <Self as menu::Catalog>::default()
+    }
+
+    /// The [`Style`] of a class with the given status.
+    fn style(
+        &self,
+        class: &<Self as Catalog>::Class<'_>,
+        status: Status,
+    ) -> Style;
+}
+
+/// A styling function for a [`MultiPickList`].
+///
+/// This is just a boxed closure: `Fn(&Theme, Status) -> Style`.
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme, Status) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> StyleFn<'a, Self> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &StyleFn<'_, Self>, status: Status) -> Style {
+        class(self, status)
+    }
+}
+
+/// The default style of the field of a [`MultiPickList`].
+pub fn default(theme: &Theme, status: Status) -> Style {
+    let palette = theme.extended_palette();
+
+    let active = Style {
+        background: palette.background.weak.color.into(),
+        placeholder_color: palette.background.strong.color,
+        handle_color: palette.background.weak.text,
+        border: Border {
+            radius: 2.0.into(),
+            width: 1.0,
+            color: palette.background.strong.color,
+        },
+        chip_text_color: palette.primary.weak.text,
+        chip_background: palette.primary.weak.color.into(),
+        chip_border: Border {
+            radius: 2.0.into(),
+            ..Border::default()
+        },
+    };
+
+    match status {
+        Status::Active => active,
+        Status::Hovered | Status::Opened { .. } => Style {
+            border: Border {
+                color: palette.primary.strong.color,
+                ..active.border
+            },
+            ..active
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type MultiPickList<'a> = super::MultiPickList<
+        'a,
+        &'static str,
+        &'a [&'static str],
+        &'a [&'static str],
+        Vec<&'static str>,
+        Theme,
+        (),
+    >;
+
+    const OPTIONS: &[&str] = &["Bug", "Feature", "Question"];
+
+    fn multi_pick_list<'a>(selected: &'a [&'static str]) -> MultiPickList<'a> {
+        MultiPickList::new(OPTIONS, selected, |selected| selected)
+    }
+
+    fn entry(index: Option<usize>) -> Entry {
+        Entry {
+            index,
+            label: String::new(),
+        }
+    }
+
+    #[test]
+    fn toggle_appends_unselected_options() {
+        let multi_pick_list = multi_pick_list(&["Question"]);
+
+        assert_eq!(
+            multi_pick_list.toggle(entry(Some(0))),
+            vec!["Question", "Bug"]
+        );
+    }
+
+    #[test]
+    fn toggle_removes_selected_options() {
+        let multi_pick_list = multi_pick_list(&["Bug", "Question"]);
+
+        assert_eq!(multi_pick_list.toggle(entry(Some(0))), vec!["Question"]);
+    }
+
+    #[test]
+    fn toggle_ignores_unknown_options() {
+        let multi_pick_list = multi_pick_list(&["Bug"]);
+
+        assert_eq!(multi_pick_list.toggle(entry(Some(3))), vec!["Bug"]);
+    }
+
+    #[test]
+    fn toggle_all_selects_every_option() {
+        let multi_pick_list = multi_pick_list(&["Question"]);
+
+        assert_eq!(multi_pick_list.toggle(entry(None)), OPTIONS.to_vec());
+    }
+
+    #[test]
+    fn toggle_all_clears_full_selection() {
+        let multi_pick_list = multi_pick_list(&["Question", "Bug", "Feature"]);
+
+        assert!(multi_pick_list.toggle(entry(None)).is_empty());
+    }
+
+    #[test]
+    fn entries_start_with_select_all() {
+        let entries = multi_pick_list(&[]).entries();
+
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.index, entry.label.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (None, "Select all"),
+                (Some(0), "Bug"),
+                (Some(1), "Feature"),
+                (Some(2), "Question"),
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_without_select_all() {
+        let entries = multi_pick_list(&[]).select_all(None::<String>).entries();
+
+        assert_eq!(
+            entries.iter().map(|entry| entry.index).collect::<Vec<_>>(),
+            vec![Some(0), Some(1), Some(2)]
+        );
+    }
+}
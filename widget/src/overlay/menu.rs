@@ -41,6 +41,8 @@ pub struct Menu<
     text_line_height: text::LineHeight,
     text_shaping: text::Shaping,
     font: Option<Renderer::Font>,
+    loading: Option<&'a str>,
+    checked: Option<&'a [bool]>,
     class: &'a <Theme as Catalog>::Class<'b>,
 }
 
@@ -75,6 +77,8 @@ where
             text_line_height: text::LineHeight::default(),
             text_shaping: text::Shaping::Basic,
             font: None,
+            loading: None,
+            checked: None,
             class,
         }
     }
@@ -118,6 +122,20 @@ where
         self
     }
 
+    /// Displays the given label after the options of the [`Menu`], to
+    /// indicate that more options are being loaded.
+    pub fn loading(mut self, label: &'a str) -> Self {
+        self.loading = Some(label);
+        self
+    }
+
+    /// Displays a checkbox before each option of the [`Menu`], checked
+    /// if the value at the same index of the given slice is `true`.
+    pub fn checkboxes(mut self, checked: &'a [bool]) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
     /// Turns the [`Menu`] into an overlay [`Element`] at the given target
     /// position.
     ///
@@ -198,6 +216,8 @@ where
             text_size,
             text_line_height,
             text_shaping,
+            loading,
+            checked,
             class,
         } = menu;
 
@@ -211,6 +231,8 @@ where
             text_line_height,
             text_shaping,
             padding,
+            loading,
+            checked,
             class,
         });
 
@@ -330,6 +352,8 @@ where
     text_line_height: text::LineHeight,
     text_shaping: text::Shaping,
     font: Option<Renderer::Font>,
+    loading: Option<&'a str>,
+    checked: Option<&'a [bool]>,
     class: &'a <Theme as Catalog>::Class<'b>,
 }
 
@@ -376,7 +400,8 @@ where
             let intrinsic = Size::new(
                 0.0,
                 (f32::from(text_line_height) + self.padding.vertical())
-                    * self.options.len() as f32,
+                    * (self.options.len() + usize::from(self.loading.is_some()))
+                        as f32,
             );
 
             limits.resolve(Length::Fill, Length::Shrink, intrinsic)
@@ -541,8 +566,61 @@ where
                         border: border::rounded(style.border.radius),
                         ..renderer::Quad::default()
                     },
-                    style.selected_background,
+                    style.selected_background.clone(),
+                );
+            }
+
+            let text_color = if is_selected {
+                style.selected_text_color
+            } else {
+                style.text_color
+            };
+
+            let mut label_x = bounds.x + self.padding.left;
+
+            if let Some(checked) = self.checked {
+                let size = f32::from(text_size) * 0.9;
+
+                let checkbox = Rectangle {
+                    x: label_x,
+                    y: bounds.center_y() - size / 2.0,
+                    width: size,
+                    height: size,
+                };
+
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: checkbox,
+                        border: Border {
+                            color: text_color,
+                            width: 1.0,
+                            radius: 2.0.into(),
+                        },
+                        ..renderer::Quad::default()
+                    },
+                    Color::TRANSPARENT,
                 );
+
+                if checked.get(i).copied().unwrap_or(false) {
+                    renderer.fill_text(
+                        Text {
+                            content: Renderer::CHECKMARK_ICON.to_string(),
+                            font: Renderer::ICON_FONT,
+                            size: Pixels(size * 0.7),
+                            line_height: text::LineHeight::default(),
+                            bounds: checkbox.size(),
+                            horizontal_alignment: alignment::Horizontal::Center,
+                            vertical_alignment: alignment::Vertical::Center,
+                            shaping: text::Shaping::Basic,
+                            wrapping: text::Wrapping::default(),
+                        },
+                        checkbox.center(),
+                        text_color,
+                        *viewport,
+                    );
+                }
+
+                label_x += size + self.padding.left;
             }
 
             renderer.fill_text(
@@ -557,15 +635,41 @@ where
                     shaping: self.text_shaping,
                     wrapping: text::Wrapping::default(),
                 },
-                Point::new(bounds.x + self.padding.left, bounds.center_y()),
-                if is_selected {
-                    style.selected_text_color
-                } else {
-                    style.text_color
-                },
+                Point::new(label_x, bounds.center_y()),
+                text_color,
                 *viewport,
             );
         }
+
+        if let Some(label) = self.loading {
+            let bounds = Rectangle {
+                x: bounds.x,
+                y: bounds.y + (option_height * self.options.len() as f32),
+                width: bounds.width,
+                height: option_height,
+            };
+
+            if bounds.intersects(viewport) {
+                renderer.fill_text(
+                    Text {
+                        content: label.to_owned(),
+                        bounds: Size::new(f32::INFINITY, bounds.height),
+                        size: text_size,
+                        line_height: self.text_line_height,
+                        font: self
+                            .font
+                            .unwrap_or_else(|| renderer.default_font()),
+                        horizontal_alignment: alignment::Horizontal::Left,
+                        vertical_alignment: alignment::Vertical::Center,
+                        shaping: self.text_shaping,
+                        wrapping: text::Wrapping::default(),
+                    },
+                    Point::new(bounds.x + self.padding.left, bounds.center_y()),
+                    style.text_color.scale_alpha(0.5),
+                    *viewport,
+                );
+            }
+        }
     }
 }
 
@@ -585,7 +689,7 @@ where
 }
 
 /// The appearance of a [`Menu`].
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Style {
     /// The [`Background`] of the menu.
     pub background: Background,
@@ -1885,7 +1885,7 @@ pub enum Status {
 }
 
 /// The appearance of a scrollable.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Style {
     /// The [`container::Style`] of a scrollable.
     pub container: container::Style,
@@ -1898,7 +1898,7 @@ pub struct Style {
 }
 
 /// The appearance of the scrollbar of a scrollable.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Rail {
     /// The [`Background`] of a scrollbar.
     pub background: Option<Background>,
@@ -1960,7 +1960,7 @@ pub fn default(theme: &Theme, status: Status) -> Style {
     match status {
         Status::Active => Style {
             container: container::Style::default(),
-            vertical_rail: scrollbar,
+            vertical_rail: scrollbar.clone(),
             horizontal_rail: scrollbar,
             gap: None,
         },
@@ -1973,15 +1973,15 @@ pub fn default(theme: &Theme, status: Status) -> Style {
                     color: palette.primary.strong.color,
                     ..scrollbar.scroller
                 },
-                ..scrollbar
+                ..scrollbar.clone()
             };
 
             Style {
                 container: container::Style::default(),
                 vertical_rail: if is_vertical_scrollbar_hovered {
-                    hovered_scrollbar
+                    hovered_scrollbar.clone()
                 } else {
-                    scrollbar
+                    scrollbar.clone()
                 },
                 horizontal_rail: if is_horizontal_scrollbar_hovered {
                     hovered_scrollbar
@@ -2000,15 +2000,15 @@ pub fn default(theme: &Theme, status: Status) -> Style {
                     color: palette.primary.base.color,
                     ..scrollbar.scroller
                 },
-                ..scrollbar
+                ..scrollbar.clone()
             };
 
             Style {
                 container: container::Style::default(),
                 vertical_rail: if is_vertical_scrollbar_dragged {
-                    dragged_scrollbar
+                    dragged_scrollbar.clone()
                 } else {
-                    scrollbar
+                    scrollbar.clone()
                 },
                 horizontal_rail: if is_horizontal_scrollbar_dragged {
                     dragged_scrollbar
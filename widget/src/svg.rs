@@ -25,6 +25,7 @@ use crate::core::{
     Size, Theme, Vector, Widget,
 };
 
+use std::borrow::Cow;
 use std::path::PathBuf;
 
 pub use crate::core::svg::Handle;
@@ -61,6 +62,7 @@ where
     height: Length,
     content_fit: ContentFit,
     class: Theme::Class<'a>,
+    classes: Vec<(Cow<'static, str>, Color)>,
     rotation: Rotation,
     opacity: f32,
 }
@@ -77,6 +79,7 @@ where
             height: Length::Shrink,
             content_fit: ContentFit::Contain,
             class: Theme::default(),
+            classes: Vec::new(),
             rotation: Rotation::default(),
             opacity: 1.0,
         }
@@ -132,6 +135,20 @@ where
         self
     }
 
+    /// Overrides the [`Color`] of the elements of the [`Svg`] with the
+    /// given class.
+    ///
+    /// Useful for coloring the different parts of a duotone icon.
+    #[must_use]
+    pub fn class_color(
+        mut self,
+        class: impl Into<Cow<'static, str>>,
+        color: impl Into<Color>,
+    ) -> Self {
+        self.classes.push((class.into(), color.into()));
+        self
+    }
+
     /// Applies the given [`Rotation`] to the [`Svg`].
     pub fn rotation(mut self, rotation: impl Into<Rotation>) -> Self {
         self.rotation = rotation.into();
@@ -246,6 +263,7 @@ where
                 svg::Svg {
                     handle: self.handle.clone(),
                     color: style.color,
+                    classes: self.classes.clone(),
                     rotation: self.rotation.radians(),
                     opacity: self.opacity,
                 },
@@ -4,7 +4,7 @@ use crate::core::mouse;
 use crate::core::renderer;
 use crate::core::text::{Paragraph, Span};
 use crate::core::widget::text::{
-    self, Catalog, LineHeight, Shaping, Style, StyleFn, Wrapping,
+    self, Catalog, LineHeight, Selection, Shaping, Style, StyleFn, Wrapping,
 };
 use crate::core::widget::tree::{self, Tree};
 use crate::core::{
@@ -29,6 +29,7 @@ where
     align_x: alignment::Horizontal,
     align_y: alignment::Vertical,
     wrapping: Wrapping,
+    selectable: bool,
     class: Theme::Class<'a>,
 }
 
@@ -51,6 +52,7 @@ where
             align_x: alignment::Horizontal::Left,
             align_y: alignment::Vertical::Top,
             wrapping: Wrapping::default(),
+            selectable: false,
             class: Theme::default(),
         }
     }
@@ -125,6 +127,14 @@ where
         self
     }
 
+    /// Sets whether the [`Rich`] text can be selected and copied by the user.
+    ///
+    /// By default, it cannot.
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
     /// Sets the default style of the [`Rich`] text.
     #[must_use]
     pub fn style(mut self, style: impl Fn(&Theme) -> Style + 'a) -> Self
@@ -150,7 +160,10 @@ where
     {
         let color = color.map(Into::into);
 
-        self.style(move |_theme| Style { color })
+        self.style(move |_theme| Style {
+            color,
+            ..Style::default()
+        })
     }
 
     /// Sets the default style class of the [`Rich`] text.
@@ -177,6 +190,7 @@ where
 struct State<Link, P: Paragraph> {
     spans: Vec<Span<'static, Link, P::Font>>,
     span_pressed: Option<usize>,
+    selection: Selection,
     paragraph: P,
 }
 
@@ -195,6 +209,7 @@ where
         tree::State::new(State::<Link, _> {
             spans: Vec::new(),
             span_pressed: None,
+            selection: Selection::default(),
             paragraph: Renderer::Paragraph::default(),
         })
     }
@@ -249,6 +264,17 @@ where
             .position_in(layout.bounds())
             .and_then(|position| state.paragraph.hit_span(position));
 
+        if self.selectable {
+            state.selection.draw(
+                renderer,
+                layout.bounds(),
+                &state.paragraph,
+                style.selection.unwrap_or_else(|| {
+                    style.color.unwrap_or(defaults.text_color).scale_alpha(0.3)
+                }),
+            );
+        }
+
         for (index, span) in self.spans.as_ref().as_ref().iter().enumerate() {
             let is_hovered_link =
                 span.link.is_some() && Some(index) == hovered_span;
@@ -261,7 +287,7 @@ where
                 let translation = layout.position() - Point::ORIGIN;
                 let regions = state.paragraph.span_bounds(index);
 
-                if let Some(highlight) = span.highlight {
+                if let Some(highlight) = &span.highlight {
                     for bounds in &regions {
                         let bounds = Rectangle::new(
                             bounds.position()
@@ -282,7 +308,7 @@ where
                                 border: highlight.border,
                                 ..Default::default()
                             },
-                            highlight.background,
+                            highlight.background.clone(),
                         );
                     }
                 }
@@ -361,11 +387,11 @@ where
         layout: Layout<'_>,
         cursor: mouse::Cursor,
         _renderer: &Renderer,
-        _clipboard: &mut dyn Clipboard,
+        clipboard: &mut dyn Clipboard,
         shell: &mut Shell<'_, Link>,
         _viewport: &Rectangle,
     ) {
-        match event {
+        match &event {
             Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
                 if let Some(position) = cursor.position_in(layout.bounds()) {
                     let state = tree
@@ -415,6 +441,21 @@ where
             }
             _ => {}
         }
+
+        if self.selectable && !shell.is_event_captured() {
+            let state = tree
+                .state
+                .downcast_mut::<State<Link, Renderer::Paragraph>>();
+
+            state.selection.update(
+                &event,
+                layout.bounds(),
+                cursor,
+                &state.paragraph,
+                clipboard,
+                shell,
+            );
+        }
     }
 
     fn mouse_interaction(
@@ -439,6 +480,10 @@ where
                     return mouse::Interaction::Pointer;
                 }
             }
+
+            if self.selectable {
+                return mouse::Interaction::Text;
+            }
         }
 
         mouse::Interaction::None
@@ -485,6 +530,7 @@ where
             state.paragraph =
                 Renderer::Paragraph::with_spans(text_with_spans());
             state.spans = spans.iter().cloned().map(Span::to_static).collect();
+            state.selection = Selection::default();
         } else {
             match state.paragraph.compare(core::Text {
                 content: (),
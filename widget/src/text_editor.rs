@@ -33,6 +33,7 @@
 //! ```
 use crate::core::alignment;
 use crate::core::clipboard::{self, Clipboard};
+use crate::core::input_method::{self, InputMethod, Preedit};
 use crate::core::keyboard;
 use crate::core::keyboard::key;
 use crate::core::layout::{self, Layout};
@@ -303,6 +304,39 @@ where
         self.class = class.into();
         self
     }
+
+    fn input_method(
+        &self,
+        state: &State<Highlighter>,
+        renderer: &Renderer,
+        layout: Layout<'_>,
+    ) -> InputMethod {
+        if !state.focus.is_some_and(|focus| focus.is_window_focused) {
+            return InputMethod::Disabled;
+        }
+
+        let text_bounds = layout.bounds().shrink(self.padding);
+        let translation = text_bounds.position() - Point::ORIGIN;
+
+        let position = match self.content.0.borrow().editor.cursor() {
+            Cursor::Caret(position) => position,
+            Cursor::Selection(ranges) => ranges
+                .first()
+                .map(Rectangle::position)
+                .unwrap_or(Point::ORIGIN),
+        };
+
+        let line_height = self.line_height.to_absolute(
+            self.text_size.unwrap_or_else(|| renderer.default_size()),
+        );
+
+        InputMethod::Enabled {
+            cursor: Rectangle::new(
+                position + translation,
+                Size::new(1.0, line_height.into()),
+            ),
+        }
+    }
 }
 
 /// The content of a [`TextEditor`].
@@ -456,6 +490,7 @@ pub struct State<Highlighter: text::Highlighter> {
     last_click: Option<mouse::Click>,
     drag_click: Option<mouse::click::Kind>,
     partial_scroll: f32,
+    preedit: Option<Preedit>,
     highlighter: RefCell<Highlighter>,
     highlighter_settings: Highlighter::Settings,
     highlighter_format_address: usize,
@@ -530,6 +565,7 @@ where
             last_click: None,
             drag_click: None,
             partial_scroll: 0.0,
+            preedit: None,
             highlighter: RefCell::new(Highlighter::new(
                 &self.highlighter_settings,
             )),
@@ -604,7 +640,7 @@ where
         event: Event,
         layout: Layout<'_>,
         cursor: mouse::Cursor,
-        _renderer: &Renderer,
+        renderer: &Renderer,
         clipboard: &mut dyn Clipboard,
         shell: &mut Shell<'_, Message>,
         _viewport: &Rectangle,
@@ -650,6 +686,10 @@ where
                         );
                     }
                 }
+
+                shell.request_input_method(
+                    &self.input_method(state, renderer, layout),
+                );
             }
             _ => {}
         }
@@ -685,6 +725,24 @@ where
                 Update::Release => {
                     state.drag_click = None;
                 }
+                Update::Preedit(preedit) => {
+                    state.preedit = preedit;
+
+                    shell.request_redraw();
+                    shell.capture_event();
+                }
+                Update::Commit(content) => {
+                    state.preedit = None;
+
+                    if let Some(focus) = &mut state.focus {
+                        focus.updated_at = Instant::now();
+                    }
+
+                    shell.publish(on_edit(Action::Edit(Edit::Paste(
+                        Arc::new(content),
+                    ))));
+                    shell.capture_event();
+                }
                 Update::Scroll(lines) => {
                     let bounds = self.content.0.borrow().editor.bounds();
 
@@ -864,7 +922,7 @@ where
                 border: style.border,
                 ..renderer::Quad::default()
             },
-            style.background,
+            style.background.clone(),
         );
 
         let text_bounds = bounds.shrink(self.padding);
@@ -944,6 +1002,29 @@ where
                 }
                 Cursor::Caret(_) => {}
             }
+
+            if let Some(preedit) = state.preedit.as_ref() {
+                let position = match internal.editor.cursor() {
+                    Cursor::Caret(position) => position,
+                    Cursor::Selection(ranges) => ranges
+                        .first()
+                        .map(Rectangle::position)
+                        .unwrap_or(Point::ORIGIN),
+                };
+
+                crate::text_input::draw_preedit(
+                    renderer,
+                    preedit,
+                    position + translation,
+                    self.text_size.unwrap_or_else(|| renderer.default_size()),
+                    self.line_height,
+                    font,
+                    style.value,
+                    style.selection,
+                    style.background,
+                    text_bounds,
+                );
+            }
         }
     }
 
@@ -1125,6 +1206,8 @@ enum Update<Message> {
     Click(mouse::Click),
     Drag(Point),
     Release,
+    Preedit(Option<Preedit>),
+    Commit(String),
     Scroll(f32),
     Binding(Binding<Message>),
 }
@@ -1216,6 +1299,16 @@ impl<Message> Update<Message> {
                 }
                 .map(Self::Binding)
             }
+            Event::InputMethod(event) if state.focus.is_some() => match event {
+                input_method::Event::Opened => None,
+                input_method::Event::Preedit(content, selection) => {
+                    Some(Update::Preedit(Preedit::new(content, selection)))
+                }
+                input_method::Event::Commit(content) => {
+                    Some(Update::Commit(content))
+                }
+                input_method::Event::Closed => Some(Update::Preedit(None)),
+            },
             _ => None,
         }
     }
@@ -1252,7 +1345,7 @@ pub enum Status {
 }
 
 /// The appearance of a text input.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Style {
     /// The [`Background`] of the text input.
     pub background: Background,
@@ -34,14 +34,17 @@ mod editor;
 mod value;
 
 pub mod cursor;
+pub mod filter;
 
 pub use cursor::Cursor;
+pub use filter::{Filter, Mask};
 pub use value::Value;
 
 use editor::Editor;
 
 use crate::core::alignment;
 use crate::core::clipboard::{self, Clipboard};
+use crate::core::input_method::{self, InputMethod, Preedit};
 use crate::core::keyboard;
 use crate::core::keyboard::key;
 use crate::core::layout;
@@ -118,6 +121,8 @@ pub struct TextInput<
     on_paste: Option<Box<dyn Fn(String) -> Message + 'a>>,
     on_submit: Option<Message>,
     icon: Option<Icon<Renderer::Font>>,
+    filter: Option<Box<dyn Filter + 'a>>,
+    error: Option<String>,
     class: Theme::Class<'a>,
     last_status: Option<Status>,
 }
@@ -149,6 +154,8 @@ where
             on_paste: None,
             on_submit: None,
             icon: None,
+            filter: None,
+            error: None,
             class: Theme::default(),
             last_status: None,
         }
@@ -238,6 +245,37 @@ where
         self
     }
 
+    /// Sets the [`Filter`] of the [`TextInput`].
+    ///
+    /// The [`Filter`] is applied to the [`Value`] after every edit, before
+    /// any message is produced. Rejected edits are discarded.
+    pub fn filter(mut self, filter: impl Filter + 'a) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Formats the input of the [`TextInput`] with a [`Mask`] built from
+    /// the given pattern.
+    pub fn mask(self, pattern: &str) -> Self {
+        self.filter(Mask::new(pattern))
+    }
+
+    /// Sets the error message of the [`TextInput`].
+    ///
+    /// The [`TextInput`] will be drawn with [`Status::Error`] and the
+    /// message will be displayed below the field. An empty message changes
+    /// the [`Status`] without displaying anything.
+    pub fn error(mut self, message: impl Into<String>) -> Self {
+        self.error = Some(message.into());
+        self
+    }
+
+    /// Sets the error message of the [`TextInput`], if `Some`.
+    pub fn error_maybe(mut self, message: Option<impl Into<String>>) -> Self {
+        self.error = message.map(Into::into);
+        self
+    }
+
     /// Sets the width of the [`TextInput`].
     pub fn width(mut self, width: impl Into<Length>) -> Self {
         self.width = width.into();
@@ -334,7 +372,7 @@ where
             ..placeholder_text
         });
 
-        if let Some(icon) = &self.icon {
+        let mut children = if let Some(icon) = &self.icon {
             let mut content = [0; 4];
 
             let icon_text = Text {
@@ -379,15 +417,57 @@ where
                 layout::Node::new(Size::new(icon_width, text_bounds.height))
                     .move_to(icon_position);
 
-            layout::Node::with_children(
-                text_bounds.expand(padding),
-                vec![text_node, icon_node],
-            )
+            vec![text_node, icon_node]
         } else {
             let text = layout::Node::new(text_bounds)
                 .move_to(Point::new(padding.left, padding.top));
 
-            layout::Node::with_children(text_bounds.expand(padding), vec![text])
+            vec![text]
+        };
+
+        let size = text_bounds.expand(padding);
+
+        match self.error.as_deref().filter(|message| !message.is_empty()) {
+            Some(message) => {
+                state.error.update(Text {
+                    content: message,
+                    bounds: Size::new(size.width, f32::INFINITY),
+                    vertical_alignment: alignment::Vertical::Top,
+                    wrapping: text::Wrapping::Word,
+                    ..placeholder_text
+                });
+
+                let message_size =
+                    Size::new(size.width, state.error.min_bounds().height);
+
+                children.push(
+                    layout::Node::new(message_size)
+                        .move_to(Point::new(0.0, size.height + ERROR_SPACING)),
+                );
+
+                layout::Node::with_children(
+                    Size::new(
+                        size.width,
+                        size.height + ERROR_SPACING + message_size.height,
+                    ),
+                    children,
+                )
+            }
+            None => layout::Node::with_children(size, children),
+        }
+    }
+
+    /// Returns the bounds of the field of the [`TextInput`], excluding its
+    /// error message.
+    fn field_bounds(&self, layout: Layout<'_>) -> Rectangle {
+        let bounds = layout.bounds();
+
+        match layout.children().nth(1 + usize::from(self.icon.is_some())) {
+            Some(message) => Rectangle {
+                height: message.bounds().y - bounds.y - ERROR_SPACING,
+                ..bounds
+            },
+            None => bounds,
         }
     }
 
@@ -412,7 +492,7 @@ where
         let secure_value = self.is_secure.then(|| value.secure());
         let value = secure_value.as_ref().unwrap_or(value);
 
-        let bounds = layout.bounds();
+        let bounds = self.field_bounds(layout);
 
         let mut children_layout = layout.children();
         let text_bounds = children_layout.next().unwrap().bounds();
@@ -426,7 +506,7 @@ where
                 border: style.border,
                 ..renderer::Quad::default()
             },
-            style.background,
+            style.background.clone(),
         );
 
         if self.icon.is_some() {
@@ -440,9 +520,18 @@ where
             );
         }
 
+        if let Some(message_layout) = children_layout.next() {
+            renderer.fill_paragraph(
+                state.error.raw(),
+                message_layout.bounds().position(),
+                style.error,
+                *viewport,
+            );
+        }
+
         let text = value.to_string();
 
-        let (cursor, offset, is_selecting) = if let Some(focus) = state
+        let (cursor, caret, offset, is_selecting) = if let Some(focus) = state
             .is_focused
             .as_ref()
             .filter(|focus| focus.is_window_focused)
@@ -480,7 +569,7 @@ where
                         None
                     };
 
-                    (cursor, offset, false)
+                    (cursor, text_value_width, offset, false)
                 }
                 cursor::State::Selection { start, end } => {
                     let left = start.min(end);
@@ -515,6 +604,7 @@ where
                             },
                             style.selection,
                         )),
+                        left_position,
                         if end == right {
                             right_offset
                         } else {
@@ -525,7 +615,7 @@ where
                 }
             }
         } else {
-            (None, 0.0, false)
+            (None, 0.0, 0.0, false)
         };
 
         let draw = |renderer: &mut Renderer, viewport| {
@@ -571,6 +661,79 @@ where
         } else {
             draw(renderer, text_bounds);
         }
+
+        if let Some(preedit) = state.preedit.as_ref() {
+            let alignment_offset = alignment_offset(
+                text_bounds.width,
+                state.value.raw().min_width(),
+                self.alignment,
+            );
+
+            draw_preedit(
+                renderer,
+                preedit,
+                Point::new(
+                    text_bounds.x + caret + alignment_offset - offset,
+                    text_bounds.y,
+                ),
+                self.size.unwrap_or_else(|| renderer.default_size()),
+                self.line_height,
+                self.font.unwrap_or_else(|| renderer.default_font()),
+                style.value,
+                style.selection,
+                style.background,
+                text_bounds,
+            );
+        }
+    }
+
+    fn input_method(
+        &self,
+        state: &State<Renderer::Paragraph>,
+        layout: Layout<'_>,
+    ) -> InputMethod {
+        if self.on_input.is_none()
+            || !state
+                .is_focused
+                .is_some_and(|focus| focus.is_window_focused)
+        {
+            return InputMethod::Disabled;
+        }
+
+        let value = if self.is_secure {
+            self.value.secure()
+        } else {
+            self.value.clone()
+        };
+
+        let text_bounds = layout.children().next().unwrap().bounds();
+
+        let caret_index = match state.cursor.state(&value) {
+            cursor::State::Index(position) => position,
+            cursor::State::Selection { start, end } => start.min(end),
+        };
+
+        let (caret, offset) = measure_cursor_and_scroll_offset(
+            state.value.raw(),
+            text_bounds,
+            caret_index,
+        );
+
+        let alignment_offset = alignment_offset(
+            text_bounds.width,
+            state.value.raw().min_width(),
+            self.alignment,
+        );
+
+        InputMethod::Enabled {
+            cursor: Rectangle::new(
+                Point::new(
+                    (text_bounds.x + caret + alignment_offset - offset).floor(),
+                    text_bounds.y,
+                ),
+                Size::new(1.0, text_bounds.height),
+            ),
+        }
     }
 }
 
@@ -656,7 +819,8 @@ where
                 let state = state::<Renderer>(tree);
                 let cursor_before = state.cursor;
 
-                let click_position = cursor.position_over(layout.bounds());
+                let click_position =
+                    cursor.position_over(self.field_bounds(layout));
 
                 state.is_focused = if click_position.is_some() {
                     let now = Instant::now();
@@ -853,8 +1017,11 @@ where
                                 );
                             }
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
+                            let mut editor = Editor::new(
+                                &mut self.value,
+                                &mut state.cursor,
+                                self.filter.as_deref(),
+                            );
                             editor.delete();
 
                             let message = (on_input)(editor.contents());
@@ -887,8 +1054,11 @@ where
                                 }
                             };
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
+                            let mut editor = Editor::new(
+                                &mut self.value,
+                                &mut state.cursor,
+                                self.filter.as_deref(),
+                            );
                             editor.paste(content.clone());
 
                             let message = if let Some(paste) = &self.on_paste {
@@ -933,8 +1103,11 @@ where
                         if let Some(c) =
                             text.chars().next().filter(|c| !c.is_control())
                         {
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
+                            let mut editor = Editor::new(
+                                &mut self.value,
+                                &mut state.cursor,
+                                self.filter.as_deref(),
+                            );
 
                             editor.insert(c);
 
@@ -974,8 +1147,11 @@ where
                                 }
                             }
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
+                            let mut editor = Editor::new(
+                                &mut self.value,
+                                &mut state.cursor,
+                                self.filter.as_deref(),
+                            );
                             editor.backspace();
 
                             let message = (on_input)(editor.contents());
@@ -1007,8 +1183,11 @@ where
                                 }
                             }
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
+                            let mut editor = Editor::new(
+                                &mut self.value,
+                                &mut state.cursor,
+                                self.filter.as_deref(),
+                            );
                             editor.delete();
 
                             let message = (on_input)(editor.contents());
@@ -1188,6 +1367,54 @@ where
 
                 state.keyboard_modifiers = *modifiers;
             }
+            Event::InputMethod(event) => match event {
+                input_method::Event::Opened => {}
+                input_method::Event::Preedit(content, selection) => {
+                    let state = state::<Renderer>(tree);
+
+                    if state.is_focused.is_some() {
+                        state.preedit =
+                            Preedit::new(content.clone(), selection.clone());
+
+                        shell.request_redraw();
+                        shell.capture_event();
+                    }
+                }
+                input_method::Event::Commit(text) => {
+                    let state = state::<Renderer>(tree);
+
+                    if let Some(focus) = &mut state.is_focused {
+                        let Some(on_input) = &self.on_input else {
+                            return;
+                        };
+
+                        state.preedit = None;
+                        state.is_pasting = None;
+
+                        let mut editor = Editor::new(
+                            &mut self.value,
+                            &mut state.cursor,
+                            self.filter.as_deref(),
+                        );
+                        editor.paste(Value::new(text));
+
+                        let message = (on_input)(editor.contents());
+                        shell.publish(message);
+                        shell.capture_event();
+
+                        focus.updated_at = Instant::now();
+                        update_cache(state, &self.value);
+                        return;
+                    }
+                }
+                input_method::Event::Closed => {
+                    let state = state::<Renderer>(tree);
+
+                    if state.preedit.take().is_some() {
+                        shell.request_redraw();
+                    }
+                }
+            },
             Event::Window(window::Event::Unfocused) => {
                 let state = state::<Renderer>(tree);
 
@@ -1228,6 +1455,8 @@ where
                         );
                     }
                 }
+
+                shell.request_input_method(&self.input_method(state, layout));
             }
             _ => {}
         }
@@ -1235,13 +1464,19 @@ where
         let state = state::<Renderer>(tree);
         let is_disabled = self.on_input.is_none();
 
+        let bounds = self.field_bounds(layout);
+
         let status = if is_disabled {
             Status::Disabled
+        } else if self.error.is_some() {
+            Status::Error {
+                is_focused: state.is_focused(),
+            }
         } else if state.is_focused() {
             Status::Focused {
-                is_hovered: cursor.is_over(layout.bounds()),
+                is_hovered: cursor.is_over(bounds),
             }
-        } else if cursor.is_over(layout.bounds()) {
+        } else if cursor.is_over(bounds) {
             Status::Hovered
         } else {
             Status::Active
@@ -1278,7 +1513,7 @@ where
         _viewport: &Rectangle,
         _renderer: &Renderer,
     ) -> mouse::Interaction {
-        if cursor.is_over(layout.bounds()) {
+        if cursor.is_over(self.field_bounds(layout)) {
             if self.on_input.is_none() {
                 mouse::Interaction::Idle
             } else {
@@ -1407,11 +1642,13 @@ pub struct State<P: text::Paragraph> {
     value: paragraph::Plain<P>,
     placeholder: paragraph::Plain<P>,
     icon: paragraph::Plain<P>,
+    error: paragraph::Plain<P>,
     is_focused: Option<Focus>,
     is_dragging: bool,
     is_pasting: Option<Value>,
     last_click: Option<mouse::Click>,
     cursor: Cursor,
+    preedit: Option<Preedit>,
     keyboard_modifiers: keyboard::Modifiers,
     // TODO: Add stateful horizontal scrolling offset
 }
@@ -1613,6 +1850,8 @@ fn replace_paragraph<Renderer>(
 
 const CURSOR_BLINK_INTERVAL_MILLIS: u128 = 500;
 
+const ERROR_SPACING: f32 = 4.0;
+
 /// The possible status of a [`TextInput`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Status {
@@ -1627,10 +1866,15 @@ pub enum Status {
     },
     /// The [`TextInput`] cannot be interacted with.
     Disabled,
+    /// The [`TextInput`] has an error.
+    Error {
+        /// Whether the [`TextInput`] is focused, while in error.
+        is_focused: bool,
+    },
 }
 
 /// The appearance of a text input.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Style {
     /// The [`Background`] of the text input.
     pub background: Background,
@@ -1644,6 +1888,8 @@ pub struct Style {
     pub value: Color,
     /// The [`Color`] of the selection of the text input.
     pub selection: Color,
+    /// The [`Color`] of the error message of the text input.
+    pub error: Color,
 }
 
 /// The theme catalog of a [`TextInput`].
@@ -1690,6 +1936,7 @@ pub fn default(theme: &Theme, status: Status) -> Style {
         placeholder: palette.background.strong.color,
         value: palette.background.base.text,
         selection: palette.primary.weak.color,
+        error: palette.danger.base.color,
     };
 
     match status {
@@ -1713,9 +1960,105 @@ pub fn default(theme: &Theme, status: Status) -> Style {
             value: active.placeholder,
             ..active
         },
+        Status::Error { is_focused } => Style {
+            border: Border {
+                color: if is_focused {
+                    palette.danger.strong.color
+                } else {
+                    palette.danger.base.color
+                },
+                ..active.border
+            },
+            ..active
+        },
     }
 }
 
+/// Draws the [`Preedit`] of an input method at the given position, with
+/// an underline and an opaque background covering the text behind it.
+///
+/// The selection of the [`Preedit`], if any, is highlighted with the given
+/// `selection` color.
+pub(crate) fn draw_preedit<Renderer>(
+    renderer: &mut Renderer,
+    preedit: &Preedit,
+    position: Point,
+    size: Pixels,
+    line_height: text::LineHeight,
+    font: Renderer::Font,
+    color: Color,
+    selection: Color,
+    background: Background,
+    viewport: Rectangle,
+) where
+    Renderer: text::Renderer,
+{
+    let paragraph = Renderer::Paragraph::with_text(Text {
+        content: &preedit.content,
+        bounds: Size::INFINITY,
+        size,
+        line_height,
+        font,
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Top,
+        shaping: text::Shaping::Advanced,
+        wrapping: text::Wrapping::None,
+    });
+
+    let height = f32::from(line_height.to_absolute(size));
+    let bounds = Rectangle::new(
+        position,
+        Size::new(paragraph.min_width().ceil(), height),
+    );
+
+    renderer.with_layer(viewport, |renderer| {
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                ..renderer::Quad::default()
+            },
+            background,
+        );
+
+        if let Some(range) =
+            preedit.selection.as_ref().filter(|range| !range.is_empty())
+        {
+            let start = paragraph::Position {
+                line: 0,
+                index: range.start,
+            };
+
+            let end = paragraph::Position {
+                line: 0,
+                index: range.end,
+            };
+
+            for region in paragraph.range_bounds(start, end) {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: region + Vector::new(position.x, position.y),
+                        ..renderer::Quad::default()
+                    },
+                    selection,
+                );
+            }
+        }
+
+        renderer.fill_paragraph(&paragraph, position, color, viewport);
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle::new(
+                    Point::new(bounds.x, bounds.y + bounds.height - 1.0),
+                    Size::new(bounds.width, 1.0),
+                ),
+                ..renderer::Quad::default()
+            },
+            color,
+        );
+    });
+}
+
 fn alignment_offset(
     text_bounds_width: f32,
     text_min_width: f32,
@@ -1733,3 +2076,121 @@ fn alignment_offset(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type TextInput<'a> = super::TextInput<'a, String, Theme, ()>;
+
+    const SIZE: Size = Size::new(100.0, 30.0);
+
+    fn tree(text_input: &TextInput<'_>) -> Tree {
+        let mut tree = Tree::new(text_input as &dyn Widget<String, Theme, ()>);
+
+        state::<()>(&mut tree).focus();
+
+        tree
+    }
+
+    fn update(
+        text_input: &mut TextInput<'_>,
+        tree: &mut Tree,
+        event: input_method::Event,
+    ) -> Vec<String> {
+        let node =
+            layout::Node::with_children(SIZE, vec![layout::Node::new(SIZE)]);
+        let mut messages = Vec::new();
+        let mut shell = Shell::new(&mut messages);
+
+        text_input.update(
+            tree,
+            Event::InputMethod(event),
+            Layout::new(&node),
+            mouse::Cursor::Unavailable,
+            &(),
+            &mut clipboard::Null,
+            &mut shell,
+            &Rectangle::with_size(SIZE),
+        );
+
+        messages
+    }
+
+    fn preedit(content: &str) -> input_method::Event {
+        input_method::Event::Preedit(content.to_owned(), Some(0..1))
+    }
+
+    #[test]
+    fn preedit_is_stored_while_focused() {
+        let mut text_input = TextInput::new("", "").on_input(String::from);
+        let mut tree = tree(&text_input);
+
+        let _ = update(&mut text_input, &mut tree, preedit("ka"));
+
+        assert_eq!(
+            state::<()>(&mut tree).preedit,
+            Some(Preedit {
+                content: "ka".to_owned(),
+                selection: Some(0..1),
+            })
+        );
+
+        let _ = update(&mut text_input, &mut tree, preedit(""));
+
+        assert_eq!(state::<()>(&mut tree).preedit, None);
+    }
+
+    #[test]
+    fn preedit_is_ignored_while_unfocused() {
+        let mut text_input = TextInput::new("", "").on_input(String::from);
+        let mut tree = tree(&text_input);
+
+        state::<()>(&mut tree).unfocus();
+
+        let _ = update(&mut text_input, &mut tree, preedit("ka"));
+
+        assert_eq!(state::<()>(&mut tree).preedit, None);
+    }
+
+    #[test]
+    fn commit_clears_preedit_and_inserts_text() {
+        let mut text_input = TextInput::new("", "ab").on_input(String::from);
+        let mut tree = tree(&text_input);
+
+        let _ = update(&mut text_input, &mut tree, preedit("ka"));
+        let messages = update(
+            &mut text_input,
+            &mut tree,
+            input_method::Event::Commit("か".to_owned()),
+        );
+
+        assert_eq!(messages, vec!["abか".to_owned()]);
+        assert_eq!(state::<()>(&mut tree).preedit, None);
+    }
+
+    #[test]
+    fn commit_is_ignored_without_on_input() {
+        let mut text_input = TextInput::new("", "ab");
+        let mut tree = tree(&text_input);
+
+        let messages = update(
+            &mut text_input,
+            &mut tree,
+            input_method::Event::Commit("か".to_owned()),
+        );
+
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn closed_clears_preedit() {
+        let mut text_input = TextInput::new("", "").on_input(String::from);
+        let mut tree = tree(&text_input);
+
+        let _ = update(&mut text_input, &mut tree, preedit("ka"));
+        let _ = update(&mut text_input, &mut tree, input_method::Event::Closed);
+
+        assert_eq!(state::<()>(&mut tree).preedit, None);
+    }
+}
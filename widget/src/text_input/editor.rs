@@ -1,13 +1,22 @@
-use crate::text_input::{Cursor, Value};
+use crate::text_input::{Cursor, Filter, Value};
 
 pub struct Editor<'a> {
     value: &'a mut Value,
     cursor: &'a mut Cursor,
+    filter: Option<&'a dyn Filter>,
 }
 
 impl<'a> Editor<'a> {
-    pub fn new(value: &'a mut Value, cursor: &'a mut Cursor) -> Editor<'a> {
-        Editor { value, cursor }
+    pub fn new(
+        value: &'a mut Value,
+        cursor: &'a mut Cursor,
+        filter: Option<&'a dyn Filter>,
+    ) -> Editor<'a> {
+        Editor {
+            value,
+            cursor,
+            filter,
+        }
     }
 
     pub fn contents(&self) -> String {
@@ -15,28 +24,55 @@ impl<'a> Editor<'a> {
     }
 
     pub fn insert(&mut self, character: char) {
-        if let Some((left, right)) = self.cursor.selection(self.value) {
-            self.cursor.move_left(self.value);
-            self.value.remove_many(left, right);
-        }
+        self.filtered(|editor| {
+            if let Some((left, right)) = editor.cursor.selection(editor.value) {
+                editor.cursor.move_left(editor.value);
+                editor.value.remove_many(left, right);
+            }
 
-        self.value.insert(self.cursor.end(self.value), character);
-        self.cursor.move_right(self.value);
+            editor
+                .value
+                .insert(editor.cursor.end(editor.value), character);
+            editor.cursor.move_right(editor.value);
+        });
     }
 
     pub fn paste(&mut self, content: Value) {
-        let length = content.len();
-        if let Some((left, right)) = self.cursor.selection(self.value) {
-            self.cursor.move_left(self.value);
-            self.value.remove_many(left, right);
-        }
+        self.filtered(|editor| {
+            let length = content.len();
+            if let Some((left, right)) = editor.cursor.selection(editor.value) {
+                editor.cursor.move_left(editor.value);
+                editor.value.remove_many(left, right);
+            }
 
-        self.value.insert_many(self.cursor.end(self.value), content);
+            editor
+                .value
+                .insert_many(editor.cursor.end(editor.value), content);
 
-        self.cursor.move_right_by_amount(self.value, length);
+            editor.cursor.move_right_by_amount(editor.value, length);
+        });
     }
 
     pub fn backspace(&mut self) {
+        self.filtered(Self::remove_backward);
+    }
+
+    pub fn delete(&mut self) {
+        self.filtered(|editor| match editor.cursor.selection(editor.value) {
+            Some(_) => {
+                editor.remove_backward();
+            }
+            None => {
+                let end = editor.cursor.end(editor.value);
+
+                if end < editor.value.len() {
+                    editor.value.remove(end);
+                }
+            }
+        });
+    }
+
+    fn remove_backward(&mut self) {
         match self.cursor.selection(self.value) {
             Some((start, end)) => {
                 self.cursor.move_left(self.value);
@@ -53,18 +89,134 @@ impl<'a> Editor<'a> {
         }
     }
 
-    pub fn delete(&mut self) {
-        match self.cursor.selection(self.value) {
-            Some(_) => {
-                self.backspace();
+    fn filtered(&mut self, edit: impl FnOnce(&mut Self)) {
+        let Some(filter) = self.filter else {
+            edit(self);
+            return;
+        };
+
+        let value_before = self.value.clone();
+        let cursor_before = *self.cursor;
+
+        edit(self);
+
+        match filter.filter(self.value, self.cursor.end(self.value)) {
+            Some((value, cursor)) => {
+                *self.value = value;
+                self.cursor.move_to(cursor);
             }
             None => {
-                let end = self.cursor.end(self.value);
-
-                if end < self.value.len() {
-                    self.value.remove(end);
-                }
+                *self.value = value_before;
+                *self.cursor = cursor_before;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_input::cursor;
+    use crate::text_input::Mask;
+
+    fn digits(text: &str) -> bool {
+        text.chars().all(|c| c.is_ascii_digit())
+    }
+
+    fn edit(
+        text: &str,
+        cursor: usize,
+        filter: Option<&dyn Filter>,
+        f: impl FnOnce(&mut Editor<'_>),
+    ) -> (String, cursor::State) {
+        let mut value = Value::new(text);
+        let mut state = Cursor::default();
+        state.move_to(cursor);
+
+        f(&mut Editor::new(&mut value, &mut state, filter));
+
+        (value.to_string(), state.state(&value))
+    }
+
+    #[test]
+    fn insert_accepted_by_filter() {
+        assert_eq!(
+            edit("12", 1, Some(&digits), |editor| editor.insert('3')),
+            ("132".to_owned(), cursor::State::Index(2))
+        );
+    }
+
+    #[test]
+    fn insert_rejected_by_filter_is_reverted() {
+        assert_eq!(
+            edit("12", 1, Some(&digits), |editor| editor.insert('x')),
+            ("12".to_owned(), cursor::State::Index(1))
+        );
+    }
+
+    #[test]
+    fn paste_rejected_by_filter_is_reverted() {
+        assert_eq!(
+            edit("12", 2, Some(&digits), |editor| {
+                editor.paste(Value::new("3a4"));
+            }),
+            ("12".to_owned(), cursor::State::Index(2))
+        );
+    }
+
+    #[test]
+    fn rejected_edit_keeps_selection() {
+        let mut value = Value::new("1234");
+        let mut state = Cursor::default();
+        state.select_range(1, 3);
+
+        let filter: &dyn Filter = &digits;
+        Editor::new(&mut value, &mut state, Some(filter)).insert('x');
+
+        assert_eq!(value.to_string(), "1234");
+        assert_eq!(
+            state.state(&value),
+            cursor::State::Selection { start: 1, end: 3 }
+        );
+    }
+
+    #[test]
+    fn backspace_is_filtered() {
+        fn non_empty(text: &str) -> bool {
+            !text.is_empty()
+        }
+
+        assert_eq!(
+            edit("1", 1, Some(&non_empty), |editor| {
+                editor.backspace();
+            }),
+            ("1".to_owned(), cursor::State::Index(1))
+        );
+        assert_eq!(
+            edit("12", 2, Some(&non_empty), |editor| {
+                editor.backspace();
+            }),
+            ("1".to_owned(), cursor::State::Index(1))
+        );
+    }
+
+    #[test]
+    fn paste_is_transformed_by_mask() {
+        let mask = Mask::new("(999) 999-9999");
+
+        assert_eq!(
+            edit("", 0, Some(&mask), |editor| {
+                editor.paste(Value::new("5551234"));
+            }),
+            ("(555) 123-4".to_owned(), cursor::State::Index(11))
+        );
+    }
+
+    #[test]
+    fn edits_are_unfiltered_without_filter() {
+        assert_eq!(
+            edit("12", 2, None, |editor| editor.paste(Value::new("ab"))),
+            ("12ab".to_owned(), cursor::State::Index(4))
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! Reject or transform the input of a text input.
+use crate::text_input::Value;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A filter that can reject or transform the [`Value`] of a [`TextInput`]
+/// after every edit.
+///
+/// Since filtering happens before any message is produced, the cursor
+/// position and the paste behavior of the [`TextInput`] are preserved.
+///
+/// Any `Fn(&str) -> bool` closure is a [`Filter`] that accepts the edits
+/// producing text for which it returns `true`.
+///
+/// [`TextInput`]: super::TextInput
+pub trait Filter {
+    /// Filters the new [`Value`] of an edit with the cursor at the given
+    /// grapheme index.
+    ///
+    /// Returns the accepted [`Value`] and the new cursor index; or `None`
+    /// to reject the edit, keeping the previous [`Value`].
+    fn filter(&self, value: &Value, cursor: usize) -> Option<(Value, usize)>;
+}
+
+impl<F> Filter for F
+where
+    F: Fn(&str) -> bool,
+{
+    fn filter(&self, value: &Value, cursor: usize) -> Option<(Value, usize)> {
+        self(&value.to_string()).then(|| (value.clone(), cursor))
+    }
+}
+
+/// A [`Filter`] that formats the input following a pattern.
+///
+/// The characters of the pattern have the following meaning:
+///
+/// - `9` accepts a digit.
+/// - `a` accepts a letter.
+/// - `A` accepts a letter and converts it to uppercase.
+/// - `*` accepts a letter or a digit.
+/// - `\` escapes the next character, making it a literal.
+///
+/// Any other character is a literal, which is inserted automatically as
+/// soon as the input reaches it. Input that does not fit the pattern is
+/// discarded.
+///
+/// # Example
+/// ```
+/// use iced_widget::text_input::Mask;
+///
+/// let phone = Mask::new("(999) 999-9999");
+/// let (formatted, cursor) = phone.apply("5551234567", 10);
+///
+/// assert_eq!(formatted, "(555) 123-4567");
+/// assert_eq!(cursor, 14);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    slots: Vec<Slot>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Literal(char),
+    Digit,
+    Letter,
+    Uppercase,
+    Alphanumeric,
+}
+
+impl Slot {
+    fn accept(self, c: char) -> Option<char> {
+        match self {
+            Slot::Literal(literal) => (c == literal).then_some(c),
+            Slot::Digit => c.is_ascii_digit().then_some(c),
+            Slot::Letter => c.is_alphabetic().then_some(c),
+            Slot::Uppercase => c
+                .is_alphabetic()
+                .then(|| c.to_uppercase().next().unwrap_or(c)),
+            Slot::Alphanumeric => c.is_alphanumeric().then_some(c),
+        }
+    }
+}
+
+impl Mask {
+    /// Creates a new [`Mask`] with the given pattern.
+    pub fn new(pattern: &str) -> Self {
+        let mut chars = pattern.chars();
+        let mut slots = Vec::new();
+
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                '9' => Slot::Digit,
+                'a' => Slot::Letter,
+                'A' => Slot::Uppercase,
+                '*' => Slot::Alphanumeric,
+                '\\' => Slot::Literal(chars.next().unwrap_or('\\')),
+                _ => Slot::Literal(c),
+            });
+        }
+
+        Self { slots }
+    }
+
+    /// Applies the [`Mask`] to the given input with the cursor at the given
+    /// grapheme index.
+    ///
+    /// Returns the formatted input and the new cursor index.
+    pub fn apply(&self, input: &str, cursor: usize) -> (String, usize) {
+        let mut output = String::new();
+        let mut length = 0;
+        let mut new_cursor = None;
+        let mut next = 0;
+
+        for (i, grapheme) in input.graphemes(true).enumerate() {
+            if i == cursor {
+                new_cursor = Some(length);
+            }
+
+            if next == self.slots.len() {
+                break;
+            }
+
+            let mut chars = grapheme.chars();
+
+            let (Some(c), None) = (chars.next(), chars.next()) else {
+                continue;
+            };
+
+            let literals = self.slots[next..]
+                .iter()
+                .take_while(|slot| matches!(slot, Slot::Literal(_)))
+                .count();
+
+            let accepted = self.slots[next..next + literals]
+                .iter()
+                .position(|slot| slot.accept(c).is_some())
+                .map(|position| (position, c))
+                .or_else(|| {
+                    let slot = self.slots.get(next + literals)?;
+
+                    slot.accept(c).map(|c| (literals, c))
+                });
+
+            if let Some((position, c)) = accepted {
+                for slot in &self.slots[next..next + position] {
+                    if let Slot::Literal(literal) = slot {
+                        output.push(*literal);
+                        length += 1;
+                    }
+                }
+
+                output.push(c);
+                length += 1;
+                next += position + 1;
+            }
+        }
+
+        (output, new_cursor.unwrap_or(length))
+    }
+}
+
+impl Filter for Mask {
+    fn filter(&self, value: &Value, cursor: usize) -> Option<(Value, usize)> {
+        let (output, cursor) = self.apply(&value.to_string(), cursor);
+
+        Some((Value::new(&output), cursor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closures_accept_matching_values() {
+        let digits = |text: &str| text.chars().all(|c| c.is_ascii_digit());
+
+        let (value, cursor) =
+            digits.filter(&Value::new("123"), 2).expect("Accept digits");
+
+        assert_eq!(value.to_string(), "123");
+        assert_eq!(cursor, 2);
+    }
+
+    #[test]
+    fn closures_reject_other_values() {
+        let digits = |text: &str| text.chars().all(|c| c.is_ascii_digit());
+
+        assert!(digits.filter(&Value::new("12a"), 3).is_none());
+    }
+
+    #[test]
+    fn mask_filter_moves_cursor_past_literals() {
+        let mask = Mask::new("99-99");
+
+        let (value, cursor) =
+            mask.filter(&Value::new("123"), 3).expect("Accept input");
+
+        assert_eq!(value.to_string(), "12-3");
+        assert_eq!(cursor, 4);
+    }
+
+    #[test]
+    fn mask_inserts_literals() {
+        let mask = Mask::new("(999) 999-9999");
+
+        assert_eq!(mask.apply("555", 3), ("(555".to_owned(), 4));
+        assert_eq!(mask.apply("(5551", 5), ("(555) 1".to_owned(), 7));
+        assert_eq!(
+            mask.apply("5551234567", 10),
+            ("(555) 123-4567".to_owned(), 14)
+        );
+    }
+
+    #[test]
+    fn mask_discards_invalid_input() {
+        let mask = Mask::new("99/99/9999");
+
+        assert_eq!(mask.apply("1x2", 3), ("12".to_owned(), 2));
+        assert_eq!(
+            mask.apply("12/31/20241", 11),
+            ("12/31/2024".to_owned(), 10)
+        );
+    }
+
+    #[test]
+    fn mask_keeps_cursor_in_place() {
+        let mask = Mask::new("(999) 999-9999");
+
+        assert_eq!(mask.apply("(5x55) 1", 3), ("(555) 1".to_owned(), 2));
+    }
+
+    #[test]
+    fn mask_transforms_case() {
+        let mask = Mask::new("AA99 9999");
+
+        assert_eq!(mask.apply("de891234", 8), ("DE89 1234".to_owned(), 9));
+    }
+}
@@ -1,7 +1,39 @@
 //! Access the clipboard.
+#[cfg(all(
+    feature = "dnd",
+    any(feature = "x11", feature = "wayland"),
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+#[path = "clipboard/unix.rs"]
+mod platform;
 
-use crate::core::clipboard::Kind;
-use std::sync::Arc;
+#[cfg(not(all(
+    feature = "dnd",
+    any(feature = "x11", feature = "wayland"),
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+)))]
+#[path = "clipboard/unsupported.rs"]
+mod platform;
+
+mod poller;
+
+use crate::core::clipboard::{Content, Format, Kind};
+
+use poller::Poller;
+use std::mem;
+use std::sync::{Arc, Mutex};
 use winit::window::{Window, WindowId};
 
 /// A buffer for short-term storage and transfer within and between
@@ -14,6 +46,12 @@ pub struct Clipboard {
 enum State {
     Connected {
         clipboard: window_clipboard::Clipboard,
+        // Typed contents are only supported by some platforms
+        platform: Option<platform::Platform>,
+        // Polls for changes when the platform is not supported, while the
+        // changes are listened to
+        poller: Option<Poller>,
+        changes: Changes,
         // Held until drop to satisfy the safety invariants of
         // `window_clipboard::Clipboard`.
         //
@@ -35,8 +73,25 @@ impl Clipboard {
         let clipboard =
             unsafe { window_clipboard::Clipboard::connect(&window) };
 
+        let changes = Changes {
+            kinds: Arc::default(),
+            window: window.clone(),
+        };
+
+        // SAFETY: The display of the window outlives the platform, for the
+        // same reasons.
+        #[allow(unsafe_code)]
+        let platform =
+            unsafe { platform::Platform::connect(&window, changes.clone()) };
+
         let state = match clipboard {
-            Ok(clipboard) => State::Connected { clipboard, window },
+            Ok(clipboard) => State::Connected {
+                clipboard,
+                poller: None,
+                platform,
+                changes,
+                window,
+            },
             Err(_) => State::Unavailable,
         };
 
@@ -84,6 +139,94 @@ impl Clipboard {
         }
     }
 
+    /// Returns the formats the current content of the [`Clipboard`] is
+    /// available in.
+    ///
+    /// Only X11 and Wayland are supported; other platforms produce no
+    /// formats.
+    pub fn formats(&self, kind: Kind) -> Vec<String> {
+        match &self.state {
+            State::Connected {
+                platform: Some(platform),
+                ..
+            } => platform.formats(kind),
+            State::Connected { .. } | State::Unavailable => Vec::new(),
+        }
+    }
+
+    /// Reads the current content of the [`Clipboard`] in the given
+    /// [`Format`].
+    ///
+    /// Platforms other than X11 and Wayland only support [`Format::Text`].
+    pub fn read_content(&self, kind: Kind, format: &Format) -> Option<Content> {
+        match &self.state {
+            State::Connected {
+                platform: Some(platform),
+                ..
+            } => platform.read(kind, format),
+            State::Connected { .. } | State::Unavailable => match format {
+                Format::Text => self.read(kind).map(Content::Text),
+                _ => None,
+            },
+        }
+    }
+
+    /// Writes the given contents to the [`Clipboard`], each one in its own
+    /// format.
+    ///
+    /// Platforms other than X11 and Wayland only write the first
+    /// [`Content::Text`].
+    pub fn write_content(&mut self, kind: Kind, contents: Vec<Content>) {
+        match &mut self.state {
+            State::Connected {
+                platform: Some(platform),
+                ..
+            } => platform.write(kind, contents),
+            State::Connected { .. } | State::Unavailable => {
+                let text =
+                    contents.into_iter().find_map(|content| match content {
+                        Content::Text(text) => Some(text),
+                        _ => None,
+                    });
+
+                if let Some(text) = text {
+                    self.write(kind, text);
+                }
+            }
+        }
+    }
+
+    /// Returns the kinds of [`Clipboard`] whose contents have changed since
+    /// the last call.
+    ///
+    /// The event loop is woken up with a redraw request of the window of the
+    /// [`Clipboard`] when a change happens.
+    ///
+    /// Platforms other than X11 and Wayland are polled for changes, but only
+    /// while the application is listening to them; for instance, with a
+    /// running [`listen_clipboard`] subscription.
+    ///
+    /// [`listen_clipboard`]: crate::futures::event::listen_clipboard
+    pub fn changes(&mut self, is_listening: bool) -> Vec<Kind> {
+        match &mut self.state {
+            State::Connected {
+                platform,
+                poller,
+                changes,
+                ..
+            } => {
+                if platform.is_none() && is_listening != poller.is_some() {
+                    *poller = is_listening
+                        .then(|| Poller::spawn(changes.clone()))
+                        .flatten();
+                }
+
+                changes.drain()
+            }
+            State::Unavailable => Vec::new(),
+        }
+    }
+
     /// Returns the identifier of the window used to create the [`Clipboard`], if any.
     pub fn window_id(&self) -> Option<WindowId> {
         match &self.state {
@@ -93,6 +236,32 @@ impl Clipboard {
     }
 }
 
+/// The changes of the clipboards noticed by a platform or a [`Poller`].
+#[derive(Clone)]
+struct Changes {
+    kinds: Arc<Mutex<Vec<Kind>>>,
+    window: Arc<Window>,
+}
+
+impl Changes {
+    /// Records a change and wakes up the event loop.
+    fn push(&self, kind: Kind) {
+        {
+            let mut kinds = self.kinds.lock().expect("Lock clipboard changes");
+
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+
+        self.window.request_redraw();
+    }
+
+    fn drain(&self) -> Vec<Kind> {
+        mem::take(&mut *self.kinds.lock().expect("Lock clipboard changes"))
+    }
+}
+
 impl crate::core::Clipboard for Clipboard {
     fn read(&self, kind: Kind) -> Option<String> {
         self.read(kind)
@@ -101,4 +270,16 @@ impl crate::core::Clipboard for Clipboard {
     fn write(&mut self, kind: Kind, contents: String) {
         self.write(kind, contents);
     }
+
+    fn formats(&self, kind: Kind) -> Vec<String> {
+        self.formats(kind)
+    }
+
+    fn read_content(&self, kind: Kind, format: &Format) -> Option<Content> {
+        self.read_content(kind, format)
+    }
+
+    fn write_content(&mut self, kind: Kind, contents: Vec<Content>) {
+        self.write_content(kind, contents);
+    }
 }
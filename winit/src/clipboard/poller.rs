@@ -0,0 +1,113 @@
+use super::Changes;
+use crate::core::clipboard::Kind;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// The interval between polls.
+const INTERVAL: Duration = Duration::from_millis(500);
+
+/// A thread polling the clipboards for changes.
+pub struct Poller {
+    sender: mpsc::Sender<()>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Poller {
+    pub fn spawn(changes: Changes) -> Option<Self> {
+        let (sender, receiver) = mpsc::channel();
+
+        let thread = thread::Builder::new()
+            .name("iced clipboard poller".to_owned())
+            .spawn(move || run(&changes, &receiver))
+            .map_err(|error| {
+                log::warn!("Failed to spawn clipboard poller: {error}");
+            })
+            .ok()?;
+
+        Some(Self {
+            sender,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for Poller {
+    fn drop(&mut self) {
+        let _ = self.sender.send(());
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Polls the change counter of the system clipboard, which is bumped by
+/// every write; so its contents never need to be read.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[allow(unsafe_code)]
+fn run(changes: &Changes, receiver: &mpsc::Receiver<()>) {
+    // SAFETY: The function takes no arguments and has no preconditions.
+    #[cfg(target_os = "windows")]
+    let change_count =
+        || unsafe { winapi::um::winuser::GetClipboardSequenceNumber() };
+
+    // SAFETY: The general pasteboard can be used from any thread.
+    #[cfg(target_os = "macos")]
+    let change_count = {
+        let pasteboard =
+            unsafe { objc2_app_kit::NSPasteboard::generalPasteboard() };
+
+        move || unsafe { pasteboard.changeCount() }
+    };
+
+    let mut last = change_count();
+
+    while let Err(mpsc::RecvTimeoutError::Timeout) =
+        receiver.recv_timeout(INTERVAL)
+    {
+        let current = change_count();
+
+        if current != last {
+            last = current;
+            changes.push(Kind::Standard);
+        }
+    }
+}
+
+/// Polls the text contents of the clipboards, since the platform offers no
+/// way to know when they change.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn run(changes: &Changes, receiver: &mpsc::Receiver<()>) {
+    // SAFETY: The window is held by the changes, which outlive the
+    // clipboard.
+    #[allow(unsafe_code)]
+    let clipboard =
+        unsafe { window_clipboard::Clipboard::connect(&changes.window) };
+
+    let Ok(clipboard) = clipboard else {
+        return;
+    };
+
+    let read = |kind| match kind {
+        Kind::Standard => clipboard.read().ok(),
+        Kind::Primary => clipboard.read_primary().and_then(Result::ok),
+    };
+
+    let mut contents =
+        [Kind::Standard, Kind::Primary].map(|kind| (kind, read(kind)));
+
+    while let Err(mpsc::RecvTimeoutError::Timeout) =
+        receiver.recv_timeout(INTERVAL)
+    {
+        for (kind, last) in &mut contents {
+            let current = read(*kind);
+
+            if current != *last {
+                *last = current;
+                changes.push(*kind);
+            }
+        }
+    }
+}
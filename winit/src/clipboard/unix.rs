@@ -0,0 +1,368 @@
+#[cfg(feature = "wayland")]
+mod wayland;
+
+#[cfg(feature = "x11")]
+mod x11;
+
+use crate::clipboard::Changes;
+use crate::core::clipboard::{Content, Format, Kind};
+use crate::core::dnd::{Offer, Payload};
+use crate::dnd::encode_png;
+
+use std::sync::Arc;
+use std::time::Duration;
+use winit::window::Window;
+
+/// The time to wait for the owner of the clipboard to send its contents.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+pub enum Platform {
+    #[cfg(feature = "wayland")]
+    Wayland(wayland::Worker),
+    #[cfg(feature = "x11")]
+    X11(x11::Worker),
+}
+
+impl Platform {
+    /// # Safety
+    /// The display of the window must outlive the [`Platform`].
+    #[allow(unsafe_code)]
+    pub unsafe fn connect(window: &Window, changes: Changes) -> Option<Self> {
+        use winit::raw_window_handle::{HasDisplayHandle, RawDisplayHandle};
+
+        match window.display_handle().ok()?.as_raw() {
+            #[cfg(feature = "wayland")]
+            RawDisplayHandle::Wayland(display) => {
+                // SAFETY: Upheld by the caller.
+                unsafe { wayland::Worker::spawn(display.display, changes) }
+                    .map(Self::Wayland)
+            }
+            #[cfg(feature = "x11")]
+            RawDisplayHandle::Xlib(_) | RawDisplayHandle::Xcb(_) => {
+                x11::Worker::spawn(changes).map(Self::X11)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn formats(&self, kind: Kind) -> Vec<String> {
+        match self {
+            #[cfg(feature = "wayland")]
+            Self::Wayland(worker) => worker.formats(kind),
+            #[cfg(feature = "x11")]
+            Self::X11(worker) => worker.formats(kind),
+        }
+    }
+
+    pub fn read(&self, kind: Kind, format: &Format) -> Option<Content> {
+        let formats = self.formats(kind);
+
+        let mime = mime_types(format)
+            .into_iter()
+            .find(|mime| formats.iter().any(|offered| offered == mime))?;
+
+        let bytes = match self {
+            #[cfg(feature = "wayland")]
+            Self::Wayland(worker) => worker.receive(kind, mime),
+            #[cfg(feature = "x11")]
+            Self::X11(worker) => worker.receive(kind, mime),
+        }?;
+
+        decode(format, mime, bytes)
+    }
+
+    pub fn write(&mut self, kind: Kind, contents: Vec<Content>) {
+        let offers = offers(contents);
+
+        if offers.is_empty() {
+            return;
+        }
+
+        match self {
+            #[cfg(feature = "wayland")]
+            Self::Wayland(worker) => worker.offer(kind, offers),
+            #[cfg(feature = "x11")]
+            Self::X11(worker) => worker.offer(kind, offers),
+        }
+    }
+}
+
+/// Returns the MIME types a [`Format`] may be read from, in order of
+/// preference.
+fn mime_types(format: &Format) -> Vec<&str> {
+    match format {
+        Format::Text => Offer::TEXT.to_vec(),
+        Format::Html => vec!["text/html"],
+        Format::Image => vec!["image/png"],
+        Format::Mime(mime) => vec![mime.as_str()],
+    }
+}
+
+/// Decodes the given bytes of the given MIME type into some [`Content`]
+/// of the given [`Format`].
+fn decode(format: &Format, mime: &str, bytes: Vec<u8>) -> Option<Content> {
+    match format {
+        Format::Text => match Payload::new(mime, bytes) {
+            Payload::Text(text) => Some(Content::Text(text)),
+            _ => None,
+        },
+        Format::Html => {
+            // Some browsers offer HTML in UTF-16
+            let html = match bytes.strip_prefix(&[0xff, 0xfe]) {
+                Some(utf16) => String::from_utf16_lossy(
+                    &utf16
+                        .chunks_exact(2)
+                        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                        .collect::<Vec<_>>(),
+                ),
+                None => String::from_utf8_lossy(&bytes).into_owned(),
+            };
+
+            Some(Content::Html(html))
+        }
+        Format::Image => {
+            let (width, height, pixels) = decode_png(&bytes)
+                .map_err(|error| {
+                    log::warn!("Failed to decode clipboard image: {error}");
+                })
+                .ok()?;
+
+            Some(Content::image(width, height, pixels))
+        }
+        Format::Mime(format) => Some(Content::mime(format.clone(), bytes)),
+    }
+}
+
+/// Returns the MIME types and bytes offered when writing the given
+/// contents.
+fn offers(contents: Vec<Content>) -> Vec<(String, Arc<[u8]>)> {
+    let mut offers = Vec::new();
+
+    for content in contents {
+        match content {
+            Content::Text(text) => {
+                let text: Arc<[u8]> = Arc::from(text.into_bytes());
+
+                offers.extend(
+                    ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"]
+                        .into_iter()
+                        .map(|mime| (mime.to_owned(), text.clone())),
+                );
+            }
+            Content::Html(html) => {
+                offers.push((
+                    "text/html".to_owned(),
+                    Arc::from(html.into_bytes()),
+                ));
+            }
+            Content::Image {
+                width,
+                height,
+                pixels,
+            } => match encode_png(width, height, &pixels) {
+                Ok(png) => {
+                    offers.push(("image/png".to_owned(), Arc::from(png)));
+                }
+                Err(error) => {
+                    log::warn!("Failed to encode clipboard image: {error}");
+                }
+            },
+            Content::Mime { format, bytes } => {
+                offers.push((format, Arc::from(bytes.as_ref())));
+            }
+        }
+    }
+
+    offers
+}
+
+/// Decodes the given PNG into its dimensions and RGBA pixels.
+fn decode_png(png: &[u8]) -> Result<(u32, u32, Vec<u8>), png::DecodingError> {
+    let mut decoder = png::Decoder::new(png);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+    buffer.truncate(info.buffer_size());
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], u8::MAX])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]])
+            .collect(),
+        // Indexed colors are expanded by the transformations
+        png::ColorType::Grayscale | png::ColorType::Indexed => buffer
+            .into_iter()
+            .flat_map(|gray| [gray, gray, gray, u8::MAX])
+            .collect(),
+    };
+
+    Ok((info.width, info.height, pixels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_types_prefer_utf8_text() {
+        assert_eq!(mime_types(&Format::Text), Offer::TEXT);
+        assert_eq!(mime_types(&Format::Html), ["text/html"]);
+        assert_eq!(mime_types(&Format::Image), ["image/png"]);
+        assert_eq!(
+            mime_types(&Format::Mime(String::from("application/json"))),
+            ["application/json"]
+        );
+    }
+
+    #[test]
+    fn decode_text() {
+        assert_eq!(
+            decode(&Format::Text, "UTF8_STRING", "héllo".into()),
+            Some(Content::text("héllo"))
+        );
+        assert_eq!(
+            decode(&Format::Text, "STRING", b"h\xE9llo".to_vec()),
+            Some(Content::text("héllo"))
+        );
+        assert_eq!(decode(&Format::Text, "image/png", Vec::new()), None);
+    }
+
+    #[test]
+    fn decode_html_in_utf8_and_utf16() {
+        assert_eq!(
+            decode(&Format::Html, "text/html", b"<b>a</b>".to_vec()),
+            Some(Content::html("<b>a</b>"))
+        );
+
+        let utf16 = [0xff, 0xfe]
+            .into_iter()
+            .chain("<i>é</i>".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+
+        assert_eq!(
+            decode(&Format::Html, "text/html", utf16),
+            Some(Content::html("<i>é</i>"))
+        );
+    }
+
+    #[test]
+    fn decode_mime_keeps_bytes() {
+        let format = Format::Mime(String::from("application/json"));
+
+        assert_eq!(
+            decode(&format, "application/json", b"{}".to_vec()),
+            Some(Content::mime("application/json", b"{}".to_vec()))
+        );
+    }
+
+    #[test]
+    fn decode_invalid_image() {
+        assert_eq!(decode(&Format::Image, "image/png", b"png".to_vec()), None);
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let pixels = [255, 0, 0, 255, 0, 255, 0, 128];
+        let png = encode_png(2, 1, &pixels).expect("Encode PNG");
+
+        assert_eq!(
+            decode(&Format::Image, "image/png", png),
+            Some(Content::image(2, 1, pixels.to_vec()))
+        );
+    }
+
+    #[test]
+    fn decode_png_expands_to_rgba() {
+        let encode = |color, depth, pixels: &[u8]| {
+            let mut png = Vec::new();
+
+            {
+                let mut encoder = png::Encoder::new(&mut png, 2, 1);
+                encoder.set_color(color);
+                encoder.set_depth(depth);
+
+                encoder
+                    .write_header()
+                    .and_then(|mut writer| writer.write_image_data(pixels))
+                    .expect("Encode PNG");
+            }
+
+            decode_png(&png).expect("Decode PNG")
+        };
+
+        let eight = png::BitDepth::Eight;
+
+        assert_eq!(
+            encode(png::ColorType::Rgb, eight, &[1, 2, 3, 4, 5, 6]),
+            (2, 1, vec![1, 2, 3, 255, 4, 5, 6, 255])
+        );
+        assert_eq!(
+            encode(png::ColorType::GrayscaleAlpha, eight, &[7, 8, 9, 10]),
+            (2, 1, vec![7, 7, 7, 8, 9, 9, 9, 10])
+        );
+        assert_eq!(
+            encode(png::ColorType::Grayscale, eight, &[11, 12]),
+            (2, 1, vec![11, 11, 11, 255, 12, 12, 12, 255])
+        );
+
+        // 16-bit channels are stripped to 8 bits
+        assert_eq!(
+            encode(
+                png::ColorType::Grayscale,
+                png::BitDepth::Sixteen,
+                &[0x12, 0x34, 0xAB, 0xCD]
+            ),
+            (2, 1, vec![0x12, 0x12, 0x12, 255, 0xAB, 0xAB, 0xAB, 255])
+        );
+    }
+
+    #[test]
+    fn offers_every_content() {
+        let offers = offers(vec![
+            Content::text("a"),
+            Content::html("<b>a</b>"),
+            Content::mime("application/json", b"{}".to_vec()),
+        ]);
+
+        let offers: Vec<_> = offers
+            .iter()
+            .map(|(mime, bytes)| (mime.as_str(), bytes.as_ref()))
+            .collect();
+
+        assert_eq!(
+            offers,
+            [
+                ("text/plain;charset=utf-8", &b"a"[..]),
+                ("UTF8_STRING", b"a"),
+                ("text/plain", b"a"),
+                ("text/html", b"<b>a</b>"),
+                ("application/json", b"{}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn offers_images_as_png() {
+        let pixels = [1, 2, 3, 4];
+        let offers = offers(vec![Content::image(1, 1, pixels.to_vec())]);
+
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].0, "image/png");
+        assert_eq!(
+            decode_png(&offers[0].1).expect("Decode PNG"),
+            (1, 1, pixels.to_vec())
+        );
+    }
+
+    #[test]
+    fn offers_skip_invalid_images() {
+        assert!(offers(vec![Content::image(2, 2, vec![0; 4])]).is_empty());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::clipboard::Changes;
+use crate::core::clipboard::{Content, Format, Kind};
+
+use winit::window::Window;
+
+pub enum Platform {}
+
+impl Platform {
+    #[allow(unsafe_code)]
+    pub unsafe fn connect(_window: &Window, _changes: Changes) -> Option<Self> {
+        None
+    }
+
+    pub fn formats(&self, _kind: Kind) -> Vec<String> {
+        match *self {}
+    }
+
+    pub fn read(&self, _kind: Kind, _format: &Format) -> Option<Content> {
+        match *self {}
+    }
+
+    pub fn write(&mut self, _kind: Kind, _contents: Vec<Content>) {
+        match *self {}
+    }
+}
@@ -0,0 +1,716 @@
+//! Access the clipboard through the selections of the Wayland compositor.
+use super::TIMEOUT;
+use crate::clipboard::Changes;
+use crate::core::clipboard::Kind;
+
+use rustc_hash::FxHashMap;
+use std::ffi::c_void;
+use std::io::{self, Read, Write};
+use std::ptr::NonNull;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use sctk::data_device_manager::data_device::{DataDevice, DataDeviceHandler};
+use sctk::data_device_manager::data_offer::{DataOfferHandler, DragOffer};
+use sctk::data_device_manager::data_source::{
+    CopyPasteSource, DataSourceHandler,
+};
+use sctk::data_device_manager::{DataDeviceManagerState, ReadPipe, WritePipe};
+use sctk::primary_selection::device::{
+    PrimarySelectionDevice, PrimarySelectionDeviceHandler,
+};
+use sctk::primary_selection::selection::{
+    PrimarySelectionSource, PrimarySelectionSourceHandler,
+};
+use sctk::primary_selection::PrimarySelectionManagerState;
+use sctk::reexports::calloop::channel;
+use sctk::reexports::calloop::EventLoop;
+use sctk::reexports::calloop_wayland_source::WaylandSource;
+use sctk::reexports::client::backend::{Backend, ObjectId};
+use sctk::reexports::client::globals::registry_queue_init;
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_data_source::WlDataSource;
+use sctk::reexports::client::protocol::wl_keyboard::{self, WlKeyboard};
+use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::primary_selection::zv1::client::{
+    zwp_primary_selection_device_v1::ZwpPrimarySelectionDeviceV1,
+    zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
+};
+use sctk::registry::{ProvidesRegistryState, RegistryState};
+use sctk::seat::pointer::{
+    PointerData, PointerEvent, PointerEventKind, PointerHandler,
+};
+use sctk::seat::{Capability, SeatHandler, SeatState};
+use sctk::{
+    delegate_data_device, delegate_pointer, delegate_primary_selection,
+    delegate_registry, delegate_seat, registry_handlers,
+};
+
+/// A thread dispatching its own event queue on the display connection of
+/// the application.
+pub struct Worker {
+    sender: channel::Sender<Command>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+enum Command {
+    Formats {
+        kind: Kind,
+        sender: mpsc::Sender<Vec<String>>,
+    },
+    Receive {
+        kind: Kind,
+        mime: String,
+        sender: mpsc::Sender<Option<Vec<u8>>>,
+    },
+    Offer {
+        kind: Kind,
+        offers: Vec<(String, Arc<[u8]>)>,
+    },
+    Exit,
+}
+
+impl Worker {
+    /// # Safety
+    /// The display must be a valid `wl_display` pointer that outlives the
+    /// [`Worker`].
+    #[allow(unsafe_code)]
+    pub unsafe fn spawn(
+        display: NonNull<c_void>,
+        changes: Changes,
+    ) -> Option<Self> {
+        // SAFETY: Upheld by the caller.
+        let backend =
+            unsafe { Backend::from_foreign_display(display.as_ptr().cast()) };
+
+        let connection = Connection::from_backend(backend);
+        let (sender, receiver) = channel::channel();
+
+        let thread = thread::Builder::new()
+            .name("iced clipboard".to_owned())
+            .spawn(move || run(connection, receiver, changes))
+            .map_err(|error| {
+                log::warn!("Failed to spawn clipboard thread: {error}");
+            })
+            .ok()?;
+
+        Some(Self {
+            sender,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn formats(&self, kind: Kind) -> Vec<String> {
+        let (sender, receiver) = mpsc::channel();
+
+        if self.sender.send(Command::Formats { kind, sender }).is_err() {
+            return Vec::new();
+        }
+
+        receiver.recv_timeout(TIMEOUT).unwrap_or_default()
+    }
+
+    pub fn receive(&self, kind: Kind, mime: &str) -> Option<Vec<u8>> {
+        let (sender, receiver) = mpsc::channel();
+
+        self.sender
+            .send(Command::Receive {
+                kind,
+                mime: mime.to_owned(),
+                sender,
+            })
+            .ok()?;
+
+        receiver.recv_timeout(TIMEOUT).ok().flatten()
+    }
+
+    pub fn offer(&mut self, kind: Kind, offers: Vec<(String, Arc<[u8]>)>) {
+        let _ = self.sender.send(Command::Offer { kind, offers });
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let _ = self.sender.send(Command::Exit);
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run(
+    connection: Connection,
+    receiver: channel::Channel<Command>,
+    changes: Changes,
+) {
+    let Ok((globals, queue)) = registry_queue_init::<State>(&connection) else {
+        return;
+    };
+
+    let Ok(mut event_loop) = EventLoop::<State>::try_new() else {
+        return;
+    };
+
+    let queue_handle = queue.handle();
+    let loop_handle = event_loop.handle();
+
+    let mut state = State {
+        registry: RegistryState::new(&globals),
+        seat_state: SeatState::new(&globals, &queue_handle),
+        data_device_manager: DataDeviceManagerState::bind(
+            &globals,
+            &queue_handle,
+        )
+        .ok(),
+        primary_selection_manager: PrimarySelectionManagerState::bind(
+            &globals,
+            &queue_handle,
+        )
+        .ok(),
+        seats: FxHashMap::default(),
+        latest_seat: None,
+        selection: None,
+        primary_selection: None,
+        changes,
+        queue_handle: queue_handle.clone(),
+        exit: false,
+    };
+
+    let commands =
+        loop_handle.insert_source(receiver, |event, _, state| match event {
+            channel::Event::Msg(Command::Formats { kind, sender }) => {
+                let _ = sender.send(state.formats(kind));
+            }
+            channel::Event::Msg(Command::Receive { kind, mime, sender }) => {
+                state.receive(kind, mime, sender);
+            }
+            channel::Event::Msg(Command::Offer { kind, offers }) => {
+                state.offer(kind, offers);
+            }
+            channel::Event::Msg(Command::Exit) | channel::Event::Closed => {
+                state.exit = true;
+            }
+        });
+
+    let source = WaylandSource::new(connection, queue).insert(loop_handle);
+
+    if commands.is_err() || source.is_err() {
+        return;
+    }
+
+    while !state.exit {
+        if event_loop.dispatch(None, &mut state).is_err() {
+            break;
+        }
+    }
+}
+
+struct State {
+    registry: RegistryState,
+    seat_state: SeatState,
+    data_device_manager: Option<DataDeviceManagerState>,
+    primary_selection_manager: Option<PrimarySelectionManagerState>,
+    seats: FxHashMap<ObjectId, Seat>,
+    latest_seat: Option<ObjectId>,
+    selection: Option<Selection<CopyPasteSource>>,
+    primary_selection: Option<Selection<PrimarySelectionSource>>,
+    changes: Changes,
+    queue_handle: QueueHandle<Self>,
+    exit: bool,
+}
+
+#[derive(Default)]
+struct Seat {
+    keyboard: Option<WlKeyboard>,
+    pointer: Option<WlPointer>,
+    data_device: Option<DataDevice>,
+    primary_selection_device: Option<PrimarySelectionDevice>,
+    serial: Option<u32>,
+}
+
+/// Some contents owned by the application.
+struct Selection<T> {
+    source: T,
+    offers: Vec<(String, Arc<[u8]>)>,
+}
+
+impl State {
+    /// Returns the seat with the latest input, if any.
+    fn seat(&self) -> Option<&Seat> {
+        self.latest_seat
+            .as_ref()
+            .and_then(|seat| self.seats.get(seat))
+            .or_else(|| self.seats.values().next())
+    }
+
+    fn formats(&self, kind: Kind) -> Vec<String> {
+        let seat = self.seat();
+
+        let formats = match kind {
+            Kind::Standard => seat
+                .and_then(|seat| seat.data_device.as_ref())
+                .and_then(|device| device.data().selection_offer())
+                .map(|offer| offer.with_mime_types(<[String]>::to_vec)),
+            Kind::Primary => seat
+                .and_then(|seat| seat.primary_selection_device.as_ref())
+                .and_then(|device| device.data().selection_offer())
+                .map(|offer| offer.with_mime_types(<[String]>::to_vec)),
+        };
+
+        formats.unwrap_or_default()
+    }
+
+    /// Receives the contents of the selection in the given MIME type on a
+    /// separate thread, since the pipe may block.
+    fn receive(
+        &self,
+        kind: Kind,
+        mime: String,
+        sender: mpsc::Sender<Option<Vec<u8>>>,
+    ) {
+        let seat = self.seat();
+
+        let pipe = match kind {
+            Kind::Standard => seat
+                .and_then(|seat| seat.data_device.as_ref())
+                .and_then(|device| device.data().selection_offer())
+                .map(|offer| offer.receive(mime).map_err(io::Error::other)),
+            Kind::Primary => seat
+                .and_then(|seat| seat.primary_selection_device.as_ref())
+                .and_then(|device| device.data().selection_offer())
+                .map(|offer| offer.receive(mime)),
+        };
+
+        let Some(pipe) = pipe else {
+            let _ = sender.send(None);
+            return;
+        };
+
+        let result = pipe.and_then(|pipe| {
+            thread::Builder::new()
+                .name("iced clipboard reader".to_owned())
+                .spawn(move || {
+                    let _ = sender.send(read(pipe));
+                })
+        });
+
+        if let Err(error) = result {
+            log::warn!("Failed to read clipboard: {error}");
+        }
+    }
+
+    fn offer(&mut self, kind: Kind, offers: Vec<(String, Arc<[u8]>)>) {
+        let Some((seat, serial)) =
+            self.seat().and_then(|seat| Some((seat, seat.serial?)))
+        else {
+            log::warn!("No input to write to the clipboard from");
+            return;
+        };
+
+        let mimes = offers.iter().map(|(mime, _)| mime);
+
+        match kind {
+            Kind::Standard => {
+                let (Some(manager), Some(device)) =
+                    (&self.data_device_manager, &seat.data_device)
+                else {
+                    return;
+                };
+
+                let source =
+                    manager.create_copy_paste_source(&self.queue_handle, mimes);
+
+                source.set_selection(device, serial);
+
+                self.selection = Some(Selection { source, offers });
+            }
+            Kind::Primary => {
+                let (Some(manager), Some(device)) = (
+                    &self.primary_selection_manager,
+                    &seat.primary_selection_device,
+                ) else {
+                    return;
+                };
+
+                let source =
+                    manager.create_selection_source(&self.queue_handle, mimes);
+
+                source.set_selection(device, serial);
+
+                self.primary_selection = Some(Selection { source, offers });
+            }
+        }
+    }
+
+    fn seat_mut(&mut self, seat: &WlSeat) -> Option<&mut Seat> {
+        let state = self.seats.get_mut(&seat.id())?;
+        self.latest_seat = Some(seat.id());
+
+        Some(state)
+    }
+}
+
+/// Reads the pipe of a selection to its end.
+fn read(mut pipe: ReadPipe) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    match pipe.read_to_end(&mut bytes) {
+        Ok(_) => Some(bytes),
+        Err(error) => {
+            log::warn!("Failed to read clipboard: {error}");
+
+            None
+        }
+    }
+}
+
+/// Writes the bytes of the requested offer, if any, to the given pipe on
+/// a separate thread, since the pipe may block.
+fn send(offers: &[(String, Arc<[u8]>)], mime: &str, mut pipe: WritePipe) {
+    let Some(bytes) = offers
+        .iter()
+        .find(|(offer, _)| offer == mime)
+        .map(|(_, bytes)| bytes.clone())
+    else {
+        return;
+    };
+
+    let writer = thread::Builder::new()
+        .name("iced clipboard writer".to_owned())
+        .spawn(move || {
+            if let Err(error) = pipe.write_all(&bytes) {
+                log::warn!("Failed to write clipboard: {error}");
+            }
+        });
+
+    if let Err(error) = writer {
+        log::warn!("Failed to spawn clipboard writer: {error}");
+    }
+}
+
+impl SeatHandler for State {
+    fn seat_state(&mut self) -> &mut SeatState {
+        &mut self.seat_state
+    }
+
+    fn new_seat(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        seat: WlSeat,
+    ) {
+        let _ = self.seats.insert(seat.id(), Seat::default());
+    }
+
+    fn new_capability(
+        &mut self,
+        _: &Connection,
+        queue_handle: &QueueHandle<Self>,
+        seat: WlSeat,
+        capability: Capability,
+    ) {
+        let state = self.seats.entry(seat.id()).or_default();
+
+        match capability {
+            Capability::Keyboard if state.keyboard.is_none() => {
+                state.keyboard =
+                    Some(seat.get_keyboard(queue_handle, seat.id()));
+            }
+            Capability::Pointer if state.pointer.is_none() => {
+                state.pointer =
+                    self.seat_state.get_pointer(queue_handle, &seat).ok();
+            }
+            _ => {}
+        }
+
+        if state.data_device.is_none() {
+            state.data_device = self
+                .data_device_manager
+                .as_ref()
+                .map(|manager| manager.get_data_device(queue_handle, &seat));
+        }
+
+        if state.primary_selection_device.is_none() {
+            state.primary_selection_device =
+                self.primary_selection_manager.as_ref().map(|manager| {
+                    manager.get_selection_device(queue_handle, &seat)
+                });
+        }
+    }
+
+    fn remove_capability(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        seat: WlSeat,
+        capability: Capability,
+    ) {
+        let Some(state) = self.seats.get_mut(&seat.id()) else {
+            return;
+        };
+
+        match capability {
+            Capability::Keyboard => {
+                if let Some(keyboard) = state.keyboard.take() {
+                    if keyboard.version() >= 3 {
+                        keyboard.release();
+                    }
+                }
+            }
+            Capability::Pointer => {
+                if let Some(pointer) = state.pointer.take() {
+                    if pointer.version() >= 3 {
+                        pointer.release();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn remove_seat(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        seat: WlSeat,
+    ) {
+        let _ = self.seats.remove(&seat.id());
+    }
+}
+
+impl PointerHandler for State {
+    fn pointer_frame(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        pointer: &WlPointer,
+        events: &[PointerEvent],
+    ) {
+        let Some(seat) = pointer.data::<PointerData>().map(PointerData::seat)
+        else {
+            return;
+        };
+
+        for event in events {
+            if let PointerEventKind::Press { serial, .. } = event.kind {
+                if let Some(state) = self.seat_mut(seat) {
+                    state.serial = Some(serial);
+                }
+            }
+        }
+    }
+}
+
+impl Dispatch<WlKeyboard, ObjectId> for State {
+    fn event(
+        state: &mut Self,
+        _: &WlKeyboard,
+        event: wl_keyboard::Event,
+        seat: &ObjectId,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let (wl_keyboard::Event::Enter { serial, .. }
+        | wl_keyboard::Event::Key { serial, .. }) = event
+        else {
+            return;
+        };
+
+        if let Some(seat_state) = state.seats.get_mut(seat) {
+            seat_state.serial = Some(serial);
+            state.latest_seat = Some(seat.clone());
+        }
+    }
+}
+
+impl DataDeviceHandler for State {
+    fn enter(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+        _: f64,
+        _: f64,
+        _: &WlSurface,
+    ) {
+    }
+
+    fn leave(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+    ) {
+    }
+
+    fn motion(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+        _: f64,
+        _: f64,
+    ) {
+    }
+
+    fn selection(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+    ) {
+        self.changes.push(Kind::Standard);
+    }
+
+    fn drop_performed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+    ) {
+    }
+}
+
+impl DataOfferHandler for State {
+    fn source_actions(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+
+    fn selected_action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+}
+
+impl DataSourceHandler for State {
+    fn accept_mime(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        mime: String,
+        pipe: WritePipe,
+    ) {
+        if let Some(selection) = self
+            .selection
+            .as_ref()
+            .filter(|selection| selection.source.inner() == source)
+        {
+            send(&selection.offers, &mime, pipe);
+        }
+    }
+
+    fn cancelled(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+    ) {
+        let _ = self
+            .selection
+            .take_if(|selection| selection.source.inner() == source);
+    }
+
+    fn dnd_dropped(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+    ) {
+    }
+
+    fn dnd_finished(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+    ) {
+    }
+
+    fn action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: DndAction,
+    ) {
+    }
+}
+
+impl PrimarySelectionDeviceHandler for State {
+    fn selection(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &ZwpPrimarySelectionDeviceV1,
+    ) {
+        self.changes.push(Kind::Primary);
+    }
+}
+
+impl PrimarySelectionSourceHandler for State {
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &ZwpPrimarySelectionSourceV1,
+        mime: String,
+        pipe: WritePipe,
+    ) {
+        if let Some(selection) = self
+            .primary_selection
+            .as_ref()
+            .filter(|selection| selection.source.inner() == source)
+        {
+            send(&selection.offers, &mime, pipe);
+        }
+    }
+
+    fn cancelled(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &ZwpPrimarySelectionSourceV1,
+    ) {
+        let _ = self
+            .primary_selection
+            .take_if(|selection| selection.source.inner() == source);
+    }
+}
+
+impl ProvidesRegistryState for State {
+    registry_handlers![SeatState];
+
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry
+    }
+}
+
+delegate_registry!(State);
+delegate_seat!(State);
+delegate_pointer!(State);
+delegate_data_device!(State);
+delegate_primary_selection!(State);
@@ -0,0 +1,553 @@
+//! Access the clipboard through the selections of the X server.
+use super::TIMEOUT;
+use crate::clipboard::Changes;
+use crate::core::clipboard::Kind;
+
+use rustc_hash::FxHashMap;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use x11rb::connection::{Connection, RequestConnection as _};
+use x11rb::errors::ReplyOrIdError;
+use x11rb::protocol::xfixes::{ConnectionExt as _, SelectionEventMask};
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ChangeWindowAttributesAux, ConnectionExt as _,
+    CreateWindowAux, EventMask, PropMode, Property, SelectionNotifyEvent,
+    SelectionRequestEvent, Window, WindowClass, SELECTION_NOTIFY_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+use x11rb::{COPY_DEPTH_FROM_PARENT, COPY_FROM_PARENT, CURRENT_TIME, NONE};
+
+x11rb::atom_manager! {
+    Atoms: AtomsCookie {
+        CLIPBOARD,
+        TARGETS,
+        INCR,
+        _ICED_CLIPBOARD,
+    }
+}
+
+/// A thread owning and converting selections through a hidden window on
+/// its own connection to the X server.
+pub struct Worker {
+    connection: Arc<RustConnection>,
+    atoms: Atoms,
+    window: Window,
+    state: Arc<Mutex<State>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    pub fn spawn(changes: Changes) -> Option<Self> {
+        let (connection, screen) = x11rb::connect(None)
+            .map_err(|error| {
+                log::warn!("Failed to connect to the X server: {error}");
+            })
+            .ok()?;
+
+        let connection = Arc::new(connection);
+        let root = connection.setup().roots[screen].root;
+
+        let (atoms, window) = create_window(&connection, root)
+            .map_err(|error| {
+                log::warn!("Failed to create clipboard window: {error}");
+            })
+            .ok()?;
+
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let thread = thread::Builder::new()
+            .name("iced clipboard".to_owned())
+            .spawn({
+                let connection = connection.clone();
+                let state = state.clone();
+
+                move || {
+                    let owner = Owner {
+                        connection: &connection,
+                        atoms: &atoms,
+                        window,
+                        changes: &changes,
+                    };
+
+                    owner.run(&state);
+                }
+            })
+            .map_err(|error| {
+                log::warn!("Failed to spawn clipboard thread: {error}");
+            })
+            .ok()?;
+
+        Some(Self {
+            connection,
+            atoms,
+            window,
+            state,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn formats(&self, kind: Kind) -> Vec<String> {
+        let Some(targets) = self.convert(kind, self.atoms.TARGETS) else {
+            return Vec::new();
+        };
+
+        let result = targets
+            .chunks_exact(4)
+            .map(|atom| {
+                u32::from_ne_bytes([atom[0], atom[1], atom[2], atom[3]])
+            })
+            .filter(|atom| *atom != self.atoms.TARGETS)
+            .map(|atom| {
+                let reply = self.connection.get_atom_name(atom)?.reply()?;
+
+                Ok(String::from_utf8_lossy(&reply.name).into_owned())
+            })
+            .collect::<Result<_, ReplyOrIdError>>();
+
+        result.unwrap_or_else(|error| {
+            log::warn!("Failed to read clipboard formats: {error}");
+
+            Vec::new()
+        })
+    }
+
+    pub fn receive(&self, kind: Kind, mime: &str) -> Option<Vec<u8>> {
+        let target = self.intern(mime)?;
+
+        self.convert(kind, target)
+    }
+
+    pub fn offer(&mut self, kind: Kind, offers: Vec<(String, Arc<[u8]>)>) {
+        let offers = offers
+            .into_iter()
+            .filter_map(|(mime, bytes)| Some((self.intern(&mime)?, bytes)))
+            .collect();
+
+        let selection = self.selection(kind);
+
+        let _ = self
+            .state
+            .lock()
+            .expect("Lock clipboard")
+            .selections
+            .insert(selection, offers);
+
+        let result = self
+            .connection
+            .set_selection_owner(self.window, selection, CURRENT_TIME)
+            .map(|_| ())
+            .and_then(|()| self.connection.flush());
+
+        if let Err(error) = result {
+            log::warn!("Failed to write to clipboard: {error}");
+        }
+    }
+
+    /// Converts the selection of the given [`Kind`] to the given target,
+    /// waiting for its owner to send the result.
+    fn convert(&self, kind: Kind, target: Atom) -> Option<Vec<u8>> {
+        let selection = self.selection(kind);
+        let (sender, receiver) = mpsc::channel();
+
+        self.state.lock().expect("Lock clipboard").request = Some(Request {
+            selection,
+            target,
+            sender,
+            transfer: None,
+        });
+
+        let result = self
+            .connection
+            .convert_selection(
+                self.window,
+                selection,
+                target,
+                self.atoms._ICED_CLIPBOARD,
+                CURRENT_TIME,
+            )
+            .map(|_| ())
+            .and_then(|()| self.connection.flush());
+
+        if let Err(error) = result {
+            log::warn!("Failed to read clipboard: {error}");
+            return None;
+        }
+
+        receiver.recv_timeout(TIMEOUT).ok().flatten()
+    }
+
+    fn intern(&self, name: &str) -> Option<Atom> {
+        let atom = self
+            .connection
+            .intern_atom(false, name.as_bytes())
+            .map_err(ReplyOrIdError::from)
+            .and_then(|cookie| Ok(cookie.reply()?.atom));
+
+        atom.map_err(|error| {
+            log::warn!("Failed to intern clipboard format: {error}");
+        })
+        .ok()
+    }
+
+    fn selection(&self, kind: Kind) -> Atom {
+        match kind {
+            Kind::Standard => self.atoms.CLIPBOARD,
+            Kind::Primary => AtomEnum::PRIMARY.into(),
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        // Destroying the window stops the worker
+        let _ = self.connection.destroy_window(self.window);
+        let _ = self.connection.flush();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    selections: FxHashMap<Atom, Vec<(Atom, Arc<[u8]>)>>,
+    request: Option<Request>,
+    transfers: FxHashMap<(Window, Atom), Transfer>,
+}
+
+/// A pending conversion of a selection.
+struct Request {
+    selection: Atom,
+    target: Atom,
+    sender: mpsc::Sender<Option<Vec<u8>>>,
+    transfer: Option<Vec<u8>>,
+}
+
+/// An incremental transfer of some owned contents to a requestor.
+struct Transfer {
+    target: Atom,
+    bytes: Arc<[u8]>,
+    offset: usize,
+}
+
+struct Owner<'a> {
+    connection: &'a RustConnection,
+    atoms: &'a Atoms,
+    window: Window,
+    changes: &'a Changes,
+}
+
+impl Owner<'_> {
+    fn run(&self, state: &Mutex<State>) {
+        loop {
+            let event = match self.connection.wait_for_event() {
+                Ok(Event::DestroyNotify(event))
+                    if event.window == self.window =>
+                {
+                    return;
+                }
+                Ok(event) => event,
+                Err(error) => {
+                    log::warn!("Lost connection of clipboard: {error}");
+                    return;
+                }
+            };
+
+            let mut state = state.lock().expect("Lock clipboard");
+
+            if let Err(error) = self.handle(&mut state, event) {
+                log::warn!("Failed to handle clipboard event: {error}");
+            }
+        }
+    }
+
+    fn handle(
+        &self,
+        state: &mut State,
+        event: Event,
+    ) -> Result<(), ReplyOrIdError> {
+        match event {
+            Event::SelectionRequest(request) => {
+                self.send(state, &request)?;
+            }
+            Event::SelectionClear(event) if event.owner == self.window => {
+                let _ = state.selections.remove(&event.selection);
+            }
+            Event::XfixesSelectionNotify(event) => {
+                if event.selection == self.atoms.CLIPBOARD {
+                    self.changes.push(Kind::Standard);
+                } else if event.selection == u32::from(AtomEnum::PRIMARY) {
+                    self.changes.push(Kind::Primary);
+                }
+            }
+            Event::SelectionNotify(event) if event.requestor == self.window => {
+                let Some(request) = state.request.take_if(|request| {
+                    request.selection == event.selection
+                        && request.target == event.target
+                }) else {
+                    return Ok(());
+                };
+
+                if event.property == NONE {
+                    let _ = request.sender.send(None);
+                    return Ok(());
+                }
+
+                let reply = self
+                    .connection
+                    .get_property(
+                        true,
+                        self.window,
+                        event.property,
+                        AtomEnum::ANY,
+                        0,
+                        u32::MAX,
+                    )?
+                    .reply()?;
+
+                if reply.type_ == self.atoms.INCR {
+                    // Deleting the property starts the incremental transfer
+                    state.request = Some(Request {
+                        transfer: Some(Vec::new()),
+                        ..request
+                    });
+                } else {
+                    let _ = request.sender.send(Some(reply.value));
+                }
+            }
+            Event::PropertyNotify(event)
+                if event.window == self.window
+                    && event.atom == self.atoms._ICED_CLIPBOARD
+                    && event.state == Property::NEW_VALUE =>
+            {
+                let Some(request) = &mut state.request else {
+                    return Ok(());
+                };
+
+                let Some(transfer) = &mut request.transfer else {
+                    return Ok(());
+                };
+
+                let reply = self
+                    .connection
+                    .get_property(
+                        true,
+                        self.window,
+                        event.atom,
+                        AtomEnum::ANY,
+                        0,
+                        u32::MAX,
+                    )?
+                    .reply()?;
+
+                if !reply.value.is_empty() {
+                    transfer.extend(reply.value);
+                } else if let Some(request) = state.request.take() {
+                    let _ = request.sender.send(request.transfer);
+                }
+            }
+            Event::PropertyNotify(event) if event.state == Property::DELETE => {
+                self.resume(state, event.window, event.atom)?;
+            }
+            _ => {}
+        }
+
+        self.connection.flush()?;
+
+        Ok(())
+    }
+
+    /// Sends the owned contents requested to the requestor.
+    fn send(
+        &self,
+        state: &mut State,
+        request: &SelectionRequestEvent,
+    ) -> Result<(), ReplyOrIdError> {
+        let property = if request.property == NONE {
+            request.target
+        } else {
+            request.property
+        };
+
+        let offers = state.selections.get(&request.selection);
+
+        let property = if offers.is_none() {
+            NONE
+        } else if request.target == self.atoms.TARGETS {
+            let mut targets: Vec<Atom> = offers
+                .into_iter()
+                .flatten()
+                .map(|(target, _)| *target)
+                .collect();
+
+            targets.push(self.atoms.TARGETS);
+
+            let _ = self.connection.change_property32(
+                PropMode::REPLACE,
+                request.requestor,
+                property,
+                AtomEnum::ATOM,
+                &targets,
+            )?;
+
+            property
+        } else if let Some((target, bytes)) = offers
+            .into_iter()
+            .flatten()
+            .find(|(target, _)| *target == request.target)
+        {
+            if bytes.len() > self.chunk_size() {
+                // Announce an incremental transfer and wait for the
+                // requestor to delete the property
+                let _ = self.connection.change_window_attributes(
+                    request.requestor,
+                    &ChangeWindowAttributesAux::new()
+                        .event_mask(EventMask::PROPERTY_CHANGE),
+                )?;
+
+                let _ = self.connection.change_property32(
+                    PropMode::REPLACE,
+                    request.requestor,
+                    property,
+                    self.atoms.INCR,
+                    &[u32::try_from(bytes.len()).unwrap_or(u32::MAX)],
+                )?;
+
+                let _ = state.transfers.insert(
+                    (request.requestor, property),
+                    Transfer {
+                        target: *target,
+                        bytes: bytes.clone(),
+                        offset: 0,
+                    },
+                );
+            } else {
+                let _ = self.connection.change_property8(
+                    PropMode::REPLACE,
+                    request.requestor,
+                    property,
+                    *target,
+                    bytes,
+                )?;
+            }
+
+            property
+        } else {
+            NONE
+        };
+
+        let _ = self.connection.send_event(
+            false,
+            request.requestor,
+            EventMask::NO_EVENT,
+            SelectionNotifyEvent {
+                response_type: SELECTION_NOTIFY_EVENT,
+                sequence: 0,
+                time: request.time,
+                requestor: request.requestor,
+                selection: request.selection,
+                target: request.target,
+                property,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Sends the next chunk of an incremental transfer, if any.
+    fn resume(
+        &self,
+        state: &mut State,
+        requestor: Window,
+        property: Atom,
+    ) -> Result<(), ReplyOrIdError> {
+        let Some(transfer) = state.transfers.get_mut(&(requestor, property))
+        else {
+            return Ok(());
+        };
+
+        let end =
+            (transfer.offset + self.chunk_size()).min(transfer.bytes.len());
+        let chunk = &transfer.bytes[transfer.offset..end];
+
+        // An empty chunk ends the transfer
+        let _ = self.connection.change_property8(
+            PropMode::REPLACE,
+            requestor,
+            property,
+            transfer.target,
+            chunk,
+        )?;
+
+        if chunk.is_empty() {
+            let _ = state.transfers.remove(&(requestor, property));
+        } else {
+            transfer.offset = end;
+        }
+
+        Ok(())
+    }
+
+    fn chunk_size(&self) -> usize {
+        self.connection.maximum_request_bytes() / 4
+    }
+}
+
+/// Creates the hidden window that owns and converts selections and is
+/// notified of their changes.
+fn create_window(
+    connection: &RustConnection,
+    root: Window,
+) -> Result<(Atoms, Window), ReplyOrIdError> {
+    let atoms = Atoms::new(connection)?.reply()?;
+    let window = connection.generate_id()?;
+
+    let _ = connection.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        window,
+        root,
+        -1,
+        -1,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_ONLY,
+        COPY_FROM_PARENT,
+        &CreateWindowAux::new().override_redirect(1).event_mask(
+            EventMask::PROPERTY_CHANGE | EventMask::STRUCTURE_NOTIFY,
+        ),
+    )?;
+
+    if let Err(error) = watch(connection, &atoms, window) {
+        log::warn!("Failed to watch clipboard changes: {error}");
+    }
+
+    connection.flush()?;
+
+    Ok((atoms, window))
+}
+
+/// Selects the owner changes of the selections with the XFixes extension.
+fn watch(
+    connection: &RustConnection,
+    atoms: &Atoms,
+    window: Window,
+) -> Result<(), ReplyOrIdError> {
+    let _ = connection.xfixes_query_version(5, 0)?.reply()?;
+
+    for selection in [atoms.CLIPBOARD, AtomEnum::PRIMARY.into()] {
+        let _ = connection.xfixes_select_selection_input(
+            window,
+            selection,
+            SelectionEventMask::SET_SELECTION_OWNER
+                | SelectionEventMask::SELECTION_WINDOW_DESTROY
+                | SelectionEventMask::SELECTION_CLIENT_CLOSE,
+        )?;
+    }
+
+    Ok(())
+}
@@ -2,6 +2,7 @@
 //!
 //! [`winit`]: https://github.com/rust-windowing/winit
 //! [`iced_runtime`]: https://github.com/iced-rs/iced/tree/0.13/runtime
+use crate::core::input_method;
 use crate::core::keyboard;
 use crate::core::mouse;
 use crate::core::touch;
@@ -294,6 +295,9 @@ pub fn window_event(
         WindowEvent::Touch(touch) => {
             Some(Event::Touch(touch_event(touch, scale_factor)))
         }
+        WindowEvent::Ime(ime) => {
+            Some(Event::InputMethod(input_method_event(ime)))
+        }
         WindowEvent::Moved(position) => {
             let winit::dpi::LogicalPosition { x, y } =
                 position.to_logical(scale_factor);
@@ -304,6 +308,28 @@ pub fn window_event(
     }
 }
 
+/// Converts a drag and drop event of a window into an iced window event.
+pub fn dnd_event(event: crate::dnd::Event, scale_factor: f64) -> window::Event {
+    use crate::dnd::Event;
+
+    match event {
+        Event::DataHovered { position, offer } => window::Event::DataHovered {
+            position: cursor_position(position, scale_factor),
+            offer,
+        },
+        Event::DataDropped { position, payload } => {
+            window::Event::DataDropped {
+                position: cursor_position(position, scale_factor),
+                payload,
+            }
+        }
+        Event::DataHoveredLeft => window::Event::DataHoveredLeft,
+        Event::FileHovered(path) => window::Event::FileHovered(path),
+        Event::FileDropped(path) => window::Event::FileDropped(path),
+        Event::FilesHoveredLeft => window::Event::FilesHoveredLeft,
+    }
+}
+
 /// Converts a [`window::Level`] to a [`winit`] window level.
 ///
 /// [`winit`]: https://github.com/rust-windowing/winit
@@ -503,6 +529,26 @@ pub fn cursor_position(
     Point::new(logical_position.x, logical_position.y)
 }
 
+/// Converts an `Ime` event from [`winit`] to an [`iced`] input method event.
+///
+/// [`winit`]: https://github.com/rust-windowing/winit
+/// [`iced`]: https://github.com/iced-rs/iced/tree/0.13
+pub fn input_method_event(ime: winit::event::Ime) -> input_method::Event {
+    match ime {
+        winit::event::Ime::Enabled => input_method::Event::Opened,
+        winit::event::Ime::Preedit(content, selection) => {
+            input_method::Event::Preedit(
+                content,
+                selection.map(|(start, end)| start..end),
+            )
+        }
+        winit::event::Ime::Commit(content) => {
+            input_method::Event::Commit(content)
+        }
+        winit::event::Ime::Disabled => input_method::Event::Closed,
+    }
+}
+
 /// Converts a `Touch` from [`winit`] to an [`iced`] touch event.
 ///
 /// [`winit`]: https://github.com/rust-windowing/winit
@@ -0,0 +1,244 @@
+//! Drag and drop data between applications.
+#[cfg(all(
+    feature = "dnd",
+    any(feature = "x11", feature = "wayland"),
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+#[path = "dnd/unix.rs"]
+mod platform;
+
+#[cfg(all(feature = "dnd", target_os = "windows"))]
+#[path = "dnd/windows.rs"]
+mod platform;
+
+#[cfg(all(feature = "dnd", target_os = "macos"))]
+#[path = "dnd/macos.rs"]
+mod platform;
+
+#[cfg(not(all(
+    feature = "dnd",
+    any(
+        all(
+            any(feature = "x11", feature = "wayland"),
+            any(
+                target_os = "linux",
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            )
+        ),
+        target_os = "windows",
+        target_os = "macos"
+    )
+)))]
+#[path = "dnd/unsupported.rs"]
+mod platform;
+
+use crate::core::dnd::{Data, Offer, Outcome, Payload, Response};
+use crate::futures::futures::channel::oneshot;
+
+use std::mem;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use winit::dpi::PhysicalPosition;
+use winit::window::{Window, WindowId};
+
+/// The drag and drop capabilities of the windows of an application.
+#[allow(missing_debug_implementations)]
+pub struct DragAndDrop {
+    state: State,
+    queue: Queue,
+}
+
+/// A drag and drop event of a window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Some data is being dragged over the window.
+    DataHovered {
+        /// The position of the cursor, in physical coordinates relative to
+        /// the window.
+        position: PhysicalPosition<f64>,
+        /// The formats of the data being dragged.
+        offer: Offer,
+    },
+
+    /// Some data has been dropped into the window.
+    DataDropped {
+        /// The position of the cursor, in physical coordinates relative to
+        /// the window.
+        position: PhysicalPosition<f64>,
+        /// The dropped data.
+        payload: Payload,
+    },
+
+    /// Some data was hovered, but has exited the window.
+    DataHoveredLeft,
+
+    /// A file is being hovered over the window.
+    FileHovered(PathBuf),
+
+    /// A file has been dropped into the window.
+    FileDropped(PathBuf),
+
+    /// A file was hovered, but has exited the window.
+    FilesHoveredLeft,
+}
+
+enum State {
+    Connected {
+        platform: platform::Platform,
+        // Held until drop, since the platform may share the display
+        // connection of the window.
+        //
+        // Note that the field ordering is load-bearing.
+        window: Arc<Window>,
+    },
+    Unavailable,
+}
+
+impl DragAndDrop {
+    /// Creates a new [`DragAndDrop`] for the display of the given window.
+    ///
+    /// The window is not registered as a drop target. See
+    /// [`DragAndDrop::register`].
+    pub fn connect(window: Arc<Window>) -> DragAndDrop {
+        let queue = Queue::default();
+
+        // SAFETY: The display of the window will stay alive throughout the
+        // entire lifetime of the platform, because we hold the `Arc<Window>`
+        // together with `State`, and enum variant fields get dropped in
+        // declaration order.
+        #[allow(unsafe_code)]
+        let platform =
+            unsafe { platform::Platform::connect(&window, queue.clone()) };
+
+        let state = match platform {
+            Some(platform) => State::Connected { platform, window },
+            None => State::Unavailable,
+        };
+
+        DragAndDrop { state, queue }
+    }
+
+    /// Creates a new [`DragAndDrop`] that isn't associated with a window.
+    ///
+    /// Any drag started with it will be unsupported.
+    pub fn unconnected() -> DragAndDrop {
+        DragAndDrop {
+            state: State::Unavailable,
+            queue: Queue::default(),
+        }
+    }
+
+    /// Registers the given window as a drop target.
+    ///
+    /// Windows are woken up with a redraw request whenever they produce new
+    /// [`Event`]s.
+    pub fn register(&mut self, window: Arc<Window>) {
+        if let State::Connected { platform, .. } = &mut self.state {
+            platform.register(window);
+        }
+    }
+
+    /// Unregisters the window with the given identifier as a drop target.
+    pub fn unregister(&mut self, window: WindowId) {
+        if let State::Connected { platform, .. } = &mut self.state {
+            platform.unregister(window);
+        }
+    }
+
+    /// Responds to the latest [`Event::DataHovered`] of the given window.
+    ///
+    /// The [preferred] format of the [`Offer`] is accepted when there is no
+    /// [`Response`].
+    ///
+    /// [preferred]: Offer::preferred
+    pub fn respond(&mut self, window: WindowId, response: Option<Response>) {
+        if let State::Connected { platform, .. } = &mut self.state {
+            platform.respond(window, response);
+        }
+    }
+
+    /// Returns the pending [`Event`]s of the registered windows.
+    pub fn events(&mut self) -> Vec<(WindowId, Event)> {
+        self.queue.drain()
+    }
+
+    /// Starts dragging the given [`Data`] out of the given window, sending
+    /// the [`Outcome`] of the drag through the channel once it is over.
+    pub fn start_drag(
+        &mut self,
+        window: &Window,
+        data: Data,
+        channel: oneshot::Sender<Outcome>,
+    ) {
+        match &mut self.state {
+            State::Connected { platform, .. } => {
+                platform.start_drag(window, data, channel);
+            }
+            State::Unavailable => {
+                let _ = channel.send(Outcome::Unsupported);
+            }
+        }
+    }
+
+    /// Returns the identifier of the window used to create the
+    /// [`DragAndDrop`], if any.
+    pub fn window_id(&self) -> Option<WindowId> {
+        match &self.state {
+            State::Connected { window, .. } => Some(window.id()),
+            State::Unavailable => None,
+        }
+    }
+}
+
+/// The pending events of the registered windows.
+#[derive(Debug, Clone, Default)]
+struct Queue {
+    events: Arc<Mutex<Vec<(WindowId, Event)>>>,
+}
+
+impl Queue {
+    /// Pushes an [`Event`] for the given window and wakes it up.
+    #[allow(dead_code)] // Unused on platforms without drop targets
+    fn push(&self, window: &Window, event: Event) {
+        self.events
+            .lock()
+            .expect("Lock drag and drop events")
+            .push((window.id(), event));
+
+        window.request_redraw();
+    }
+
+    fn drain(&self) -> Vec<(WindowId, Event)> {
+        mem::take(&mut *self.events.lock().expect("Lock drag and drop events"))
+    }
+}
+
+/// Encodes the given RGBA pixels as a PNG image.
+#[cfg(feature = "dnd")]
+#[allow(dead_code)] // Unused on platforms without outgoing drags
+pub(crate) fn encode_png(
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+) -> Result<Vec<u8>, png::EncodingError> {
+    let mut png = Vec::new();
+
+    {
+        let mut encoder = png::Encoder::new(&mut png, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder.write_header()?.write_image_data(pixels)?;
+    }
+
+    Ok(png)
+}
@@ -0,0 +1,355 @@
+//! Outgoing drags through the dragging sessions of AppKit.
+//!
+//! Windows are registered as drop targets for files by winit itself, so
+//! only outgoing drags are handled here.
+use crate::core::dnd::{Data, Outcome, Response};
+use crate::dnd::{self, Queue};
+use crate::futures::futures::channel::oneshot;
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::sync::Arc;
+use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use winit::window::{Window, WindowId};
+
+use objc2::rc::Retained;
+use objc2::runtime::{NSObject, NSObjectProtocol, ProtocolObject};
+use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
+use objc2_app_kit::{
+    NSDragOperation, NSDraggingContext, NSDraggingItem, NSDraggingSession,
+    NSDraggingSource, NSEvent, NSEventModifierFlags, NSEventType, NSImage,
+    NSPasteboardItem, NSPasteboardTypeFileURL, NSPasteboardTypePNG,
+    NSPasteboardTypeString, NSView,
+};
+use objc2_foundation::{
+    MainThreadMarker, NSArray, NSData, NSPoint, NSProcessInfo, NSRect, NSSize,
+    NSString, NSURL,
+};
+
+/// The maximum width or height of the preview of a dragged image, in points.
+const MAX_PREVIEW_SIZE: f64 = 128.0;
+
+/// The size of the dragging frame of data without a preview, in points.
+const FRAME_SIZE: f64 = 32.0;
+
+pub struct Platform {
+    /// The sources of the ongoing drags, which are not retained by their
+    /// sessions.
+    sources: Vec<Retained<DragSource>>,
+}
+
+impl Platform {
+    #[allow(unsafe_code)]
+    pub unsafe fn connect(_window: &Window, _queue: Queue) -> Option<Self> {
+        Some(Self {
+            sources: Vec::new(),
+        })
+    }
+
+    pub fn register(&mut self, _window: Arc<Window>) {}
+
+    pub fn unregister(&mut self, _window: WindowId) {}
+
+    pub fn respond(&mut self, _window: WindowId, _response: Option<Response>) {}
+
+    #[allow(unsafe_code)]
+    pub fn start_drag(
+        &mut self,
+        window: &Window,
+        data: Data,
+        channel: oneshot::Sender<Outcome>,
+    ) {
+        self.sources.retain(|source| !source.is_over());
+
+        let Some(mtm) = MainThreadMarker::new() else {
+            let _ = channel.send(Outcome::Unsupported);
+            return;
+        };
+
+        let Some(view) = view(window) else {
+            let _ = channel.send(Outcome::Unsupported);
+            return;
+        };
+
+        let Some((event, location)) = drag_event(&view) else {
+            let _ = channel.send(Outcome::Cancelled);
+            return;
+        };
+
+        let items = items(data, location);
+
+        if items.is_empty() {
+            let _ = channel.send(Outcome::Cancelled);
+            return;
+        }
+
+        let source = DragSource::new(channel, mtm);
+
+        // SAFETY: The session runs on the main thread along with the view,
+        // and the source is kept alive until the session ends.
+        let _session = unsafe {
+            view.beginDraggingSessionWithItems_event_source(
+                &NSArray::from_vec(items),
+                &event,
+                ProtocolObject::from_ref(&*source),
+            )
+        };
+
+        self.sources.push(source);
+    }
+}
+
+/// Returns the content view of the given window.
+#[allow(unsafe_code)]
+fn view(window: &Window) -> Option<Retained<NSView>> {
+    let RawWindowHandle::AppKit(handle) = window.window_handle().ok()?.as_raw()
+    else {
+        return None;
+    };
+
+    // SAFETY: The view of the window is alive while the window is.
+    unsafe { Retained::retain(handle.ns_view.as_ptr().cast::<NSView>()) }
+}
+
+/// Returns a mouse event that starts a drag at the current location of the
+/// cursor, along with the location in the coordinates of the given view.
+///
+/// Drags are started asynchronously, so the event that triggered them may
+/// no longer be the current one.
+#[allow(unsafe_code)]
+fn drag_event(view: &NSView) -> Option<(Retained<NSEvent>, NSPoint)> {
+    let window = view.window()?;
+
+    // SAFETY: The window is alive and only used from the main thread.
+    unsafe {
+        let location = window.mouseLocationOutsideOfEventStream();
+
+        let event = NSEvent::mouseEventWithType_location_modifierFlags_timestamp_windowNumber_context_eventNumber_clickCount_pressure(
+            NSEventType::LeftMouseDragged,
+            location,
+            NSEventModifierFlags::empty(),
+            NSProcessInfo::processInfo().systemUptime(),
+            window.windowNumber(),
+            None,
+            0,
+            1,
+            1.0,
+        )?;
+
+        Some((event, view.convertPoint_fromView(location, None)))
+    }
+}
+
+/// Returns the dragging items of the given [`Data`], centered on the given
+/// location.
+#[allow(unsafe_code)]
+fn items(data: Data, location: NSPoint) -> Vec<Retained<NSDraggingItem>> {
+    let item = |pasteboard_item: &NSPasteboardItem,
+                size: NSSize,
+                preview: Option<&NSImage>| {
+        // SAFETY: The pasteboard item conforms to `NSPasteboardWriting`, and
+        // the preview, if any, is an image as expected.
+        unsafe {
+            let item = NSDraggingItem::initWithPasteboardWriter(
+                NSDraggingItem::alloc(),
+                ProtocolObject::from_ref(pasteboard_item),
+            );
+
+            item.setDraggingFrame_contents(
+                NSRect::new(
+                    NSPoint::new(
+                        location.x - size.width / 2.0,
+                        location.y - size.height / 2.0,
+                    ),
+                    size,
+                ),
+                preview.map(|preview| preview.as_ref()),
+            );
+
+            item
+        }
+    };
+
+    let frame_size = NSSize::new(FRAME_SIZE, FRAME_SIZE);
+
+    match data {
+        Data::Files(paths) => paths
+            .iter()
+            .filter_map(|path| file_url(path))
+            .map(|url| {
+                // SAFETY: The type is a valid pasteboard type.
+                let pasteboard_item = unsafe {
+                    let pasteboard_item = NSPasteboardItem::new();
+                    let _ = pasteboard_item
+                        .setString_forType(&url, NSPasteboardTypeFileURL);
+
+                    pasteboard_item
+                };
+
+                item(&pasteboard_item, frame_size, None)
+            })
+            .collect(),
+        Data::Text(text) => {
+            // SAFETY: The type is a valid pasteboard type.
+            let pasteboard_item = unsafe {
+                let pasteboard_item = NSPasteboardItem::new();
+                let _ = pasteboard_item.setString_forType(
+                    &NSString::from_str(&text),
+                    NSPasteboardTypeString,
+                );
+
+                pasteboard_item
+            };
+
+            vec![item(&pasteboard_item, frame_size, None)]
+        }
+        Data::Image {
+            width,
+            height,
+            pixels,
+        } => match dnd::encode_png(width, height, &pixels) {
+            Ok(png) => {
+                let png = NSData::with_bytes(&png);
+
+                // SAFETY: The type is a valid pasteboard type.
+                let pasteboard_item = unsafe {
+                    let pasteboard_item = NSPasteboardItem::new();
+                    let _ = pasteboard_item
+                        .setData_forType(&png, NSPasteboardTypePNG);
+
+                    pasteboard_item
+                };
+
+                let preview = NSImage::initWithData(NSImage::alloc(), &png);
+                let (width, height) = preview_size(width, height);
+
+                vec![item(
+                    &pasteboard_item,
+                    NSSize::new(width, height),
+                    preview.as_deref(),
+                )]
+            }
+            Err(error) => {
+                log::warn!("Failed to encode dragged image: {error}");
+
+                Vec::new()
+            }
+        },
+    }
+}
+
+/// Returns the `file://` URL of the given path, if absolute.
+#[allow(unsafe_code)]
+fn file_url(path: &Path) -> Option<Retained<NSString>> {
+    if !path.is_absolute() {
+        log::warn!("Dragged file path is not absolute: {}", path.display());
+
+        return None;
+    }
+
+    let Some(path) = path.to_str() else {
+        log::warn!("Dragged file path is not valid UTF-8: {}", path.display());
+
+        return None;
+    };
+
+    // SAFETY: The path is a valid string.
+    unsafe {
+        NSURL::fileURLWithPath(&NSString::from_str(path)).absoluteString()
+    }
+}
+
+/// Returns the size of the preview of an image with the given dimensions,
+/// scaled down to fit [`MAX_PREVIEW_SIZE`].
+fn preview_size(width: u32, height: u32) -> (f64, f64) {
+    let (width, height) = (f64::from(width), f64::from(height));
+    let scale = (MAX_PREVIEW_SIZE / width.max(height)).min(1.0);
+
+    (width * scale, height * scale)
+}
+
+struct Ivars {
+    channel: RefCell<Option<oneshot::Sender<Outcome>>>,
+}
+
+declare_class!(
+    /// The source of a drag, which reports its [`Outcome`] once it ends.
+    struct DragSource;
+
+    // SAFETY:
+    // - The superclass NSObject does not have any subclassing requirements.
+    // - Dragging sources are only used from the main thread.
+    // - `DragSource` does not implement `Drop`.
+    unsafe impl ClassType for DragSource {
+        type Super = NSObject;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "IcedDragSource";
+    }
+
+    impl DeclaredClass for DragSource {
+        type Ivars = Ivars;
+    }
+
+    unsafe impl NSObjectProtocol for DragSource {}
+
+    unsafe impl NSDraggingSource for DragSource {
+        #[method(draggingSession:sourceOperationMaskForDraggingContext:)]
+        fn operation_mask(
+            &self,
+            _session: &NSDraggingSession,
+            _context: NSDraggingContext,
+        ) -> NSDragOperation {
+            NSDragOperation::Copy
+        }
+
+        #[method(draggingSession:endedAtPoint:operation:)]
+        fn ended(
+            &self,
+            _session: &NSDraggingSession,
+            _point: NSPoint,
+            operation: NSDragOperation,
+        ) {
+            let Some(channel) = self.ivars().channel.borrow_mut().take() else {
+                return;
+            };
+
+            let _ = channel.send(if operation.is_empty() {
+                Outcome::Cancelled
+            } else {
+                Outcome::Accepted
+            });
+        }
+    }
+);
+
+impl DragSource {
+    #[allow(unsafe_code)]
+    fn new(
+        channel: oneshot::Sender<Outcome>,
+        mtm: MainThreadMarker,
+    ) -> Retained<Self> {
+        let this = mtm.alloc::<Self>().set_ivars(Ivars {
+            channel: RefCell::new(Some(channel)),
+        });
+
+        // SAFETY: `NSObject` is initialized with `init`.
+        unsafe { msg_send_id![super(this), init] }
+    }
+
+    /// Returns whether the drag of the source has ended.
+    fn is_over(&self) -> bool {
+        self.ivars().channel.borrow().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews_are_scaled_down_to_fit() {
+        assert_eq!(preview_size(64, 32), (64.0, 32.0));
+        assert_eq!(preview_size(512, 256), (128.0, 64.0));
+        assert_eq!(preview_size(100, 400), (32.0, 128.0));
+    }
+}
@@ -0,0 +1,230 @@
+#[cfg(feature = "wayland")]
+mod wayland;
+
+#[cfg(feature = "x11")]
+mod x11;
+
+use crate::core::dnd::{Data, Offer, Outcome, Response};
+use crate::dnd::{self, Queue};
+use crate::futures::futures::channel::oneshot;
+
+use std::path::Path;
+use std::sync::Arc;
+use winit::window::{Window, WindowId};
+
+pub enum Platform {
+    #[cfg(feature = "wayland")]
+    Wayland(wayland::Worker),
+    #[cfg(feature = "x11")]
+    X11(x11::Worker),
+}
+
+impl Platform {
+    /// # Safety
+    /// The display of the window must outlive the [`Platform`].
+    #[allow(unsafe_code)]
+    pub unsafe fn connect(window: &Window, queue: Queue) -> Option<Self> {
+        use winit::raw_window_handle::{HasDisplayHandle, RawDisplayHandle};
+
+        match window.display_handle().ok()?.as_raw() {
+            #[cfg(feature = "wayland")]
+            RawDisplayHandle::Wayland(display) => {
+                // SAFETY: Upheld by the caller.
+                unsafe { wayland::Worker::spawn(display.display, queue) }
+                    .map(Self::Wayland)
+            }
+            #[cfg(feature = "x11")]
+            RawDisplayHandle::Xlib(_) | RawDisplayHandle::Xcb(_) => {
+                x11::Worker::spawn(queue).map(Self::X11)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn start_drag(
+        &mut self,
+        window: &Window,
+        data: Data,
+        channel: oneshot::Sender<Outcome>,
+    ) {
+        match self {
+            #[cfg(feature = "wayland")]
+            Self::Wayland(worker) => worker.start_drag(window, data, channel),
+            #[cfg(feature = "x11")]
+            Self::X11(_) => {
+                use winit::window::CursorGrabMode;
+
+                // The pressed button grabs the pointer for the window, which
+                // only its own connection can release; winit releases it
+                // before changing the grab mode
+                let _ = window.set_cursor_grab(CursorGrabMode::Confined);
+                let _ = window.set_cursor_grab(CursorGrabMode::None);
+
+                x11::start_drag(data, channel);
+            }
+        }
+    }
+
+    pub fn register(&mut self, window: Arc<Window>) {
+        match self {
+            #[cfg(feature = "wayland")]
+            Self::Wayland(worker) => worker.register(window),
+            #[cfg(feature = "x11")]
+            Self::X11(worker) => worker.register(window),
+        }
+    }
+
+    pub fn unregister(&mut self, window: WindowId) {
+        match self {
+            #[cfg(feature = "wayland")]
+            Self::Wayland(worker) => worker.unregister(window),
+            #[cfg(feature = "x11")]
+            Self::X11(worker) => worker.unregister(window),
+        }
+    }
+
+    pub fn respond(&mut self, window: WindowId, response: Option<Response>) {
+        match self {
+            #[cfg(feature = "wayland")]
+            Self::Wayland(worker) => worker.respond(window, response),
+            #[cfg(feature = "x11")]
+            Self::X11(worker) => worker.respond(window, response),
+        }
+    }
+}
+
+/// Returns the index of the format of the [`Offer`] accepted with the given
+/// [`Response`], if any.
+fn accepted(offer: &Offer, response: Option<&Response>) -> Option<usize> {
+    let format = match response {
+        Some(Response::Accept(format)) => format.as_str(),
+        Some(Response::Reject) => return None,
+        None => offer.preferred()?,
+    };
+
+    offer.formats().iter().position(|offered| offered == format)
+}
+
+/// Returns the MIME types and contents offered when dragging the given
+/// [`Data`].
+fn offers(data: Data) -> Vec<(&'static str, Arc<[u8]>)> {
+    match data {
+        Data::Files(paths) => {
+            let uris: String = paths
+                .iter()
+                .filter_map(|path| uri(path))
+                .map(|uri| uri + "\r\n")
+                .collect();
+
+            vec![("text/uri-list", Arc::from(uris.into_bytes()))]
+        }
+        Data::Text(text) => {
+            let text: Arc<[u8]> = Arc::from(text.into_bytes());
+
+            ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"]
+                .into_iter()
+                .map(|mime| (mime, text.clone()))
+                .collect()
+        }
+        Data::Image {
+            width,
+            height,
+            pixels,
+        } => match dnd::encode_png(width, height, &pixels) {
+            Ok(png) => vec![("image/png", Arc::from(png))],
+            Err(error) => {
+                log::warn!("Failed to encode dragged image: {error}");
+
+                Vec::new()
+            }
+        },
+    }
+}
+
+/// Returns the `file://` URI of the given path, if absolute.
+fn uri(path: &Path) -> Option<String> {
+    use std::fmt::Write as _;
+    use std::os::unix::ffi::OsStrExt;
+
+    if !path.is_absolute() {
+        log::warn!("Dragged file path is not absolute: {}", path.display());
+
+        return None;
+    }
+
+    let mut uri = String::from("file://");
+
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            let _ = write!(uri, "%{byte:02X}");
+        }
+    }
+
+    Some(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_are_offered_as_uri_list() {
+        let offers = offers(Data::files(["/tmp/a b.txt", "relative", "/ü"]));
+
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].0, "text/uri-list");
+        assert_eq!(
+            &*offers[0].1,
+            b"file:///tmp/a%20b.txt\r\nfile:///%C3%BC\r\n"
+        );
+    }
+
+    #[test]
+    fn text_is_offered_in_every_text_type() {
+        let offers = offers(Data::text("Hello"));
+
+        assert_eq!(
+            offers.iter().map(|(mime, _)| *mime).collect::<Vec<_>>(),
+            ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"]
+        );
+        assert!(offers.iter().all(|(_, contents)| &**contents == b"Hello"));
+    }
+
+    #[test]
+    fn images_are_offered_as_png() {
+        let pixels = vec![255, 0, 0, 255, 0, 0, 255, 128];
+        let offers = offers(Data::image(2, 1, pixels.clone()));
+
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].0, "image/png");
+
+        let mut reader = png::Decoder::new(&*offers[0].1)
+            .read_info()
+            .expect("Read PNG header");
+
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).expect("Decode PNG");
+
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(decoded, pixels);
+    }
+
+    #[test]
+    fn images_with_wrong_sizes_are_not_offered() {
+        assert!(offers(Data::image(2, 2, vec![0; 4])).is_empty());
+    }
+
+    #[test]
+    fn accepted_defaults_to_preferred_format() {
+        let offer = Offer::new(["image/png", "text/plain"]);
+
+        assert_eq!(accepted(&offer, None), Some(1));
+        assert_eq!(
+            accepted(&offer, Some(&Response::Accept("image/png".to_owned()))),
+            Some(0)
+        );
+        assert_eq!(accepted(&offer, Some(&Response::Reject)), None);
+    }
+}
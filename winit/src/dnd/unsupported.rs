@@ -0,0 +1,40 @@
+//! Outgoing drags are only implemented for X11, Wayland, Windows and macOS.
+//! On any other platform, the [`DragAndDrop`](crate::dnd::DragAndDrop) of a
+//! window is never connected; so every drag resolves with
+//! `Outcome::Unsupported`.
+use crate::core::dnd::{Data, Outcome, Response};
+use crate::dnd::Queue;
+use crate::futures::futures::channel::oneshot;
+
+use std::sync::Arc;
+use winit::window::{Window, WindowId};
+
+pub enum Platform {}
+
+impl Platform {
+    #[allow(unsafe_code)]
+    pub unsafe fn connect(_window: &Window, _queue: Queue) -> Option<Self> {
+        None
+    }
+
+    pub fn register(&mut self, _window: Arc<Window>) {
+        match *self {}
+    }
+
+    pub fn unregister(&mut self, _window: WindowId) {
+        match *self {}
+    }
+
+    pub fn respond(&mut self, _window: WindowId, _response: Option<Response>) {
+        match *self {}
+    }
+
+    pub fn start_drag(
+        &mut self,
+        _window: &Window,
+        _data: Data,
+        _channel: oneshot::Sender<Outcome>,
+    ) {
+        match *self {}
+    }
+}
@@ -0,0 +1,850 @@
+//! Drag and drop data with the data devices of the Wayland compositor.
+use crate::core::dnd::{Data, Offer, Outcome, Payload, Response};
+use crate::dnd::{Event, Queue};
+use crate::futures::futures::channel::oneshot;
+
+use rustc_hash::FxHashMap;
+use std::ffi::c_void;
+use std::io::{self, Read, Write};
+use std::ptr::NonNull;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use winit::dpi::{LogicalPosition, PhysicalPosition};
+use winit::window::{Window, WindowId};
+
+use sctk::data_device_manager::data_device::{
+    DataDevice, DataDeviceData, DataDeviceHandler,
+};
+use sctk::data_device_manager::data_offer::{DataOfferHandler, DragOffer};
+use sctk::data_device_manager::data_source::{DataSourceHandler, DragSource};
+use sctk::data_device_manager::{DataDeviceManagerState, WritePipe};
+use sctk::reexports::calloop::channel;
+use sctk::reexports::calloop::EventLoop;
+use sctk::reexports::calloop_wayland_source::WaylandSource;
+use sctk::reexports::client::backend::{Backend, ObjectId};
+use sctk::reexports::client::globals::registry_queue_init;
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_data_source::WlDataSource;
+use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::registry::{ProvidesRegistryState, RegistryState};
+use sctk::seat::pointer::{
+    PointerData, PointerEvent, PointerEventKind, PointerHandler,
+};
+use sctk::seat::{Capability, SeatHandler, SeatState};
+use sctk::{
+    delegate_data_device, delegate_pointer, delegate_registry, delegate_seat,
+    registry_handlers,
+};
+
+/// A thread dispatching its own event queue on the display connection of
+/// the application.
+pub struct Worker {
+    connection: Connection,
+    sender: channel::Sender<Command>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+enum Command {
+    StartDrag {
+        surface: WlSurface,
+        data: Data,
+        channel: oneshot::Sender<Outcome>,
+    },
+    Register {
+        surface: WlSurface,
+        window: Arc<Window>,
+    },
+    Unregister(WindowId),
+    Respond {
+        window: WindowId,
+        response: Option<Response>,
+    },
+    Received {
+        serial: u32,
+        is_dropped: bool,
+        result: io::Result<Vec<u8>>,
+    },
+    Exit,
+}
+
+impl Worker {
+    /// # Safety
+    /// The display must be a valid `wl_display` pointer that outlives the
+    /// [`Worker`].
+    #[allow(unsafe_code)]
+    pub unsafe fn spawn(
+        display: NonNull<c_void>,
+        events: Queue,
+    ) -> Option<Self> {
+        // SAFETY: Upheld by the caller.
+        let backend =
+            unsafe { Backend::from_foreign_display(display.as_ptr().cast()) };
+
+        let connection = Connection::from_backend(backend);
+        let (sender, receiver) = channel::channel();
+
+        let thread = thread::Builder::new()
+            .name("iced dnd".to_owned())
+            .spawn({
+                let connection = connection.clone();
+                let sender = sender.clone();
+
+                move || run(connection, sender, receiver, events)
+            })
+            .map_err(|error| {
+                log::warn!("Failed to spawn drag and drop thread: {error}");
+            })
+            .ok()?;
+
+        Some(Self {
+            connection,
+            sender,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn start_drag(
+        &mut self,
+        window: &Window,
+        data: Data,
+        channel: oneshot::Sender<Outcome>,
+    ) {
+        let Some(surface) = self.surface(window) else {
+            let _ = channel.send(Outcome::Unsupported);
+            return;
+        };
+
+        if let Err(mpsc::SendError(Command::StartDrag { channel, .. })) =
+            self.sender.send(Command::StartDrag {
+                surface,
+                data,
+                channel,
+            })
+        {
+            let _ = channel.send(Outcome::Cancelled);
+        }
+    }
+
+    pub fn register(&mut self, window: Arc<Window>) {
+        if let Some(surface) = self.surface(&window) {
+            let _ = self.sender.send(Command::Register { surface, window });
+        }
+    }
+
+    pub fn unregister(&mut self, window: WindowId) {
+        let _ = self.sender.send(Command::Unregister(window));
+    }
+
+    pub fn respond(&mut self, window: WindowId, response: Option<Response>) {
+        let _ = self.sender.send(Command::Respond { window, response });
+    }
+
+    fn surface(&self, window: &Window) -> Option<WlSurface> {
+        use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+        match window.window_handle().map(|handle| handle.as_raw()) {
+            Ok(RawWindowHandle::Wayland(handle)) => {
+                // SAFETY: The surface of the window is alive while we
+                // borrow the window.
+                #[allow(unsafe_code)]
+                let id = unsafe {
+                    ObjectId::from_ptr(
+                        WlSurface::interface(),
+                        handle.surface.as_ptr().cast(),
+                    )
+                };
+
+                id.ok().and_then(|id| {
+                    WlSurface::from_id(&self.connection, id).ok()
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let _ = self.sender.send(Command::Exit);
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run(
+    connection: Connection,
+    sender: channel::Sender<Command>,
+    receiver: channel::Channel<Command>,
+    events: Queue,
+) {
+    let Ok((globals, queue)) = registry_queue_init::<State>(&connection) else {
+        return;
+    };
+
+    let Ok(mut event_loop) = EventLoop::<State>::try_new() else {
+        return;
+    };
+
+    let queue_handle = queue.handle();
+    let loop_handle = event_loop.handle();
+
+    let mut state = State {
+        registry: RegistryState::new(&globals),
+        seat_state: SeatState::new(&globals, &queue_handle),
+        data_device_manager: DataDeviceManagerState::bind(
+            &globals,
+            &queue_handle,
+        )
+        .ok(),
+        seats: FxHashMap::default(),
+        latest_seat: None,
+        drag: None,
+        windows: FxHashMap::default(),
+        hover: None,
+        events,
+        sender,
+        queue_handle: queue_handle.clone(),
+        exit: false,
+    };
+
+    let commands =
+        loop_handle.insert_source(receiver, |event, _, state| match event {
+            channel::Event::Msg(Command::StartDrag {
+                surface,
+                data,
+                channel,
+            }) => {
+                state.start_drag(&surface, data, channel);
+            }
+            channel::Event::Msg(Command::Register { surface, window }) => {
+                let _ = state.windows.insert(surface.id(), window);
+            }
+            channel::Event::Msg(Command::Unregister(window)) => {
+                state.unregister(window);
+            }
+            channel::Event::Msg(Command::Respond { window, response }) => {
+                state.respond(window, response);
+            }
+            channel::Event::Msg(Command::Received {
+                serial,
+                is_dropped,
+                result,
+            }) => {
+                state.received(serial, is_dropped, result);
+            }
+            channel::Event::Msg(Command::Exit) | channel::Event::Closed => {
+                state.exit = true;
+            }
+        });
+
+    let source = WaylandSource::new(connection, queue).insert(loop_handle);
+
+    if commands.is_err() || source.is_err() {
+        return;
+    }
+
+    while !state.exit {
+        if event_loop.dispatch(None, &mut state).is_err() {
+            break;
+        }
+    }
+}
+
+struct State {
+    registry: RegistryState,
+    seat_state: SeatState,
+    data_device_manager: Option<DataDeviceManagerState>,
+    seats: FxHashMap<ObjectId, Seat>,
+    latest_seat: Option<ObjectId>,
+    drag: Option<Drag>,
+    windows: FxHashMap<ObjectId, Arc<Window>>,
+    hover: Option<Hover>,
+    events: Queue,
+    sender: channel::Sender<Command>,
+    queue_handle: QueueHandle<Self>,
+    exit: bool,
+}
+
+#[derive(Default)]
+struct Seat {
+    pointer: Option<WlPointer>,
+    data_device: Option<DataDevice>,
+    serial: Option<u32>,
+}
+
+struct Drag {
+    source: DragSource,
+    offers: Vec<(&'static str, Arc<[u8]>)>,
+    channel: oneshot::Sender<Outcome>,
+}
+
+struct Hover {
+    offer: DragOffer,
+    formats: Offer,
+    window: Arc<Window>,
+    position: PhysicalPosition<f64>,
+    accepted: Option<usize>,
+    uris: Option<Vec<u8>>,
+    is_dropped: bool,
+}
+
+impl State {
+    fn start_drag(
+        &mut self,
+        surface: &WlSurface,
+        data: Data,
+        channel: oneshot::Sender<Outcome>,
+    ) {
+        let Some(manager) = self
+            .data_device_manager
+            .as_ref()
+            .filter(|manager| manager.data_device_manager().version() >= 3)
+        else {
+            let _ = channel.send(Outcome::Unsupported);
+            return;
+        };
+
+        let seat = self
+            .latest_seat
+            .as_ref()
+            .and_then(|seat| self.seats.get(seat))
+            .and_then(|seat| Some((seat.data_device.as_ref()?, seat.serial?)));
+
+        let Some((data_device, serial)) = seat else {
+            log::warn!("No pointer press to start a drag from");
+
+            let _ = channel.send(Outcome::Cancelled);
+            return;
+        };
+
+        let offers = super::offers(data);
+
+        if offers.is_empty() {
+            let _ = channel.send(Outcome::Cancelled);
+            return;
+        }
+
+        let source = manager.create_drag_and_drop_source(
+            &self.queue_handle,
+            offers.iter().map(|(mime, _)| *mime),
+            DndAction::Copy,
+        );
+
+        source.start_drag(data_device, surface, None, serial);
+
+        if let Some(previous) = self.drag.replace(Drag {
+            source,
+            offers,
+            channel,
+        }) {
+            let _ = previous.channel.send(Outcome::Cancelled);
+        }
+    }
+
+    fn unregister(&mut self, window: WindowId) {
+        self.windows
+            .retain(|_, registered| registered.id() != window);
+
+        if let Some(hover) =
+            self.hover.take_if(|hover| hover.window.id() == window)
+        {
+            hover.offer.destroy();
+        }
+    }
+
+    fn respond(&mut self, window: WindowId, response: Option<Response>) {
+        let Some(hover) = self
+            .hover
+            .as_mut()
+            .filter(|hover| hover.window.id() == window && !hover.is_dropped)
+        else {
+            return;
+        };
+
+        hover.accepted = super::accepted(&hover.formats, response.as_ref());
+
+        let format = hover
+            .accepted
+            .map(|index| hover.formats.formats()[index].clone());
+
+        let action = if format.is_some() {
+            DndAction::Copy
+        } else {
+            DndAction::empty()
+        };
+
+        hover.offer.accept_mime_type(hover.offer.serial, format);
+        hover.offer.set_actions(action, action);
+    }
+
+    /// Receives the data of the offer in the given format on a separate
+    /// thread, since the pipe may block.
+    fn receive(&self, offer: &DragOffer, format: &str, is_dropped: bool) {
+        let serial = offer.serial;
+
+        let result = offer.receive(format.to_owned()).and_then(|mut pipe| {
+            let sender = self.sender.clone();
+
+            thread::Builder::new()
+                .name("iced dnd reader".to_owned())
+                .spawn(move || {
+                    let mut bytes = Vec::new();
+
+                    let result = pipe.read_to_end(&mut bytes).map(|_| bytes);
+
+                    let _ = sender.send(Command::Received {
+                        serial,
+                        is_dropped,
+                        result,
+                    });
+                })
+        });
+
+        if let Err(error) = result {
+            let _ = self.sender.send(Command::Received {
+                serial,
+                is_dropped,
+                result: Err(error),
+            });
+        }
+    }
+
+    fn received(
+        &mut self,
+        serial: u32,
+        is_dropped: bool,
+        result: io::Result<Vec<u8>>,
+    ) {
+        let Some(hover) = self
+            .hover
+            .as_mut()
+            .filter(|hover| hover.offer.serial == serial)
+        else {
+            return;
+        };
+
+        let bytes = match result {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                log::warn!("Failed to receive dragged data: {error}");
+
+                if is_dropped {
+                    self.leave();
+                }
+
+                return;
+            }
+        };
+
+        if !is_dropped {
+            if hover.uris.is_none() {
+                let payload = Payload::new(Offer::URI_LIST, bytes.clone());
+
+                for path in payload.paths() {
+                    self.events.push(&hover.window, Event::FileHovered(path));
+                }
+
+                hover.uris = Some(bytes);
+            }
+
+            return;
+        }
+
+        let Some(accepted) = hover.accepted else {
+            return;
+        };
+
+        let payload =
+            Payload::new(hover.formats.formats()[accepted].clone(), bytes);
+
+        for path in payload.paths() {
+            self.events.push(&hover.window, Event::FileDropped(path));
+        }
+
+        self.events.push(
+            &hover.window,
+            Event::DataDropped {
+                position: hover.position,
+                payload,
+            },
+        );
+
+        hover.offer.finish();
+        hover.offer.destroy();
+
+        self.hover = None;
+    }
+
+    /// Ends the current hover, notifying its window.
+    fn leave(&mut self) {
+        let Some(hover) = self.hover.take() else {
+            return;
+        };
+
+        if hover.uris.is_some() {
+            self.events.push(&hover.window, Event::FilesHoveredLeft);
+        }
+
+        self.events.push(&hover.window, Event::DataHoveredLeft);
+
+        if hover.is_dropped {
+            hover.offer.destroy();
+        }
+    }
+
+    fn finish_drag(&mut self, source: &WlDataSource, outcome: Outcome) {
+        if self
+            .drag
+            .as_ref()
+            .is_some_and(|drag| drag.source.inner() == source)
+        {
+            if let Some(drag) = self.drag.take() {
+                let _ = drag.channel.send(outcome);
+            }
+        }
+    }
+}
+
+impl SeatHandler for State {
+    fn seat_state(&mut self) -> &mut SeatState {
+        &mut self.seat_state
+    }
+
+    fn new_seat(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        seat: WlSeat,
+    ) {
+        let _ = self.seats.insert(seat.id(), Seat::default());
+    }
+
+    fn new_capability(
+        &mut self,
+        _: &Connection,
+        queue_handle: &QueueHandle<Self>,
+        seat: WlSeat,
+        capability: Capability,
+    ) {
+        if capability != Capability::Pointer {
+            return;
+        }
+
+        let state = self.seats.entry(seat.id()).or_default();
+
+        if state.pointer.is_none() {
+            state.pointer =
+                self.seat_state.get_pointer(queue_handle, &seat).ok();
+        }
+
+        if state.data_device.is_none() {
+            state.data_device = self
+                .data_device_manager
+                .as_ref()
+                .map(|manager| manager.get_data_device(queue_handle, &seat));
+        }
+    }
+
+    fn remove_capability(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        seat: WlSeat,
+        capability: Capability,
+    ) {
+        if capability != Capability::Pointer {
+            return;
+        }
+
+        if let Some(state) = self.seats.get_mut(&seat.id()) {
+            if let Some(pointer) = state.pointer.take() {
+                if pointer.version() >= 3 {
+                    pointer.release();
+                }
+            }
+
+            state.serial = None;
+        }
+    }
+
+    fn remove_seat(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        seat: WlSeat,
+    ) {
+        let _ = self.seats.remove(&seat.id());
+    }
+}
+
+impl PointerHandler for State {
+    fn pointer_frame(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        pointer: &WlPointer,
+        events: &[PointerEvent],
+    ) {
+        let Some(seat) = pointer.data::<PointerData>().map(PointerData::seat)
+        else {
+            return;
+        };
+
+        let Some(state) = self.seats.get_mut(&seat.id()) else {
+            return;
+        };
+
+        for event in events {
+            if let PointerEventKind::Press { serial, .. } = event.kind {
+                state.serial = Some(serial);
+                self.latest_seat = Some(seat.id());
+            }
+        }
+    }
+}
+
+impl DataDeviceHandler for State {
+    fn enter(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        data_device: &WlDataDevice,
+        x: f64,
+        y: f64,
+        surface: &WlSurface,
+    ) {
+        let Some(window) = self.windows.get(&surface.id()).cloned() else {
+            return;
+        };
+
+        let Some(offer) = data_device
+            .data::<DataDeviceData>()
+            .and_then(DataDeviceData::drag_offer)
+        else {
+            return;
+        };
+
+        let formats = Offer::new(offer.with_mime_types(<[String]>::to_vec));
+
+        // Receive the URIs early to notify of hovered files
+        if let Some(uris) = formats.uris() {
+            self.receive(&offer, uris, false);
+        }
+
+        let position =
+            LogicalPosition::new(x, y).to_physical(window.scale_factor());
+
+        self.events.push(
+            &window,
+            Event::DataHovered {
+                position,
+                offer: formats.clone(),
+            },
+        );
+
+        self.hover = Some(Hover {
+            offer,
+            formats,
+            window,
+            position,
+            accepted: None,
+            uris: None,
+            is_dropped: false,
+        });
+    }
+
+    fn leave(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+    ) {
+        // Dropped data is still being received
+        if self.hover.as_ref().is_some_and(|hover| !hover.is_dropped) {
+            self.leave();
+        }
+    }
+
+    fn motion(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+        x: f64,
+        y: f64,
+    ) {
+        let Some(hover) = &mut self.hover else {
+            return;
+        };
+
+        hover.position =
+            LogicalPosition::new(x, y).to_physical(hover.window.scale_factor());
+
+        self.events.push(
+            &hover.window,
+            Event::DataHovered {
+                position: hover.position,
+                offer: hover.formats.clone(),
+            },
+        );
+    }
+
+    fn selection(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+    ) {
+    }
+
+    fn drop_performed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+    ) {
+        let Some(hover) = &mut self.hover else {
+            return;
+        };
+
+        hover.is_dropped = true;
+
+        let Some(accepted) = hover.accepted else {
+            self.leave();
+            return;
+        };
+
+        let offer = hover.offer.clone();
+        let format = hover.formats.formats()[accepted].clone();
+
+        if format == Offer::URI_LIST {
+            if let Some(uris) = hover.uris.clone() {
+                self.received(offer.serial, true, Ok(uris));
+                return;
+            }
+        }
+
+        self.receive(&offer, &format, true);
+    }
+}
+
+impl DataOfferHandler for State {
+    fn source_actions(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+
+    fn selected_action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+}
+
+impl DataSourceHandler for State {
+    fn accept_mime(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        mime: String,
+        mut pipe: WritePipe,
+    ) {
+        let Some(contents) = self
+            .drag
+            .as_ref()
+            .filter(|drag| drag.source.inner() == source)
+            .and_then(|drag| {
+                drag.offers
+                    .iter()
+                    .find(|(offer, _)| *offer == mime)
+                    .map(|(_, contents)| contents.clone())
+            })
+        else {
+            return;
+        };
+
+        // Write on a separate thread, since the pipe may block
+        let writer = thread::Builder::new()
+            .name("iced dnd writer".to_owned())
+            .spawn(move || {
+                if let Err(error) = pipe.write_all(&contents) {
+                    log::warn!("Failed to send dragged data: {error}");
+                }
+            });
+
+        if let Err(error) = writer {
+            log::warn!("Failed to spawn drag and drop writer: {error}");
+        }
+    }
+
+    fn cancelled(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+    ) {
+        self.finish_drag(source, Outcome::Cancelled);
+    }
+
+    fn dnd_dropped(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+    ) {
+    }
+
+    fn dnd_finished(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+    ) {
+        self.finish_drag(source, Outcome::Accepted);
+    }
+
+    fn action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: DndAction,
+    ) {
+    }
+}
+
+impl ProvidesRegistryState for State {
+    registry_handlers![SeatState];
+
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry
+    }
+}
+
+delegate_registry!(State);
+delegate_seat!(State);
+delegate_pointer!(State);
+delegate_data_device!(State);
@@ -0,0 +1,475 @@
+//! Outgoing drags through OLE.
+//!
+//! Windows are registered as drop targets for files by winit itself, so
+//! only outgoing drags are handled here.
+use crate::core::dnd::{Data, Outcome, Response};
+use crate::dnd::{self, Queue};
+use crate::futures::futures::channel::oneshot;
+
+use std::mem::ManuallyDrop;
+use std::path::Path;
+use std::ptr;
+use std::sync::Arc;
+use winit::window::{Window, WindowId};
+
+use windows::Win32::Foundation::{
+    GlobalFree, BOOL, DATA_S_SAMEFORMATETC, DRAGDROP_S_CANCEL, DRAGDROP_S_DROP,
+    DRAGDROP_S_USEDEFAULTCURSORS, DV_E_FORMATETC, E_NOTIMPL,
+    OLE_E_ADVISENOTSUPPORTED, S_OK,
+};
+use windows::Win32::System::Com::{
+    IAdviseSink, IDataObject, IDataObject_Impl, IEnumFORMATETC, IEnumSTATDATA,
+    DATADIR_GET, DVASPECT_CONTENT, FORMATETC, STGMEDIUM, STGMEDIUM_0,
+    TYMED_HGLOBAL,
+};
+use windows::Win32::System::DataExchange::RegisterClipboardFormatW;
+use windows::Win32::System::Memory::{
+    GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE,
+};
+use windows::Win32::System::Ole::{
+    DoDragDrop, IDropSource, IDropSource_Impl, OleInitialize, OleUninitialize,
+    CF_DIB, CF_HDROP, CF_UNICODETEXT, DROPEFFECT, DROPEFFECT_COPY,
+    DROPEFFECT_NONE,
+};
+use windows::Win32::System::SystemServices::{
+    MK_LBUTTON, MK_MBUTTON, MK_RBUTTON, MODIFIERKEYS_FLAGS,
+};
+use windows::Win32::UI::Shell::SHCreateStdEnumFmtEtc;
+use windows_core::{implement, w, HRESULT};
+
+pub struct Platform;
+
+impl Platform {
+    #[allow(unsafe_code)]
+    pub unsafe fn connect(_window: &Window, _queue: Queue) -> Option<Self> {
+        Some(Self)
+    }
+
+    pub fn register(&mut self, _window: Arc<Window>) {}
+
+    pub fn unregister(&mut self, _window: WindowId) {}
+
+    pub fn respond(&mut self, _window: WindowId, _response: Option<Response>) {}
+
+    /// Drags the [`Data`] until it is dropped.
+    ///
+    /// OLE runs a modal loop until the drag is over, which keeps
+    /// dispatching the messages of every window of the thread.
+    pub fn start_drag(
+        &mut self,
+        _window: &Window,
+        data: Data,
+        channel: oneshot::Sender<Outcome>,
+    ) {
+        let _ = channel.send(drag(data));
+    }
+}
+
+#[allow(unsafe_code)]
+fn drag(data: Data) -> Outcome {
+    let formats: Vec<_> = contents(data)
+        .into_iter()
+        .filter_map(|(format, contents)| {
+            Some((format_etc(format.clipboard_format()?), contents))
+        })
+        .collect();
+
+    if formats.is_empty() {
+        return Outcome::Cancelled;
+    }
+
+    let data_object: IDataObject = DataObject { formats }.into();
+    let drop_source: IDropSource = DropSource.into();
+
+    let mut effect = DROPEFFECT_NONE;
+
+    // SAFETY: OLE is initialized for the current thread until the drag is
+    // over, and both objects outlive it.
+    let result = unsafe {
+        // winit initializes OLE for windows with drag and drop enabled, so
+        // this may only increase its reference count
+        let is_initialized = OleInitialize(None).is_ok();
+
+        let result = DoDragDrop(
+            &data_object,
+            &drop_source,
+            DROPEFFECT_COPY,
+            &mut effect,
+        );
+
+        if is_initialized {
+            OleUninitialize();
+        }
+
+        result
+    };
+
+    match result {
+        DRAGDROP_S_DROP if effect != DROPEFFECT_NONE => Outcome::Accepted,
+        DRAGDROP_S_DROP | DRAGDROP_S_CANCEL => Outcome::Cancelled,
+        error => {
+            log::warn!("Failed to drag data out: {error}");
+
+            Outcome::Cancelled
+        }
+    }
+}
+
+/// A format offered when dragging some [`Data`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// UTF-16 text, terminated by a null character.
+    Text,
+    /// A `DROPFILES` structure followed by a list of UTF-16 paths.
+    Files,
+    /// A PNG image.
+    Png,
+    /// A `BITMAPINFOHEADER` followed by bottom-up BGRA rows.
+    Bitmap,
+}
+
+impl Format {
+    /// Returns the identifier of the clipboard format, if registered.
+    #[allow(unsafe_code)]
+    fn clipboard_format(self) -> Option<u16> {
+        match self {
+            Format::Text => Some(CF_UNICODETEXT.0),
+            Format::Files => Some(CF_HDROP.0),
+            Format::Bitmap => Some(CF_DIB.0),
+            Format::Png => {
+                // SAFETY: The name of the format is a null-terminated string.
+                let format = unsafe { RegisterClipboardFormatW(w!("PNG")) };
+
+                u16::try_from(format).ok().filter(|format| *format != 0)
+            }
+        }
+    }
+}
+
+/// Returns the formats and contents offered when dragging the given
+/// [`Data`], by order of preference.
+fn contents(data: Data) -> Vec<(Format, Vec<u8>)> {
+    match data {
+        Data::Files(paths) => {
+            let mut files = drop_files_header();
+
+            for path in paths.iter().filter(|path| is_absolute(path)) {
+                files.extend(wide(path.as_os_str()));
+            }
+
+            // The list of paths ends with an empty one
+            files.extend([0, 0]);
+
+            vec![(Format::Files, files)]
+        }
+        Data::Text(text) => {
+            let text =
+                text.encode_utf16().chain([0]).flat_map(u16::to_le_bytes);
+
+            vec![(Format::Text, text.collect())]
+        }
+        Data::Image {
+            width,
+            height,
+            pixels,
+        } => match dnd::encode_png(width, height, &pixels) {
+            Ok(png) => vec![
+                (Format::Png, png),
+                (Format::Bitmap, bitmap(width, height, &pixels)),
+            ],
+            Err(error) => {
+                log::warn!("Failed to encode dragged image: {error}");
+
+                Vec::new()
+            }
+        },
+    }
+}
+
+/// Returns a `DROPFILES` structure with wide paths right after it.
+fn drop_files_header() -> Vec<u8> {
+    const SIZE: u32 = 20;
+
+    let mut header = Vec::with_capacity(SIZE as usize);
+
+    header.extend(SIZE.to_le_bytes()); // pFiles
+    header.extend([0; 8]); // pt
+    header.extend(0u32.to_le_bytes()); // fNC
+    header.extend(1u32.to_le_bytes()); // fWide
+
+    header
+}
+
+/// Returns the null-terminated UTF-16 bytes of the given string.
+fn wide(string: &std::ffi::OsStr) -> impl Iterator<Item = u8> + '_ {
+    use std::os::windows::ffi::OsStrExt;
+
+    string.encode_wide().chain([0]).flat_map(u16::to_le_bytes)
+}
+
+fn is_absolute(path: &Path) -> bool {
+    if !path.is_absolute() {
+        log::warn!("Dragged file path is not absolute: {}", path.display());
+    }
+
+    path.is_absolute()
+}
+
+/// Returns a device-independent bitmap of the given RGBA pixels, which must
+/// have the given dimensions.
+fn bitmap(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    const HEADER_SIZE: u32 = 40;
+
+    let mut bitmap = Vec::with_capacity(HEADER_SIZE as usize + pixels.len());
+
+    bitmap.extend(HEADER_SIZE.to_le_bytes()); // biSize
+    bitmap.extend(width.to_le_bytes()); // biWidth
+    bitmap.extend(height.to_le_bytes()); // biHeight
+    bitmap.extend(1u16.to_le_bytes()); // biPlanes
+    bitmap.extend(32u16.to_le_bytes()); // biBitCount
+    bitmap.extend(0u32.to_le_bytes()); // biCompression (BI_RGB)
+    bitmap.extend((pixels.len() as u32).to_le_bytes()); // biSizeImage
+    bitmap.extend([0; 16]); // Resolution and palette
+
+    // Positive heights are stored bottom-up
+    for row in pixels.chunks_exact(width as usize * 4).rev() {
+        for pixel in row.chunks_exact(4) {
+            bitmap.extend([pixel[2], pixel[1], pixel[0], pixel[3]]);
+        }
+    }
+
+    bitmap
+}
+
+fn format_etc(format: u16) -> FORMATETC {
+    FORMATETC {
+        cfFormat: format,
+        ptd: ptr::null_mut(),
+        dwAspect: DVASPECT_CONTENT.0,
+        lindex: -1,
+        tymed: TYMED_HGLOBAL.0 as u32,
+    }
+}
+
+/// The data being dragged, by clipboard format.
+#[implement(IDataObject)]
+struct DataObject {
+    formats: Vec<(FORMATETC, Vec<u8>)>,
+}
+
+impl DataObject {
+    /// Returns the contents of the requested format, if offered.
+    #[allow(unsafe_code)]
+    fn find(&self, format: *const FORMATETC) -> Option<&[u8]> {
+        // SAFETY: OLE passes either a valid format or a null pointer.
+        let format = unsafe { format.as_ref()? };
+
+        if format.dwAspect != DVASPECT_CONTENT.0
+            || format.tymed & TYMED_HGLOBAL.0 as u32 == 0
+        {
+            return None;
+        }
+
+        self.formats
+            .iter()
+            .find(|(offered, _)| offered.cfFormat == format.cfFormat)
+            .map(|(_, contents)| contents.as_slice())
+    }
+}
+
+#[allow(non_snake_case, unsafe_code)]
+impl IDataObject_Impl for DataObject_Impl {
+    fn GetData(
+        &self,
+        format: *const FORMATETC,
+    ) -> windows_core::Result<STGMEDIUM> {
+        let contents = self.find(format).ok_or(DV_E_FORMATETC)?;
+
+        // SAFETY: The memory is allocated with the size of the contents and
+        // stays locked while they are copied; its ownership is then passed
+        // to the receiver of the medium.
+        unsafe {
+            let global = GlobalAlloc(GMEM_MOVEABLE, contents.len())?;
+            let pointer = GlobalLock(global);
+
+            if pointer.is_null() {
+                let error = windows_core::Error::from_win32();
+                let _ = GlobalFree(global);
+
+                return Err(error);
+            }
+
+            ptr::copy_nonoverlapping(
+                contents.as_ptr(),
+                pointer.cast::<u8>(),
+                contents.len(),
+            );
+
+            // Fails when the memory is no longer locked, which is expected
+            let _ = GlobalUnlock(global);
+
+            Ok(STGMEDIUM {
+                tymed: TYMED_HGLOBAL.0 as u32,
+                u: STGMEDIUM_0 { hGlobal: global },
+                pUnkForRelease: ManuallyDrop::new(None),
+            })
+        }
+    }
+
+    fn GetDataHere(
+        &self,
+        _format: *const FORMATETC,
+        _medium: *mut STGMEDIUM,
+    ) -> windows_core::Result<()> {
+        Err(E_NOTIMPL.into())
+    }
+
+    fn QueryGetData(&self, format: *const FORMATETC) -> HRESULT {
+        if self.find(format).is_some() {
+            S_OK
+        } else {
+            DV_E_FORMATETC
+        }
+    }
+
+    fn GetCanonicalFormatEtc(
+        &self,
+        _format: *const FORMATETC,
+        canonical: *mut FORMATETC,
+    ) -> HRESULT {
+        // SAFETY: OLE passes either a valid format or a null pointer.
+        if let Some(canonical) = unsafe { canonical.as_mut() } {
+            canonical.ptd = ptr::null_mut();
+        }
+
+        DATA_S_SAMEFORMATETC
+    }
+
+    fn SetData(
+        &self,
+        _format: *const FORMATETC,
+        _medium: *const STGMEDIUM,
+        _release: BOOL,
+    ) -> windows_core::Result<()> {
+        Err(E_NOTIMPL.into())
+    }
+
+    fn EnumFormatEtc(
+        &self,
+        direction: u32,
+    ) -> windows_core::Result<IEnumFORMATETC> {
+        if direction != DATADIR_GET.0 as u32 {
+            return Err(E_NOTIMPL.into());
+        }
+
+        let formats: Vec<_> =
+            self.formats.iter().map(|(format, _)| *format).collect();
+
+        // SAFETY: The formats are copied by the enumerator.
+        unsafe { SHCreateStdEnumFmtEtc(&formats) }
+    }
+
+    fn DAdvise(
+        &self,
+        _format: *const FORMATETC,
+        _flags: u32,
+        _sink: Option<&IAdviseSink>,
+    ) -> windows_core::Result<u32> {
+        Err(OLE_E_ADVISENOTSUPPORTED.into())
+    }
+
+    fn DUnadvise(&self, _connection: u32) -> windows_core::Result<()> {
+        Err(OLE_E_ADVISENOTSUPPORTED.into())
+    }
+
+    fn EnumDAdvise(&self) -> windows_core::Result<IEnumSTATDATA> {
+        Err(OLE_E_ADVISENOTSUPPORTED.into())
+    }
+}
+
+/// Drops the data once every mouse button is released.
+#[implement(IDropSource)]
+struct DropSource;
+
+#[allow(non_snake_case)]
+impl IDropSource_Impl for DropSource_Impl {
+    fn QueryContinueDrag(
+        &self,
+        is_escape_pressed: BOOL,
+        keys: MODIFIERKEYS_FLAGS,
+    ) -> HRESULT {
+        let buttons = MK_LBUTTON.0 | MK_MBUTTON.0 | MK_RBUTTON.0;
+
+        if is_escape_pressed.as_bool() {
+            DRAGDROP_S_CANCEL
+        } else if keys.0 & buttons == 0 {
+            DRAGDROP_S_DROP
+        } else {
+            S_OK
+        }
+    }
+
+    fn GiveFeedback(&self, _effect: DROPEFFECT) -> HRESULT {
+        DRAGDROP_S_USEDEFAULTCURSORS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(bytes: &[u8]) -> Vec<u16> {
+        bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn text_is_offered_as_null_terminated_utf16() {
+        let contents = contents(Data::text("hé"));
+
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0].0, Format::Text);
+        assert_eq!(utf16(&contents[0].1), [u16::from(b'h'), 0xE9, 0]);
+    }
+
+    #[test]
+    fn files_are_offered_as_wide_drop_files() {
+        let contents = contents(Data::files([r"C:\a.txt", "relative"]));
+
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0].0, Format::Files);
+
+        let (header, paths) = contents[0].1.split_at(20);
+
+        assert_eq!(&header[..4], &20u32.to_le_bytes());
+        assert_eq!(&header[16..], &1u32.to_le_bytes());
+        assert_eq!(String::from_utf16(&utf16(paths)).unwrap(), "C:\\a.txt\0\0");
+    }
+
+    #[test]
+    fn images_are_offered_as_png_and_bottom_up_bitmap() {
+        let pixels = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let contents = contents(Data::image(1, 2, pixels));
+
+        assert_eq!(
+            contents
+                .iter()
+                .map(|(format, _)| *format)
+                .collect::<Vec<_>>(),
+            [Format::Png, Format::Bitmap]
+        );
+
+        let (header, rows) = contents[1].1.split_at(40);
+
+        assert_eq!(&header[4..12], &[1, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(&header[14..16], &32u16.to_le_bytes());
+        assert_eq!(rows, [7, 6, 5, 8, 3, 2, 1, 4]);
+    }
+
+    #[test]
+    fn images_with_wrong_sizes_are_not_offered() {
+        assert!(contents(Data::image(2, 2, vec![0; 4])).is_empty());
+    }
+}
@@ -0,0 +1,29 @@
+//! Drag and drop data with the XDND protocol.
+mod source;
+mod target;
+
+pub use source::start_drag;
+pub use target::Worker;
+
+x11rb::atom_manager! {
+    Atoms: AtomsCookie {
+        XdndAware,
+        XdndProxy,
+        XdndSelection,
+        XdndTypeList,
+        XdndEnter,
+        XdndPosition,
+        XdndStatus,
+        XdndLeave,
+        XdndDrop,
+        XdndFinished,
+        XdndActionCopy,
+        TARGETS,
+        INCR,
+        _ICED_DND_HOVER,
+        _ICED_DND_DROP,
+    }
+}
+
+/// The latest version of the XDND protocol supported.
+const VERSION: u32 = 5;
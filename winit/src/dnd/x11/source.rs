@@ -0,0 +1,579 @@
+use super::{Atoms, VERSION};
+use crate::core::dnd::{Data, Outcome};
+use crate::futures::futures::channel::oneshot;
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use x11rb::connection::Connection;
+use x11rb::errors::ReplyOrIdError;
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ClientMessageEvent, ConnectionExt as _, CreateWindowAux,
+    EventMask, GrabMode, GrabStatus, KeyButMask, PropMode,
+    SelectionNotifyEvent, SelectionRequestEvent, Window, WindowClass,
+    SELECTION_NOTIFY_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+use x11rb::{COPY_DEPTH_FROM_PARENT, COPY_FROM_PARENT, CURRENT_TIME, NONE};
+
+/// The maximum amount of attempts to grab the pointer, while the window
+/// where the drag started releases its own grab.
+const GRAB_ATTEMPTS: usize = 50;
+
+/// The interval between attempts to grab the pointer.
+const GRAB_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The time to wait for the target to finish after a drop.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The interval between checks for the end of a drop.
+const FINISH_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Starts dragging the given [`Data`] on a new thread with its own
+/// connection to the X server.
+///
+/// The pointer must be released by the connection of the window where the
+/// drag starts, so the new connection can grab it.
+pub fn start_drag(data: Data, channel: oneshot::Sender<Outcome>) {
+    let drag =
+        thread::Builder::new()
+            .name("iced drag".to_owned())
+            .spawn(move || {
+                let outcome = match x11rb::connect(None) {
+                    Ok((connection, screen)) => run(&connection, screen, data)
+                        .unwrap_or_else(|error| {
+                            log::warn!("Failed to drag data out: {error}");
+
+                            Outcome::Cancelled
+                        }),
+                    Err(error) => {
+                        log::warn!(
+                            "Failed to connect to the X server: {error}"
+                        );
+
+                        Outcome::Unsupported
+                    }
+                };
+
+                let _ = channel.send(outcome);
+            });
+
+    if let Err(error) = drag {
+        log::warn!("Failed to spawn drag thread: {error}");
+    }
+}
+
+fn run(
+    connection: &RustConnection,
+    screen: usize,
+    data: Data,
+) -> Result<Outcome, ReplyOrIdError> {
+    let root = connection.setup().roots[screen].root;
+    let atoms = Atoms::new(connection)?.reply()?;
+
+    let offers = super::super::offers(data);
+
+    let types = offers
+        .iter()
+        .map(|(mime, _)| {
+            Ok(connection
+                .intern_atom(false, mime.as_bytes())?
+                .reply()?
+                .atom)
+        })
+        .collect::<Result<Vec<Atom>, ReplyOrIdError>>()?;
+
+    if types.is_empty() {
+        return Ok(Outcome::Cancelled);
+    }
+
+    let source = connection.generate_id()?;
+
+    let _ = connection.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        source,
+        root,
+        -1,
+        -1,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_ONLY,
+        COPY_FROM_PARENT,
+        &CreateWindowAux::new().override_redirect(1),
+    )?;
+
+    // Only viewable windows can grab the pointer
+    let _ = connection.map_window(source)?;
+
+    let _ = connection.change_property32(
+        PropMode::REPLACE,
+        source,
+        atoms.XdndTypeList,
+        AtomEnum::ATOM,
+        &types,
+    )?;
+
+    let _ = connection.set_selection_owner(
+        source,
+        atoms.XdndSelection,
+        CURRENT_TIME,
+    )?;
+
+    if !grab_pointer(connection, source)? {
+        log::warn!("Failed to grab the pointer to drag data out");
+
+        return Ok(Outcome::Cancelled);
+    }
+
+    let mut drag = Drag {
+        connection,
+        atoms: &atoms,
+        source,
+        types: &types,
+        offers: &offers,
+        target: None,
+        pointer: (0, 0),
+        is_dropped: false,
+    };
+
+    // The buttons may have been released before the grab
+    let pointer = connection.query_pointer(root)?.reply()?;
+    drag.hover(root, pointer.root_x, pointer.root_y)?;
+    connection.flush()?;
+
+    let buttons = u16::from(
+        KeyButMask::BUTTON1 | KeyButMask::BUTTON2 | KeyButMask::BUTTON3,
+    );
+    let mut is_released = u16::from(pointer.mask) & buttons == 0;
+
+    while !is_released {
+        let mut event = Some(connection.wait_for_event()?);
+        let mut moved_to = None;
+
+        // Only the latest motion of a batch of events is relevant
+        while let Some(current) = event {
+            match current {
+                Event::MotionNotify(motion) => {
+                    moved_to = Some((motion.root_x, motion.root_y));
+                }
+                Event::ButtonRelease(release) => {
+                    let button = 1_u16
+                        .checked_shl(7 + u32::from(release.detail))
+                        .unwrap_or(0);
+
+                    is_released =
+                        u16::from(release.state) & buttons & !button == 0;
+                }
+                event => {
+                    let _ = drag.handle(event)?;
+                }
+            }
+
+            event = connection.poll_for_event()?;
+        }
+
+        if let Some((x, y)) = moved_to {
+            drag.hover(root, x, y)?;
+        }
+
+        connection.flush()?;
+    }
+
+    let _ = connection.ungrab_pointer(CURRENT_TIME)?;
+
+    let is_dropped = drag.drop()?;
+    connection.flush()?;
+
+    if !is_dropped {
+        return Ok(Outcome::Cancelled);
+    }
+
+    let dropped_at = Instant::now();
+
+    while dropped_at.elapsed() < TIMEOUT {
+        while let Some(event) = connection.poll_for_event()? {
+            if let Some(outcome) = drag.handle(event)? {
+                return Ok(outcome);
+            }
+        }
+
+        connection.flush()?;
+        thread::sleep(FINISH_INTERVAL);
+    }
+
+    Ok(Outcome::Cancelled)
+}
+
+/// Grabs the pointer with the given window, retrying while the window
+/// where the drag started still holds it.
+fn grab_pointer(
+    connection: &RustConnection,
+    window: Window,
+) -> Result<bool, ReplyOrIdError> {
+    for _ in 0..GRAB_ATTEMPTS {
+        let reply = connection
+            .grab_pointer(
+                false,
+                window,
+                EventMask::POINTER_MOTION | EventMask::BUTTON_RELEASE,
+                GrabMode::ASYNC,
+                GrabMode::ASYNC,
+                NONE,
+                NONE,
+                CURRENT_TIME,
+            )?
+            .reply()?;
+
+        if reply.status == GrabStatus::SUCCESS {
+            return Ok(true);
+        }
+
+        thread::sleep(GRAB_INTERVAL);
+    }
+
+    Ok(false)
+}
+
+/// Returns the data of an `XdndEnter` message offering the given types
+/// with the given protocol version.
+///
+/// The first three types are included in the message; the rest must be
+/// read from the `XdndTypeList` of the source.
+fn enter(version: u32, types: &[Atom]) -> [u32; 4] {
+    let [first, second, third] =
+        [0, 1, 2].map(|i| types.get(i).copied().unwrap_or(NONE));
+
+    [
+        version << 24 | u32::from(types.len() > 3),
+        first,
+        second,
+        third,
+    ]
+}
+
+/// Packs the given root coordinates as expected by `XdndPosition`.
+fn coordinates(x: i16, y: i16) -> u32 {
+    (u32::from(x as u16) << 16) | u32::from(y as u16)
+}
+
+struct Drag<'a> {
+    connection: &'a RustConnection,
+    atoms: &'a Atoms,
+    source: Window,
+    types: &'a [Atom],
+    offers: &'a [(&'static str, Arc<[u8]>)],
+    target: Option<Target>,
+    pointer: (i16, i16),
+    is_dropped: bool,
+}
+
+struct Target {
+    window: Window,
+    proxy: Window,
+    version: u32,
+    position: (i16, i16),
+    is_accepted: bool,
+    is_waiting: bool,
+}
+
+impl Drag<'_> {
+    /// Handles an event of the connection, returning the [`Outcome`] of the
+    /// drag once the target finishes.
+    fn handle(
+        &mut self,
+        event: Event,
+    ) -> Result<Option<Outcome>, ReplyOrIdError> {
+        match event {
+            Event::SelectionRequest(request)
+                if request.selection == self.atoms.XdndSelection =>
+            {
+                self.send(&request)?;
+            }
+            Event::ClientMessage(message)
+                if message.type_ == self.atoms.XdndStatus =>
+            {
+                let [window, flags, ..] = message.data.as_data32();
+
+                let Some(target) = &mut self.target else {
+                    return Ok(None);
+                };
+
+                if target.window == window {
+                    target.is_accepted = flags & 1 == 1;
+                    target.is_waiting = false;
+
+                    // Catch up with the motion skipped while waiting
+                    if !self.is_dropped && target.position != self.pointer {
+                        self.position()?;
+                    }
+                }
+            }
+            Event::ClientMessage(message)
+                if message.type_ == self.atoms.XdndFinished =>
+            {
+                let [window, flags, ..] = message.data.as_data32();
+
+                if let Some(target) = &self.target {
+                    if target.window == window && self.is_dropped {
+                        let is_accepted = target.version < 5 || flags & 1 == 1;
+
+                        return Ok(Some(if is_accepted {
+                            Outcome::Accepted
+                        } else {
+                            Outcome::Cancelled
+                        }));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn hover(
+        &mut self,
+        root: Window,
+        x: i16,
+        y: i16,
+    ) -> Result<(), ReplyOrIdError> {
+        self.pointer = (x, y);
+
+        let aware = self.find_aware(root)?;
+
+        if self.target.as_ref().map(|target| target.window)
+            != aware.map(|(window, _, _)| window)
+        {
+            if let Some(target) = self.target.take() {
+                self.message(&target, self.atoms.XdndLeave, [0; 4])?;
+            }
+
+            if let Some((window, proxy, version)) = aware {
+                let target = Target {
+                    window,
+                    proxy,
+                    version: version.min(VERSION),
+                    position: (x, y),
+                    is_accepted: false,
+                    is_waiting: false,
+                };
+
+                self.message(
+                    &target,
+                    self.atoms.XdndEnter,
+                    enter(target.version, self.types),
+                )?;
+
+                self.target = Some(target);
+                self.position()?;
+            }
+        } else if self
+            .target
+            .as_ref()
+            .is_some_and(|target| target.position != (x, y))
+        {
+            self.position()?;
+        }
+
+        Ok(())
+    }
+
+    fn position(&mut self) -> Result<(), ReplyOrIdError> {
+        let Some(target) = &self.target else {
+            return Ok(());
+        };
+
+        // Wait for the status of the last position before sending a new one
+        if target.is_waiting {
+            return Ok(());
+        }
+
+        let (x, y) = self.pointer;
+
+        self.message(
+            target,
+            self.atoms.XdndPosition,
+            [
+                0,
+                coordinates(x, y),
+                CURRENT_TIME,
+                self.atoms.XdndActionCopy,
+            ],
+        )?;
+
+        if let Some(target) = &mut self.target {
+            target.position = (x, y);
+            target.is_waiting = true;
+        }
+
+        Ok(())
+    }
+
+    fn drop(&mut self) -> Result<bool, ReplyOrIdError> {
+        let Some(target) = &self.target else {
+            return Ok(false);
+        };
+
+        if target.is_accepted {
+            self.message(target, self.atoms.XdndDrop, [0, CURRENT_TIME, 0, 0])?;
+            self.is_dropped = true;
+
+            Ok(true)
+        } else {
+            self.message(target, self.atoms.XdndLeave, [0; 4])?;
+
+            Ok(false)
+        }
+    }
+
+    fn send(
+        &self,
+        request: &SelectionRequestEvent,
+    ) -> Result<(), ReplyOrIdError> {
+        let property = if request.property == NONE {
+            request.target
+        } else {
+            request.property
+        };
+
+        let offer = self
+            .types
+            .iter()
+            .zip(self.offers)
+            .find(|(atom, _)| **atom == request.target);
+
+        let property = if request.target == self.atoms.TARGETS {
+            let mut targets = self.types.to_vec();
+            targets.push(self.atoms.TARGETS);
+
+            let _ = self.connection.change_property32(
+                PropMode::REPLACE,
+                request.requestor,
+                property,
+                AtomEnum::ATOM,
+                &targets,
+            )?;
+
+            property
+        } else if let Some((atom, (_, contents))) = offer {
+            let _ = self.connection.change_property8(
+                PropMode::REPLACE,
+                request.requestor,
+                property,
+                *atom,
+                contents,
+            )?;
+
+            property
+        } else {
+            NONE
+        };
+
+        let _ = self.connection.send_event(
+            false,
+            request.requestor,
+            EventMask::NO_EVENT,
+            SelectionNotifyEvent {
+                response_type: SELECTION_NOTIFY_EVENT,
+                sequence: 0,
+                time: request.time,
+                requestor: request.requestor,
+                selection: request.selection,
+                target: request.target,
+                property,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn message(
+        &self,
+        target: &Target,
+        kind: Atom,
+        [a, b, c, d]: [u32; 4],
+    ) -> Result<(), ReplyOrIdError> {
+        let _ = self.connection.send_event(
+            false,
+            target.proxy,
+            EventMask::NO_EVENT,
+            ClientMessageEvent::new(
+                32,
+                target.window,
+                kind,
+                [self.source, a, b, c, d],
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    /// Finds the topmost XDND aware window under the pointer, together with
+    /// its proxy and protocol version.
+    fn find_aware(
+        &self,
+        root: Window,
+    ) -> Result<Option<(Window, Window, u32)>, ReplyOrIdError> {
+        let mut window = root;
+
+        loop {
+            let child = self.connection.query_pointer(window)?.reply()?.child;
+
+            if child == NONE {
+                return Ok(None);
+            }
+
+            window = child;
+
+            let proxy = self
+                .property(window, self.atoms.XdndProxy, AtomEnum::WINDOW)?
+                .unwrap_or(window);
+
+            if let Some(version) =
+                self.property(proxy, self.atoms.XdndAware, AtomEnum::ATOM)?
+            {
+                return Ok((version >= 3).then_some((window, proxy, version)));
+            }
+        }
+    }
+
+    fn property(
+        &self,
+        window: Window,
+        property: Atom,
+        kind: AtomEnum,
+    ) -> Result<Option<u32>, ReplyOrIdError> {
+        let reply = self
+            .connection
+            .get_property(false, window, property, kind, 0, 1)?
+            .reply()?;
+
+        Ok(reply.value32().and_then(|mut values| values.next()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enter_includes_first_three_types() {
+        assert_eq!(enter(5, &[10, 20]), [5 << 24, 10, 20, NONE]);
+        assert_eq!(enter(5, &[10, 20, 30]), [5 << 24, 10, 20, 30]);
+    }
+
+    #[test]
+    fn enter_flags_longer_type_lists() {
+        assert_eq!(enter(4, &[10, 20, 30, 40]), [4 << 24 | 1, 10, 20, 30]);
+    }
+
+    #[test]
+    fn coordinates_pack_x_and_y() {
+        assert_eq!(coordinates(1, 2), 0x0001_0002);
+        assert_eq!(coordinates(-1, 0), 0xFFFF_0000);
+    }
+}
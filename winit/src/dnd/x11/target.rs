@@ -0,0 +1,625 @@
+use super::{Atoms, VERSION};
+use crate::core::dnd::{Offer, Payload, Response};
+use crate::dnd::{Event, Queue};
+
+use rustc_hash::FxHashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use winit::dpi::PhysicalPosition;
+use winit::window::WindowId;
+
+use x11rb::connection::Connection;
+use x11rb::errors::ReplyOrIdError;
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ClientMessageEvent, ConnectionExt as _, CreateWindowAux,
+    EventMask, PropMode, Property, Window, WindowClass,
+};
+use x11rb::protocol::Event as XEvent;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+use x11rb::{COPY_DEPTH_FROM_PARENT, COPY_FROM_PARENT, CURRENT_TIME, NONE};
+
+/// A thread receiving the drops of the registered windows through a proxy
+/// window on its own connection to the X server.
+pub struct Worker {
+    connection: Arc<RustConnection>,
+    atoms: Atoms,
+    proxy: Window,
+    state: Arc<Mutex<State>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    pub fn spawn(queue: Queue) -> Option<Self> {
+        let (connection, screen) = x11rb::connect(None)
+            .map_err(|error| {
+                log::warn!("Failed to connect to the X server: {error}");
+            })
+            .ok()?;
+
+        let connection = Arc::new(connection);
+        let root = connection.setup().roots[screen].root;
+
+        let (atoms, proxy) = create_proxy(&connection, root)
+            .map_err(|error| {
+                log::warn!("Failed to create drop target: {error}");
+            })
+            .ok()?;
+
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let thread = thread::Builder::new()
+            .name("iced drop".to_owned())
+            .spawn({
+                let connection = connection.clone();
+                let state = state.clone();
+
+                move || {
+                    let target = Target {
+                        connection: &connection,
+                        atoms: &atoms,
+                        proxy,
+                        root,
+                        queue: &queue,
+                    };
+
+                    target.run(&state);
+                }
+            })
+            .map_err(|error| {
+                log::warn!("Failed to spawn drop target thread: {error}");
+            })
+            .ok()?;
+
+        Some(Self {
+            connection,
+            atoms,
+            proxy,
+            state,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn register(&mut self, window: Arc<winit::window::Window>) {
+        let Some(id) = id(&window) else {
+            return;
+        };
+
+        let result = self
+            .connection
+            .change_property32(
+                PropMode::REPLACE,
+                id,
+                self.atoms.XdndProxy,
+                AtomEnum::WINDOW,
+                &[self.proxy],
+            )
+            .map(|_| ())
+            .and_then(|()| self.connection.flush());
+
+        if let Err(error) = result {
+            log::warn!("Failed to register drop target: {error}");
+            return;
+        }
+
+        let _ = self
+            .state
+            .lock()
+            .expect("Lock drop target")
+            .windows
+            .insert(id, window);
+    }
+
+    pub fn unregister(&mut self, window: WindowId) {
+        let mut state = self.state.lock().expect("Lock drop target");
+
+        state
+            .windows
+            .retain(|_, registered| registered.id() != window);
+
+        if state
+            .drag
+            .as_ref()
+            .is_some_and(|drag| drag.window.id() == window)
+        {
+            state.drag = None;
+        }
+    }
+
+    pub fn respond(&mut self, window: WindowId, response: Option<Response>) {
+        let mut state = self.state.lock().expect("Lock drop target");
+
+        let Some(drag) = state
+            .drag
+            .as_mut()
+            .filter(|drag| drag.window.id() == window && !drag.is_dropped)
+        else {
+            return;
+        };
+
+        drag.accepted = super::super::accepted(&drag.offer, response.as_ref());
+
+        let result = self
+            .connection
+            .send_event(
+                false,
+                drag.source,
+                EventMask::NO_EVENT,
+                ClientMessageEvent::new(
+                    32,
+                    drag.source,
+                    self.atoms.XdndStatus,
+                    [
+                        drag.target,
+                        // Keep sending positions, even if unchanged
+                        0b10 | u32::from(drag.accepted.is_some()),
+                        0,
+                        0,
+                        if drag.accepted.is_some() {
+                            self.atoms.XdndActionCopy
+                        } else {
+                            NONE
+                        },
+                    ],
+                ),
+            )
+            .map(|_| ())
+            .and_then(|()| self.connection.flush());
+
+        if let Err(error) = result {
+            log::warn!("Failed to respond to drag: {error}");
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        // Destroying the proxy window stops the worker
+        let _ = self.connection.destroy_window(self.proxy);
+        let _ = self.connection.flush();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    windows: FxHashMap<Window, Arc<winit::window::Window>>,
+    drag: Option<Drag>,
+}
+
+struct Drag {
+    source: Window,
+    target: Window,
+    window: Arc<winit::window::Window>,
+    offer: Offer,
+    formats: Vec<Atom>,
+    position: PhysicalPosition<f64>,
+    accepted: Option<usize>,
+    uris: Option<Vec<u8>>,
+    transfers: FxHashMap<Atom, Vec<u8>>,
+    is_dropped: bool,
+}
+
+struct Target<'a> {
+    connection: &'a RustConnection,
+    atoms: &'a Atoms,
+    proxy: Window,
+    root: Window,
+    queue: &'a Queue,
+}
+
+impl Target<'_> {
+    fn run(&self, state: &Mutex<State>) {
+        loop {
+            let event = match self.connection.wait_for_event() {
+                Ok(XEvent::DestroyNotify(event))
+                    if event.window == self.proxy =>
+                {
+                    return;
+                }
+                Ok(event) => event,
+                Err(error) => {
+                    log::warn!("Lost connection of drop target: {error}");
+                    return;
+                }
+            };
+
+            let mut state = state.lock().expect("Lock drop target");
+
+            if let Err(error) = self.handle(&mut state, event) {
+                log::warn!("Failed to handle drop: {error}");
+            }
+        }
+    }
+
+    fn handle(
+        &self,
+        state: &mut State,
+        event: XEvent,
+    ) -> Result<(), ReplyOrIdError> {
+        match event {
+            XEvent::ClientMessage(message) => {
+                let [source, flags, a, b, c] = message.data.as_data32();
+
+                if message.type_ == self.atoms.XdndEnter {
+                    let Some(window) = state.windows.get(&message.window)
+                    else {
+                        return Ok(());
+                    };
+
+                    state.drag = Some(self.enter(
+                        source,
+                        message.window,
+                        window.clone(),
+                        flags,
+                        [a, b, c],
+                    )?);
+
+                    return Ok(());
+                }
+
+                let Some(drag) = state.drag.as_mut().filter(|drag| {
+                    drag.source == source && drag.target == message.window
+                }) else {
+                    return Ok(());
+                };
+
+                if message.type_ == self.atoms.XdndPosition {
+                    self.position(drag, a)?;
+                } else if message.type_ == self.atoms.XdndLeave {
+                    self.leave(drag);
+                    state.drag = None;
+                } else if message.type_ == self.atoms.XdndDrop {
+                    if self.drop(drag, a)? {
+                        state.drag = None;
+                    }
+                }
+            }
+            XEvent::SelectionNotify(event)
+                if event.requestor == self.proxy
+                    && event.selection == self.atoms.XdndSelection =>
+            {
+                let Some(drag) = &mut state.drag else {
+                    return Ok(());
+                };
+
+                if event.property == NONE {
+                    if drag.is_dropped {
+                        self.finish(drag, false)?;
+                        self.leave(drag);
+                        state.drag = None;
+                    }
+
+                    return Ok(());
+                }
+
+                let reply = self
+                    .connection
+                    .get_property(
+                        true,
+                        self.proxy,
+                        event.property,
+                        AtomEnum::ANY,
+                        0,
+                        u32::MAX,
+                    )?
+                    .reply()?;
+
+                if reply.type_ == self.atoms.INCR {
+                    // Deleting the property starts the incremental transfer
+                    let _ = drag.transfers.insert(event.property, Vec::new());
+                } else if self.receive(drag, event.property, reply.value)? {
+                    state.drag = None;
+                }
+            }
+            XEvent::PropertyNotify(event)
+                if event.window == self.proxy
+                    && event.state == Property::NEW_VALUE =>
+            {
+                let Some(drag) = &mut state.drag else {
+                    return Ok(());
+                };
+
+                let Some(transfer) = drag.transfers.get_mut(&event.atom) else {
+                    return Ok(());
+                };
+
+                let reply = self
+                    .connection
+                    .get_property(
+                        true,
+                        self.proxy,
+                        event.atom,
+                        AtomEnum::ANY,
+                        0,
+                        u32::MAX,
+                    )?
+                    .reply()?;
+
+                if !reply.value.is_empty() {
+                    transfer.extend(reply.value);
+                } else {
+                    let bytes = drag
+                        .transfers
+                        .remove(&event.atom)
+                        .expect("Remove finished transfer");
+
+                    if self.receive(drag, event.atom, bytes)? {
+                        state.drag = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        self.connection.flush()?;
+
+        Ok(())
+    }
+
+    fn enter(
+        &self,
+        source: Window,
+        target: Window,
+        window: Arc<winit::window::Window>,
+        flags: u32,
+        types: [Atom; 3],
+    ) -> Result<Drag, ReplyOrIdError> {
+        let formats: Vec<Atom> = if flags & 1 == 1 {
+            self.connection
+                .get_property(
+                    false,
+                    source,
+                    self.atoms.XdndTypeList,
+                    AtomEnum::ATOM,
+                    0,
+                    u32::MAX,
+                )?
+                .reply()?
+                .value32()
+                .map(Iterator::collect)
+                .unwrap_or_default()
+        } else {
+            types.into_iter().filter(|atom| *atom != NONE).collect()
+        };
+
+        let names = formats
+            .iter()
+            .map(|atom| {
+                let reply = self.connection.get_atom_name(*atom)?.reply()?;
+
+                Ok(String::from_utf8_lossy(&reply.name).into_owned())
+            })
+            .collect::<Result<Vec<_>, ReplyOrIdError>>()?;
+
+        let offer = Offer::new(names);
+
+        // Fetch the URIs early to notify of hovered files
+        if let Some(index) = offer
+            .formats()
+            .iter()
+            .position(|format| format == Offer::URI_LIST)
+        {
+            let _ = self.connection.convert_selection(
+                self.proxy,
+                self.atoms.XdndSelection,
+                formats[index],
+                self.atoms._ICED_DND_HOVER,
+                CURRENT_TIME,
+            )?;
+        }
+
+        Ok(Drag {
+            source,
+            target,
+            window,
+            offer,
+            formats,
+            position: PhysicalPosition::default(),
+            accepted: None,
+            uris: None,
+            transfers: FxHashMap::default(),
+            is_dropped: false,
+        })
+    }
+
+    fn position(
+        &self,
+        drag: &mut Drag,
+        coordinates: u32,
+    ) -> Result<(), ReplyOrIdError> {
+        let x = (coordinates >> 16) as i16;
+        let y = (coordinates & 0xffff) as i16;
+
+        let translated = self
+            .connection
+            .translate_coordinates(self.root, drag.target, x, y)?
+            .reply()?;
+
+        drag.position = PhysicalPosition::new(
+            f64::from(translated.dst_x),
+            f64::from(translated.dst_y),
+        );
+
+        self.queue.push(
+            &drag.window,
+            Event::DataHovered {
+                position: drag.position,
+                offer: drag.offer.clone(),
+            },
+        );
+
+        Ok(())
+    }
+
+    fn leave(&self, drag: &Drag) {
+        if drag.uris.is_some() {
+            self.queue.push(&drag.window, Event::FilesHoveredLeft);
+        }
+
+        self.queue.push(&drag.window, Event::DataHoveredLeft);
+    }
+
+    /// Returns whether the drag is over.
+    fn drop(&self, drag: &mut Drag, time: u32) -> Result<bool, ReplyOrIdError> {
+        let Some(accepted) = drag.accepted else {
+            self.finish(drag, false)?;
+            self.leave(drag);
+
+            return Ok(true);
+        };
+
+        drag.is_dropped = true;
+
+        if drag.offer.formats()[accepted] == Offer::URI_LIST {
+            if let Some(uris) = drag.uris.clone() {
+                return self.receive(drag, self.atoms._ICED_DND_DROP, uris);
+            }
+        }
+
+        let _ = self.connection.convert_selection(
+            self.proxy,
+            self.atoms.XdndSelection,
+            drag.formats[accepted],
+            self.atoms._ICED_DND_DROP,
+            time,
+        )?;
+
+        Ok(false)
+    }
+
+    /// Returns whether the drag is over.
+    fn receive(
+        &self,
+        drag: &mut Drag,
+        property: Atom,
+        bytes: Vec<u8>,
+    ) -> Result<bool, ReplyOrIdError> {
+        if property == self.atoms._ICED_DND_HOVER && drag.uris.is_none() {
+            let payload = Payload::new(Offer::URI_LIST, bytes.clone());
+
+            for path in payload.paths() {
+                self.queue.push(&drag.window, Event::FileHovered(path));
+            }
+
+            drag.uris = Some(bytes);
+
+            return Ok(false);
+        }
+
+        let Some(accepted) = drag.accepted.filter(|_| {
+            drag.is_dropped && property == self.atoms._ICED_DND_DROP
+        }) else {
+            return Ok(false);
+        };
+
+        let payload =
+            Payload::new(drag.offer.formats()[accepted].clone(), bytes);
+
+        for path in payload.paths() {
+            self.queue.push(&drag.window, Event::FileDropped(path));
+        }
+
+        self.queue.push(
+            &drag.window,
+            Event::DataDropped {
+                position: drag.position,
+                payload,
+            },
+        );
+
+        self.finish(drag, true)?;
+
+        Ok(true)
+    }
+
+    fn finish(
+        &self,
+        drag: &Drag,
+        is_accepted: bool,
+    ) -> Result<(), ReplyOrIdError> {
+        let _ = self.connection.send_event(
+            false,
+            drag.source,
+            EventMask::NO_EVENT,
+            ClientMessageEvent::new(
+                32,
+                drag.source,
+                self.atoms.XdndFinished,
+                [
+                    drag.target,
+                    u32::from(is_accepted),
+                    if is_accepted {
+                        self.atoms.XdndActionCopy
+                    } else {
+                        NONE
+                    },
+                    0,
+                    0,
+                ],
+            ),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Creates the window that receives the XDND messages of the registered
+/// windows.
+fn create_proxy(
+    connection: &RustConnection,
+    root: Window,
+) -> Result<(Atoms, Window), ReplyOrIdError> {
+    let atoms = Atoms::new(connection)?.reply()?;
+    let proxy = connection.generate_id()?;
+
+    let _ = connection.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        proxy,
+        root,
+        -1,
+        -1,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_ONLY,
+        COPY_FROM_PARENT,
+        &CreateWindowAux::new().override_redirect(1).event_mask(
+            EventMask::PROPERTY_CHANGE | EventMask::STRUCTURE_NOTIFY,
+        ),
+    )?;
+
+    let _ = connection.change_property32(
+        PropMode::REPLACE,
+        proxy,
+        atoms.XdndAware,
+        AtomEnum::ATOM,
+        &[VERSION],
+    )?;
+
+    let _ = connection.change_property32(
+        PropMode::REPLACE,
+        proxy,
+        atoms.XdndProxy,
+        AtomEnum::WINDOW,
+        &[proxy],
+    )?;
+
+    connection.flush()?;
+
+    Ok((atoms, proxy))
+}
+
+/// Returns the X11 identifier of the given window, if any.
+fn id(window: &winit::window::Window) -> Option<Window> {
+    use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+    match window.window_handle().ok()?.as_raw() {
+        RawWindowHandle::Xlib(handle) => Window::try_from(handle.window).ok(),
+        RawWindowHandle::Xcb(handle) => Some(handle.window.get()),
+        _ => None,
+    }
+}
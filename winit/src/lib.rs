@@ -26,7 +26,9 @@ pub use winit;
 
 pub mod clipboard;
 pub mod conversion;
+pub mod dnd;
 pub mod settings;
+pub mod tray;
 
 #[cfg(feature = "program")]
 pub mod program;
@@ -38,9 +40,11 @@ mod error;
 mod proxy;
 
 pub use clipboard::Clipboard;
+pub use dnd::DragAndDrop;
 pub use error::Error;
 pub use proxy::Proxy;
 pub use settings::Settings;
+pub use tray::SystemTray;
 
 #[cfg(feature = "program")]
 pub use program::Program;
@@ -9,13 +9,14 @@ use crate::core;
 use crate::core::mouse;
 use crate::core::renderer;
 use crate::core::time::Instant;
+use crate::core::tray::Tray;
 use crate::core::widget::operation;
 use crate::core::window;
 use crate::core::{Color, Element, Point, Size, Theme};
 use crate::futures::futures::channel::mpsc;
 use crate::futures::futures::channel::oneshot;
 use crate::futures::futures::task;
-use crate::futures::futures::{Future, StreamExt};
+use crate::futures::futures::{Future, SinkExt, StreamExt};
 use crate::futures::subscription::{self, Subscription};
 use crate::futures::{Executor, Runtime};
 use crate::graphics;
@@ -23,7 +24,7 @@ use crate::graphics::{compositor, Compositor};
 use crate::runtime::user_interface::{self, UserInterface};
 use crate::runtime::Debug;
 use crate::runtime::{self, Action, Task};
-use crate::{Clipboard, Error, Proxy, Settings};
+use crate::{Clipboard, DragAndDrop, Error, Proxy, Settings, SystemTray};
 
 use window_manager::WindowManager;
 
@@ -136,6 +137,17 @@ where
     fn scale_factor(&self, window: window::Id) -> f64 {
         1.0
     }
+
+    /// Returns the [`Tray`] of the [`Program`], if any.
+    ///
+    /// Like the widgets of a window, the [`Tray`] is described
+    /// declaratively and kept up to date after every update. The system
+    /// tray is only supported on Linux with the `tray` feature enabled.
+    ///
+    /// By default, it returns `None`.
+    fn tray(&self) -> Option<Tray<Self::Message>> {
+        None
+    }
 }
 
 /// The appearance of a program.
@@ -173,7 +185,7 @@ pub fn default(theme: &Theme) -> Appearance {
 /// settings.
 pub fn run<P, C>(
     settings: Settings,
-    graphics_settings: graphics::Settings,
+    mut graphics_settings: graphics::Settings,
     window_settings: Option<window::Settings>,
     flags: P::Flags,
 ) -> Result<(), Error>
@@ -193,6 +205,19 @@ where
 
     let (proxy, worker) = Proxy::new(event_loop.create_proxy());
 
+    // Draw images as soon as they finish decoding in the background
+    graphics_settings.image_waker = Some(graphics::image::Waker::new({
+        let proxy = std::sync::Mutex::new(proxy.clone());
+
+        move || {
+            // A fresh sender always has room for one message
+            let mut proxy = proxy.lock().expect("Lock proxy").clone();
+            let _ = proxy.start_send_unpin(Action::Window(
+                runtime::window::Action::RedrawAll,
+            ));
+        }
+    }));
+
     let mut runtime = {
         let executor =
             P::Executor::new().map_err(Error::ExecutorCreationFailed)?;
@@ -689,8 +714,13 @@ async fn run_instance<P, C>(
     let mut actions = 0;
 
     let mut ui_caches = FxHashMap::default();
+    let mut offscreen_renderer = None;
     let mut user_interfaces = ManuallyDrop::new(FxHashMap::default());
     let mut clipboard = Clipboard::unconnected();
+    let mut dnd = DragAndDrop::unconnected();
+    let mut tray = SystemTray::new(proxy.clone());
+
+    tray.update(program.tray());
 
     debug.startup_finished();
 
@@ -753,6 +783,12 @@ async fn run_instance<P, C>(
                     clipboard = Clipboard::connect(window.raw.clone());
                 }
 
+                if dnd.window_id().is_none() {
+                    dnd = DragAndDrop::connect(window.raw.clone());
+                }
+
+                dnd.register(window.raw.clone());
+
                 let _ = on_open.send(id);
                 is_window_opening = false;
             }
@@ -795,9 +831,11 @@ async fn run_instance<P, C>(
                             action,
                             &program,
                             &mut compositor,
+                            &mut offscreen_renderer,
                             &mut events,
                             &mut messages,
                             &mut clipboard,
+                            &mut dnd,
                             &mut control_sender,
                             &mut debug,
                             &mut user_interfaces,
@@ -897,17 +935,21 @@ async fn run_instance<P, C>(
                         });
 
                         if let user_interface::State::Updated {
-                            redraw_request: Some(redraw_request),
+                            redraw_request,
+                            input_method,
                         } = ui_state
                         {
                             match redraw_request {
-                                window::RedrawRequest::NextFrame => {
+                                Some(window::RedrawRequest::NextFrame) => {
                                     window.raw.request_redraw();
                                 }
-                                window::RedrawRequest::At(at) => {
+                                Some(window::RedrawRequest::At(at)) => {
                                     window.redraw_at = Some(at);
                                 }
+                                None => {}
                             }
+
+                            window.request_input_method(input_method);
                         }
 
                         debug.render_started();
@@ -987,9 +1029,11 @@ async fn run_instance<P, C>(
                                 )),
                                 &program,
                                 &mut compositor,
+                                &mut offscreen_renderer,
                                 &mut events,
                                 &mut messages,
                                 &mut clipboard,
+                                &mut dnd,
                                 &mut control_sender,
                                 &mut debug,
                                 &mut user_interfaces,
@@ -1014,6 +1058,34 @@ async fn run_instance<P, C>(
                         }
                     }
                     event::Event::AboutToWait => {
+                        // Offscreen renders are rare, so their renderer is
+                        // dropped once the queued actions are done
+                        offscreen_renderer = None;
+
+                        for kind in
+                            clipboard.changes(runtime.is_listening_clipboard())
+                        {
+                            runtime.broadcast(
+                                subscription::Event::ClipboardChanged(kind),
+                            );
+                        }
+
+                        for (id, event) in dnd.events() {
+                            let Some((id, window)) =
+                                window_manager.get_mut_alias(id)
+                            else {
+                                continue;
+                            };
+
+                            events.push((
+                                id,
+                                core::Event::Window(conversion::dnd_event(
+                                    event,
+                                    window.state.scale_factor(),
+                                )),
+                            ));
+                        }
+
                         if events.is_empty()
                             && messages.is_empty()
                             && window_manager.is_idle()
@@ -1040,16 +1112,34 @@ async fn run_instance<P, C>(
                                 continue;
                             }
 
-                            let (ui_state, statuses) = user_interfaces
+                            let is_drop_hovered =
+                                window_events.iter().any(|event| {
+                                    matches!(
+                                        event,
+                                        core::Event::Window(
+                                            window::Event::DataHovered { .. }
+                                        )
+                                    )
+                                });
+
+                            let ui = user_interfaces
                                 .get_mut(&id)
-                                .expect("Get user interface")
-                                .update(
-                                    &window_events,
-                                    window.state.cursor(),
-                                    &mut window.renderer,
-                                    &mut clipboard,
-                                    &mut messages,
+                                .expect("Get user interface");
+
+                            let (ui_state, statuses) = ui.update(
+                                &window_events,
+                                window.state.cursor(),
+                                &mut window.renderer,
+                                &mut clipboard,
+                                &mut messages,
+                            );
+
+                            if is_drop_hovered {
+                                dnd.respond(
+                                    window.raw.id(),
+                                    ui.drop_response().cloned(),
                                 );
+                            }
 
                             #[cfg(feature = "unconditional-rendering")]
                             window.raw.request_redraw();
@@ -1060,6 +1150,7 @@ async fn run_instance<P, C>(
                                 ))]
                                 user_interface::State::Updated {
                                     redraw_request: Some(redraw_request),
+                                    ..
                                 } => match redraw_request {
                                     window::RedrawRequest::NextFrame => {
                                         window.raw.request_redraw();
@@ -1116,6 +1207,8 @@ async fn run_instance<P, C>(
                                 &mut messages,
                             );
 
+                            tray.update(program.tray());
+
                             for (id, window) in window_manager.iter_mut() {
                                 window.state.synchronize(
                                     &program,
@@ -1211,9 +1304,11 @@ fn run_action<P, C>(
     action: Action<P::Message>,
     program: &P,
     compositor: &mut C,
+    offscreen_renderer: &mut Option<P::Renderer>,
     events: &mut Vec<(window::Id, core::Event)>,
     messages: &mut Vec<P::Message>,
     clipboard: &mut Clipboard,
+    dnd: &mut DragAndDrop,
     control_sender: &mut mpsc::UnboundedSender<Control>,
     debug: &mut Debug,
     interfaces: &mut FxHashMap<
@@ -1226,9 +1321,11 @@ fn run_action<P, C>(
 ) where
     P: Program,
     C: Compositor<Renderer = P::Renderer> + 'static,
-    P::Theme: DefaultStyle,
+    P::Theme: DefaultStyle + 'static,
+    P::Renderer: 'static,
 {
     use crate::runtime::clipboard;
+    use crate::runtime::dnd;
     use crate::runtime::system;
     use crate::runtime::window;
 
@@ -1243,6 +1340,32 @@ fn run_action<P, C>(
             clipboard::Action::Write { target, contents } => {
                 clipboard.write(target, contents);
             }
+            clipboard::Action::ReadFormats { target, channel } => {
+                let _ = channel.send(clipboard.formats(target));
+            }
+            clipboard::Action::ReadContent {
+                target,
+                format,
+                channel,
+            } => {
+                let _ = channel.send(clipboard.read_content(target, &format));
+            }
+            clipboard::Action::WriteContent { target, contents } => {
+                clipboard.write_content(target, contents);
+            }
+        },
+        Action::Dnd(action) => match action {
+            dnd::Action::StartDrag {
+                window,
+                data,
+                channel,
+            } => {
+                if let Some(window) = window_manager.get_mut(window) {
+                    dnd.start_drag(&window.raw, data, channel);
+                } else {
+                    let _ = channel.send(core::dnd::Outcome::Unsupported);
+                }
+            }
         },
         Action::Window(action) => match action {
             window::Action::Open(id, settings, channel) => {
@@ -1273,6 +1396,20 @@ fn run_action<P, C>(
                             .unwrap_or_else(Clipboard::unconnected);
                     }
 
+                    if dnd.window_id() == Some(window.raw.id()) {
+                        *dnd = window_manager
+                            .first()
+                            .map(|window| window.raw.clone())
+                            .map(DragAndDrop::connect)
+                            .unwrap_or_else(DragAndDrop::unconnected);
+
+                        for (_id, window) in window_manager.iter_mut() {
+                            dnd.register(window.raw.clone());
+                        }
+                    } else {
+                        dnd.unregister(window.raw.id());
+                    }
+
                     events.push((
                         id,
                         core::Event::Window(core::window::Event::Closed),
@@ -1468,6 +1605,95 @@ fn run_action<P, C>(
                     ));
                 }
             }
+            window::Action::RedrawAll => {
+                for (_id, window) in window_manager.iter_mut() {
+                    window.raw.request_redraw();
+                }
+            }
+            window::Action::GetImageCacheStats(id, channel) => {
+                if let Some(window) = window_manager.get(id) {
+                    let _ = channel
+                        .send(compositor.image_cache_stats(&window.renderer));
+                }
+            }
+            window::Action::Render {
+                id,
+                view,
+                size,
+                scale_factor,
+                channel,
+            } => {
+                let Some(window) = window_manager.get_mut(id) else {
+                    let _ = channel.send(Err(
+                        window::screenshot::RenderError::WindowNotFound,
+                    ));
+
+                    return;
+                };
+
+                let Ok(view) =
+                    view.downcast::<window::View<P::Theme, P::Renderer>>()
+                else {
+                    log::error!(
+                        "The element to render does not match the theme \
+                        and renderer of the application"
+                    );
+
+                    let _ = channel
+                        .send(Err(window::screenshot::RenderError::Mismatch));
+
+                    return;
+                };
+
+                let Some(viewport) = offscreen_viewport(size, scale_factor)
+                else {
+                    let _ = channel.send(Ok(window::Screenshot::new(
+                        Vec::new(),
+                        Size::new(0, 0),
+                        scale_factor,
+                    )));
+
+                    return;
+                };
+
+                let physical_size = viewport.physical_size();
+
+                // The renderer of the window keeps the layers of its last
+                // frame for screenshots, so offscreen renders use their own;
+                // which also decodes images synchronously
+                let renderer = offscreen_renderer.get_or_insert_with(|| {
+                    compositor.create_offscreen_renderer()
+                });
+
+                let mut interface = UserInterface::build(
+                    view(),
+                    viewport.logical_size(),
+                    user_interface::Cache::default(),
+                    renderer,
+                );
+
+                let _ = interface.draw(
+                    renderer,
+                    window.state.theme(),
+                    &renderer::Style {
+                        text_color: window.state.text_color(),
+                    },
+                    mouse::Cursor::Unavailable,
+                );
+
+                let bytes = compositor.screenshot::<&str>(
+                    renderer,
+                    &viewport,
+                    Color::TRANSPARENT,
+                    &[],
+                );
+
+                let _ = channel.send(Ok(window::Screenshot::new(
+                    bytes,
+                    physical_size,
+                    scale_factor,
+                )));
+            }
             window::Action::EnableMousePassthrough(id) => {
                 if let Some(window) = window_manager.get_mut(id) {
                     let _ = window.raw.set_cursor_hittest(false);
@@ -1578,3 +1804,45 @@ pub fn user_force_quit(
         _ => false,
     }
 }
+
+/// Returns the [`graphics::Viewport`] of an offscreen render with the given
+/// logical size and scale factor, or `None` if it has no physical pixels.
+fn offscreen_viewport(
+    size: Size,
+    scale_factor: f64,
+) -> Option<graphics::Viewport> {
+    let physical_size = Size::new(
+        (size.width as f64 * scale_factor).round() as u32,
+        (size.height as f64 * scale_factor).round() as u32,
+    );
+
+    if physical_size.width == 0 || physical_size.height == 0 {
+        return None;
+    }
+
+    Some(graphics::Viewport::with_physical_size(
+        physical_size,
+        scale_factor,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offscreen_viewport_scales_size() {
+        let viewport = offscreen_viewport(Size::new(100.0, 50.0), 1.5)
+            .expect("Create viewport");
+
+        assert_eq!(viewport.physical_size(), Size::new(150, 75));
+        assert_eq!(viewport.scale_factor(), 1.5);
+    }
+
+    #[test]
+    fn offscreen_viewport_rejects_empty_sizes() {
+        assert!(offscreen_viewport(Size::new(0.0, 50.0), 1.0).is_none());
+        assert!(offscreen_viewport(Size::new(100.0, 0.2), 1.0).is_none());
+        assert!(offscreen_viewport(Size::new(100.0, 50.0), 0.0).is_none());
+    }
+}
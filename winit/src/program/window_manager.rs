@@ -1,7 +1,7 @@
 use crate::core::mouse;
 use crate::core::time::Instant;
 use crate::core::window::Id;
-use crate::core::{Point, Size};
+use crate::core::{InputMethod, Point, Size};
 use crate::graphics::Compositor;
 use crate::program::{DefaultStyle, Program, State};
 
@@ -63,6 +63,7 @@ where
                 surface,
                 renderer,
                 mouse_interaction: mouse::Interaction::None,
+                input_method: InputMethod::Disabled,
                 redraw_at: None,
             },
         );
@@ -151,6 +152,7 @@ where
     pub viewport_version: u64,
     pub exit_on_close_request: bool,
     pub mouse_interaction: mouse::Interaction,
+    pub input_method: InputMethod,
     pub surface: C::Surface,
     pub renderer: P::Renderer,
     pub redraw_at: Option<Instant>,
@@ -178,4 +180,36 @@ where
 
         Size::new(size.width, size.height)
     }
+
+    pub fn request_input_method(&mut self, input_method: InputMethod) {
+        if self.input_method == input_method {
+            return;
+        }
+
+        match input_method {
+            InputMethod::Disabled => {
+                self.raw.set_ime_allowed(false);
+            }
+            InputMethod::Enabled { cursor } => {
+                if !self.input_method.is_enabled() {
+                    self.raw.set_ime_allowed(true);
+                }
+
+                let scale_factor = self.state.scale_factor();
+
+                self.raw.set_ime_cursor_area(
+                    winit::dpi::PhysicalPosition::new(
+                        f64::from(cursor.x) * scale_factor,
+                        f64::from(cursor.y) * scale_factor,
+                    ),
+                    winit::dpi::PhysicalSize::new(
+                        f64::from(cursor.width) * scale_factor,
+                        f64::from(cursor.height) * scale_factor,
+                    ),
+                );
+            }
+        }
+
+        self.input_method = input_method;
+    }
 }
@@ -0,0 +1,305 @@
+//! Show an icon with a menu in the system tray.
+#[cfg(all(feature = "tray", target_os = "linux"))]
+#[path = "tray/linux.rs"]
+mod platform;
+
+#[cfg(not(all(feature = "tray", target_os = "linux")))]
+#[path = "tray/unsupported.rs"]
+mod platform;
+
+use crate::core::tray::{Icon, Item, Menu, Tray};
+use crate::futures::futures::SinkExt;
+use crate::runtime::Action;
+use crate::Proxy;
+
+use std::sync::{Arc, Mutex};
+
+/// An icon in the system tray, kept in sync with the [`Tray`] of a
+/// program.
+#[allow(missing_debug_implementations)]
+pub struct SystemTray<Message: 'static> {
+    proxy: Proxy<Message>,
+    tray: Arc<Mutex<Option<Tray<Message>>>>,
+    platform: Option<platform::Platform>,
+    is_unsupported: bool,
+}
+
+impl<Message> SystemTray<Message>
+where
+    Message: std::fmt::Debug + Send + 'static,
+{
+    /// Creates a new [`SystemTray`] that will send the messages of its
+    /// [`Tray`] through the given [`Proxy`].
+    ///
+    /// No icon is shown until a [`Tray`] is provided with
+    /// [`SystemTray::update`].
+    pub fn new(proxy: Proxy<Message>) -> Self {
+        Self {
+            proxy,
+            tray: Arc::new(Mutex::new(None)),
+            platform: None,
+            is_unsupported: false,
+        }
+    }
+
+    /// Synchronizes the [`SystemTray`] with the given [`Tray`].
+    ///
+    /// The icon is removed from the system tray if `None`.
+    pub fn update(&mut self, tray: Option<Tray<Message>>) {
+        let Some(tray) = tray else {
+            self.platform = None;
+            *self.tray.lock().expect("Lock tray") = None;
+            return;
+        };
+
+        let snapshot = Snapshot::new(&tray);
+        *self.tray.lock().expect("Lock tray") = Some(tray);
+
+        if let Some(platform) = &mut self.platform {
+            platform.update(snapshot);
+            return;
+        }
+
+        if self.is_unsupported {
+            return;
+        }
+
+        let handler = {
+            let tray = self.tray.clone();
+            let proxy = Mutex::new(self.proxy.clone());
+
+            Box::new(move |event| {
+                let message =
+                    tray.lock().expect("Lock tray").as_ref().and_then(|tray| {
+                        match event {
+                            Event::Clicked => tray.click(),
+                            Event::MiddleClicked => tray.middle_click(),
+                            Event::ItemActivated(id) => {
+                                find(&tray.menu, id, &mut 0)?.activate()
+                            }
+                        }
+                    });
+
+                if let Some(message) = message {
+                    // A fresh sender always has room for one message
+                    let mut proxy = proxy.lock().expect("Lock proxy").clone();
+                    let _ = proxy.start_send_unpin(Action::Output(message));
+                }
+            })
+        };
+
+        self.platform = platform::Platform::spawn(snapshot, handler);
+
+        if self.platform.is_none() {
+            log::warn!("The system tray is not available");
+            self.is_unsupported = true;
+        }
+    }
+}
+
+/// An interaction with the system tray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(all(feature = "tray", target_os = "linux")), allow(dead_code))]
+enum Event {
+    Clicked,
+    MiddleClicked,
+    ItemActivated(i32),
+}
+
+/// A handler of the interactions with the system tray.
+type Handler = Box<dyn Fn(Event) + Send + Sync>;
+
+/// The platform-agnostic description of a [`Tray`], free of messages.
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    title: String,
+    tooltip: String,
+    icon: Icon,
+    is_clickable: bool,
+    menu: Vec<Entry>,
+}
+
+impl Snapshot {
+    fn new<Message>(tray: &Tray<Message>) -> Self {
+        Self {
+            title: tray.title.clone().unwrap_or_default(),
+            tooltip: tray.tooltip.clone().unwrap_or_default(),
+            icon: tray.icon.clone(),
+            is_clickable: tray.is_clickable(),
+            menu: entries(&tray.menu, &mut 0),
+        }
+    }
+}
+
+/// An [`Item`] of a [`Menu`], identified by its position in a depth-first
+/// traversal starting at 1.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    id: i32,
+    label: String,
+    is_enabled: bool,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Button,
+    Checkbox { is_checked: bool },
+    Separator,
+    Submenu(Vec<Entry>),
+}
+
+fn entries<Message>(menu: &Menu<Message>, last_id: &mut i32) -> Vec<Entry> {
+    menu.items
+        .iter()
+        .map(|item| {
+            *last_id += 1;
+            let id = *last_id;
+
+            let kind = match item {
+                Item::Button { .. } => Kind::Button,
+                Item::Checkbox { is_checked, .. } => Kind::Checkbox {
+                    is_checked: *is_checked,
+                },
+                Item::Separator => Kind::Separator,
+                Item::Submenu { menu, .. } => {
+                    Kind::Submenu(entries(menu, last_id))
+                }
+            };
+
+            Entry {
+                id,
+                label: item.label().unwrap_or_default().to_owned(),
+                is_enabled: item.is_enabled(),
+                kind,
+            }
+        })
+        .collect()
+}
+
+fn find<'a, Message>(
+    menu: &'a Menu<Message>,
+    id: i32,
+    last_id: &mut i32,
+) -> Option<&'a Item<Message>> {
+    for item in &menu.items {
+        *last_id += 1;
+
+        if *last_id == id {
+            return Some(item);
+        }
+
+        if let Item::Submenu { menu, .. } = item {
+            if let Some(item) = find(menu, id, last_id) {
+                return Some(item);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu() -> Menu<&'static str> {
+        Menu::new()
+            .push(Item::button("Open").on_click("open"))
+            .push(Item::separator())
+            .push(Item::submenu(
+                "More",
+                Menu::new()
+                    .push(Item::checkbox("Sync", true).on_toggle(|_| "sync"))
+                    .push(Item::submenu(
+                        "Even more",
+                        Menu::new().push(Item::button("Deep").on_click("deep")),
+                    )),
+            ))
+            .push(Item::button("Quit"))
+    }
+
+    fn ids(entries: &[Entry]) -> Vec<i32> {
+        entries
+            .iter()
+            .flat_map(|entry| {
+                let children = match &entry.kind {
+                    Kind::Submenu(entries) => ids(entries),
+                    _ => Vec::new(),
+                };
+
+                std::iter::once(entry.id).chain(children)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entries_are_numbered_depth_first() {
+        let entries = entries(&menu(), &mut 0);
+
+        assert_eq!(ids(&entries), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(entries[0].label, "Open");
+        assert_eq!(entries[1].kind, Kind::Separator);
+        assert_eq!(entries[3].label, "Quit");
+        assert_eq!(entries[3].id, 7);
+    }
+
+    #[test]
+    fn entries_describe_items() {
+        let entries = entries(&menu(), &mut 0);
+
+        assert!(entries[0].is_enabled);
+        assert_eq!(entries[0].kind, Kind::Button);
+        assert!(!entries[1].is_enabled);
+        assert!(!entries[3].is_enabled);
+
+        let Kind::Submenu(children) = &entries[2].kind else {
+            panic!("Expected a submenu: {:?}", entries[2]);
+        };
+
+        assert!(entries[2].is_enabled);
+        assert_eq!(children[0].label, "Sync");
+        assert_eq!(children[0].kind, Kind::Checkbox { is_checked: true });
+        assert!(children[0].is_enabled);
+    }
+
+    #[test]
+    fn find_matches_entries() {
+        let menu = menu();
+        let labels = ["Open", "", "More", "Sync", "Even more", "Deep", "Quit"];
+
+        for (id, label) in (1..).zip(labels) {
+            let item = find(&menu, id, &mut 0).expect("Find item");
+
+            assert_eq!(item.label().unwrap_or_default(), label, "id {id}");
+        }
+    }
+
+    #[test]
+    fn find_activates_nested_items() {
+        let menu = menu();
+
+        assert_eq!(
+            find(&menu, 1, &mut 0).and_then(Item::activate),
+            Some("open")
+        );
+        assert_eq!(
+            find(&menu, 4, &mut 0).and_then(Item::activate),
+            Some("sync")
+        );
+        assert_eq!(
+            find(&menu, 6, &mut 0).and_then(Item::activate),
+            Some("deep")
+        );
+        assert_eq!(find(&menu, 7, &mut 0).and_then(Item::activate), None);
+    }
+
+    #[test]
+    fn find_returns_none_for_unknown_ids() {
+        let menu = menu();
+
+        assert!(find(&menu, 0, &mut 0).is_none());
+        assert!(find(&menu, 8, &mut 0).is_none());
+        assert!(find(&Menu::<()>::new(), 1, &mut 0).is_none());
+    }
+}
@@ -0,0 +1,492 @@
+//! The system tray of Linux, implemented with the `StatusNotifierItem` and
+//! `dbusmenu` D-Bus specifications.
+use crate::core::tray::Icon;
+use crate::tray::{Entry, Event, Handler, Kind, Snapshot};
+
+use std::collections::HashMap;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use zbus::blocking::connection;
+use zbus::zvariant::{OwnedValue, Structure, Value};
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const ITEM_INTERFACE: &str = "org.kde.StatusNotifierItem";
+const MENU_PATH: &str = "/MenuBar";
+const MENU_INTERFACE: &str = "com.canonical.dbusmenu";
+
+/// The pixmaps of an icon: width, height and ARGB32 pixels in network
+/// byte order.
+type Pixmaps = Vec<(i32, i32, Vec<u8>)>;
+
+/// A dbusmenu layout: id, properties and children layouts.
+type Layout = (
+    i32,
+    HashMap<&'static str, Value<'static>>,
+    Vec<Value<'static>>,
+);
+
+pub struct Platform {
+    state: Arc<Mutex<State>>,
+    signals: mpsc::Sender<Signal>,
+}
+
+struct State {
+    snapshot: Snapshot,
+    revision: u32,
+}
+
+/// A change to notify to the host of the system tray.
+enum Signal {
+    Item(&'static str),
+    LayoutUpdated(u32),
+}
+
+impl Platform {
+    /// Registers the item in the system tray on its own thread, since
+    /// connecting to the session bus blocks.
+    pub fn spawn(snapshot: Snapshot, handler: Handler) -> Option<Self> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let name = format!(
+            "org.kde.StatusNotifierItem-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, atomic::Ordering::Relaxed) + 1
+        );
+
+        let state = Arc::new(Mutex::new(State {
+            snapshot,
+            revision: 1,
+        }));
+
+        let (signals, receiver) = mpsc::channel();
+
+        let _ = thread::Builder::new()
+            .name("iced system tray".to_owned())
+            .spawn({
+                let state = state.clone();
+
+                move || run(&name, state, Arc::new(handler), &receiver)
+            })
+            .map_err(|error| {
+                log::warn!("Failed to spawn system tray thread: {error}");
+            })
+            .ok()?;
+
+        Some(Self { state, signals })
+    }
+
+    pub fn update(&mut self, snapshot: Snapshot) {
+        let (old, revision) = {
+            let mut state = self.state.lock().expect("Lock tray state");
+
+            if state.snapshot == snapshot {
+                return;
+            }
+
+            let old = std::mem::replace(&mut state.snapshot, snapshot.clone());
+
+            if old.menu != snapshot.menu {
+                state.revision += 1;
+            }
+
+            (old, state.revision)
+        };
+
+        let mut signals = Vec::new();
+
+        if old.title != snapshot.title {
+            signals.push(Signal::Item("NewTitle"));
+        }
+
+        if old.icon != snapshot.icon {
+            signals.push(Signal::Item("NewIcon"));
+        }
+
+        if old.tooltip != snapshot.tooltip || old.title != snapshot.title {
+            signals.push(Signal::Item("NewToolTip"));
+        }
+
+        if old.menu != snapshot.menu {
+            signals.push(Signal::LayoutUpdated(revision));
+        }
+
+        for signal in signals {
+            let _ = self.signals.send(signal);
+        }
+    }
+}
+
+/// Connects to the session bus and registers the item, then emits the
+/// signals of every update until the [`Platform`] is dropped.
+fn run(
+    name: &str,
+    state: Arc<Mutex<State>>,
+    handler: Arc<Handler>,
+    signals: &mpsc::Receiver<Signal>,
+) {
+    let connect = || {
+        let connection = connection::Builder::session()?
+            .name(name)?
+            .serve_at(
+                ITEM_PATH,
+                Item {
+                    state: state.clone(),
+                    handler: handler.clone(),
+                },
+            )?
+            .serve_at(
+                MENU_PATH,
+                DBusMenu {
+                    state: state.clone(),
+                    handler: handler.clone(),
+                },
+            )?
+            .build()?;
+
+        let _ = connection.call_method(
+            Some("org.kde.StatusNotifierWatcher"),
+            "/StatusNotifierWatcher",
+            Some("org.kde.StatusNotifierWatcher"),
+            "RegisterStatusNotifierItem",
+            &(name,),
+        )?;
+
+        Ok::<_, zbus::Error>(connection)
+    };
+
+    let connection = match connect() {
+        Ok(connection) => connection,
+        Err(error) => {
+            log::warn!("Failed to register system tray: {error}");
+            return;
+        }
+    };
+
+    for signal in signals {
+        let result = match signal {
+            Signal::Item(signal) => connection.emit_signal(
+                None::<()>,
+                ITEM_PATH,
+                ITEM_INTERFACE,
+                signal,
+                &(),
+            ),
+            Signal::LayoutUpdated(revision) => connection.emit_signal(
+                None::<()>,
+                MENU_PATH,
+                MENU_INTERFACE,
+                "LayoutUpdated",
+                &(revision, 0i32),
+            ),
+        };
+
+        if let Err(error) = result {
+            log::warn!("Failed to update system tray: {error}");
+        }
+    }
+}
+
+struct Item {
+    state: Arc<Mutex<State>>,
+    handler: Arc<Handler>,
+}
+
+impl Item {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("Lock tray state")
+    }
+}
+
+#[zbus::interface(name = "org.kde.StatusNotifierItem")]
+impl Item {
+    fn activate(&self, _x: i32, _y: i32) {
+        (self.handler)(Event::Clicked);
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {
+        (self.handler)(Event::MiddleClicked);
+    }
+
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: &str) {}
+
+    #[zbus(property)]
+    fn category(&self) -> &str {
+        "ApplicationStatus"
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        self.state().snapshot.title.clone()
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> String {
+        self.state().snapshot.title.clone()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "Active"
+    }
+
+    #[zbus(property)]
+    fn window_id(&self) -> i32 {
+        0
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> String {
+        match &self.state().snapshot.icon {
+            Icon::Named(name) => name.clone(),
+            Icon::Rgba { .. } => String::new(),
+        }
+    }
+
+    #[zbus(property)]
+    fn icon_pixmap(&self) -> Pixmaps {
+        pixmaps(&self.state().snapshot.icon)
+    }
+
+    #[zbus(property)]
+    fn tool_tip(&self) -> (String, Pixmaps, String, String) {
+        let state = self.state();
+
+        (
+            String::new(),
+            Vec::new(),
+            state.snapshot.title.clone(),
+            state.snapshot.tooltip.clone(),
+        )
+    }
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        let state = self.state();
+
+        !state.snapshot.is_clickable && !state.snapshot.menu.is_empty()
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> zbus::zvariant::ObjectPath<'_> {
+        zbus::zvariant::ObjectPath::from_static_str_unchecked(MENU_PATH)
+    }
+}
+
+struct DBusMenu {
+    state: Arc<Mutex<State>>,
+    handler: Arc<Handler>,
+}
+
+#[zbus::interface(name = "com.canonical.dbusmenu")]
+impl DBusMenu {
+    fn get_layout(
+        &self,
+        parent_id: i32,
+        recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> zbus::fdo::Result<(u32, Layout)> {
+        let state = self.state.lock().expect("Lock tray state");
+        let menu = &state.snapshot.menu;
+
+        let layout = if parent_id == 0 {
+            (0, root(), children(menu, recursion_depth))
+        } else {
+            let entry = find(menu, parent_id).ok_or_else(|| {
+                zbus::fdo::Error::InvalidArgs(format!(
+                    "Unknown menu item: {parent_id}"
+                ))
+            })?;
+
+            layout(entry, recursion_depth)
+        };
+
+        Ok((state.revision, layout))
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        _property_names: Vec<String>,
+    ) -> Vec<(i32, HashMap<&'static str, Value<'static>>)> {
+        let state = self.state.lock().expect("Lock tray state");
+        let menu = &state.snapshot.menu;
+
+        ids.into_iter()
+            .filter_map(|id| {
+                if id == 0 {
+                    Some((id, root()))
+                } else {
+                    find(menu, id).map(|entry| (id, properties(entry)))
+                }
+            })
+            .collect()
+    }
+
+    fn get_property(
+        &self,
+        id: i32,
+        name: &str,
+    ) -> zbus::fdo::Result<OwnedValue> {
+        let state = self.state.lock().expect("Lock tray state");
+
+        let mut properties = if id == 0 {
+            root()
+        } else {
+            find(&state.snapshot.menu, id)
+                .map(properties)
+                .ok_or_else(|| {
+                    zbus::fdo::Error::InvalidArgs(format!(
+                        "Unknown menu item: {id}"
+                    ))
+                })?
+        };
+
+        properties
+            .remove(name)
+            .and_then(|value| value.try_into().ok())
+            .ok_or_else(|| {
+                zbus::fdo::Error::InvalidArgs(format!(
+                    "Unknown property: {name}"
+                ))
+            })
+    }
+
+    fn event(
+        &self,
+        id: i32,
+        event_id: &str,
+        _data: Value<'_>,
+        _timestamp: u32,
+    ) {
+        if event_id == "clicked" {
+            (self.handler)(Event::ItemActivated(id));
+        }
+    }
+
+    fn event_group(
+        &self,
+        events: Vec<(i32, String, Value<'_>, u32)>,
+    ) -> Vec<i32> {
+        for (id, event_id, data, timestamp) in events {
+            self.event(id, &event_id, data, timestamp);
+        }
+
+        Vec::new()
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> &str {
+        "ltr"
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "normal"
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+fn pixmaps(icon: &Icon) -> Pixmaps {
+    match icon {
+        Icon::Named(_) => Vec::new(),
+        Icon::Rgba {
+            width,
+            height,
+            pixels,
+        } => {
+            let argb = pixels
+                .chunks_exact(4)
+                .flat_map(|rgba| [rgba[3], rgba[0], rgba[1], rgba[2]])
+                .collect();
+
+            vec![(*width as i32, *height as i32, argb)]
+        }
+    }
+}
+
+fn root() -> HashMap<&'static str, Value<'static>> {
+    HashMap::from([("children-display", Value::from("submenu"))])
+}
+
+fn properties(entry: &Entry) -> HashMap<&'static str, Value<'static>> {
+    let mut properties = HashMap::new();
+
+    let _ = properties.insert("enabled", Value::from(entry.is_enabled));
+
+    match &entry.kind {
+        Kind::Button => {}
+        Kind::Checkbox { is_checked } => {
+            let _ = properties.insert("toggle-type", Value::from("checkmark"));
+            let _ = properties
+                .insert("toggle-state", Value::from(i32::from(*is_checked)));
+        }
+        Kind::Separator => {
+            let _ = properties.insert("type", Value::from("separator"));
+        }
+        Kind::Submenu(_) => {
+            let _ =
+                properties.insert("children-display", Value::from("submenu"));
+        }
+    }
+
+    if !entry.label.is_empty() {
+        let _ = properties.insert("label", Value::from(entry.label.clone()));
+    }
+
+    properties
+}
+
+fn layout(entry: &Entry, depth: i32) -> Layout {
+    let children = match &entry.kind {
+        Kind::Submenu(entries) => children(entries, depth),
+        _ => Vec::new(),
+    };
+
+    (entry.id, properties(entry), children)
+}
+
+/// Returns the layouts of the given entries, recursing up to the given
+/// depth; where a negative depth means unlimited.
+fn children(entries: &[Entry], depth: i32) -> Vec<Value<'static>> {
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    entries
+        .iter()
+        .map(|entry| Value::from(Structure::from(layout(entry, depth - 1))))
+        .collect()
+}
+
+fn find(entries: &[Entry], id: i32) -> Option<&Entry> {
+    entries.iter().find_map(|entry| {
+        if entry.id == id {
+            return Some(entry);
+        }
+
+        match &entry.kind {
+            Kind::Submenu(entries) => find(entries, id),
+            _ => None,
+        }
+    })
+}
@@ -0,0 +1,13 @@
+use crate::tray::{Handler, Snapshot};
+
+pub enum Platform {}
+
+impl Platform {
+    pub fn spawn(_snapshot: Snapshot, _handler: Handler) -> Option<Self> {
+        None
+    }
+
+    pub fn update(&mut self, _snapshot: Snapshot) {
+        match *self {}
+    }
+}